@@ -0,0 +1,119 @@
+//! Escape handling for `console attach`.
+//!
+//! While attached, everything the operator types on the REPL port goes to the
+//! Jetson UART. [`AttachEscape`] watches that stream for the detach sequence:
+//! `~` at the start of a line followed by `.` returns to the REPL, `~~` sends a
+//! single `~`, and a `~` followed by anything else is passed through as typed.
+//! Requiring a line start keeps a `~` in the middle of a command intact.
+
+/// Byte that starts an escape sequence.
+pub const ATTACH_ESCAPE: u8 = b'~';
+
+/// Byte that completes the detach sequence after [`ATTACH_ESCAPE`].
+pub const ATTACH_DETACH: u8 = b'.';
+
+/// What the caller should do with one byte typed while attached.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AttachStep {
+    /// Nothing to send yet; the byte may start an escape.
+    Hold,
+    /// Send `byte` to the Jetson, preceded by a held `~` when `escape` is set.
+    Send { escape: bool, byte: u8 },
+    /// The operator typed the detach sequence.
+    Detach,
+}
+
+/// Line-start tracker that picks the detach sequence out of typed input.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AttachEscape {
+    line_start: bool,
+    held: bool,
+}
+
+impl AttachEscape {
+    /// Creates a tracker for a fresh session, which counts as a line start.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            line_start: true,
+            held: false,
+        }
+    }
+
+    /// Classifies the next typed byte.
+    pub fn push(&mut self, byte: u8) -> AttachStep {
+        if self.held {
+            self.held = false;
+            return match byte {
+                ATTACH_DETACH => {
+                    self.line_start = true;
+                    AttachStep::Detach
+                }
+                ATTACH_ESCAPE => self.send(false, byte),
+                _ => self.send(true, byte),
+            };
+        }
+        if self.line_start && byte == ATTACH_ESCAPE {
+            self.held = true;
+            return AttachStep::Hold;
+        }
+        self.send(false, byte)
+    }
+
+    fn send(&mut self, escape: bool, byte: u8) -> AttachStep {
+        self.line_start = matches!(byte, b'\r' | b'\n');
+        AttachStep::Send { escape, byte }
+    }
+}
+
+impl Default for AttachEscape {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(escape: &mut AttachEscape, input: &[u8]) -> (std::vec::Vec<u8>, Option<usize>) {
+        let mut sent = std::vec::Vec::new();
+        for (index, &byte) in input.iter().enumerate() {
+            match escape.push(byte) {
+                AttachStep::Hold => {}
+                AttachStep::Send { escape, byte } => {
+                    if escape {
+                        sent.push(ATTACH_ESCAPE);
+                    }
+                    sent.push(byte);
+                }
+                AttachStep::Detach => return (sent, Some(index)),
+            }
+        }
+        (sent, None)
+    }
+
+    #[test]
+    fn detaches_only_at_line_start() {
+        let mut escape = AttachEscape::new();
+        assert_eq!(run(&mut escape, b"ls ~.\r"), (b"ls ~.\r".to_vec(), None));
+        assert_eq!(
+            run(&mut escape, b"~.status"),
+            (std::vec::Vec::new(), Some(1))
+        );
+
+        let mut escape = AttachEscape::new();
+        assert_eq!(run(&mut escape, b"cd\n~"), (b"cd\n".to_vec(), None));
+        assert_eq!(run(&mut escape, b"."), (std::vec::Vec::new(), Some(0)));
+    }
+
+    #[test]
+    fn passes_other_escapes_through() {
+        let mut escape = AttachEscape::new();
+        assert_eq!(run(&mut escape, b"~~x"), (b"~x".to_vec(), None));
+
+        let mut escape = AttachEscape::new();
+        assert_eq!(run(&mut escape, b"~/bin\r~"), (b"~/bin\r".to_vec(), None));
+        assert_eq!(run(&mut escape, b"\r"), (b"~\r".to_vec(), None));
+    }
+}
@@ -0,0 +1,440 @@
+//! Finding the Jetson console baud rate.
+//!
+//! Carrier boards do not agree on a console rate, and a mismatch shows up as
+//! garbage on the bridge plus a stream of framing errors. `console baud auto`
+//! arms an [`AutobaudDetector`], which has the platform switch the UART through
+//! each candidate in [`AutobaudConfig::candidates`], scores the traffic seen at
+//! every rate by printable characters against bytes and receive errors, and
+//! locks onto the best one. The final [`AutobaudReport`] is printed by the REPL
+//! and the chosen rate is reported by `status` through [`BaudSource`].
+
+use core::fmt;
+use core::time::Duration;
+
+use crate::power::saturating_millis;
+use crate::telemetry::TelemetryInstant;
+
+use super::line_coding::{LINE_BAUD_MAX, LINE_BAUD_MIN};
+
+/// Rates tried by `console baud auto`, most common Jetson settings first.
+pub const AUTOBAUD_CANDIDATES: [u32; 6] = [115_200, 921_600, 1_500_000, 230_400, 460_800, 57_600];
+
+/// Rate requested by `console baud`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BaudSetting {
+    /// Search [`AUTOBAUD_CANDIDATES`] for the rate the Jetson is using.
+    Auto,
+    /// Switch to this rate.
+    Fixed(u32),
+}
+
+impl BaudSetting {
+    /// Returns a fixed setting when `baud` is within the range the UART can produce.
+    #[must_use]
+    pub const fn fixed(baud: u32) -> Option<Self> {
+        if baud >= LINE_BAUD_MIN && baud <= LINE_BAUD_MAX {
+            Some(BaudSetting::Fixed(baud))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for BaudSetting {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BaudSetting::Auto => f.write_str("auto"),
+            BaudSetting::Fixed(baud) => write!(f, "{baud}"),
+        }
+    }
+}
+
+/// How the console baud rate in effect was chosen.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum BaudSource {
+    /// Set by the host through CDC1 line coding, or the power-on default.
+    #[default]
+    Host,
+    /// Set with `console baud <rate>`.
+    Manual,
+    /// `console baud auto` is still searching.
+    Detecting,
+    /// Locked by `console baud auto`.
+    Detected,
+    /// `console baud auto` found no convincing rate and kept the previous one.
+    Undetected,
+}
+
+impl fmt::Display for BaudSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BaudSource::Host => "host",
+            BaudSource::Manual => "manual",
+            BaudSource::Detecting => "detecting",
+            BaudSource::Detected => "auto",
+            BaudSource::Undetected => "auto-failed",
+        })
+    }
+}
+
+/// Settings for a `console baud auto` run.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AutobaudConfig {
+    /// Rates to try, in order.
+    pub candidates: &'static [u32],
+    /// Time spent listening at each rate.
+    pub dwell: Duration,
+    /// Time after a switch during which traffic is ignored, so bytes the
+    /// UART buffered at the previous rate are not credited to the new one.
+    pub settle: Duration,
+    /// Bytes a rate must see before it can be chosen.
+    pub min_bytes: u32,
+    /// Lowest score, in percent, a rate needs to be chosen.
+    pub min_score: u8,
+}
+
+impl AutobaudConfig {
+    /// Listens 300 ms at each of [`AUTOBAUD_CANDIDATES`] and wants 16 mostly
+    /// printable bytes.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            candidates: &AUTOBAUD_CANDIDATES,
+            dwell: Duration::from_millis(300),
+            settle: Duration::from_millis(20),
+            min_bytes: 16,
+            min_score: 80,
+        }
+    }
+}
+
+impl Default for AutobaudConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Traffic seen while listening at one rate.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CandidateScore {
+    pub baud: u32,
+    pub bytes: u32,
+    /// Bytes that were printable ASCII or console whitespace.
+    pub printable: u32,
+    /// Framing, noise, and parity errors.
+    pub errors: u32,
+}
+
+impl CandidateScore {
+    const fn new(baud: u32) -> Self {
+        Self {
+            baud,
+            bytes: 0,
+            printable: 0,
+            errors: 0,
+        }
+    }
+
+    /// Printable bytes as a percentage of bytes plus errors; `0` with no traffic.
+    #[must_use]
+    pub fn score(&self) -> u8 {
+        let seen = u64::from(self.bytes) + u64::from(self.errors);
+        if seen == 0 {
+            return 0;
+        }
+        let percent = u64::from(self.printable) * 100 / seen;
+        u8::try_from(percent).unwrap_or(100)
+    }
+
+    /// `true` when the rate saw neither bytes nor errors.
+    #[must_use]
+    pub const fn is_silent(&self) -> bool {
+        self.bytes == 0 && self.errors == 0
+    }
+
+    fn beats(&self, other: &Self) -> bool {
+        (self.score(), self.bytes) > (other.score(), other.bytes)
+    }
+}
+
+/// How a `console baud auto` run ended.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AutobaudResult {
+    /// A rate scored well enough and is now in use.
+    Locked,
+    /// The console was silent at every rate.
+    NoSignal,
+    /// Traffic arrived but no rate scored well enough.
+    Inconclusive,
+}
+
+impl AutobaudResult {
+    /// `true` when a rate was locked.
+    #[must_use]
+    pub const fn is_success(self) -> bool {
+        matches!(self, AutobaudResult::Locked)
+    }
+}
+
+impl fmt::Display for AutobaudResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AutobaudResult::Locked => "locked",
+            AutobaudResult::NoSignal => "no-signal",
+            AutobaudResult::Inconclusive => "inconclusive",
+        })
+    }
+}
+
+/// Outcome of a `console baud auto` run.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AutobaudReport {
+    pub result: AutobaudResult,
+    /// Best scoring rate, if any rate saw traffic.
+    pub best: Option<CandidateScore>,
+    /// Time since the run started.
+    pub elapsed: Duration,
+}
+
+impl AutobaudReport {
+    /// Rate the platform should switch to, if one was locked.
+    #[must_use]
+    pub fn locked_baud(&self) -> Option<u32> {
+        match (self.result, self.best) {
+            (AutobaudResult::Locked, Some(best)) => Some(best.baud),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AutobaudReport {
+    /// Formats as `autobaud locked baud=115200 score=97% errors=0 elapsed=1800ms`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "autobaud {}", self.result)?;
+        if let Some(best) = self.best {
+            write!(
+                f,
+                " baud={} score={}% errors={}",
+                best.baud,
+                best.score(),
+                best.errors
+            )?;
+        }
+        write!(f, " elapsed={}ms", saturating_millis(self.elapsed))
+    }
+}
+
+/// Work the platform must carry out for the detector.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AutobaudStep {
+    /// Switch the UART to this rate.
+    Apply(u32),
+    /// The run ended; switch to [`AutobaudReport::locked_baud`] or restore the
+    /// previous rate.
+    Finished(AutobaudReport),
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Run<TInstant> {
+    started: TInstant,
+    switched_at: TInstant,
+    index: usize,
+    current: CandidateScore,
+    best: Option<CandidateScore>,
+}
+
+/// Steps the UART through candidate rates and picks the best scoring one.
+pub struct AutobaudDetector<TInstant> {
+    config: AutobaudConfig,
+    run: Option<Run<TInstant>>,
+}
+
+impl<TInstant> AutobaudDetector<TInstant>
+where
+    TInstant: TelemetryInstant,
+{
+    /// Creates an idle detector.
+    #[must_use]
+    pub const fn new(config: AutobaudConfig) -> Self {
+        Self { config, run: None }
+    }
+
+    /// Returns the active settings.
+    #[must_use]
+    pub const fn config(&self) -> &AutobaudConfig {
+        &self.config
+    }
+
+    /// Returns `true` while a run is in progress.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// Starts a run and returns the first rate to apply. Returns `None` if a
+    /// run is already in progress or there is nothing to try.
+    pub fn start(&mut self, now: TInstant) -> Option<u32> {
+        if self.run.is_some() {
+            return None;
+        }
+        let baud = *self.config.candidates.first()?;
+        self.run = Some(Run {
+            started: now,
+            switched_at: now,
+            index: 0,
+            current: CandidateScore::new(baud),
+            best: None,
+        });
+        Some(baud)
+    }
+
+    /// Credits console bytes received at the current rate.
+    pub fn feed(&mut self, now: TInstant, bytes: &[u8]) {
+        let Some(run) = self.settled_run(now) else {
+            return;
+        };
+        for &byte in bytes {
+            run.current.bytes = run.current.bytes.saturating_add(1);
+            if matches!(byte, b' '..=b'~' | b'\r' | b'\n' | b'\t') {
+                run.current.printable = run.current.printable.saturating_add(1);
+            }
+        }
+    }
+
+    /// Counts a framing, noise, or parity error at the current rate.
+    pub fn record_error(&mut self, now: TInstant) {
+        if let Some(run) = self.settled_run(now) {
+            run.current.errors = run.current.errors.saturating_add(1);
+        }
+    }
+
+    /// Returns the next rate to apply or the final report, if either is due.
+    pub fn poll(&mut self, now: TInstant) -> Option<AutobaudStep> {
+        let run = self.run.as_mut()?;
+        if now.saturating_duration_since(run.switched_at) < self.config.dwell {
+            return None;
+        }
+
+        if !run.current.is_silent() && run.best.is_none_or(|best| run.current.beats(&best)) {
+            run.best = Some(run.current);
+        }
+
+        run.index += 1;
+        if let Some(&baud) = self.config.candidates.get(run.index) {
+            run.switched_at = now;
+            run.current = CandidateScore::new(baud);
+            return Some(AutobaudStep::Apply(baud));
+        }
+
+        let run = self.run.take()?;
+        let result = match run.best {
+            None => AutobaudResult::NoSignal,
+            Some(best)
+                if best.bytes >= self.config.min_bytes && best.score() >= self.config.min_score =>
+            {
+                AutobaudResult::Locked
+            }
+            Some(_) => AutobaudResult::Inconclusive,
+        };
+        Some(AutobaudStep::Finished(AutobaudReport {
+            result,
+            best: run.best,
+            elapsed: now.saturating_duration_since(run.started),
+        }))
+    }
+
+    fn settled_run(&mut self, now: TInstant) -> Option<&mut Run<TInstant>> {
+        let settle = self.config.settle;
+        self.run
+            .as_mut()
+            .filter(|run| now.saturating_duration_since(run.switched_at) >= settle)
+    }
+}
+
+impl<TInstant> Default for AutobaudDetector<TInstant>
+where
+    TInstant: TelemetryInstant,
+{
+    fn default() -> Self {
+        Self::new(AutobaudConfig::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::test_support::MillisInstant;
+
+    const CONFIG: AutobaudConfig = AutobaudConfig {
+        candidates: &[115_200, 921_600, 1_500_000],
+        dwell: Duration::from_millis(100),
+        settle: Duration::from_millis(10),
+        min_bytes: 8,
+        min_score: 80,
+    };
+
+    #[test]
+    fn locks_onto_the_cleanest_rate() {
+        let mut detector = AutobaudDetector::new(CONFIG);
+        assert_eq!(detector.start(MillisInstant(0)), Some(115_200));
+        assert_eq!(detector.start(MillisInstant(0)), None);
+
+        // Leftovers from before the switch are ignored.
+        detector.feed(MillisInstant(5), b"stale text");
+        detector.feed(MillisInstant(20), &[0xf0, 0x80, b'x', 0x00]);
+        detector.record_error(MillisInstant(30));
+        detector.record_error(MillisInstant(40));
+        assert_eq!(detector.poll(MillisInstant(50)), None);
+        assert_eq!(
+            detector.poll(MillisInstant(100)),
+            Some(AutobaudStep::Apply(921_600))
+        );
+
+        detector.feed(MillisInstant(150), b"\r\nUbuntu 22.04 tegra ttyTCU0\r\n");
+        assert_eq!(
+            detector.poll(MillisInstant(200)),
+            Some(AutobaudStep::Apply(1_500_000))
+        );
+
+        let Some(AutobaudStep::Finished(report)) = detector.poll(MillisInstant(300)) else {
+            panic!("run should finish after the last candidate");
+        };
+        assert!(!detector.is_active());
+        assert_eq!(report.locked_baud(), Some(921_600));
+        let mut text = std::string::String::new();
+        core::fmt::write(&mut text, format_args!("{report}")).expect("report should format");
+        assert_eq!(
+            text,
+            "autobaud locked baud=921600 score=100% errors=0 elapsed=300ms"
+        );
+    }
+
+    #[test]
+    fn reports_silence_and_garbage_without_locking() {
+        let mut detector = AutobaudDetector::new(CONFIG);
+        detector.start(MillisInstant(0));
+        for now in [100, 200] {
+            assert!(matches!(
+                detector.poll(MillisInstant(now)),
+                Some(AutobaudStep::Apply(_))
+            ));
+        }
+        let Some(AutobaudStep::Finished(report)) = detector.poll(MillisInstant(300)) else {
+            panic!("run should finish");
+        };
+        assert_eq!(report.result, AutobaudResult::NoSignal);
+        assert_eq!(report.best, None);
+
+        detector.start(MillisInstant(1_000));
+        detector.feed(MillisInstant(1_050), &[0xff; 12]);
+        detector.feed(MillisInstant(1_060), b"ok");
+        detector.poll(MillisInstant(1_100));
+        detector.poll(MillisInstant(1_200));
+        let Some(AutobaudStep::Finished(report)) = detector.poll(MillisInstant(1_300)) else {
+            panic!("run should finish");
+        };
+        assert_eq!(report.result, AutobaudResult::Inconclusive);
+        assert_eq!(report.locked_baud(), None);
+        assert_eq!(report.best.map(|best| best.baud), Some(115_200));
+    }
+}
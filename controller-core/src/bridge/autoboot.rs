@@ -0,0 +1,326 @@
+//! Stopping U-Boot at its prompt after a reboot.
+//!
+//! `reboot to-bootloader` queues a normal reboot and arms an
+//! [`AutobootInterrupter`]. The platform feeds it the Jetson console bytes;
+//! once the autoboot countdown appears it asks for the interrupt key to be
+//! sent every [`AutobootConfig::resend_interval`] until the bootloader prompt
+//! shows up or [`AutobootConfig::timeout`] expires. The final
+//! [`AutobootReport`] is printed by the REPL and recorded as telemetry.
+
+use core::fmt;
+use core::time::Duration;
+
+use heapless::Vec;
+
+use crate::power::saturating_millis;
+use crate::telemetry::TelemetryInstant;
+
+/// Longest countdown or prompt pattern the interrupter can watch for.
+pub const AUTOBOOT_PATTERN_CAPACITY: usize = 32;
+
+/// Settings for a `reboot to-bootloader` run.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AutobootConfig {
+    /// Text U-Boot prints while counting down to autoboot.
+    pub countdown: &'static [u8],
+    /// Text that shows the bootloader is waiting for commands.
+    pub prompt: &'static [u8],
+    /// Byte sent to stop the countdown.
+    pub interrupt_key: u8,
+    /// Time between interrupt keys while waiting for the prompt.
+    pub resend_interval: Duration,
+    /// Time allowed from the reboot request until the prompt appears.
+    pub timeout: Duration,
+}
+
+impl AutobootConfig {
+    /// U-Boot defaults: any key stops the countdown, and the prompt is `=> `.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            countdown: b"Hit any key to stop autoboot",
+            prompt: b"=> ",
+            interrupt_key: b' ',
+            resend_interval: Duration::from_millis(100),
+            timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+impl Default for AutobootConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How a `reboot to-bootloader` run ended.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AutobootResult {
+    /// The bootloader prompt appeared.
+    Reached,
+    /// The timeout expired before the countdown appeared.
+    NoCountdown,
+    /// The countdown appeared but the prompt did not follow in time.
+    NoPrompt,
+    /// The run was cancelled by the platform.
+    Stopped,
+}
+
+impl AutobootResult {
+    /// `true` when the Jetson is sitting at the bootloader prompt.
+    #[must_use]
+    pub const fn is_success(self) -> bool {
+        matches!(self, AutobootResult::Reached)
+    }
+}
+
+impl fmt::Display for AutobootResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AutobootResult::Reached => "prompt",
+            AutobootResult::NoCountdown => "no-countdown",
+            AutobootResult::NoPrompt => "no-prompt",
+            AutobootResult::Stopped => "stopped",
+        })
+    }
+}
+
+/// Outcome of a `reboot to-bootloader` run.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AutobootReport {
+    pub result: AutobootResult,
+    /// Time since the reboot was requested.
+    pub elapsed: Duration,
+    /// Interrupt keys sent.
+    pub keys_sent: u16,
+}
+
+impl fmt::Display for AutobootReport {
+    /// Formats as `bootloader prompt elapsed=8200ms keys=3`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "bootloader {} elapsed={}ms keys={}",
+            self.result,
+            saturating_millis(self.elapsed),
+            self.keys_sent
+        )
+    }
+}
+
+/// Work the platform must carry out for the interrupter.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AutobootEvent {
+    /// Write this byte to the Jetson console.
+    SendKey(u8),
+    /// The run ended; the interrupter is idle again.
+    Finished(AutobootReport),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Phase {
+    AwaitingCountdown,
+    Interrupting,
+    Reached,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Run<TInstant> {
+    phase: Phase,
+    started: TInstant,
+    last_key: Option<TInstant>,
+    keys_sent: u16,
+}
+
+/// Watches console output for the autoboot countdown and interrupts it.
+pub struct AutobootInterrupter<TInstant> {
+    config: AutobootConfig,
+    run: Option<Run<TInstant>>,
+    window: Vec<u8, AUTOBOOT_PATTERN_CAPACITY>,
+}
+
+impl<TInstant> AutobootInterrupter<TInstant>
+where
+    TInstant: TelemetryInstant,
+{
+    /// Creates an idle interrupter.
+    #[must_use]
+    pub const fn new(config: AutobootConfig) -> Self {
+        Self {
+            config,
+            run: None,
+            window: Vec::new(),
+        }
+    }
+
+    /// Returns the active settings.
+    #[must_use]
+    pub const fn config(&self) -> &AutobootConfig {
+        &self.config
+    }
+
+    /// Returns `true` while a run is in progress.
+    #[must_use]
+    pub const fn is_active(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// Starts watching for the countdown. Returns `false` if a run is already
+    /// in progress.
+    pub fn arm(&mut self, now: TInstant) -> bool {
+        if self.run.is_some() {
+            return false;
+        }
+        self.window.clear();
+        self.run = Some(Run {
+            phase: Phase::AwaitingCountdown,
+            started: now,
+            last_key: None,
+            keys_sent: 0,
+        });
+        true
+    }
+
+    /// Cancels the active run, returning its report.
+    pub fn stop(&mut self, now: TInstant) -> Option<AutobootReport> {
+        self.finish(AutobootResult::Stopped, now)
+    }
+
+    /// Scans console output for the countdown or the prompt.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        let Some(run) = self.run.as_mut() else {
+            return;
+        };
+        for &byte in bytes {
+            if self.window.is_full() {
+                self.window.remove(0);
+            }
+            let _ = self.window.push(byte);
+            let pattern = match run.phase {
+                Phase::AwaitingCountdown => self.config.countdown,
+                Phase::Interrupting => self.config.prompt,
+                Phase::Reached => return,
+            };
+            if !pattern.is_empty() && self.window.ends_with(pattern) {
+                self.window.clear();
+                run.phase = match run.phase {
+                    Phase::AwaitingCountdown => Phase::Interrupting,
+                    Phase::Interrupting | Phase::Reached => Phase::Reached,
+                };
+            }
+        }
+    }
+
+    /// Returns the next key to send or the final report, if either is due.
+    pub fn poll(&mut self, now: TInstant) -> Option<AutobootEvent> {
+        let run = self.run.as_mut()?;
+        let result = match run.phase {
+            Phase::Reached => Some(AutobootResult::Reached),
+            _ if now.saturating_duration_since(run.started) >= self.config.timeout => {
+                Some(match run.phase {
+                    Phase::AwaitingCountdown => AutobootResult::NoCountdown,
+                    _ => AutobootResult::NoPrompt,
+                })
+            }
+            Phase::Interrupting => {
+                let due = run.last_key.is_none_or(|sent| {
+                    now.saturating_duration_since(sent) >= self.config.resend_interval
+                });
+                if !due {
+                    return None;
+                }
+                run.last_key = Some(now);
+                run.keys_sent = run.keys_sent.saturating_add(1);
+                return Some(AutobootEvent::SendKey(self.config.interrupt_key));
+            }
+            Phase::AwaitingCountdown => None,
+        }?;
+        self.finish(result, now).map(AutobootEvent::Finished)
+    }
+
+    fn finish(&mut self, result: AutobootResult, now: TInstant) -> Option<AutobootReport> {
+        let run = self.run.take()?;
+        Some(AutobootReport {
+            result,
+            elapsed: now.saturating_duration_since(run.started),
+            keys_sent: run.keys_sent,
+        })
+    }
+}
+
+impl<TInstant> Default for AutobootInterrupter<TInstant>
+where
+    TInstant: TelemetryInstant,
+{
+    fn default() -> Self {
+        Self::new(AutobootConfig::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::test_support::MillisInstant;
+
+    #[test]
+    fn interrupts_countdown_until_prompt() {
+        let mut interrupter = AutobootInterrupter::<MillisInstant>::default();
+        assert!(interrupter.arm(MillisInstant(0)));
+        assert!(!interrupter.arm(MillisInstant(0)));
+        assert_eq!(interrupter.poll(MillisInstant(10)), None);
+
+        interrupter.feed(b"U-Boot 2020.04\r\nHit any key to stop ");
+        assert_eq!(interrupter.poll(MillisInstant(4_000)), None);
+        interrupter.feed(b"autoboot:  2 ");
+        assert_eq!(
+            interrupter.poll(MillisInstant(4_100)),
+            Some(AutobootEvent::SendKey(b' '))
+        );
+        assert_eq!(interrupter.poll(MillisInstant(4_150)), None);
+        assert_eq!(
+            interrupter.poll(MillisInstant(4_200)),
+            Some(AutobootEvent::SendKey(b' '))
+        );
+
+        interrupter.feed(b"\x08\x08\x08 0 \r\n=> ");
+        let Some(AutobootEvent::Finished(report)) = interrupter.poll(MillisInstant(4_250)) else {
+            panic!("prompt should finish the run");
+        };
+        assert_eq!(report.result, AutobootResult::Reached);
+        assert_eq!(report.keys_sent, 2);
+        assert_eq!(
+            std::format!("{report}"),
+            "bootloader prompt elapsed=4250ms keys=2"
+        );
+        assert!(!interrupter.is_active());
+    }
+
+    #[test]
+    fn reports_which_stage_timed_out() {
+        let mut interrupter = AutobootInterrupter::<MillisInstant>::default();
+        let _ = interrupter.arm(MillisInstant(0));
+        interrupter.feed(b"=> ");
+        let Some(AutobootEvent::Finished(report)) = interrupter.poll(MillisInstant(60_000)) else {
+            panic!("timeout should finish the run");
+        };
+        assert_eq!(report.result, AutobootResult::NoCountdown);
+
+        let _ = interrupter.arm(MillisInstant(0));
+        interrupter.feed(b"Hit any key to stop autoboot");
+        let _ = interrupter.poll(MillisInstant(1));
+        let Some(AutobootEvent::Finished(report)) = interrupter.poll(MillisInstant(60_000)) else {
+            panic!("timeout should finish the run");
+        };
+        assert_eq!(report.result, AutobootResult::NoPrompt);
+        assert_eq!(report.keys_sent, 1);
+
+        let _ = interrupter.arm(MillisInstant(0));
+        assert_eq!(
+            interrupter
+                .stop(MillisInstant(5))
+                .map(|report| report.result),
+            Some(AutobootResult::Stopped)
+        );
+        assert_eq!(interrupter.stop(MillisInstant(5)), None);
+    }
+}
@@ -0,0 +1,605 @@
+//! Streaming line assembly and marker matching for the Jetson console.
+//!
+//! [`ConsoleMatcher`] consumes the raw Jetson→USB byte stream in whatever
+//! chunks the UART hands over, splits it into lines, and scans every byte
+//! against a small set of runtime-registrable patterns. Matching happens as
+//! bytes arrive, so prompts that never end in a newline (such as
+//! `login: `) are still recognised. Nothing allocates: patterns and the
+//! current line live in fixed-capacity buffers.
+
+use core::fmt;
+
+use heapless::Vec;
+
+/// Longest line retained by the assembler; longer lines are truncated.
+pub const CONSOLE_LINE_CAPACITY: usize = 128;
+/// Longest pattern accepted by [`ConsoleMatcher::register`].
+pub const CONSOLE_PATTERN_CAPACITY: usize = 32;
+/// Maximum number of patterns registered at once.
+pub const MAX_CONSOLE_PATTERNS: usize = 12;
+/// Longest slice of a panic line retained by [`PanicExcerpt`].
+pub const PANIC_EXCERPT_CAPACITY: usize = 48;
+
+/// Default patterns installed by [`ConsoleMatcher::with_default_markers`].
+pub const DEFAULT_CONSOLE_PATTERNS: [(ConsoleMarker, &[u8]); 5] = [
+    (ConsoleMarker::BootBanner, b"Jetson UEFI firmware"),
+    (ConsoleMarker::BootBanner, b"U-Boot "),
+    (ConsoleMarker::LoginPrompt, b"login: "),
+    (ConsoleMarker::KernelPanic, b"Kernel panic"),
+    (ConsoleMarker::TegraRecovery, b"Attempting Recovery Boot"),
+];
+
+/// Identifier handed out when a pattern is registered.
+pub type PatternId = u8;
+
+/// Category of console output a pattern detects.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ConsoleMarker {
+    /// Bootloader or firmware banner printed after reset.
+    BootBanner,
+    /// Getty login prompt, i.e. the OS finished booting.
+    LoginPrompt,
+    /// Linux kernel panic banner.
+    KernelPanic,
+    /// Tegra bootloader falling back to its recovery path.
+    TegraRecovery,
+    /// Operator-defined marker.
+    Custom,
+}
+
+impl ConsoleMarker {
+    /// Every marker, in the order `console pattern add` lists them.
+    pub const ALL: [ConsoleMarker; 5] = [
+        ConsoleMarker::BootBanner,
+        ConsoleMarker::LoginPrompt,
+        ConsoleMarker::KernelPanic,
+        ConsoleMarker::TegraRecovery,
+        ConsoleMarker::Custom,
+    ];
+
+    /// Keyword used for the marker on the command line.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            ConsoleMarker::BootBanner => "boot-banner",
+            ConsoleMarker::LoginPrompt => "login-prompt",
+            ConsoleMarker::KernelPanic => "kernel-panic",
+            ConsoleMarker::TegraRecovery => "tegra-recovery",
+            ConsoleMarker::Custom => "custom",
+        }
+    }
+
+    /// Looks up a marker by keyword (case insensitive).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|marker| marker.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for ConsoleMarker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Errors returned when changing the pattern table.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum PatternError {
+    /// Patterns must contain at least one byte.
+    Empty,
+    /// Pattern exceeds [`CONSOLE_PATTERN_CAPACITY`] or contains a line break.
+    Invalid,
+    /// All [`MAX_CONSOLE_PATTERNS`] slots are taken.
+    Full,
+    /// No pattern has the given id.
+    NotRegistered(PatternId),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Empty => f.write_str("pattern is empty"),
+            PatternError::Invalid => write!(
+                f,
+                "pattern is longer than {CONSOLE_PATTERN_CAPACITY} bytes or spans lines"
+            ),
+            PatternError::Full => write!(f, "pattern table is full ({MAX_CONSOLE_PATTERNS})"),
+            PatternError::NotRegistered(id) => write!(f, "pattern {id} not registered"),
+        }
+    }
+}
+
+/// Output produced while feeding bytes into the matcher.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ConsoleEvent<'a> {
+    /// A registered pattern matched within the current line.
+    Marker {
+        pattern: PatternId,
+        marker: ConsoleMarker,
+        /// Zero-based index of the line the match occurred on.
+        line: u32,
+    },
+    /// A line terminator was seen; `text` excludes the terminator.
+    Line { text: &'a [u8], truncated: bool },
+}
+
+#[derive(Clone, Debug)]
+struct ConsolePattern {
+    id: PatternId,
+    marker: ConsoleMarker,
+    text: Vec<u8, CONSOLE_PATTERN_CAPACITY>,
+    progress: usize,
+    fired: bool,
+}
+
+impl ConsolePattern {
+    /// Advances the match state by one byte, returning `true` on a full match.
+    fn advance(&mut self, byte: u8) -> bool {
+        let text = self.text.as_slice();
+        if text[self.progress] == byte {
+            self.progress += 1;
+        } else {
+            self.progress = fallback(text, self.progress, byte);
+        }
+
+        if self.progress == text.len() {
+            self.progress = fallback(text, self.progress - 1, text[text.len() - 1]);
+            return true;
+        }
+        false
+    }
+
+    fn reset(&mut self) {
+        self.progress = 0;
+        self.fired = false;
+    }
+}
+
+/// Longest prefix of `text` that is a suffix of `text[..matched] + byte`.
+///
+/// The bytes seen so far equal `text[..matched]`, so the fallback can be
+/// derived from the pattern alone without keeping stream history.
+fn fallback(text: &[u8], matched: usize, byte: u8) -> usize {
+    (1..=matched)
+        .rev()
+        .find(|&len| text[len - 1] == byte && text[..len - 1] == text[matched - (len - 1)..matched])
+        .unwrap_or(0)
+}
+
+/// Allocation-free line assembler and multi-pattern matcher.
+#[derive(Clone, Debug)]
+pub struct ConsoleMatcher {
+    patterns: Vec<ConsolePattern, MAX_CONSOLE_PATTERNS>,
+    next_id: PatternId,
+    line: Vec<u8, CONSOLE_LINE_CAPACITY>,
+    truncated: bool,
+    last_was_cr: bool,
+    lines_seen: u32,
+}
+
+impl ConsoleMatcher {
+    /// Creates a matcher with no registered patterns.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            patterns: Vec::new(),
+            next_id: 0,
+            line: Vec::new(),
+            truncated: false,
+            last_was_cr: false,
+            lines_seen: 0,
+        }
+    }
+
+    /// Creates a matcher preloaded with [`DEFAULT_CONSOLE_PATTERNS`].
+    #[must_use]
+    pub fn with_default_markers() -> Self {
+        let mut matcher = Self::new();
+        for (marker, text) in DEFAULT_CONSOLE_PATTERNS {
+            let _ = matcher.register(marker, text);
+        }
+        matcher
+    }
+
+    /// Registers a pattern that reports `marker` whenever `text` appears in a line.
+    ///
+    /// # Errors
+    /// Returns [`PatternError`] if the pattern is empty, too long, contains a
+    /// line break, or no slots remain.
+    pub fn register(
+        &mut self,
+        marker: ConsoleMarker,
+        text: &[u8],
+    ) -> Result<PatternId, PatternError> {
+        if text.is_empty() {
+            return Err(PatternError::Empty);
+        }
+        if text.iter().any(|byte| matches!(byte, b'\r' | b'\n')) {
+            return Err(PatternError::Invalid);
+        }
+        let text = Vec::from_slice(text).map_err(|_| PatternError::Invalid)?;
+        if self.patterns.is_full() {
+            return Err(PatternError::Full);
+        }
+
+        let id = self.allocate_id();
+        let _ = self.patterns.push(ConsolePattern {
+            id,
+            marker,
+            text,
+            progress: 0,
+            fired: false,
+        });
+        Ok(id)
+    }
+
+    /// Removes a pattern, returning `true` if it was registered.
+    pub fn unregister(&mut self, id: PatternId) -> bool {
+        let Some(index) = self.patterns.iter().position(|pattern| pattern.id == id) else {
+            return false;
+        };
+        self.patterns.remove(index);
+        true
+    }
+
+    /// Removes every registered pattern.
+    pub fn clear_patterns(&mut self) {
+        self.patterns.clear();
+    }
+
+    /// Iterates the registered patterns as `(id, marker, text)`.
+    pub fn patterns(&self) -> impl Iterator<Item = (PatternId, ConsoleMarker, &[u8])> {
+        self.patterns
+            .iter()
+            .map(|pattern| (pattern.id, pattern.marker, pattern.text.as_slice()))
+    }
+
+    /// Bytes of the line currently being assembled.
+    #[must_use]
+    pub fn current_line(&self) -> &[u8] {
+        &self.line
+    }
+
+    /// Number of completed lines seen so far.
+    #[must_use]
+    pub const fn lines_seen(&self) -> u32 {
+        self.lines_seen
+    }
+
+    /// Feeds a chunk of console bytes, reporting matches and completed lines to `sink`.
+    ///
+    /// Each pattern reports at most once per line. `\r\n`, `\r`, and `\n` all
+    /// terminate a line.
+    pub fn feed(&mut self, bytes: &[u8], mut sink: impl FnMut(ConsoleEvent<'_>)) {
+        for &byte in bytes {
+            if matches!(byte, b'\r' | b'\n') {
+                let crlf = byte == b'\n' && self.last_was_cr;
+                self.last_was_cr = byte == b'\r';
+                if !crlf {
+                    self.finish_line(&mut sink);
+                }
+                continue;
+            }
+
+            self.last_was_cr = false;
+            if self.line.push(byte).is_err() {
+                self.truncated = true;
+            }
+
+            let line = self.lines_seen;
+            for pattern in &mut self.patterns {
+                if pattern.advance(byte) && !pattern.fired {
+                    pattern.fired = true;
+                    sink(ConsoleEvent::Marker {
+                        pattern: pattern.id,
+                        marker: pattern.marker,
+                        line,
+                    });
+                }
+            }
+        }
+    }
+
+    fn finish_line(&mut self, sink: &mut impl FnMut(ConsoleEvent<'_>)) {
+        sink(ConsoleEvent::Line {
+            text: &self.line,
+            truncated: self.truncated,
+        });
+        self.line.clear();
+        self.truncated = false;
+        self.lines_seen = self.lines_seen.wrapping_add(1);
+        for pattern in &mut self.patterns {
+            pattern.reset();
+        }
+    }
+
+    fn allocate_id(&mut self) -> PatternId {
+        loop {
+            let id = self.next_id;
+            self.next_id = self.next_id.wrapping_add(1);
+            if self.patterns.iter().all(|pattern| pattern.id != id) {
+                return id;
+            }
+        }
+    }
+}
+
+impl Default for ConsoleMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fixed-size copy of the console line that carried a kernel panic banner.
+///
+/// The leading `[  seconds]` kernel timestamp is dropped so the excerpt starts
+/// at the interesting part of the message.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct PanicExcerpt {
+    text: [u8; PANIC_EXCERPT_CAPACITY],
+    len: u8,
+    /// Zero-based index of the console line the panic was reported on.
+    pub line: u32,
+    /// `true` when the line did not fit in the excerpt.
+    pub truncated: bool,
+}
+
+impl PanicExcerpt {
+    /// Captures up to [`PANIC_EXCERPT_CAPACITY`] bytes of `text`.
+    #[must_use]
+    pub fn new(line: u32, text: &[u8], truncated: bool) -> Self {
+        let text = strip_kernel_timestamp(text);
+        let len = text.len().min(PANIC_EXCERPT_CAPACITY);
+        let mut excerpt = Self {
+            text: [0; PANIC_EXCERPT_CAPACITY],
+            len: u8::try_from(len).unwrap_or(u8::MAX),
+            line,
+            truncated: truncated || len < text.len(),
+        };
+        excerpt.text[..len].copy_from_slice(&text[..len]);
+        excerpt
+    }
+
+    /// Captured bytes of the panic line.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.text[..usize::from(self.len)]
+    }
+}
+
+impl fmt::Display for PanicExcerpt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &byte in self.as_bytes() {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                fmt::Write::write_char(f, char::from(byte))?;
+            } else {
+                f.write_str(".")?;
+            }
+        }
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        Ok(())
+    }
+}
+
+fn strip_kernel_timestamp(text: &[u8]) -> &[u8] {
+    let rest = match text.strip_prefix(b"[") {
+        Some(rest) => match rest.iter().position(|&byte| byte == b']') {
+            Some(end) => &rest[end + 1..],
+            None => text,
+        },
+        None => text,
+    };
+    let start = rest
+        .iter()
+        .position(|byte| !byte.is_ascii_whitespace())
+        .unwrap_or(rest.len());
+    &rest[start..]
+}
+
+/// Pairs [`ConsoleMarker::KernelPanic`] hits with the line they appeared on.
+///
+/// Only the first panic banner after each boot is captured: the kernel repeats
+/// the phrase in its `---[ end Kernel panic ...` trailer, so further panic
+/// markers are ignored until another marker (such as the boot banner) shows
+/// the Jetson has moved on.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct PanicCapture {
+    pending: Option<u32>,
+    captured: bool,
+}
+
+impl PanicCapture {
+    /// Creates an idle capture.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            pending: None,
+            captured: false,
+        }
+    }
+
+    /// Feeds a matcher event, returning the excerpt once the panic line completes.
+    pub fn observe(&mut self, event: &ConsoleEvent<'_>) -> Option<PanicExcerpt> {
+        match *event {
+            ConsoleEvent::Marker {
+                marker: ConsoleMarker::KernelPanic,
+                line,
+                ..
+            } => {
+                if !self.captured {
+                    self.pending = Some(line);
+                }
+                None
+            }
+            ConsoleEvent::Marker { .. } => {
+                self.captured = false;
+                None
+            }
+            ConsoleEvent::Line { text, truncated } => {
+                let line = self.pending.take()?;
+                self.captured = true;
+                Some(PanicExcerpt::new(line, text, truncated))
+            }
+        }
+    }
+
+    /// Forgets any pending or completed capture.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::vec::Vec as StdVec;
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Seen {
+        Marker(ConsoleMarker, u32),
+        Line(StdVec<u8>, bool),
+    }
+
+    fn feed_all(matcher: &mut ConsoleMatcher, chunks: &[&[u8]]) -> StdVec<Seen> {
+        let mut seen = StdVec::new();
+        for chunk in chunks {
+            matcher.feed(chunk, |event| match event {
+                ConsoleEvent::Marker { marker, line, .. } => seen.push(Seen::Marker(marker, line)),
+                ConsoleEvent::Line { text, truncated } => {
+                    seen.push(Seen::Line(text.to_vec(), truncated));
+                }
+            });
+        }
+        seen
+    }
+
+    #[test]
+    fn matches_across_chunk_boundaries_and_prompts_without_newline() {
+        let mut matcher = ConsoleMatcher::with_default_markers();
+        let seen = feed_all(
+            &mut matcher,
+            &[
+                b"[    5.1] Kernel pa",
+                b"nic - not syncing\r\n",
+                b"orin login: ",
+            ],
+        );
+
+        assert_eq!(
+            seen,
+            [
+                Seen::Marker(ConsoleMarker::KernelPanic, 0),
+                Seen::Line(b"[    5.1] Kernel panic - not syncing".to_vec(), false),
+                Seen::Marker(ConsoleMarker::LoginPrompt, 1),
+            ]
+        );
+        assert_eq!(matcher.current_line(), b"orin login: ");
+        assert_eq!(matcher.lines_seen(), 1);
+    }
+
+    #[test]
+    fn overlapping_prefixes_still_match() {
+        let mut matcher = ConsoleMatcher::new();
+        matcher.register(ConsoleMarker::Custom, b"aab").unwrap();
+        let seen = feed_all(&mut matcher, &[b"aaab\n"]);
+        assert_eq!(seen[0], Seen::Marker(ConsoleMarker::Custom, 0));
+    }
+
+    #[test]
+    fn pattern_fires_once_per_line() {
+        let mut matcher = ConsoleMatcher::new();
+        matcher.register(ConsoleMarker::Custom, b"ok").unwrap();
+        let seen = feed_all(&mut matcher, &[b"ok ok\nok\r"]);
+        let markers = seen
+            .iter()
+            .filter(|event| matches!(event, Seen::Marker(..)))
+            .count();
+        assert_eq!(markers, 2);
+    }
+
+    #[test]
+    fn long_lines_are_truncated_but_still_matched() {
+        let mut matcher = ConsoleMatcher::new();
+        matcher.register(ConsoleMarker::Custom, b"tail").unwrap();
+        let mut input = std::vec![b'x'; CONSOLE_LINE_CAPACITY + 10];
+        input.extend_from_slice(b"tail\n");
+        let seen = feed_all(&mut matcher, &[&input]);
+
+        assert_eq!(seen[0], Seen::Marker(ConsoleMarker::Custom, 0));
+        let Seen::Line(text, truncated) = &seen[1] else {
+            panic!("expected a line event, got {:?}", seen[1]);
+        };
+        assert_eq!(text.len(), CONSOLE_LINE_CAPACITY);
+        assert!(*truncated);
+    }
+
+    fn capture_panics(matcher: &mut ConsoleMatcher, chunks: &[&[u8]]) -> StdVec<PanicExcerpt> {
+        let mut capture = PanicCapture::new();
+        let mut excerpts = StdVec::new();
+        for chunk in chunks {
+            matcher.feed(chunk, |event| excerpts.extend(capture.observe(&event)));
+        }
+        excerpts
+    }
+
+    #[test]
+    fn panic_capture_keeps_first_banner_per_boot() {
+        let mut matcher = ConsoleMatcher::with_default_markers();
+        let excerpts = capture_panics(
+            &mut matcher,
+            &[
+                b"[   12.5] Kernel panic - not syncing: Attempted to kill init!\r\n",
+                b"[   12.6] ---[ end Kernel panic - not syncing ]---\r\n",
+                b"Jetson UEFI firmware (version 36.3)\r\n",
+                b"Kernel panic - not syncing: VFS\r\n",
+            ],
+        );
+
+        assert_eq!(excerpts.len(), 2);
+        assert_eq!(
+            excerpts[0].as_bytes(),
+            b"Kernel panic - not syncing: Attempted to kill in"
+        );
+        assert_eq!(excerpts[0].line, 0);
+        assert!(excerpts[0].truncated);
+        assert_eq!(
+            std::format!("{}", excerpts[1]),
+            "Kernel panic - not syncing: VFS"
+        );
+        assert_eq!(excerpts[1].line, 3);
+    }
+
+    #[test]
+    fn registration_is_validated_and_reversible() {
+        let mut matcher = ConsoleMatcher::new();
+        assert_eq!(
+            matcher.register(ConsoleMarker::Custom, b""),
+            Err(PatternError::Empty)
+        );
+        assert_eq!(
+            matcher.register(ConsoleMarker::Custom, b"a\nb"),
+            Err(PatternError::Invalid)
+        );
+        assert_eq!(
+            matcher.register(ConsoleMarker::Custom, &[b'a'; CONSOLE_PATTERN_CAPACITY + 1]),
+            Err(PatternError::Invalid)
+        );
+
+        let id = matcher.register(ConsoleMarker::Custom, b"boot").unwrap();
+        assert!(matcher.unregister(id));
+        assert!(!matcher.unregister(id));
+        assert!(feed_all(&mut matcher, &[b"boot"]).is_empty());
+
+        for _ in 0..MAX_CONSOLE_PATTERNS {
+            matcher.register(ConsoleMarker::Custom, b"x").unwrap();
+        }
+        assert_eq!(
+            matcher.register(ConsoleMarker::Custom, b"y"),
+            Err(PatternError::Full)
+        );
+    }
+}
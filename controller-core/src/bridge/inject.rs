@@ -0,0 +1,156 @@
+//! Text typed into the Jetson console on behalf of the REPL.
+//!
+//! `console send "<text>"` takes a double-quoted string. [`unescape`] turns the
+//! text between the quotes into the bytes written to the UART, so automation
+//! can send control characters (`\r`, `\x03`, ...) without a raw terminal. The
+//! firmware queues the result on the same USB→UART path as host traffic.
+
+use core::fmt;
+
+use heapless::Vec;
+
+/// Largest payload a single `console send` can inject.
+pub const CONSOLE_SEND_CAPACITY: usize = 96;
+
+/// Byte appended by `console send ... newline`; terminals send CR for Enter.
+pub const CONSOLE_NEWLINE: u8 = b'\r';
+
+/// Decoded bytes ready for the UART.
+pub type ConsoleText = Vec<u8, CONSOLE_SEND_CAPACITY>;
+
+/// Reasons quoted console text cannot be decoded.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TextError {
+    /// Unknown or incomplete escape sequence starting at this byte offset.
+    Escape { offset: usize },
+    /// Decoded text exceeds [`CONSOLE_SEND_CAPACITY`].
+    TooLong,
+}
+
+impl fmt::Display for TextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TextError::Escape { offset } => write!(f, "bad escape at offset {offset}"),
+            TextError::TooLong => write!(f, "longer than {CONSOLE_SEND_CAPACITY} bytes"),
+        }
+    }
+}
+
+/// Decodes the text between the quotes of a quoted-string token.
+///
+/// Supports `\n`, `\r`, `\t`, `\0`, `\e` (escape), `\\`, `\"`, and `\xHH`.
+/// Every other character is copied through as UTF-8.
+///
+/// # Errors
+/// Returns [`TextError::Escape`] for an unknown or truncated escape and
+/// [`TextError::TooLong`] when the decoded bytes do not fit.
+pub fn unescape(raw: &str) -> Result<ConsoleText, TextError> {
+    let bytes = raw.as_bytes();
+    let mut text = ConsoleText::new();
+    let mut index = 0;
+
+    while let Some(&byte) = bytes.get(index) {
+        let (decoded, width) = if byte == b'\\' {
+            let escape = TextError::Escape { offset: index };
+            match bytes.get(index + 1) {
+                Some(b'n') => (b'\n', 2),
+                Some(b'r') => (b'\r', 2),
+                Some(b't') => (b'\t', 2),
+                Some(b'0') => (0, 2),
+                Some(b'e') => (0x1b, 2),
+                Some(b'\\') => (b'\\', 2),
+                Some(b'"') => (b'"', 2),
+                Some(b'x') => {
+                    let high = bytes.get(index + 2).and_then(|digit| hex_value(*digit));
+                    let low = bytes.get(index + 3).and_then(|digit| hex_value(*digit));
+                    let (Some(high), Some(low)) = (high, low) else {
+                        return Err(escape);
+                    };
+                    ((high << 4) | low, 4)
+                }
+                _ => return Err(escape),
+            }
+        } else {
+            (byte, 1)
+        };
+
+        text.push(decoded).map_err(|_| TextError::TooLong)?;
+        index += width;
+    }
+
+    Ok(text)
+}
+
+/// Writes bytes with the escapes [`unescape`] reads back, so the result can
+/// be pasted between the quotes of a command.
+#[derive(Copy, Clone, Debug)]
+pub struct Escaped<'a>(pub &'a [u8]);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for &byte in self.0 {
+            match byte {
+                b'\r' => f.write_str("\\r")?,
+                b'\n' => f.write_str("\\n")?,
+                b'\t' => f.write_str("\\t")?,
+                b'\\' => f.write_str("\\\\")?,
+                b'"' => f.write_str("\\\"")?,
+                b' '..=b'~' => write!(f, "{}", char::from(byte))?,
+                _ => write!(f, "\\x{byte:02x}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+const fn hex_value(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_escape_sequences() {
+        let Ok(text) = unescape(r#"root\r\n\t\"q\"\\\x03\e\0"#) else {
+            panic!("escapes should decode");
+        };
+        assert_eq!(text.as_slice(), b"root\r\n\t\"q\"\\\x03\x1b\0");
+        assert_eq!(unescape("").map(|text| text.len()), Ok(0));
+        assert_eq!(
+            unescape("caf\u{e9}").map(|text| text.len()),
+            Ok("caf\u{e9}".len())
+        );
+    }
+
+    #[test]
+    fn escaped_text_round_trips() {
+        let bytes = b"U-Boot \"2024\"\\\r\n\x1b\xff";
+        let escaped = std::format!("{}", Escaped(bytes));
+        assert_eq!(escaped, r#"U-Boot \"2024\"\\\r\n\x1b\xff"#);
+        let Ok(text) = unescape(&escaped) else {
+            panic!("quoted text should decode");
+        };
+        assert_eq!(text.as_slice(), bytes);
+    }
+
+    #[test]
+    fn rejects_bad_escapes_and_overlong_text() {
+        assert_eq!(unescape(r"ab\q"), Err(TextError::Escape { offset: 2 }));
+        assert_eq!(unescape(r"\x4"), Err(TextError::Escape { offset: 0 }));
+        assert_eq!(unescape(r"\xzz"), Err(TextError::Escape { offset: 0 }));
+        assert_eq!(unescape("tail\\"), Err(TextError::Escape { offset: 4 }));
+
+        let mut long = heapless::String::<{ CONSOLE_SEND_CAPACITY + 1 }>::new();
+        for _ in 0..=CONSOLE_SEND_CAPACITY {
+            let _ = long.push('a');
+        }
+        assert_eq!(unescape(&long), Err(TextError::TooLong));
+    }
+}
@@ -0,0 +1,288 @@
+//! Serial framing and break signalling for the Jetson console UART.
+//!
+//! The USB host picks the bridge framing through CDC ACM `SET_LINE_CODING`.
+//! [`LineCoding::from_cdc`] checks the request against what the controller's
+//! USART can produce so the firmware only reconfigures the port for settings it
+//! can honour; anything else is rejected and the previous framing stays active.
+//! `SEND_BREAK` requests map onto [`LineBreak`], which the REPL also uses to
+//! deliver magic sysrq sequences.
+
+use core::fmt;
+use core::time::Duration;
+
+/// Lowest baud rate accepted from the host.
+pub const LINE_BAUD_MIN: u32 = 300;
+/// Highest baud rate accepted from the host.
+pub const LINE_BAUD_MAX: u32 = 2_000_000;
+/// Break length used ahead of a magic sysrq key.
+pub const SYSRQ_BREAK: Duration = Duration::from_millis(100);
+
+/// Parity bit setting.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Parity {
+    None,
+    Odd,
+    Even,
+}
+
+impl Parity {
+    /// Single-letter form used in `8N1` style summaries.
+    #[must_use]
+    pub const fn letter(self) -> char {
+        match self {
+            Parity::None => 'N',
+            Parity::Odd => 'O',
+            Parity::Even => 'E',
+        }
+    }
+}
+
+/// Stop bit setting.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum StopBits {
+    One,
+    OnePointFive,
+    Two,
+}
+
+impl StopBits {
+    /// Text used in `8N1` style summaries.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            StopBits::One => "1",
+            StopBits::OnePointFive => "1.5",
+            StopBits::Two => "2",
+        }
+    }
+}
+
+/// Reasons a host line coding request cannot be applied.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LineCodingError {
+    /// Baud rate outside [`LINE_BAUD_MIN`]..=[`LINE_BAUD_MAX`].
+    Baud(u32),
+    /// Only 7 and 8 data bits are supported.
+    DataBits(u8),
+    /// Mark/space parity or an unknown CDC parity code.
+    Parity(u8),
+    /// Unknown CDC stop bit code.
+    StopBits(u8),
+}
+
+impl fmt::Display for LineCodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineCodingError::Baud(baud) => write!(f, "unsupported baud {baud}"),
+            LineCodingError::DataBits(bits) => write!(f, "unsupported data bits {bits}"),
+            LineCodingError::Parity(code) => write!(f, "unsupported parity code {code}"),
+            LineCodingError::StopBits(code) => write!(f, "unsupported stop bits code {code}"),
+        }
+    }
+}
+
+/// Active UART framing.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LineCoding {
+    pub baud: u32,
+    pub data_bits: u8,
+    pub parity: Parity,
+    pub stop_bits: StopBits,
+}
+
+impl LineCoding {
+    /// Jetson debug console default: 115200 baud, 8N1.
+    pub const DEFAULT: Self = Self {
+        baud: 115_200,
+        data_bits: 8,
+        parity: Parity::None,
+        stop_bits: StopBits::One,
+    };
+
+    /// Returns the same framing at another baud rate.
+    #[must_use]
+    pub const fn with_baud(self, baud: u32) -> Self {
+        Self { baud, ..self }
+    }
+
+    /// Validates a CDC ACM line coding request.
+    ///
+    /// `stop_bits` and `parity` use the CDC PSTN encodings (`bCharFormat`,
+    /// `bParityType`).
+    ///
+    /// # Errors
+    /// Returns a [`LineCodingError`] naming the first field the USART cannot
+    /// produce.
+    pub const fn from_cdc(
+        baud: u32,
+        stop_bits: u8,
+        parity: u8,
+        data_bits: u8,
+    ) -> Result<Self, LineCodingError> {
+        if baud < LINE_BAUD_MIN || baud > LINE_BAUD_MAX {
+            return Err(LineCodingError::Baud(baud));
+        }
+        if data_bits != 7 && data_bits != 8 {
+            return Err(LineCodingError::DataBits(data_bits));
+        }
+        let parity_setting = match parity {
+            0 => Parity::None,
+            1 => Parity::Odd,
+            2 => Parity::Even,
+            _ => return Err(LineCodingError::Parity(parity)),
+        };
+        let stop_setting = match stop_bits {
+            0 => StopBits::One,
+            1 => StopBits::OnePointFive,
+            2 => StopBits::Two,
+            _ => return Err(LineCodingError::StopBits(stop_bits)),
+        };
+
+        Ok(Self {
+            baud,
+            data_bits,
+            parity: parity_setting,
+            stop_bits: stop_setting,
+        })
+    }
+}
+
+impl Default for LineCoding {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl fmt::Display for LineCoding {
+    /// Formats as `115200-8N1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{}{}{}",
+            self.baud,
+            self.data_bits,
+            self.parity.letter(),
+            self.stop_bits.label()
+        )
+    }
+}
+
+/// Break condition requested on the Jetson UART.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LineBreak {
+    /// Hold TX low for the given time.
+    Timed(Duration),
+    /// Hold TX low until a [`LineBreak::Release`] arrives.
+    Hold,
+    /// End a break that is in progress.
+    Release,
+}
+
+impl LineBreak {
+    /// Interprets the `wValue` of a CDC ACM `SEND_BREAK` request.
+    ///
+    /// `0xFFFF` holds the break until the host sends `0`, which releases it;
+    /// any other value is a duration in milliseconds.
+    #[must_use]
+    pub fn from_cdc(value: u16) -> Self {
+        match value {
+            0 => LineBreak::Release,
+            0xFFFF => LineBreak::Hold,
+            millis => LineBreak::Timed(Duration::from_millis(u64::from(millis))),
+        }
+    }
+}
+
+/// Magic sysrq command key sent after a break.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SysRqKey(u8);
+
+impl SysRqKey {
+    /// Accepts a single ASCII letter or digit; letters are folded to lower case.
+    #[must_use]
+    pub const fn new(key: u8) -> Option<Self> {
+        if key.is_ascii_alphanumeric() {
+            Some(Self(key.to_ascii_lowercase()))
+        } else {
+            None
+        }
+    }
+
+    /// The byte written to the UART after the break.
+    #[must_use]
+    pub const fn byte(self) -> u8 {
+        self.0
+    }
+}
+
+impl fmt::Display for SysRqKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Write::write_char(f, char::from(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn accepts_common_console_settings() {
+        assert_eq!(
+            LineCoding::from_cdc(115_200, 0, 0, 8),
+            Ok(LineCoding::DEFAULT)
+        );
+
+        let Ok(coding) = LineCoding::from_cdc(921_600, 2, 2, 7) else {
+            panic!("7E2 should be accepted");
+        };
+        assert_eq!(coding.parity, Parity::Even);
+        assert_eq!(coding.stop_bits, StopBits::Two);
+        assert_eq!(coding.to_string(), "921600-7E2");
+        assert_eq!(
+            LineCoding::from_cdc(9_600, 1, 1, 8).map(|coding| coding.to_string()),
+            Ok("9600-8O1.5".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_settings_the_usart_cannot_produce() {
+        assert_eq!(
+            LineCoding::from_cdc(0, 0, 0, 8),
+            Err(LineCodingError::Baud(0))
+        );
+        assert_eq!(
+            LineCoding::from_cdc(4_000_000, 0, 0, 8),
+            Err(LineCodingError::Baud(4_000_000))
+        );
+        assert_eq!(
+            LineCoding::from_cdc(115_200, 0, 0, 5),
+            Err(LineCodingError::DataBits(5))
+        );
+        assert_eq!(
+            LineCoding::from_cdc(115_200, 0, 3, 8),
+            Err(LineCodingError::Parity(3))
+        );
+        assert_eq!(
+            LineCoding::from_cdc(115_200, 7, 0, 8),
+            Err(LineCodingError::StopBits(7))
+        );
+    }
+
+    #[test]
+    fn maps_cdc_break_requests() {
+        assert_eq!(LineBreak::from_cdc(0), LineBreak::Release);
+        assert_eq!(LineBreak::from_cdc(0xFFFF), LineBreak::Hold);
+        assert_eq!(
+            LineBreak::from_cdc(250),
+            LineBreak::Timed(Duration::from_millis(250))
+        );
+    }
+
+    #[test]
+    fn sysrq_keys_are_single_alphanumerics() {
+        assert_eq!(SysRqKey::new(b'S').map(SysRqKey::byte), Some(b's'));
+        assert_eq!(SysRqKey::new(b'9').map(SysRqKey::byte), Some(b'9'));
+        assert_eq!(SysRqKey::new(b'?'), None);
+    }
+}
@@ -4,9 +4,33 @@
 //! orchestrator bridge waits, and records telemetry events when console traffic
 //! arrives. Firmware and host targets supply the transport plumbing and simply
 //! feed observed events into the monitor.
+//!
+//! The [`console`] submodule goes one level deeper and inspects the Jetson
+//! console bytes themselves for boot, login, and panic markers, while
+//! [`scrollback`] keeps recent console output for later replay,
+//! [`line_coding`] describes the UART framing requested by the host,
+//! [`inject`] decodes text the REPL types into the console, [`attach`] spots
+//! the escape that ends a `console attach` session, [`autobaud`] finds the
+//! console baud rate for `console baud auto`, [`autoboot`]
+//! stops U-Boot at its prompt after `reboot to-bootloader`, [`stats`]
+//! counts bridge traffic and transport errors, and [`release`] decides when
+//! console output is convincing enough to release REC.
 
 #![cfg_attr(not(test), allow(dead_code))]
 
+pub mod attach;
+pub mod autobaud;
+pub mod autoboot;
+pub mod console;
+pub mod inject;
+pub mod line_coding;
+pub mod release;
+pub mod scrollback;
+pub mod stats;
+
+pub use console::{ConsoleEvent, ConsoleMarker, ConsoleMatcher, PatternError, PatternId};
+pub use release::{ReleaseCriterion, ReleaseGate};
+
 use crate::orchestrator::EventId;
 use crate::telemetry::{TelemetryEventKind, TelemetryInstant, TelemetryPayload, TelemetryRecorder};
 
@@ -63,6 +87,7 @@ where
     last_rx: Option<TInstant>,
     last_tx: Option<TInstant>,
     link_attached: bool,
+    release: ReleaseGate<TInstant>,
 }
 
 impl<TInstant> BridgeActivityMonitor<TInstant>
@@ -72,22 +97,42 @@ where
     /// Creates a new monitor with no observed activity.
     #[must_use]
     pub const fn new() -> Self {
+        Self::with_release_criterion(ReleaseCriterion::AnyByte)
+    }
+
+    /// Creates a monitor that releases REC only once `criterion` is met.
+    #[must_use]
+    pub const fn with_release_criterion(criterion: ReleaseCriterion) -> Self {
         Self {
             pending_recovery_release: false,
             last_rx: None,
             last_tx: None,
             link_attached: false,
+            release: ReleaseGate::new(criterion),
         }
     }
 
+    /// Returns the criterion console activity must meet to release REC.
+    pub fn release_criterion(&self) -> ReleaseCriterion {
+        self.release.criterion()
+    }
+
+    /// Replaces the release criterion, discarding progress toward the old one.
+    pub fn set_release_criterion(&mut self, criterion: ReleaseCriterion) {
+        self.release.set_criterion(criterion);
+    }
+
     /// Returns `true` when a recovery sequence is waiting on bridge activity.
     pub fn is_pending(&self) -> bool {
         self.pending_recovery_release
     }
 
     /// Marks the monitor as waiting (or not) for activity before releasing REC.
+    ///
+    /// Each call starts the release criterion afresh.
     pub fn set_pending(&mut self, pending: bool) {
         self.pending_recovery_release = pending;
+        self.release.reset();
     }
 
     /// Returns the timestamp of the last Jetson→USB frame observed.
@@ -101,11 +146,39 @@ where
     }
 
     /// Processes a single activity event and produces a bridge update.
+    ///
+    /// Only frame sizes are available here, so content-based release criteria
+    /// never pass; use [`Self::process_frame`] when the bytes are at hand.
     pub fn process_event(
         &mut self,
         event: BridgeActivityEvent<TInstant>,
         telemetry: &mut TelemetryRecorder<TInstant>,
     ) -> Option<BridgeActivityUpdate<TInstant>>
+    where
+        TInstant: TelemetryInstant,
+    {
+        self.process(event, None, telemetry)
+    }
+
+    /// Processes an activity event together with the bytes it carried.
+    pub fn process_frame(
+        &mut self,
+        event: BridgeActivityEvent<TInstant>,
+        payload: &[u8],
+        telemetry: &mut TelemetryRecorder<TInstant>,
+    ) -> Option<BridgeActivityUpdate<TInstant>>
+    where
+        TInstant: TelemetryInstant,
+    {
+        self.process(event, Some(payload), telemetry)
+    }
+
+    fn process(
+        &mut self,
+        event: BridgeActivityEvent<TInstant>,
+        payload: Option<&[u8]>,
+        telemetry: &mut TelemetryRecorder<TInstant>,
+    ) -> Option<BridgeActivityUpdate<TInstant>>
     where
         TInstant: TelemetryInstant,
     {
@@ -114,7 +187,7 @@ where
         }
 
         self.track(&event);
-        let release = self.handle_recovery_release(&event);
+        let release = self.handle_recovery_release(&event, payload);
         let telemetry_event = Self::record_telemetry(telemetry, &event);
 
         Some(BridgeActivityUpdate {
@@ -155,10 +228,20 @@ where
         }
     }
 
-    fn handle_recovery_release(&mut self, event: &BridgeActivityEvent<TInstant>) -> bool {
+    fn handle_recovery_release(
+        &mut self,
+        event: &BridgeActivityEvent<TInstant>,
+        payload: Option<&[u8]>,
+    ) -> bool
+    where
+        TInstant: TelemetryInstant,
+    {
         if event.kind != BridgeActivityKind::JetsonToUsb || !self.pending_recovery_release {
             return false;
         }
+        if !self.release.observe(event.timestamp, event.bytes, payload) {
+            return false;
+        }
 
         self.pending_recovery_release = false;
         true
@@ -238,6 +321,34 @@ mod tests {
         assert_eq!(update.telemetry_event, Some(record.id));
     }
 
+    #[test]
+    fn release_criterion_holds_rec_through_line_noise() {
+        let mut monitor = BridgeActivityMonitor::<MicrosInstant>::with_release_criterion(
+            ReleaseCriterion::Pattern(b"Jetson"),
+        );
+        let mut telemetry = TelemetryRecorder::<MicrosInstant>::new();
+        monitor.set_pending(true);
+
+        let frame = |timestamp, bytes| BridgeActivityEvent {
+            kind: BridgeActivityKind::JetsonToUsb,
+            timestamp: MicrosInstant::from_micros(timestamp),
+            bytes,
+        };
+
+        let noise = monitor
+            .process_frame(frame(1_000, 1), &[0xff], &mut telemetry)
+            .expect("activity update missing");
+        assert!(!noise.release_recovery);
+        assert!(monitor.is_pending());
+        assert_eq!(monitor.last_rx(), Some(MicrosInstant::from_micros(1_000)));
+
+        let banner = monitor
+            .process_frame(frame(2_000, 13), b"\r\nJetson UEFI", &mut telemetry)
+            .expect("activity update missing");
+        assert!(banner.release_recovery);
+        assert!(!monitor.is_pending());
+    }
+
     #[test]
     fn usb_to_jetson_activity_updates_tx_timestamp_only() {
         let mut monitor = BridgeActivityMonitor::<MicrosInstant>::new();
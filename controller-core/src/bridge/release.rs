@@ -0,0 +1,248 @@
+//! Deciding when console output is real enough to release REC.
+//!
+//! `recovery now` holds the REC strap until the Jetson starts talking on its
+//! console. Taken literally, a single byte of line noise at power-up counts,
+//! so [`ReleaseCriterion`] lets the platform ask for more convincing evidence:
+//! a burst of bytes within a window, a mostly printable sample, or a specific
+//! banner. [`ReleaseGate`] applies the criterion to the Jetson→USB stream.
+
+use core::fmt;
+use core::time::Duration;
+
+use heapless::Vec;
+
+use crate::telemetry::TelemetryInstant;
+
+/// Longest pattern accepted by [`ReleaseCriterion::Pattern`].
+pub const RELEASE_PATTERN_CAPACITY: usize = 32;
+
+/// Evidence required before console activity releases REC.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ReleaseCriterion {
+    /// Any non-empty frame releases REC.
+    #[default]
+    AnyByte,
+    /// At least `bytes` bytes must arrive within `window` of the first one.
+    ByteCount { bytes: u32, window: Duration },
+    /// A sample of at least `sample` bytes must be `percent` printable ASCII.
+    /// Samples that fall short are discarded and a fresh one is started.
+    PrintableRatio { sample: u32, percent: u8 },
+    /// The console must print this text; longer patterns never match.
+    Pattern(&'static [u8]),
+}
+
+impl fmt::Display for ReleaseCriterion {
+    /// Formats as `any`, `bytes:16/100ms`, `printable:80%/32`, or
+    /// `pattern:"U-Boot"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::AnyByte => f.write_str("any"),
+            Self::ByteCount { bytes, window } => {
+                write!(f, "bytes:{bytes}/{}ms", window.as_millis())
+            }
+            Self::PrintableRatio { sample, percent } => {
+                write!(f, "printable:{percent}%/{sample}")
+            }
+            Self::Pattern(pattern) => {
+                f.write_str("pattern:\"")?;
+                for &byte in pattern {
+                    match byte {
+                        b' '..=b'~' if byte != b'"' && byte != b'\\' => {
+                            write!(f, "{}", char::from(byte))?;
+                        }
+                        _ => write!(f, "\\x{byte:02x}")?,
+                    }
+                }
+                f.write_str("\"")
+            }
+        }
+    }
+}
+
+/// Running state for one [`ReleaseCriterion`].
+#[derive(Clone, Debug)]
+pub struct ReleaseGate<TInstant> {
+    criterion: ReleaseCriterion,
+    window_start: Option<TInstant>,
+    counted: u32,
+    printable: u32,
+    tail: Vec<u8, RELEASE_PATTERN_CAPACITY>,
+}
+
+impl<TInstant> ReleaseGate<TInstant>
+where
+    TInstant: Copy,
+{
+    /// Creates a gate with no observed traffic.
+    #[must_use]
+    pub const fn new(criterion: ReleaseCriterion) -> Self {
+        Self {
+            criterion,
+            window_start: None,
+            counted: 0,
+            printable: 0,
+            tail: Vec::new(),
+        }
+    }
+
+    /// Returns the active criterion.
+    #[must_use]
+    pub const fn criterion(&self) -> ReleaseCriterion {
+        self.criterion
+    }
+
+    /// Replaces the criterion and forgets any progress toward the old one.
+    pub fn set_criterion(&mut self, criterion: ReleaseCriterion) {
+        self.criterion = criterion;
+        self.reset();
+    }
+
+    /// Forgets any progress toward the criterion.
+    pub fn reset(&mut self) {
+        self.window_start = None;
+        self.counted = 0;
+        self.printable = 0;
+        self.tail.clear();
+    }
+
+    /// Feeds one console frame, returning `true` once the criterion is met.
+    ///
+    /// `payload` carries the frame contents when the platform has them;
+    /// content-based criteria never pass without it.
+    pub fn observe(&mut self, timestamp: TInstant, bytes: usize, payload: Option<&[u8]>) -> bool
+    where
+        TInstant: TelemetryInstant,
+    {
+        let bytes = u32::try_from(bytes).unwrap_or(u32::MAX);
+        match self.criterion {
+            ReleaseCriterion::AnyByte => bytes > 0,
+            ReleaseCriterion::ByteCount {
+                bytes: required,
+                window,
+            } => {
+                let expired = self
+                    .window_start
+                    .is_none_or(|start| timestamp.saturating_duration_since(start) > window);
+                if expired {
+                    self.window_start = Some(timestamp);
+                    self.counted = 0;
+                }
+                self.counted = self.counted.saturating_add(bytes);
+                self.counted >= required
+            }
+            ReleaseCriterion::PrintableRatio { sample, percent } => {
+                let Some(payload) = payload else {
+                    return false;
+                };
+                self.sample_printable(payload, sample, percent)
+            }
+            ReleaseCriterion::Pattern(pattern) => {
+                let Some(payload) = payload else {
+                    return false;
+                };
+                self.scan_pattern(payload, pattern)
+            }
+        }
+    }
+
+    fn sample_printable(&mut self, payload: &[u8], sample: u32, percent: u8) -> bool {
+        for &byte in payload {
+            self.counted = self.counted.saturating_add(1);
+            if is_console_text(byte) {
+                self.printable = self.printable.saturating_add(1);
+            }
+            if self.counted < sample.max(1) {
+                continue;
+            }
+            let printable = u64::from(self.printable) * 100;
+            if printable >= u64::from(self.counted) * u64::from(percent) {
+                return true;
+            }
+            self.counted = 0;
+            self.printable = 0;
+        }
+        false
+    }
+
+    fn scan_pattern(&mut self, payload: &[u8], pattern: &[u8]) -> bool {
+        if pattern.is_empty() || pattern.len() > RELEASE_PATTERN_CAPACITY {
+            return false;
+        }
+        for &byte in payload {
+            if self.tail.is_full() {
+                self.tail.remove(0);
+            }
+            let _ = self.tail.push(byte);
+            if self.tail.ends_with(pattern) {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl<TInstant> Default for ReleaseGate<TInstant>
+where
+    TInstant: Copy,
+{
+    fn default() -> Self {
+        Self::new(ReleaseCriterion::AnyByte)
+    }
+}
+
+/// Printable ASCII plus the whitespace a console normally emits.
+const fn is_console_text(byte: u8) -> bool {
+    matches!(byte, b' '..=b'~' | b'\r' | b'\n' | b'\t')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::test_support::MillisInstant;
+
+    #[test]
+    fn byte_count_restarts_when_the_window_expires() {
+        let mut gate = ReleaseGate::new(ReleaseCriterion::ByteCount {
+            bytes: 16,
+            window: Duration::from_millis(100),
+        });
+        assert!(!gate.observe(MillisInstant(0), 1, None));
+        assert!(!gate.observe(MillisInstant(500), 10, None));
+        assert!(gate.observe(MillisInstant(550), 6, None));
+    }
+
+    #[test]
+    fn content_criteria_skip_noise() {
+        let mut gate = ReleaseGate::new(ReleaseCriterion::PrintableRatio {
+            sample: 8,
+            percent: 75,
+        });
+        assert!(!gate.observe(MillisInstant(0), 8, None));
+        assert!(!gate.observe(
+            MillisInstant(0),
+            8,
+            Some(&[0xff, 0x00, 0xfe, b'a', 0x80, 0x81, 0x82, 0x83])
+        ));
+        assert!(gate.observe(MillisInstant(1), 8, Some(b"\r\nU-Boot")));
+
+        let mut gate = ReleaseGate::new(ReleaseCriterion::Pattern(b"U-Boot"));
+        assert!(!gate.observe(MillisInstant(0), 4, Some(b"\xffU-B")));
+        assert!(gate.observe(MillisInstant(1), 6, Some(b"oot 20")));
+    }
+
+    #[test]
+    fn criteria_display_compactly() {
+        let criterion = ReleaseCriterion::ByteCount {
+            bytes: 16,
+            window: Duration::from_millis(100),
+        };
+        assert_eq!(std::format!("{criterion}"), "bytes:16/100ms");
+        let criterion = ReleaseCriterion::PrintableRatio {
+            sample: 32,
+            percent: 80,
+        };
+        assert_eq!(std::format!("{criterion}"), "printable:80%/32");
+        let criterion = ReleaseCriterion::Pattern(b"U-Boot\r\n");
+        assert_eq!(std::format!("{criterion}"), "pattern:\"U-Boot\\x0d\\x0a\"");
+    }
+}
@@ -0,0 +1,433 @@
+//! Console scrollback retained on the controller.
+//!
+//! [`ConsoleScrollback`] keeps the most recent Jetson→USB console bytes in a
+//! fixed-size ring together with per-line metadata: the controller timestamp of
+//! the first byte and the run the line belongs to. A run starts every time the
+//! platform begins a strap sequence, so `console since <run>` can show what the
+//! Jetson printed after a given reboot. The raw bytes, including line
+//! terminators, are kept verbatim so a host that opens the bridge late can be
+//! handed an exact replay of what it missed.
+
+use core::fmt;
+use core::ops::Range;
+
+use heapless::Deque;
+
+use crate::power::{saturating_millis, write_span};
+use crate::telemetry::TelemetryInstant;
+
+/// Bytes of console output retained by default.
+pub const SCROLLBACK_CAPACITY: usize = 8 * 1024;
+/// Line records retained by default.
+pub const SCROLLBACK_MAX_LINES: usize = 256;
+/// Longest line kept as a single record; longer output continues on a new record.
+pub const SCROLLBACK_LINE_LIMIT: usize = 256;
+/// Lines shown by `console tail` when no count is given.
+pub const DEFAULT_TAIL_LINES: u32 = 20;
+
+/// Identifier of a run; run `0` covers everything since controller power-on.
+pub type RunId = u32;
+
+/// Lines requested through the REPL.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ScrollbackSelection {
+    /// The most recent `n` lines.
+    Tail(u32),
+    /// Every retained line captured during or after the given run.
+    SinceRun(RunId),
+}
+
+#[derive(Copy, Clone, Debug)]
+struct LineRecord<TInstant> {
+    /// Absolute offset of the first byte.
+    start: u32,
+    /// Raw length including any line terminator.
+    raw_len: u16,
+    /// Length of the text without the terminator.
+    text_len: u16,
+    terminated: bool,
+    run: RunId,
+    timestamp: TInstant,
+}
+
+/// One retained console line.
+#[derive(Copy, Clone, Debug)]
+pub struct ScrollbackLine<'a, TInstant> {
+    /// Sequence number of the line; increases by one per line.
+    pub seq: u32,
+    pub run: RunId,
+    /// Controller time at which the first byte arrived.
+    pub timestamp: TInstant,
+    /// `false` while the Jetson is still printing this line.
+    pub terminated: bool,
+    head: &'a [u8],
+    tail: &'a [u8],
+}
+
+impl<TInstant> ScrollbackLine<'_, TInstant>
+where
+    TInstant: Copy + TelemetryInstant,
+{
+    /// Text bytes without the terminator, split where the ring wraps.
+    #[must_use]
+    pub fn segments(&self) -> [&[u8]; 2] {
+        [self.head, self.tail]
+    }
+
+    /// Length of the line text in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.head.len() + self.tail.len()
+    }
+
+    /// Returns `true` for blank lines.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Writes the line as `[r<run> <time>] <text>`, with `time` measured from `origin`.
+    ///
+    /// Bytes outside printable ASCII are shown as `.`.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure.
+    pub fn write_to<W: fmt::Write>(&self, writer: &mut W, origin: TInstant) -> fmt::Result {
+        write!(writer, "[r{} ", self.run)?;
+        write_span(
+            writer,
+            saturating_millis(self.timestamp.saturating_duration_since(origin)),
+        )?;
+        writer.write_str("] ")?;
+        for &byte in self.head.iter().chain(self.tail) {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                writer.write_char(char::from(byte))?;
+            } else {
+                writer.write_char('.')?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Ring of recent console output with line boundaries.
+///
+/// `BYTES` must be a power of two so absolute offsets stay valid when they wrap.
+pub struct ConsoleScrollback<
+    TInstant,
+    const BYTES: usize = SCROLLBACK_CAPACITY,
+    const LINES: usize = SCROLLBACK_MAX_LINES,
+> {
+    bytes: [u8; BYTES],
+    written: u32,
+    lines: Deque<LineRecord<TInstant>, LINES>,
+    first_seq: u32,
+    run: RunId,
+    open: bool,
+    last_was_cr: bool,
+    evicted: u32,
+}
+
+impl<TInstant, const BYTES: usize, const LINES: usize> ConsoleScrollback<TInstant, BYTES, LINES>
+where
+    TInstant: Copy + TelemetryInstant,
+{
+    const LINE_LIMIT: usize = if SCROLLBACK_LINE_LIMIT < BYTES / 2 {
+        SCROLLBACK_LINE_LIMIT
+    } else {
+        BYTES / 2
+    };
+
+    /// Creates an empty scrollback in run `0`.
+    #[must_use]
+    pub const fn new() -> Self {
+        const { assert!(BYTES.is_power_of_two() && BYTES <= 1 << 16) };
+        Self {
+            bytes: [0; BYTES],
+            written: 0,
+            lines: Deque::new(),
+            first_seq: 0,
+            run: 0,
+            open: false,
+            last_was_cr: false,
+            evicted: 0,
+        }
+    }
+
+    /// Starts a new run and returns its identifier.
+    ///
+    /// A line that is still being printed stays with the run it started in.
+    pub fn begin_run(&mut self) -> RunId {
+        self.run = self.run.wrapping_add(1);
+        self.run
+    }
+
+    /// Identifier of the run new lines are assigned to.
+    #[must_use]
+    pub const fn current_run(&self) -> RunId {
+        self.run
+    }
+
+    /// Number of lines currently retained.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Returns `true` when nothing has been retained.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Lines dropped to make room since power-on.
+    #[must_use]
+    pub const fn evicted_lines(&self) -> u32 {
+        self.evicted
+    }
+
+    /// Appends console bytes received at `now`.
+    ///
+    /// `\r\n`, `\r`, and `\n` all end a line; the terminator is stored with the
+    /// line it ends so replays reproduce the original stream.
+    pub fn push(&mut self, bytes: &[u8], now: TInstant) {
+        for &byte in bytes {
+            let crlf = byte == b'\n' && self.last_was_cr;
+            self.last_was_cr = byte == b'\r';
+
+            if crlf {
+                if let Some(last) = self.lines.back_mut() {
+                    last.raw_len = last.raw_len.saturating_add(1);
+                }
+                self.store(byte);
+                continue;
+            }
+
+            let needs_line = !self.open
+                || self
+                    .lines
+                    .back()
+                    .is_none_or(|last| usize::from(last.text_len) >= Self::LINE_LIMIT);
+            if needs_line {
+                self.open_line(now);
+            }
+
+            self.store(byte);
+            if let Some(last) = self.lines.back_mut() {
+                last.raw_len = last.raw_len.saturating_add(1);
+                if matches!(byte, b'\r' | b'\n') {
+                    last.terminated = true;
+                    self.open = false;
+                } else {
+                    last.text_len = last.text_len.saturating_add(1);
+                }
+            }
+        }
+    }
+
+    /// Sequence numbers covered by `selection`.
+    #[must_use]
+    pub fn select(&self, selection: ScrollbackSelection) -> Range<u32> {
+        let end = self.end_seq();
+        let start = match selection {
+            ScrollbackSelection::Tail(count) => {
+                let count = u32::try_from(self.lines.len()).map_or(count, |len| count.min(len));
+                end.wrapping_sub(count)
+            }
+            ScrollbackSelection::SinceRun(run) => self
+                .lines
+                .iter()
+                .position(|line| line.run >= run)
+                .and_then(|index| u32::try_from(index).ok())
+                .map_or(end, |index| self.first_seq.wrapping_add(index)),
+        };
+        start..end
+    }
+
+    /// Returns the line with sequence number `seq`, if it is still retained.
+    #[must_use]
+    pub fn line(&self, seq: u32) -> Option<ScrollbackLine<'_, TInstant>> {
+        let index = usize::try_from(seq.wrapping_sub(self.first_seq)).ok()?;
+        let record = self.lines.iter().nth(index)?;
+        let (head, tail) = self.span(record.start, usize::from(record.text_len));
+        Some(ScrollbackLine {
+            seq,
+            run: record.run,
+            timestamp: record.timestamp,
+            terminated: record.terminated,
+            head,
+            tail,
+        })
+    }
+
+    /// Absolute byte offsets of the retained raw stream, oldest first.
+    #[must_use]
+    pub fn raw_window(&self) -> Range<u32> {
+        let start = self.lines.front().map_or(self.written, |line| line.start);
+        start..self.written
+    }
+
+    /// Copies raw bytes starting at `offset` (up to `end`) into `out`.
+    ///
+    /// Returns the number of bytes copied, or `None` once `offset` has been
+    /// overwritten.
+    pub fn read_raw(&self, offset: u32, end: u32, out: &mut [u8]) -> Option<usize> {
+        let window = self.raw_window();
+        let behind = window.end.wrapping_sub(offset);
+        if behind > window.end.wrapping_sub(window.start) {
+            return None;
+        }
+
+        let available = usize::try_from(end.wrapping_sub(offset).min(behind)).ok()?;
+        let count = available.min(out.len());
+        let (head, tail) = self.span(offset, count);
+        out[..head.len()].copy_from_slice(head);
+        out[head.len()..count].copy_from_slice(tail);
+        Some(count)
+    }
+
+    fn end_seq(&self) -> u32 {
+        let len = u32::try_from(self.lines.len()).unwrap_or(u32::MAX);
+        self.first_seq.wrapping_add(len)
+    }
+
+    fn open_line(&mut self, now: TInstant) {
+        if self.lines.is_full() {
+            self.evict_front();
+        }
+        let _ = self.lines.push_back(LineRecord {
+            start: self.written,
+            raw_len: 0,
+            text_len: 0,
+            terminated: false,
+            run: self.run,
+            timestamp: now,
+        });
+        self.open = true;
+    }
+
+    fn store(&mut self, byte: u8) {
+        while self.lines.front().is_some_and(|line| {
+            usize::try_from(self.written.wrapping_sub(line.start)).is_ok_and(|age| age >= BYTES)
+        }) {
+            self.evict_front();
+        }
+
+        let slot = Self::slot(self.written);
+        self.bytes[slot] = byte;
+        self.written = self.written.wrapping_add(1);
+    }
+
+    fn evict_front(&mut self) {
+        if self.lines.pop_front().is_some() {
+            self.first_seq = self.first_seq.wrapping_add(1);
+            self.evicted = self.evicted.saturating_add(1);
+        }
+        if self.lines.is_empty() {
+            self.open = false;
+        }
+    }
+
+    fn slot(offset: u32) -> usize {
+        usize::try_from(offset).map_or(0, |offset| offset % BYTES)
+    }
+
+    fn span(&self, start: u32, len: usize) -> (&[u8], &[u8]) {
+        let first = Self::slot(start);
+        let head_len = len.min(BYTES - first);
+        (
+            &self.bytes[first..first + head_len],
+            &self.bytes[..len - head_len],
+        )
+    }
+}
+
+impl<TInstant, const BYTES: usize, const LINES: usize> Default
+    for ConsoleScrollback<TInstant, BYTES, LINES>
+where
+    TInstant: Copy + TelemetryInstant,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::test_support::MillisInstant;
+    use std::string::String;
+    use std::vec::Vec as StdVec;
+
+    fn render<const B: usize, const L: usize>(
+        scrollback: &ConsoleScrollback<MillisInstant, B, L>,
+        selection: ScrollbackSelection,
+    ) -> StdVec<String> {
+        scrollback
+            .select(selection)
+            .filter_map(|seq| scrollback.line(seq))
+            .map(|line| {
+                let mut text = String::new();
+                line.write_to(&mut text, MillisInstant(0)).unwrap();
+                text
+            })
+            .collect()
+    }
+
+    #[test]
+    fn keeps_lines_with_runs_and_timestamps() {
+        let mut scrollback = ConsoleScrollback::<MillisInstant>::new();
+        scrollback.push(b"U-Boot 2020.04\r\n\r\n", MillisInstant(1_200));
+        assert_eq!(scrollback.begin_run(), 1);
+        scrollback.push(b"Jetson UEFI\r\nlog", MillisInstant(65_000));
+        scrollback.push(b"in: ", MillisInstant(66_000));
+
+        assert_eq!(
+            render(&scrollback, ScrollbackSelection::Tail(10)),
+            [
+                "[r0 1.2s] U-Boot 2020.04",
+                "[r0 1.2s] ",
+                "[r1 65.0s] Jetson UEFI",
+                "[r1 65.0s] login: ",
+            ]
+        );
+        assert_eq!(
+            render(&scrollback, ScrollbackSelection::SinceRun(1)),
+            ["[r1 65.0s] Jetson UEFI", "[r1 65.0s] login: "]
+        );
+        assert!(render(&scrollback, ScrollbackSelection::SinceRun(2)).is_empty());
+        assert_eq!(render(&scrollback, ScrollbackSelection::Tail(1)).len(), 1);
+    }
+
+    #[test]
+    fn evicts_oldest_lines_and_replays_raw_bytes() {
+        let mut scrollback = ConsoleScrollback::<MillisInstant, 16, 8>::new();
+        scrollback.push(b"one\r\ntwo\r\nthree\r\nfour\n", MillisInstant(0));
+
+        assert_eq!(scrollback.evicted_lines(), 2);
+        assert_eq!(
+            render(&scrollback, ScrollbackSelection::Tail(8)),
+            ["[r0 0ms] three", "[r0 0ms] four"]
+        );
+
+        let window = scrollback.raw_window();
+        let mut out = [0u8; 32];
+        let count = scrollback
+            .read_raw(window.start, window.end, &mut out)
+            .expect("window start is retained");
+        assert_eq!(&out[..count], b"three\r\nfour\n");
+        assert_eq!(
+            scrollback.read_raw(window.start.wrapping_sub(1), window.end, &mut out),
+            None
+        );
+    }
+
+    #[test]
+    fn long_lines_continue_on_new_records() {
+        let mut scrollback = ConsoleScrollback::<MillisInstant, 16, 8>::new();
+        scrollback.push(b"abcdefghij\n", MillisInstant(0));
+
+        let lines = render(&scrollback, ScrollbackSelection::Tail(8));
+        assert_eq!(lines, ["[r0 0ms] abcdefgh", "[r0 0ms] ij"]);
+    }
+}
@@ -0,0 +1,265 @@
+//! Bridge health counters.
+//!
+//! [`BridgeStatistics`] counts the bytes and frames moved in each direction
+//! along with everything that went wrong on the way: UART receive errors,
+//! failed UART and USB writes, frames dropped on overflow, and sends that
+//! found the bridge queue full. A steady stream of framing errors usually
+//! means a baud mismatch, while noise and overruns point at the cable or a
+//! host that is not keeping up. `bridge stats` prints the counters through
+//! [`BridgeStatsFormatter`] and `status` summarizes them.
+
+use core::fmt;
+
+use super::BridgeActivityKind;
+
+/// Something that went wrong while forwarding bridge traffic.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BridgeFault {
+    /// The UART saw a missing stop bit, usually a baud mismatch.
+    UartFraming,
+    /// The UART sampled noise in the middle of a bit.
+    UartNoise,
+    /// A received byte was lost because the previous one was not read in time.
+    UartOverrun,
+    /// A received byte failed its parity check.
+    UartParity,
+    /// Writing a host frame to the Jetson UART failed.
+    UartWrite,
+    /// Writing a console frame to the USB host failed.
+    UsbWrite,
+    /// A frame was discarded before reaching the other side.
+    Dropped(BridgeActivityKind),
+    /// A frame found the bridge queue full and had to wait for room.
+    QueueFull(BridgeActivityKind),
+}
+
+/// Traffic counters for one bridge direction.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct BridgeDirectionStats {
+    pub bytes: u32,
+    pub frames: u32,
+    /// Frames discarded before reaching the other side.
+    pub dropped: u32,
+    /// Frames that found the queue full and had to wait.
+    pub queue_full: u32,
+}
+
+/// Copy of the bridge counters.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct BridgeStatsSnapshot {
+    /// Host to Jetson traffic.
+    pub usb_to_jetson: BridgeDirectionStats,
+    /// Jetson to host traffic.
+    pub jetson_to_usb: BridgeDirectionStats,
+    pub framing_errors: u32,
+    pub noise_errors: u32,
+    pub overrun_errors: u32,
+    pub parity_errors: u32,
+    pub uart_write_failures: u32,
+    pub usb_write_failures: u32,
+}
+
+impl BridgeStatsSnapshot {
+    /// Total UART receive errors plus failed UART and USB writes.
+    #[must_use]
+    pub const fn error_count(&self) -> u32 {
+        self.framing_errors
+            .saturating_add(self.noise_errors)
+            .saturating_add(self.overrun_errors)
+            .saturating_add(self.parity_errors)
+            .saturating_add(self.uart_write_failures)
+            .saturating_add(self.usb_write_failures)
+    }
+
+    /// Frames dropped in either direction.
+    #[must_use]
+    pub const fn dropped_frames(&self) -> u32 {
+        self.usb_to_jetson
+            .dropped
+            .saturating_add(self.jetson_to_usb.dropped)
+    }
+}
+
+/// Running bridge counters; every count saturates rather than wrapping.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct BridgeStatistics {
+    counters: BridgeStatsSnapshot,
+}
+
+impl BridgeStatistics {
+    /// Creates a zeroed set of counters.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            counters: BridgeStatsSnapshot {
+                usb_to_jetson: BridgeDirectionStats {
+                    bytes: 0,
+                    frames: 0,
+                    dropped: 0,
+                    queue_full: 0,
+                },
+                jetson_to_usb: BridgeDirectionStats {
+                    bytes: 0,
+                    frames: 0,
+                    dropped: 0,
+                    queue_full: 0,
+                },
+                framing_errors: 0,
+                noise_errors: 0,
+                overrun_errors: 0,
+                parity_errors: 0,
+                uart_write_failures: 0,
+                usb_write_failures: 0,
+            },
+        }
+    }
+
+    /// Counts one frame of `bytes` forwarded in the given direction.
+    pub fn record_frame(&mut self, kind: BridgeActivityKind, bytes: usize) {
+        let direction = self.direction_mut(kind);
+        direction.frames = direction.frames.saturating_add(1);
+        direction.bytes = direction
+            .bytes
+            .saturating_add(u32::try_from(bytes).unwrap_or(u32::MAX));
+    }
+
+    /// Counts one fault.
+    pub fn record_fault(&mut self, fault: BridgeFault) {
+        let counter = match fault {
+            BridgeFault::UartFraming => &mut self.counters.framing_errors,
+            BridgeFault::UartNoise => &mut self.counters.noise_errors,
+            BridgeFault::UartOverrun => &mut self.counters.overrun_errors,
+            BridgeFault::UartParity => &mut self.counters.parity_errors,
+            BridgeFault::UartWrite => &mut self.counters.uart_write_failures,
+            BridgeFault::UsbWrite => &mut self.counters.usb_write_failures,
+            BridgeFault::Dropped(kind) => &mut self.direction_mut(kind).dropped,
+            BridgeFault::QueueFull(kind) => &mut self.direction_mut(kind).queue_full,
+        };
+        *counter = counter.saturating_add(1);
+    }
+
+    /// Zeroes every counter.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns a copy of the counters.
+    #[must_use]
+    pub const fn snapshot(&self) -> BridgeStatsSnapshot {
+        self.counters
+    }
+
+    fn direction_mut(&mut self, kind: BridgeActivityKind) -> &mut BridgeDirectionStats {
+        match kind {
+            BridgeActivityKind::UsbToJetson => &mut self.counters.usb_to_jetson,
+            BridgeActivityKind::JetsonToUsb => &mut self.counters.jetson_to_usb,
+        }
+    }
+}
+
+/// Helper that renders a [`BridgeStatsSnapshot`] into REPL lines.
+#[derive(Clone, Copy, Debug)]
+pub struct BridgeStatsFormatter<'a> {
+    snapshot: &'a BridgeStatsSnapshot,
+}
+
+impl<'a> BridgeStatsFormatter<'a> {
+    /// Creates a formatter for the provided snapshot.
+    #[must_use]
+    pub const fn new(snapshot: &'a BridgeStatsSnapshot) -> Self {
+        Self { snapshot }
+    }
+
+    /// Number of lines produced by [`Self::write_line`].
+    #[must_use]
+    pub const fn line_count(&self) -> usize {
+        3
+    }
+
+    /// Writes the line at `index`.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer fails or `index` is out of range.
+    pub fn write_line<W: fmt::Write>(&self, writer: &mut W, index: usize) -> fmt::Result {
+        match index {
+            0 => write_direction_line(writer, "rx", &self.snapshot.jetson_to_usb),
+            1 => write_direction_line(writer, "tx", &self.snapshot.usb_to_jetson),
+            2 => self.write_errors_line(writer),
+            _ => Err(fmt::Error),
+        }
+    }
+
+    /// Writes the error line (e.g. `bridge errors framing=0 noise=0 overrun=2 parity=0 uart-write=0 usb-write=0`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer fails.
+    pub fn write_errors_line<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        let snapshot = self.snapshot;
+        write!(
+            writer,
+            "bridge errors framing={} noise={} overrun={} parity={} uart-write={} usb-write={}",
+            snapshot.framing_errors,
+            snapshot.noise_errors,
+            snapshot.overrun_errors,
+            snapshot.parity_errors,
+            snapshot.uart_write_failures,
+            snapshot.usb_write_failures
+        )
+    }
+}
+
+fn write_direction_line<W: fmt::Write>(
+    writer: &mut W,
+    label: &str,
+    stats: &BridgeDirectionStats,
+) -> fmt::Result {
+    write!(
+        writer,
+        "bridge {label} bytes={} frames={} dropped={} queue-full={}",
+        stats.bytes, stats.frames, stats.dropped, stats.queue_full
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_traffic_and_faults_per_direction() {
+        let mut stats = BridgeStatistics::new();
+        stats.record_frame(BridgeActivityKind::JetsonToUsb, 64);
+        stats.record_frame(BridgeActivityKind::JetsonToUsb, 12);
+        stats.record_frame(BridgeActivityKind::UsbToJetson, 3);
+        stats.record_fault(BridgeFault::UartFraming);
+        stats.record_fault(BridgeFault::UartOverrun);
+        stats.record_fault(BridgeFault::UsbWrite);
+        stats.record_fault(BridgeFault::Dropped(BridgeActivityKind::UsbToJetson));
+        stats.record_fault(BridgeFault::QueueFull(BridgeActivityKind::JetsonToUsb));
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.error_count(), 3);
+        assert_eq!(snapshot.dropped_frames(), 1);
+
+        let formatter = BridgeStatsFormatter::new(&snapshot);
+        let lines: std::vec::Vec<std::string::String> = (0..formatter.line_count())
+            .map(|index| {
+                let mut line = std::string::String::new();
+                formatter
+                    .write_line(&mut line, index)
+                    .expect("line should format");
+                line
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "bridge rx bytes=76 frames=2 dropped=0 queue-full=1",
+                "bridge tx bytes=3 frames=1 dropped=1 queue-full=0",
+                "bridge errors framing=1 noise=0 overrun=1 parity=0 uart-write=0 usb-write=1",
+            ]
+        );
+
+        stats.reset();
+        assert_eq!(stats.snapshot(), BridgeStatsSnapshot::default());
+    }
+}
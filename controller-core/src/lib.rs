@@ -13,6 +13,9 @@ extern crate alloc;
 
 pub mod bridge;
 pub mod orchestrator;
+pub mod power;
 pub mod repl;
+pub mod script;
 pub mod sequences;
+pub mod supervisor;
 pub mod telemetry;
@@ -15,6 +15,7 @@ use crate::sequences::{
     fault_recovery_template, normal_reboot_template, recovery_entry_template,
     recovery_immediate_template,
 };
+use crate::telemetry::{FaultRecoveryReason, TelemetryInstant};
 
 /// Identifier used when tracking emitted telemetry events.
 pub type EventId = u32;
@@ -23,6 +24,35 @@ pub type EventId = u32;
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum CommandSource {
     UsbHost,
+    /// Raised by the boot supervisor without operator involvement.
+    Watchdog,
+    /// Queued by a console automation script step.
+    Script,
+}
+
+/// Number chosen by the host (`@17 reboot now`) and echoed on every reply to
+/// that request, including the completion event of its strap sequence.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct RequestTag(u32);
+
+impl RequestTag {
+    /// Wraps a tag value.
+    #[must_use]
+    pub const fn new(value: u32) -> Self {
+        Self(value)
+    }
+
+    /// Returns the tag value.
+    #[must_use]
+    pub const fn get(self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for RequestTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
 
 /// Optional flags that customize how a command is executed.
@@ -34,6 +64,14 @@ pub struct CommandFlags {
     pub start_after: Option<Duration>,
     /// Optional retry budget override for sequences that support retries.
     pub retry_override: Option<u8>,
+    /// Trigger recorded with fault recovery runs (defaults to a manual request).
+    pub fault_reason: Option<FaultRecoveryReason>,
+    /// How long a bridge-activity step waits before giving up on the console
+    /// (defaults to [`DEFAULT_BRIDGE_ACTIVITY_TIMEOUT`]).
+    pub bridge_timeout: Option<Duration>,
+    /// Tag of the REPL request that queued the command, carried on the
+    /// [`SequenceCompletion`] reported when it ends.
+    pub tag: Option<RequestTag>,
 }
 
 /// Strap sequence request waiting to be processed by the orchestrator.
@@ -81,6 +119,17 @@ pub enum SequenceOutcome {
     SkippedCooldown,
 }
 
+impl SequenceOutcome {
+    /// Label used for the outcome in response and event lines.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            SequenceOutcome::Completed => "completed",
+            SequenceOutcome::SkippedCooldown => "skipped-cooldown",
+        }
+    }
+}
+
 /// Error detail captured when a sequence fails.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum SequenceError {
@@ -107,6 +156,112 @@ impl fmt::Display for SequenceError {
     }
 }
 
+impl SequenceError {
+    /// Short machine-readable label, e.g. `brown-out`.
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            SequenceError::Busy => "busy",
+            SequenceError::BrownOutDetected => "brown-out",
+            SequenceError::BridgeTimeout => "bridge-timeout",
+            SequenceError::RetryLimitExceeded => "retry-limit",
+            SequenceError::ControlLinkLost => "control-link-lost",
+            SequenceError::UnexpectedState => "unexpected-state",
+            SequenceError::TelemetryBacklog => "telemetry-backlog",
+        }
+    }
+}
+
+/// How the strap sequence behind a tagged request ended.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SequenceResult {
+    /// The run reached its end.
+    Finished(SequenceOutcome),
+    /// The run stopped part way through.
+    Failed(SequenceError),
+    /// The orchestrator dropped the command before running it.
+    Rejected(CommandRejectionReason),
+}
+
+impl SequenceResult {
+    /// Returns `completed`, `skipped-cooldown`, `failed`, or `rejected`.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            SequenceResult::Finished(outcome) => outcome.label(),
+            SequenceResult::Failed(_) => "failed",
+            SequenceResult::Rejected(_) => "rejected",
+        }
+    }
+
+    /// Returns why a failed or rejected run did not complete.
+    #[must_use]
+    pub const fn reason(self) -> Option<&'static str> {
+        match self {
+            SequenceResult::Finished(_) => None,
+            SequenceResult::Failed(error) => Some(error.code()),
+            SequenceResult::Rejected(reason) => Some(reason.code()),
+        }
+    }
+
+    /// Returns `true` when the sequence ran to the end.
+    #[must_use]
+    pub const fn is_success(self) -> bool {
+        matches!(self, SequenceResult::Finished(_))
+    }
+}
+
+impl fmt::Display for SequenceResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())?;
+        if let Some(reason) = self.reason() {
+            write!(f, " reason={reason}")?;
+        }
+        Ok(())
+    }
+}
+
+/// End of a queued strap sequence. Tagged requests print it as
+/// `DONE@17 normal-reboot completed elapsed=1220ms`; `wait complete` watches
+/// for it either way.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SequenceCompletion {
+    pub tag: Option<RequestTag>,
+    pub kind: StrapSequenceKind,
+    pub result: SequenceResult,
+    /// Time from the request to the end of the run.
+    pub elapsed: Duration,
+}
+
+impl SequenceCompletion {
+    /// Builds the event for `command` ending at `now`.
+    #[must_use]
+    pub fn for_command<TInstant: TelemetryInstant>(
+        command: &SequenceCommand<TInstant>,
+        result: SequenceResult,
+        now: TInstant,
+    ) -> Self {
+        Self {
+            tag: command.flags.tag,
+            kind: command.kind,
+            result,
+            elapsed: now.saturating_duration_since(command.requested_at),
+        }
+    }
+}
+
+impl fmt::Display for SequenceCompletion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} elapsed={}ms",
+            self.kind,
+            self.result,
+            self.elapsed.as_millis()
+        )
+    }
+}
+
 /// State machine phases for an in-flight sequence run.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum SequenceState {
@@ -555,6 +710,18 @@ pub enum CommandRejectionReason {
     ControlLinkLost,
 }
 
+impl CommandRejectionReason {
+    /// Short machine-readable label, e.g. `missing-template`.
+    #[must_use]
+    pub const fn code(self) -> &'static str {
+        match self {
+            CommandRejectionReason::Busy => "busy",
+            CommandRejectionReason::MissingTemplate => "missing-template",
+            CommandRejectionReason::ControlLinkLost => "control-link-lost",
+        }
+    }
+}
+
 /// Tracks the last command rejection for diagnostics.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CommandRejection<TInstant> {
@@ -946,6 +1113,13 @@ impl BridgeHoldConfig {
         Self { timeout }
     }
 
+    /// Returns the configuration requested by a command, falling back to
+    /// [`DEFAULT_BRIDGE_ACTIVITY_TIMEOUT`].
+    #[must_use]
+    pub fn for_flags(flags: &CommandFlags) -> Self {
+        flags.bridge_timeout.map_or_else(Self::default, Self::new)
+    }
+
     /// Returns the configured timeout duration.
     #[must_use]
     pub const fn timeout(&self) -> Duration {
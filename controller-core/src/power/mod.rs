@@ -0,0 +1,1036 @@
+//! Power-rail statistics derived from monitor samples.
+//!
+//! [`RailStatistics`] keeps running min/max/mean values, a coarse histogram,
+//! and windowed peak-to-peak estimates without allocating, so the controller
+//! can characterise the rail from its own VREFINT measurements. Strap
+//! sequences open a capture while they drive the straps so the ripple budget
+//! can be checked against the samples taken during that run.
+//!
+//! [`BrownOutHistory`] complements the statistics with a bounded log of
+//! brown-out episodes so they survive beyond the defmt stream.
+
+use core::fmt;
+use core::time::Duration;
+
+use heapless::{HistoryBuf, OldestOrdered, Vec};
+
+use crate::orchestrator::DEFAULT_BROWNOUT_RECOVERY_MV;
+use crate::sequences::StrapSequenceKind;
+use crate::telemetry::TelemetryInstant;
+
+/// Number of buckets tracked by the rail histogram.
+pub const RAIL_HISTOGRAM_BUCKETS: usize = 8;
+/// Default number of samples per peak-to-peak window (1 s at the default 5 ms period).
+pub const DEFAULT_RAIL_WINDOW_SAMPLES: u16 = 200;
+/// Default lower edge of the first bounded histogram bucket.
+pub const DEFAULT_RAIL_HISTOGRAM_FLOOR_MV: u16 = DEFAULT_BROWNOUT_RECOVERY_MV;
+/// Default width of each histogram bucket.
+pub const DEFAULT_RAIL_HISTOGRAM_BUCKET_MV: u16 = 25;
+/// Peak-to-peak ripple budget the rail must meet while a sequence runs.
+pub const RAIL_RIPPLE_LIMIT_MV: u16 = 50;
+/// Number of closed brown-out episodes retained by [`BrownOutHistory`].
+pub const BROWN_OUT_HISTORY_CAPACITY: usize = 8;
+/// Maximum entries in a [`BrownOutReport`] (closed episodes plus an open one).
+pub const BROWN_OUT_REPORT_CAPACITY: usize = BROWN_OUT_HISTORY_CAPACITY + 1;
+
+/// Parameters that control how rail samples are aggregated.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RailStatsConfig {
+    pub window_samples: u16,
+    pub histogram_floor_mv: u16,
+    pub histogram_bucket_mv: u16,
+}
+
+impl RailStatsConfig {
+    /// Builds a configuration; zero-sized windows and buckets are clamped to one.
+    #[must_use]
+    pub const fn new(
+        window_samples: u16,
+        histogram_floor_mv: u16,
+        histogram_bucket_mv: u16,
+    ) -> Self {
+        Self {
+            window_samples: if window_samples == 0 {
+                1
+            } else {
+                window_samples
+            },
+            histogram_floor_mv,
+            histogram_bucket_mv: if histogram_bucket_mv == 0 {
+                1
+            } else {
+                histogram_bucket_mv
+            },
+        }
+    }
+
+    /// Returns a copy whose peak-to-peak window spans `window` at the given sample interval.
+    #[must_use]
+    pub fn with_window(self, window: Duration, sample_interval: Duration) -> Self {
+        let interval = sample_interval.as_micros().max(1);
+        let samples = (window.as_micros() / interval).clamp(1, u128::from(u16::MAX));
+        let samples = u16::try_from(samples).unwrap_or(u16::MAX);
+        Self::new(samples, self.histogram_floor_mv, self.histogram_bucket_mv)
+    }
+}
+
+impl Default for RailStatsConfig {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_RAIL_WINDOW_SAMPLES,
+            DEFAULT_RAIL_HISTOGRAM_FLOOR_MV,
+            DEFAULT_RAIL_HISTOGRAM_BUCKET_MV,
+        )
+    }
+}
+
+/// Running min/max/mean over a set of rail samples.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RailSummary {
+    samples: u32,
+    min_mv: u16,
+    max_mv: u16,
+    sum_mv: u64,
+}
+
+impl RailSummary {
+    /// Creates an empty summary.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            samples: 0,
+            min_mv: u16::MAX,
+            max_mv: 0,
+            sum_mv: 0,
+        }
+    }
+
+    /// Folds a millivolt reading into the summary.
+    pub fn record(&mut self, millivolts: u16) {
+        self.samples = self.samples.saturating_add(1);
+        self.min_mv = self.min_mv.min(millivolts);
+        self.max_mv = self.max_mv.max(millivolts);
+        self.sum_mv = self.sum_mv.saturating_add(u64::from(millivolts));
+    }
+
+    /// Number of samples folded into the summary.
+    #[must_use]
+    pub const fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    /// Lowest reading observed, if any.
+    #[must_use]
+    pub const fn min_mv(&self) -> Option<u16> {
+        if self.samples == 0 {
+            None
+        } else {
+            Some(self.min_mv)
+        }
+    }
+
+    /// Highest reading observed, if any.
+    #[must_use]
+    pub const fn max_mv(&self) -> Option<u16> {
+        if self.samples == 0 {
+            None
+        } else {
+            Some(self.max_mv)
+        }
+    }
+
+    /// Arithmetic mean of the observed readings (rounded to the nearest millivolt).
+    #[must_use]
+    pub fn mean_mv(&self) -> Option<u16> {
+        if self.samples == 0 {
+            return None;
+        }
+
+        let count = u64::from(self.samples);
+        let mean = (self.sum_mv + count / 2) / count;
+        Some(u16::try_from(mean).unwrap_or(u16::MAX))
+    }
+
+    /// Difference between the highest and lowest readings.
+    #[must_use]
+    pub const fn peak_to_peak_mv(&self) -> Option<u16> {
+        if self.samples == 0 {
+            None
+        } else {
+            Some(self.max_mv - self.min_mv)
+        }
+    }
+}
+
+impl Default for RailSummary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fixed-width histogram of rail readings.
+///
+/// The first bucket also collects readings below the floor and the last bucket
+/// collects everything above the bounded range.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RailHistogram {
+    floor_mv: u16,
+    bucket_mv: u16,
+    counts: [u32; RAIL_HISTOGRAM_BUCKETS],
+}
+
+impl RailHistogram {
+    /// Creates an empty histogram starting at `floor_mv`.
+    #[must_use]
+    pub const fn new(floor_mv: u16, bucket_mv: u16) -> Self {
+        Self {
+            floor_mv,
+            bucket_mv: if bucket_mv == 0 { 1 } else { bucket_mv },
+            counts: [0; RAIL_HISTOGRAM_BUCKETS],
+        }
+    }
+
+    /// Returns the bucket index that `millivolts` falls into.
+    #[must_use]
+    pub fn bucket_for(&self, millivolts: u16) -> usize {
+        if millivolts < self.floor_mv {
+            return 0;
+        }
+
+        let offset = usize::from((millivolts - self.floor_mv) / self.bucket_mv);
+        offset.min(RAIL_HISTOGRAM_BUCKETS - 1)
+    }
+
+    /// Counts a reading.
+    pub fn record(&mut self, millivolts: u16) {
+        let index = self.bucket_for(millivolts);
+        self.counts[index] = self.counts[index].saturating_add(1);
+    }
+
+    /// Per-bucket sample counts.
+    #[must_use]
+    pub const fn counts(&self) -> &[u32; RAIL_HISTOGRAM_BUCKETS] {
+        &self.counts
+    }
+
+    /// Inclusive lower and exclusive upper bound of a bucket; open ends are `None`.
+    #[must_use]
+    pub fn bucket_bounds(&self, index: usize) -> (Option<u16>, Option<u16>) {
+        let bound = |edge: usize| {
+            u16::try_from(edge)
+                .ok()
+                .and_then(|edge| edge.checked_mul(self.bucket_mv))
+                .and_then(|offset| self.floor_mv.checked_add(offset))
+        };
+
+        let lower = if index == 0 { None } else { bound(index) };
+        let upper = if index + 1 >= RAIL_HISTOGRAM_BUCKETS {
+            None
+        } else {
+            bound(index + 1)
+        };
+        (lower, upper)
+    }
+}
+
+/// Rail summary collected while a strap sequence was running.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SequenceRailCapture {
+    pub kind: StrapSequenceKind,
+    pub summary: RailSummary,
+}
+
+impl SequenceRailCapture {
+    /// Returns `true` when the captured peak-to-peak stayed inside [`RAIL_RIPPLE_LIMIT_MV`].
+    ///
+    /// Captures without samples are not considered passing.
+    #[must_use]
+    pub fn within_ripple_limit(&self) -> bool {
+        self.summary
+            .peak_to_peak_mv()
+            .is_some_and(|ripple| ripple < RAIL_RIPPLE_LIMIT_MV)
+    }
+}
+
+/// Point-in-time copy of the accumulated rail statistics.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RailStatsSnapshot {
+    pub overall: RailSummary,
+    pub invalid_samples: u32,
+    pub histogram: RailHistogram,
+    pub window_samples: u16,
+    pub last_window_pp_mv: Option<u16>,
+    pub max_window_pp_mv: Option<u16>,
+    pub active_capture: Option<SequenceRailCapture>,
+    pub last_capture: Option<SequenceRailCapture>,
+}
+
+/// Allocation-free accumulator for power-rail samples.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct RailStatistics {
+    config: RailStatsConfig,
+    overall: RailSummary,
+    invalid_samples: u32,
+    histogram: RailHistogram,
+    window: RailSummary,
+    last_window_pp_mv: Option<u16>,
+    max_window_pp_mv: Option<u16>,
+    active_capture: Option<SequenceRailCapture>,
+    last_capture: Option<SequenceRailCapture>,
+}
+
+impl RailStatistics {
+    /// Creates an accumulator using the default configuration.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_config(RailStatsConfig::new(
+            DEFAULT_RAIL_WINDOW_SAMPLES,
+            DEFAULT_RAIL_HISTOGRAM_FLOOR_MV,
+            DEFAULT_RAIL_HISTOGRAM_BUCKET_MV,
+        ))
+    }
+
+    /// Creates an accumulator using the supplied configuration.
+    #[must_use]
+    pub const fn with_config(config: RailStatsConfig) -> Self {
+        Self {
+            config,
+            overall: RailSummary::new(),
+            invalid_samples: 0,
+            histogram: RailHistogram::new(config.histogram_floor_mv, config.histogram_bucket_mv),
+            window: RailSummary::new(),
+            last_window_pp_mv: None,
+            max_window_pp_mv: None,
+            active_capture: None,
+            last_capture: None,
+        }
+    }
+
+    /// Returns the active configuration.
+    #[must_use]
+    pub const fn config(&self) -> RailStatsConfig {
+        self.config
+    }
+
+    /// Replaces the configuration and clears the accumulated statistics.
+    pub fn set_config(&mut self, config: RailStatsConfig) {
+        self.config = config;
+        self.reset();
+    }
+
+    /// Records a monitor reading; `None` counts as an invalid sample.
+    pub fn record(&mut self, millivolts: Option<u16>) {
+        let Some(millivolts) = millivolts else {
+            self.invalid_samples = self.invalid_samples.saturating_add(1);
+            return;
+        };
+
+        self.overall.record(millivolts);
+        self.histogram.record(millivolts);
+        if let Some(capture) = self.active_capture.as_mut() {
+            capture.summary.record(millivolts);
+        }
+
+        self.window.record(millivolts);
+        if self.window.samples() >= u32::from(self.config.window_samples) {
+            let ripple = self.window.peak_to_peak_mv();
+            self.last_window_pp_mv = ripple;
+            self.max_window_pp_mv = self.max_window_pp_mv.max(ripple);
+            self.window = RailSummary::new();
+        }
+    }
+
+    /// Starts capturing samples for a strap sequence, replacing any open capture.
+    pub fn begin_capture(&mut self, kind: StrapSequenceKind) {
+        self.active_capture = Some(SequenceRailCapture {
+            kind,
+            summary: RailSummary::new(),
+        });
+    }
+
+    /// Closes the open capture, retaining it as the most recent result.
+    pub fn finish_capture(&mut self) -> Option<SequenceRailCapture> {
+        let capture = self.active_capture.take()?;
+        self.last_capture = Some(capture);
+        Some(capture)
+    }
+
+    /// Clears all statistics while keeping the configuration and restarting any open capture.
+    pub fn reset(&mut self) {
+        let active = self.active_capture.map(|capture| capture.kind);
+        *self = Self::with_config(self.config);
+        if let Some(kind) = active {
+            self.begin_capture(kind);
+        }
+    }
+
+    /// Returns a copy of the accumulated statistics.
+    #[must_use]
+    pub const fn snapshot(&self) -> RailStatsSnapshot {
+        RailStatsSnapshot {
+            overall: self.overall,
+            invalid_samples: self.invalid_samples,
+            histogram: self.histogram,
+            window_samples: self.config.window_samples,
+            last_window_pp_mv: self.last_window_pp_mv,
+            max_window_pp_mv: self.max_window_pp_mv,
+            active_capture: self.active_capture,
+            last_capture: self.last_capture,
+        }
+    }
+}
+
+impl Default for RailStatistics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper that renders a [`RailStatsSnapshot`] into REPL lines.
+#[derive(Clone, Copy, Debug)]
+pub struct RailStatsFormatter<'a> {
+    snapshot: &'a RailStatsSnapshot,
+}
+
+impl<'a> RailStatsFormatter<'a> {
+    /// Creates a new formatter for the provided snapshot.
+    #[must_use]
+    pub const fn new(snapshot: &'a RailStatsSnapshot) -> Self {
+        Self { snapshot }
+    }
+
+    /// Number of lines produced by [`Self::write_line`].
+    #[must_use]
+    pub const fn line_count(&self) -> usize {
+        RAIL_HISTOGRAM_BUCKETS + 4
+    }
+
+    /// Writes the report line at `index`, in display order.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure or `index` is
+    /// past [`Self::line_count`].
+    pub fn write_line<W: fmt::Write>(&self, writer: &mut W, index: usize) -> fmt::Result {
+        match index {
+            0 => self.write_samples_line(writer),
+            1 => self.write_summary_line(writer),
+            2 => self.write_ripple_line(writer),
+            bucket if bucket < 3 + RAIL_HISTOGRAM_BUCKETS => {
+                self.write_histogram_line(writer, bucket - 3)
+            }
+            capture if capture == 3 + RAIL_HISTOGRAM_BUCKETS => self.write_capture_line(writer),
+            _ => Err(fmt::Error),
+        }
+    }
+
+    /// Writes the summary line (e.g. `power rail min=3290mV max=3310mV mean=3301mV pp=20mV`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure.
+    pub fn write_summary_line<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        let overall = &self.snapshot.overall;
+        writer.write_str("power rail min=")?;
+        write_millivolts(writer, overall.min_mv())?;
+        writer.write_str(" max=")?;
+        write_millivolts(writer, overall.max_mv())?;
+        writer.write_str(" mean=")?;
+        write_millivolts(writer, overall.mean_mv())?;
+        writer.write_str(" pp=")?;
+        write_millivolts(writer, overall.peak_to_peak_mv())
+    }
+
+    /// Writes the sample-count line (e.g. `power samples=1200 invalid=0`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure.
+    pub fn write_samples_line<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(
+            writer,
+            "power samples={} invalid={}",
+            self.snapshot.overall.samples(),
+            self.snapshot.invalid_samples
+        )
+    }
+
+    /// Writes the windowed ripple line (e.g. `power ripple window=200 pp=12mV max-pp=20mV`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure.
+    pub fn write_ripple_line<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(
+            writer,
+            "power ripple window={} pp=",
+            self.snapshot.window_samples
+        )?;
+        write_millivolts(writer, self.snapshot.last_window_pp_mv)?;
+        writer.write_str(" max-pp=")?;
+        write_millivolts(writer, self.snapshot.max_window_pp_mv)
+    }
+
+    /// Writes one histogram bucket line (e.g. `power hist 3150-3174mV=42`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure.
+    pub fn write_histogram_line<W: fmt::Write>(&self, writer: &mut W, index: usize) -> fmt::Result {
+        let histogram = &self.snapshot.histogram;
+        let count = histogram.counts().get(index).copied().unwrap_or(0);
+        writer.write_str("power hist ")?;
+        match histogram.bucket_bounds(index) {
+            (None, Some(upper)) => write!(writer, "<{upper}mV")?,
+            (Some(lower), None) => write!(writer, ">={lower}mV")?,
+            (Some(lower), Some(upper)) => write!(writer, "{lower}-{}mV", upper - 1)?,
+            (None, None) => writer.write_str("all")?,
+        }
+        write!(writer, "={count}")
+    }
+
+    /// Writes the sequence capture line, preferring an in-progress capture.
+    ///
+    /// Example: `power seq normal-reboot pass samples=40 pp=8mV`.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure.
+    pub fn write_capture_line<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        let (capture, state) = match (self.snapshot.active_capture, self.snapshot.last_capture) {
+            (Some(capture), _) => (capture, "active"),
+            (None, Some(capture)) if capture.within_ripple_limit() => (capture, "pass"),
+            (None, Some(capture)) => (capture, "fail"),
+            (None, None) => return writer.write_str("power seq none"),
+        };
+
+        write!(
+            writer,
+            "power seq {} {state} samples={} pp=",
+            capture.kind,
+            capture.summary.samples()
+        )?;
+        write_millivolts(writer, capture.summary.peak_to_peak_mv())
+    }
+}
+
+/// A single brown-out episode, from the first low reading until the rail recovered.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BrownOutEpisode<TInstant> {
+    /// Timestamp of the first brown-out reading.
+    pub started_at: TInstant,
+    /// Lowest valid reading seen during the episode.
+    pub min_mv: Option<u16>,
+    /// Time until the rail read stable again; `None` while the episode is open.
+    pub duration: Option<Duration>,
+    /// Strap sequence that was running when the episode started, if any.
+    pub sequence: Option<StrapSequenceKind>,
+    /// Zero-based step index of the interrupted sequence.
+    pub step: Option<u8>,
+    /// Whether the orchestrator retried the interrupted sequence.
+    pub retried: bool,
+}
+
+impl<TInstant> BrownOutEpisode<TInstant> {
+    /// Returns `true` while the rail has not yet recovered.
+    #[must_use]
+    pub const fn is_open(&self) -> bool {
+        self.duration.is_none()
+    }
+}
+
+/// Bounded log of brown-out episodes.
+///
+/// Readings below the recovery threshold open an episode (or deepen the open
+/// one); the first stable reading closes it. Only the newest `CAPACITY`
+/// closed episodes are kept, but [`Self::total`] counts every episode.
+#[derive(Clone, Debug)]
+pub struct BrownOutHistory<TInstant, const CAPACITY: usize = BROWN_OUT_HISTORY_CAPACITY>
+where
+    TInstant: Copy,
+{
+    closed: HistoryBuf<BrownOutEpisode<TInstant>, CAPACITY>,
+    open: Option<BrownOutEpisode<TInstant>>,
+    total: u32,
+}
+
+impl<TInstant, const CAPACITY: usize> BrownOutHistory<TInstant, CAPACITY>
+where
+    TInstant: Copy + TelemetryInstant,
+{
+    /// Creates an empty history.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            closed: HistoryBuf::new(),
+            open: None,
+            total: 0,
+        }
+    }
+
+    /// Records a brown-out reading, opening a new episode if none is in progress.
+    ///
+    /// The sequence context is only captured when the episode opens. Returns
+    /// `true` when the reading started a new episode.
+    pub fn record_brown_out(
+        &mut self,
+        timestamp: TInstant,
+        millivolts: Option<u16>,
+        sequence: Option<StrapSequenceKind>,
+        step: Option<u8>,
+    ) -> bool {
+        if let Some(episode) = self.open.as_mut() {
+            episode.min_mv = match (episode.min_mv, millivolts) {
+                (Some(current), Some(value)) => Some(current.min(value)),
+                (current, value) => current.or(value),
+            };
+            return false;
+        }
+
+        self.open = Some(BrownOutEpisode {
+            started_at: timestamp,
+            min_mv: millivolts,
+            duration: None,
+            sequence,
+            step,
+            retried: false,
+        });
+        self.total = self.total.saturating_add(1);
+        true
+    }
+
+    /// Notes whether the interrupted sequence was retried.
+    pub fn mark_retry(&mut self, retried: bool) {
+        if let Some(episode) = self.open.as_mut() {
+            episode.retried = retried;
+        }
+    }
+
+    /// Closes the open episode at `recovered_at`, returning it if one was open.
+    pub fn close(&mut self, recovered_at: TInstant) -> Option<BrownOutEpisode<TInstant>> {
+        let mut episode = self.open.take()?;
+        episode.duration = Some(recovered_at.saturating_duration_since(episode.started_at));
+        self.closed.write(episode);
+        Some(episode)
+    }
+
+    /// Returns the episode still in progress, if any.
+    #[must_use]
+    pub const fn open_episode(&self) -> Option<&BrownOutEpisode<TInstant>> {
+        self.open.as_ref()
+    }
+
+    /// Iterates the retained closed episodes, oldest first.
+    pub fn oldest_first(&self) -> OldestOrdered<'_, BrownOutEpisode<TInstant>> {
+        self.closed.oldest_ordered()
+    }
+
+    /// Number of episodes observed since the history was created or cleared.
+    #[must_use]
+    pub const fn total(&self) -> u32 {
+        self.total
+    }
+
+    /// Discards every retained episode, including an open one.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Builds a report whose ages are measured relative to `now`.
+    #[must_use]
+    pub fn report(&self, now: TInstant) -> BrownOutReport {
+        let retained = self.closed.len() + usize::from(self.open.is_some());
+        let skip = retained.saturating_sub(BROWN_OUT_REPORT_CAPACITY);
+        let mut report = BrownOutReport {
+            total: self.total,
+            episodes: Vec::new(),
+        };
+
+        for episode in self.oldest_first().chain(self.open.iter()).skip(skip) {
+            let _ = report.episodes.push(BrownOutSummary {
+                age_ms: saturating_millis(now.saturating_duration_since(episode.started_at)),
+                min_mv: episode.min_mv,
+                duration_ms: episode.duration.map(saturating_millis),
+                sequence: episode.sequence,
+                step: episode.step,
+                retried: episode.retried,
+            });
+        }
+
+        report
+    }
+}
+
+impl<TInstant, const CAPACITY: usize> Default for BrownOutHistory<TInstant, CAPACITY>
+where
+    TInstant: Copy + TelemetryInstant,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Instant-independent view of a brown-out episode.
+///
+/// Times are kept in milliseconds so a full report stays small enough to
+/// return by value from the command executor.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BrownOutSummary {
+    /// Time elapsed since the episode started.
+    pub age_ms: u32,
+    pub min_mv: Option<u16>,
+    /// `None` while the episode is still open.
+    pub duration_ms: Option<u32>,
+    pub sequence: Option<StrapSequenceKind>,
+    pub step: Option<u8>,
+    pub retried: bool,
+}
+
+/// Brown-out history rendered for the REPL, oldest episode first.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BrownOutReport {
+    /// Episodes observed in total, including ones no longer retained.
+    pub total: u32,
+    pub episodes: Vec<BrownOutSummary, BROWN_OUT_REPORT_CAPACITY>,
+}
+
+/// Helper that renders a [`BrownOutReport`] into REPL lines.
+///
+/// Each episode spans two lines so the output fits the REPL frame width.
+#[derive(Clone, Copy, Debug)]
+pub struct BrownOutFormatter<'a> {
+    report: &'a BrownOutReport,
+}
+
+impl<'a> BrownOutFormatter<'a> {
+    /// Creates a new formatter for the provided report.
+    #[must_use]
+    pub const fn new(report: &'a BrownOutReport) -> Self {
+        Self { report }
+    }
+
+    /// Number of lines produced by [`Self::write_line`].
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        1 + 2 * self.report.episodes.len()
+    }
+
+    /// Writes the report line at `index`, in display order.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure or `index` is
+    /// past [`Self::line_count`].
+    pub fn write_line<W: fmt::Write>(&self, writer: &mut W, index: usize) -> fmt::Result {
+        match index {
+            0 => self.write_header_line(writer),
+            line if line < self.line_count() => {
+                let episode = (line - 1) / 2;
+                if (line - 1) % 2 == 0 {
+                    self.write_timing_line(writer, episode)
+                } else {
+                    self.write_context_line(writer, episode)
+                }
+            }
+            _ => Err(fmt::Error),
+        }
+    }
+
+    /// Writes the header line (e.g. `power history episodes=3 shown=3`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure.
+    pub fn write_header_line<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(
+            writer,
+            "power history episodes={} shown={}",
+            self.report.total,
+            self.report.episodes.len()
+        )
+    }
+
+    /// Writes the timing line for an episode (e.g. `power brownout 2 age=12.3s min=2950mV dur=45ms`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure or `episode` is out of range.
+    pub fn write_timing_line<W: fmt::Write>(&self, writer: &mut W, episode: usize) -> fmt::Result {
+        let summary = self.report.episodes.get(episode).ok_or(fmt::Error)?;
+        write!(writer, "power brownout {} age=", self.ordinal(episode))?;
+        write_span(writer, summary.age_ms)?;
+        writer.write_str(" min=")?;
+        write_millivolts(writer, summary.min_mv)?;
+        writer.write_str(" dur=")?;
+        match summary.duration_ms {
+            Some(duration) => write_span(writer, duration),
+            None => writer.write_str("ongoing"),
+        }
+    }
+
+    /// Writes the context line for an episode (e.g. `power brownout 2 seq=normal-reboot step=1 retry=yes`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure or `episode` is out of range.
+    pub fn write_context_line<W: fmt::Write>(&self, writer: &mut W, episode: usize) -> fmt::Result {
+        let summary = self.report.episodes.get(episode).ok_or(fmt::Error)?;
+        write!(writer, "power brownout {} seq=", self.ordinal(episode))?;
+        match summary.sequence {
+            Some(kind) => write!(writer, "{kind}")?,
+            None => writer.write_str("idle")?,
+        }
+        if let Some(step) = summary.step {
+            write!(writer, " step={step}")?;
+        }
+        writer.write_str(if summary.retried {
+            " retry=yes"
+        } else {
+            " retry=no"
+        })
+    }
+
+    fn ordinal(self, episode: usize) -> u32 {
+        let shown = u32::try_from(self.report.episodes.len()).unwrap_or(u32::MAX);
+        let index = u32::try_from(episode).unwrap_or(u32::MAX);
+        self.report
+            .total
+            .saturating_sub(shown)
+            .saturating_add(index)
+            .saturating_add(1)
+    }
+}
+
+pub(crate) fn saturating_millis(duration: Duration) -> u32 {
+    u32::try_from(duration.as_millis()).unwrap_or(u32::MAX)
+}
+
+pub(crate) fn write_span<W: fmt::Write>(writer: &mut W, millis: u32) -> fmt::Result {
+    if millis >= 1_000 {
+        write!(writer, "{}.{}s", millis / 1_000, (millis % 1_000) / 100)
+    } else {
+        write!(writer, "{millis}ms")
+    }
+}
+
+fn write_millivolts<W: fmt::Write>(writer: &mut W, millivolts: Option<u16>) -> fmt::Result {
+    match millivolts {
+        Some(value) => write!(writer, "{value}mV"),
+        None => writer.write_str("unknown"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::test_support::MillisInstant;
+    use std::string::String;
+
+    #[test]
+    fn summary_tracks_min_max_mean() {
+        let mut stats = RailStatistics::new();
+        for mv in [3300, 3290, 3310, 3300] {
+            stats.record(Some(mv));
+        }
+        stats.record(None);
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.overall.samples(), 4);
+        assert_eq!(snapshot.invalid_samples, 1);
+        assert_eq!(snapshot.overall.min_mv(), Some(3290));
+        assert_eq!(snapshot.overall.max_mv(), Some(3310));
+        assert_eq!(snapshot.overall.mean_mv(), Some(3300));
+        assert_eq!(snapshot.overall.peak_to_peak_mv(), Some(20));
+    }
+
+    #[test]
+    fn histogram_clamps_out_of_range_samples() {
+        let mut histogram = RailHistogram::new(3150, 25);
+        histogram.record(3000);
+        histogram.record(3160);
+        histogram.record(3175);
+        histogram.record(4000);
+
+        assert_eq!(histogram.counts()[0], 2);
+        assert_eq!(histogram.counts()[1], 1);
+        assert_eq!(histogram.counts()[RAIL_HISTOGRAM_BUCKETS - 1], 1);
+        assert_eq!(histogram.bucket_bounds(0), (None, Some(3175)));
+        assert_eq!(histogram.bucket_bounds(1), (Some(3175), Some(3200)));
+        assert_eq!(
+            histogram.bucket_bounds(RAIL_HISTOGRAM_BUCKETS - 1),
+            (Some(3325), None)
+        );
+    }
+
+    #[test]
+    fn window_peak_to_peak_rolls_over() {
+        let mut stats = RailStatistics::with_config(RailStatsConfig::new(3, 3150, 25));
+        for mv in [3300, 3310, 3295] {
+            stats.record(Some(mv));
+        }
+        assert_eq!(stats.snapshot().last_window_pp_mv, Some(15));
+
+        for mv in [3300, 3302, 3301] {
+            stats.record(Some(mv));
+        }
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.last_window_pp_mv, Some(2));
+        assert_eq!(snapshot.max_window_pp_mv, Some(15));
+    }
+
+    #[test]
+    fn window_config_derives_sample_count() {
+        let config = RailStatsConfig::default()
+            .with_window(Duration::from_millis(250), Duration::from_millis(5));
+        assert_eq!(config.window_samples, 50);
+    }
+
+    #[test]
+    fn capture_reports_ripple_against_limit() {
+        let mut stats = RailStatistics::new();
+        stats.record(Some(3000));
+        stats.begin_capture(StrapSequenceKind::NormalReboot);
+        for mv in [3300, 3320, 3290] {
+            stats.record(Some(mv));
+        }
+        let capture = stats.finish_capture().expect("capture should be open");
+        assert_eq!(capture.summary.samples(), 3);
+        assert_eq!(capture.summary.peak_to_peak_mv(), Some(30));
+        assert!(capture.within_ripple_limit());
+
+        stats.begin_capture(StrapSequenceKind::FaultRecovery);
+        stats.record(Some(3300));
+        stats.record(Some(3240));
+        let capture = stats.finish_capture().expect("capture should be open");
+        assert!(!capture.within_ripple_limit());
+        assert_eq!(stats.snapshot().last_capture, Some(capture));
+    }
+
+    #[test]
+    fn reset_clears_counts_but_keeps_capture_open() {
+        let mut stats = RailStatistics::new();
+        stats.begin_capture(StrapSequenceKind::RecoveryEntry);
+        stats.record(Some(3300));
+        stats.reset();
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.overall.samples(), 0);
+        let active = snapshot
+            .active_capture
+            .expect("capture should survive reset");
+        assert_eq!(active.kind, StrapSequenceKind::RecoveryEntry);
+        assert_eq!(active.summary.samples(), 0);
+    }
+
+    #[test]
+    fn formatter_renders_lines() {
+        let mut stats = RailStatistics::new();
+        stats.begin_capture(StrapSequenceKind::NormalReboot);
+        stats.record(Some(3290));
+        stats.record(Some(3310));
+        let _ = stats.finish_capture();
+        let snapshot = stats.snapshot();
+        let formatter = RailStatsFormatter::new(&snapshot);
+
+        let mut line = String::new();
+        formatter.write_summary_line(&mut line).unwrap();
+        assert_eq!(line, "power rail min=3290mV max=3310mV mean=3300mV pp=20mV");
+
+        line.clear();
+        formatter.write_ripple_line(&mut line).unwrap();
+        assert_eq!(line, "power ripple window=200 pp=unknown max-pp=unknown");
+
+        line.clear();
+        formatter.write_histogram_line(&mut line, 5).unwrap();
+        assert_eq!(line, "power hist 3275-3299mV=1");
+
+        line.clear();
+        formatter.write_capture_line(&mut line).unwrap();
+        assert_eq!(line, "power seq normal-reboot pass samples=2 pp=20mV");
+
+        line.clear();
+        let last = formatter.line_count() - 1;
+        formatter.write_line(&mut line, last).unwrap();
+        assert_eq!(line, "power seq normal-reboot pass samples=2 pp=20mV");
+        assert!(formatter.write_line(&mut line, last + 1).is_err());
+    }
+
+    #[test]
+    fn brown_out_history_tracks_episode_lifecycle() {
+        let mut history: BrownOutHistory<MillisInstant> = BrownOutHistory::new();
+        assert!(history.record_brown_out(
+            MillisInstant(1_000),
+            Some(3050),
+            Some(StrapSequenceKind::NormalReboot),
+            Some(1),
+        ));
+        assert!(!history.record_brown_out(MillisInstant(1_005), Some(2980), None, None));
+        history.mark_retry(true);
+
+        let open = history
+            .open_episode()
+            .copied()
+            .expect("episode should be open");
+        assert!(open.is_open());
+        assert_eq!(open.min_mv, Some(2980));
+        assert_eq!(open.sequence, Some(StrapSequenceKind::NormalReboot));
+
+        let closed = history
+            .close(MillisInstant(1_045))
+            .expect("episode should close");
+        assert_eq!(closed.duration, Some(Duration::from_millis(45)));
+        assert!(closed.retried);
+        assert!(history.open_episode().is_none());
+        assert!(history.close(MillisInstant(2_000)).is_none());
+
+        history.record_brown_out(MillisInstant(5_000), None, None, None);
+        let report = history.report(MillisInstant(13_300));
+        assert_eq!(report.total, 2);
+        assert_eq!(report.episodes.len(), 2);
+        assert_eq!(report.episodes[0].age_ms, 12_300);
+        assert_eq!(report.episodes[1].duration_ms, None);
+    }
+
+    #[test]
+    fn brown_out_history_keeps_newest_episodes() {
+        let mut history: BrownOutHistory<MillisInstant, 2> = BrownOutHistory::new();
+        for start in 0..4 {
+            history.record_brown_out(MillisInstant(start * 100), Some(3000), None, None);
+            let _ = history.close(MillisInstant(start * 100 + 10));
+        }
+
+        assert_eq!(history.total(), 4);
+        let starts: std::vec::Vec<u64> = history.oldest_first().map(|e| e.started_at.0).collect();
+        assert_eq!(starts, [200, 300]);
+    }
+
+    #[test]
+    fn brown_out_formatter_renders_lines() {
+        let mut history: BrownOutHistory<MillisInstant> = BrownOutHistory::new();
+        history.record_brown_out(
+            MillisInstant(0),
+            Some(2950),
+            Some(StrapSequenceKind::RecoveryImmediate),
+            Some(2),
+        );
+        history.mark_retry(true);
+        let _ = history.close(MillisInstant(45));
+        history.record_brown_out(MillisInstant(10_000), Some(3100), None, None);
+
+        let report = history.report(MillisInstant(12_300));
+        let formatter = BrownOutFormatter::new(&report);
+        assert_eq!(formatter.line_count(), 5);
+
+        let lines: std::vec::Vec<String> = (0..formatter.line_count())
+            .map(|index| {
+                let mut line = String::new();
+                formatter.write_line(&mut line, index).unwrap();
+                line
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "power history episodes=2 shown=2",
+                "power brownout 1 age=12.3s min=2950mV dur=45ms",
+                "power brownout 1 seq=recovery-immediate step=2 retry=yes",
+                "power brownout 2 age=2.3s min=3100mV dur=ongoing",
+                "power brownout 2 seq=idle retry=no",
+            ]
+        );
+        assert!(lines.iter().all(|line| line.len() <= 64));
+
+        let mut line = String::new();
+        assert!(formatter.write_line(&mut line, 5).is_err());
+    }
+}
@@ -5,37 +5,193 @@
 
 #![allow(dead_code)]
 
+use core::fmt;
+use core::time::Duration;
+
+use crate::sequences::fault::FAULT_RECOVERY_MAX_RETRIES;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CommandTag {
     Reboot,
     Recovery,
     Fault,
+    Power,
+    Watchdog,
+    Console,
+    Bridge,
+    Script,
     Status,
+    Mode,
+    Events,
+    Wait,
+    Macro,
     Help,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SubcommandTag {
     FaultRecover,
+    PowerStats,
+    PowerHistory,
+    ConsoleTail,
+    ConsoleSince,
+    ConsoleSysRq,
+    ConsoleSend,
+    ConsoleAttach,
+    ConsoleBaud,
+    ConsolePattern,
+    ConsolePatternAdd,
+    ConsolePatternRemove,
+    ConsolePatternList,
+    BridgeStats,
+    ScriptLoad,
+    ScriptList,
+    ScriptRun,
+    ScriptStop,
+    WaitComplete,
+    WaitIdle,
+    WaitEvent,
+    MacroDefine,
+    MacroRun,
+    MacroList,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ChoiceTag {
     RebootNow,
     RebootDelay,
+    RebootToBootloader,
     RecoveryEnter,
     RecoveryExit,
     RecoveryNow,
+    RecoveryTimeout,
     FaultRetries,
+    PowerStatsReset,
+    WatchdogStatus,
+    WatchdogOn,
+    WatchdogOff,
+    WatchdogBoot,
+    WatchdogSilence,
+    WatchdogPanic,
+    WatchdogLimit,
+    ConsoleLines,
+    ConsoleRun,
+    ConsoleKey,
+    ConsoleText,
+    ConsoleNewline,
+    ConsoleBaud,
+    ConsoleMarker,
+    ConsolePattern,
+    BridgeStatsReset,
+    ScriptName,
+    ModeText,
+    ModeJson,
+    EventsOn,
+    EventsOff,
+    EventCategories,
+    WaitTag,
+    WaitEventName,
+    WaitTimeout,
+    MacroName,
+    MacroBody,
+    MacroContinueOnError,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ValueSpec {
     None,
     Duration,
-    IntegerAssignment {
-        suggestions: &'static [&'static str],
-    },
+    /// `key=value` option such as `retries=2` or `timeout=30s`.
+    Assignment(OptionSpec),
+    /// Bare unsigned integer such as a line count or run id.
+    Count,
+    /// Single letter or digit, such as a sysrq key.
+    Key,
+    /// Double-quoted string with backslash escapes.
+    Text,
+    /// Identifier naming a stored script.
+    Name,
+    /// Baud rate in bits per second, or `auto`.
+    Baud,
+    /// Comma-separated `events` categories such as `strap,power`.
+    Categories,
+    /// Telemetry event name such as `strap-released`.
+    EventName,
+    /// Console marker name such as `login-prompt`.
+    Marker,
+}
+
+/// Declaration of a `key=value` option. The parser validates values against
+/// `kind`, help prints its bounds, and completion offers `suggestions`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OptionSpec {
+    pub kind: OptionKind,
+    /// Whole `key=value` words offered by tab completion.
+    pub suggestions: &'static [&'static str],
+}
+
+/// Type and bounds of an option value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionKind {
+    /// Unsigned integer from `min` to `max` inclusive.
+    Integer { min: u32, max: u32 },
+    /// Duration from `min` to `max` inclusive.
+    Duration { min: Duration, max: Duration },
+    /// One of the listed words (case insensitive).
+    Enum(&'static [&'static str]),
+    /// Double-quoted string with backslash escapes.
+    Text,
+}
+
+/// Describes the accepted values, as in `retries must be 1-3`.
+impl fmt::Display for OptionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionKind::Integer { min, max } => write!(f, "{min}-{max}"),
+            OptionKind::Duration { min, max } => {
+                write!(f, "{}-{}", DurationBound(*min), DurationBound(*max))
+            }
+            OptionKind::Enum(words) => {
+                f.write_str("one of ")?;
+                for (index, word) in words.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(word)?;
+                }
+                Ok(())
+            }
+            OptionKind::Text => f.write_str("a quoted string"),
+        }
+    }
+}
+
+/// Duration units and their length in nanoseconds, largest first.
+pub const DURATION_UNITS: [(&str, u64); 5] = [
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+];
+
+/// Prints a duration bound the way it would be typed, in the largest unit
+/// that divides it: `500ms`, `30s`, or `1h`.
+struct DurationBound(Duration);
+
+impl fmt::Display for DurationBound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = u64::try_from(self.0.as_nanos()).unwrap_or(u64::MAX);
+        if nanos == 0 {
+            return f.write_str("0s");
+        }
+        let (unit, unit_nanos) = DURATION_UNITS
+            .iter()
+            .copied()
+            .find(|(_, unit_nanos)| nanos % unit_nanos == 0)
+            .unwrap_or(("us", 1_000));
+        write!(f, "{}{unit}", nanos / unit_nanos)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -50,6 +206,8 @@ pub struct CommandSpec {
     pub tag: CommandTag,
     pub grammar: &'static Node,
     pub help: HelpTopics,
+    /// One-line description shown by `help`.
+    pub summary: &'static str,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -60,6 +218,15 @@ pub enum Node {
         default: Option<DefaultChoice>,
     },
     Subcommands(&'static [SubcommandBranch]),
+    /// Positional value without a keyword.
+    Argument {
+        label: &'static str,
+        tag: ChoiceTag,
+        value: ValueSpec,
+        required: bool,
+        doc: &'static str,
+        next: &'static Node,
+    },
     Topic {
         topics: HelpTopics,
         next: &'static Node,
@@ -71,6 +238,8 @@ pub struct ChoiceBranch {
     pub keyword: &'static str,
     pub tag: ChoiceTag,
     pub value: ValueSpec,
+    /// What the option does, shown by `help <command>`.
+    pub doc: &'static str,
     pub next: &'static Node,
 }
 
@@ -85,21 +254,32 @@ pub struct SubcommandBranch {
     pub name: &'static str,
     pub tag: SubcommandTag,
     pub grammar: &'static Node,
+    /// One-line description shown by `help <command>`.
+    pub summary: &'static str,
 }
 
 const END: Node = Node::End;
 
-const REBOOT_CHOICES: [ChoiceBranch; 2] = [
+const REBOOT_CHOICES: [ChoiceBranch; 3] = [
     ChoiceBranch {
         keyword: "now",
         tag: ChoiceTag::RebootNow,
         value: ValueSpec::None,
+        doc: "start the sequence right away",
         next: &END,
     },
     ChoiceBranch {
         keyword: "delay",
         tag: ChoiceTag::RebootDelay,
         value: ValueSpec::Duration,
+        doc: "wait before starting the sequence",
+        next: &END,
+    },
+    ChoiceBranch {
+        keyword: "to-bootloader",
+        tag: ChoiceTag::RebootToBootloader,
+        value: ValueSpec::None,
+        doc: "reboot and stop at the U-Boot prompt",
         next: &END,
     },
 ];
@@ -117,22 +297,46 @@ const RECOVERY_CHOICES: [ChoiceBranch; 3] = [
         keyword: "enter",
         tag: ChoiceTag::RecoveryEnter,
         value: ValueSpec::None,
+        doc: "reset into forced recovery with REC held",
         next: &END,
     },
     ChoiceBranch {
         keyword: "exit",
         tag: ChoiceTag::RecoveryExit,
         value: ValueSpec::None,
+        doc: "leave recovery with a normal reset",
         next: &END,
     },
     ChoiceBranch {
         keyword: "now",
         tag: ChoiceTag::RecoveryNow,
         value: ValueSpec::None,
-        next: &END,
+        doc: "hold REC until the console starts talking",
+        next: &RECOVERY_NOW_GRAMMAR,
     },
 ];
 
+const RECOVERY_TIMEOUT_SUGGESTIONS: [&str; 3] = ["timeout=10s", "timeout=30s", "timeout=60s"];
+
+const RECOVERY_NOW_CHOICES: [ChoiceBranch; 1] = [ChoiceBranch {
+    keyword: "timeout",
+    tag: ChoiceTag::RecoveryTimeout,
+    value: ValueSpec::Assignment(OptionSpec {
+        kind: OptionKind::Duration {
+            min: Duration::ZERO,
+            max: Duration::from_secs(3600),
+        },
+        suggestions: &RECOVERY_TIMEOUT_SUGGESTIONS,
+    }),
+    doc: "release REC anyway after this long (0s waits forever)",
+    next: &END,
+}];
+
+const RECOVERY_NOW_GRAMMAR: Node = Node::OptionalChoice {
+    choices: &RECOVERY_NOW_CHOICES,
+    default: None,
+};
+
 const RECOVERY_GRAMMAR: Node = Node::OptionalChoice {
     choices: &RECOVERY_CHOICES,
     default: Some(DefaultChoice {
@@ -141,14 +345,23 @@ const RECOVERY_GRAMMAR: Node = Node::OptionalChoice {
     }),
 };
 
+// `u32::from` is not callable in a const.
+#[allow(clippy::cast_lossless)]
+const FAULT_RETRIES_MAX: u32 = FAULT_RECOVERY_MAX_RETRIES as u32;
+
 const FAULT_RETRY_SUGGESTIONS: [&str; 3] = ["retries=1", "retries=2", "retries=3"];
 
 const FAULT_RECOVER_CHOICES: [ChoiceBranch; 1] = [ChoiceBranch {
     keyword: "retries",
     tag: ChoiceTag::FaultRetries,
-    value: ValueSpec::IntegerAssignment {
+    value: ValueSpec::Assignment(OptionSpec {
+        kind: OptionKind::Integer {
+            min: 1,
+            max: FAULT_RETRIES_MAX,
+        },
         suggestions: &FAULT_RETRY_SUGGESTIONS,
-    },
+    }),
+    doc: "attempts before giving up",
     next: &END,
 }];
 
@@ -161,45 +374,623 @@ const FAULT_SUBCOMMANDS: [SubcommandBranch; 1] = [SubcommandBranch {
     name: "recover",
     tag: SubcommandTag::FaultRecover,
     grammar: &FAULT_RECOVER_GRAMMAR,
+    summary: "run the fault recovery sequence",
 }];
 
 const FAULT_GRAMMAR: Node = Node::Subcommands(&FAULT_SUBCOMMANDS);
 
+const POWER_STATS_CHOICES: [ChoiceBranch; 1] = [ChoiceBranch {
+    keyword: "reset",
+    tag: ChoiceTag::PowerStatsReset,
+    value: ValueSpec::None,
+    doc: "clear the statistics after printing them",
+    next: &END,
+}];
+
+const POWER_STATS_GRAMMAR: Node = Node::OptionalChoice {
+    choices: &POWER_STATS_CHOICES,
+    default: None,
+};
+
+const POWER_SUBCOMMANDS: [SubcommandBranch; 2] = [
+    SubcommandBranch {
+        name: "stats",
+        tag: SubcommandTag::PowerStats,
+        grammar: &POWER_STATS_GRAMMAR,
+        summary: "print rail statistics",
+    },
+    SubcommandBranch {
+        name: "history",
+        tag: SubcommandTag::PowerHistory,
+        grammar: &END,
+        summary: "list recent brown-out episodes",
+    },
+];
+
+const POWER_GRAMMAR: Node = Node::Subcommands(&POWER_SUBCOMMANDS);
+
+const WATCHDOG_BOOT_SUGGESTIONS: [&str; 3] = ["boot=120s", "boot=180s", "boot=300s"];
+const WATCHDOG_SILENCE_SUGGESTIONS: [&str; 3] = ["silence=0s", "silence=300s", "silence=600s"];
+const WATCHDOG_PANIC_SUGGESTIONS: [&str; 3] = ["panic=0s", "panic=30s", "panic=60s"];
+const WATCHDOG_LIMIT_SUGGESTIONS: [&str; 3] = ["limit=0", "limit=3", "limit=5"];
+
+const WATCHDOG_CHOICES: [ChoiceBranch; 7] = [
+    ChoiceBranch {
+        keyword: "status",
+        tag: ChoiceTag::WatchdogStatus,
+        value: ValueSpec::None,
+        doc: "print the supervisor state",
+        next: &END,
+    },
+    ChoiceBranch {
+        keyword: "on",
+        tag: ChoiceTag::WatchdogOn,
+        value: ValueSpec::None,
+        doc: "enable the supervisor",
+        next: &END,
+    },
+    ChoiceBranch {
+        keyword: "off",
+        tag: ChoiceTag::WatchdogOff,
+        value: ValueSpec::None,
+        doc: "disable the supervisor",
+        next: &END,
+    },
+    ChoiceBranch {
+        keyword: "boot",
+        tag: ChoiceTag::WatchdogBoot,
+        value: ValueSpec::Assignment(OptionSpec {
+            kind: OptionKind::Duration {
+                min: Duration::from_secs(1),
+                max: Duration::from_secs(3600),
+            },
+            suggestions: &WATCHDOG_BOOT_SUGGESTIONS,
+        }),
+        doc: "deadline for a confirmed boot",
+        next: &END,
+    },
+    ChoiceBranch {
+        keyword: "silence",
+        tag: ChoiceTag::WatchdogSilence,
+        value: ValueSpec::Assignment(OptionSpec {
+            kind: OptionKind::Duration {
+                min: Duration::ZERO,
+                max: Duration::from_secs(24 * 3600),
+            },
+            suggestions: &WATCHDOG_SILENCE_SUGGESTIONS,
+        }),
+        doc: "longest console silence allowed (0s disables)",
+        next: &END,
+    },
+    ChoiceBranch {
+        keyword: "panic",
+        tag: ChoiceTag::WatchdogPanic,
+        value: ValueSpec::Assignment(OptionSpec {
+            kind: OptionKind::Duration {
+                min: Duration::ZERO,
+                max: Duration::from_secs(3600),
+            },
+            suggestions: &WATCHDOG_PANIC_SUGGESTIONS,
+        }),
+        doc: "grace period after a kernel panic (0s disables)",
+        next: &END,
+    },
+    ChoiceBranch {
+        keyword: "limit",
+        tag: ChoiceTag::WatchdogLimit,
+        value: ValueSpec::Assignment(OptionSpec {
+            kind: OptionKind::Integer { min: 0, max: 10 },
+            suggestions: &WATCHDOG_LIMIT_SUGGESTIONS,
+        }),
+        doc: "consecutive automatic recoveries allowed",
+        next: &END,
+    },
+];
+
+const WATCHDOG_GRAMMAR: Node = Node::OptionalChoice {
+    choices: &WATCHDOG_CHOICES,
+    default: Some(DefaultChoice {
+        tag: ChoiceTag::WatchdogStatus,
+        next: &END,
+    }),
+};
+
+const CONSOLE_TAIL_GRAMMAR: Node = Node::Argument {
+    label: "line count",
+    tag: ChoiceTag::ConsoleLines,
+    value: ValueSpec::Count,
+    required: false,
+    doc: "lines to print (default 20)",
+    next: &END,
+};
+
+const CONSOLE_SINCE_GRAMMAR: Node = Node::Argument {
+    label: "run id",
+    tag: ChoiceTag::ConsoleRun,
+    value: ValueSpec::Count,
+    required: true,
+    doc: "run number shown in the scrollback",
+    next: &END,
+};
+
+const CONSOLE_SYSRQ_GRAMMAR: Node = Node::Argument {
+    label: "sysrq key",
+    tag: ChoiceTag::ConsoleKey,
+    value: ValueSpec::Key,
+    required: true,
+    doc: "key to send after the break",
+    next: &END,
+};
+
+const CONSOLE_SEND_CHOICES: [ChoiceBranch; 1] = [ChoiceBranch {
+    keyword: "newline",
+    tag: ChoiceTag::ConsoleNewline,
+    value: ValueSpec::None,
+    doc: "press Enter after the text",
+    next: &END,
+}];
+
+const CONSOLE_SEND_OPTIONS: Node = Node::OptionalChoice {
+    choices: &CONSOLE_SEND_CHOICES,
+    default: None,
+};
+
+const CONSOLE_SEND_GRAMMAR: Node = Node::Argument {
+    label: "quoted text",
+    tag: ChoiceTag::ConsoleText,
+    value: ValueSpec::Text,
+    required: true,
+    doc: "text with backslash escapes",
+    next: &CONSOLE_SEND_OPTIONS,
+};
+
+const CONSOLE_BAUD_GRAMMAR: Node = Node::Argument {
+    label: "baud rate or auto",
+    tag: ChoiceTag::ConsoleBaud,
+    value: ValueSpec::Baud,
+    required: true,
+    doc: "fixed rate (300-2000000) or auto",
+    next: &END,
+};
+
+const CONSOLE_PATTERN_TEXT_GRAMMAR: Node = Node::Argument {
+    label: "quoted text",
+    tag: ChoiceTag::ConsoleText,
+    value: ValueSpec::Text,
+    required: true,
+    doc: "text to watch for, up to 32 bytes on one line",
+    next: &END,
+};
+
+const CONSOLE_PATTERN_ADD_GRAMMAR: Node = Node::Argument {
+    label: "marker",
+    tag: ChoiceTag::ConsoleMarker,
+    value: ValueSpec::Marker,
+    required: true,
+    doc: "boot-banner, login-prompt, kernel-panic, tegra-recovery, or custom",
+    next: &CONSOLE_PATTERN_TEXT_GRAMMAR,
+};
+
+const CONSOLE_PATTERN_REMOVE_GRAMMAR: Node = Node::Argument {
+    label: "pattern id",
+    tag: ChoiceTag::ConsolePattern,
+    value: ValueSpec::Count,
+    required: true,
+    doc: "id shown by console pattern list",
+    next: &END,
+};
+
+const CONSOLE_PATTERN_SUBCOMMANDS: [SubcommandBranch; 3] = [
+    SubcommandBranch {
+        name: "add",
+        tag: SubcommandTag::ConsolePatternAdd,
+        grammar: &CONSOLE_PATTERN_ADD_GRAMMAR,
+        summary: "report a marker whenever the text appears",
+    },
+    SubcommandBranch {
+        name: "remove",
+        tag: SubcommandTag::ConsolePatternRemove,
+        grammar: &CONSOLE_PATTERN_REMOVE_GRAMMAR,
+        summary: "stop watching for a pattern",
+    },
+    SubcommandBranch {
+        name: "list",
+        tag: SubcommandTag::ConsolePatternList,
+        grammar: &END,
+        summary: "print the registered patterns",
+    },
+];
+
+const CONSOLE_PATTERN_GRAMMAR: Node = Node::Subcommands(&CONSOLE_PATTERN_SUBCOMMANDS);
+
+const CONSOLE_SUBCOMMANDS: [SubcommandBranch; 7] = [
+    SubcommandBranch {
+        name: "tail",
+        tag: SubcommandTag::ConsoleTail,
+        grammar: &CONSOLE_TAIL_GRAMMAR,
+        summary: "print the newest scrollback lines",
+    },
+    SubcommandBranch {
+        name: "since",
+        tag: SubcommandTag::ConsoleSince,
+        grammar: &CONSOLE_SINCE_GRAMMAR,
+        summary: "print scrollback from a boot run onward",
+    },
+    SubcommandBranch {
+        name: "sysrq",
+        tag: SubcommandTag::ConsoleSysRq,
+        grammar: &CONSOLE_SYSRQ_GRAMMAR,
+        summary: "send a break and a magic sysrq key",
+    },
+    SubcommandBranch {
+        name: "send",
+        tag: SubcommandTag::ConsoleSend,
+        grammar: &CONSOLE_SEND_GRAMMAR,
+        summary: "type text into the console",
+    },
+    SubcommandBranch {
+        name: "attach",
+        tag: SubcommandTag::ConsoleAttach,
+        grammar: &END,
+        summary: "bridge this port to the console until ~.",
+    },
+    SubcommandBranch {
+        name: "baud",
+        tag: SubcommandTag::ConsoleBaud,
+        grammar: &CONSOLE_BAUD_GRAMMAR,
+        summary: "set or detect the console baud rate",
+    },
+    SubcommandBranch {
+        name: "pattern",
+        tag: SubcommandTag::ConsolePattern,
+        grammar: &CONSOLE_PATTERN_GRAMMAR,
+        summary: "manage the console marker patterns",
+    },
+];
+
+const CONSOLE_GRAMMAR: Node = Node::Subcommands(&CONSOLE_SUBCOMMANDS);
+
+const BRIDGE_STATS_CHOICES: [ChoiceBranch; 1] = [ChoiceBranch {
+    keyword: "reset",
+    tag: ChoiceTag::BridgeStatsReset,
+    value: ValueSpec::None,
+    doc: "clear the counters after printing them",
+    next: &END,
+}];
+
+const BRIDGE_STATS_GRAMMAR: Node = Node::OptionalChoice {
+    choices: &BRIDGE_STATS_CHOICES,
+    default: None,
+};
+
+const BRIDGE_SUBCOMMANDS: [SubcommandBranch; 1] = [SubcommandBranch {
+    name: "stats",
+    tag: SubcommandTag::BridgeStats,
+    grammar: &BRIDGE_STATS_GRAMMAR,
+    summary: "print bridge counters",
+}];
+
+const BRIDGE_GRAMMAR: Node = Node::Subcommands(&BRIDGE_SUBCOMMANDS);
+
+const SCRIPT_NAME_GRAMMAR: Node = Node::Argument {
+    label: "script name",
+    tag: ChoiceTag::ScriptName,
+    value: ValueSpec::Name,
+    required: true,
+    doc: "stored script name",
+    next: &END,
+};
+
+const SCRIPT_SUBCOMMANDS: [SubcommandBranch; 4] = [
+    SubcommandBranch {
+        name: "load",
+        tag: SubcommandTag::ScriptLoad,
+        grammar: &SCRIPT_NAME_GRAMMAR,
+        summary: "enter script lines until end",
+    },
+    SubcommandBranch {
+        name: "list",
+        tag: SubcommandTag::ScriptList,
+        grammar: &END,
+        summary: "show stored scripts",
+    },
+    SubcommandBranch {
+        name: "run",
+        tag: SubcommandTag::ScriptRun,
+        grammar: &SCRIPT_NAME_GRAMMAR,
+        summary: "start a stored script",
+    },
+    SubcommandBranch {
+        name: "stop",
+        tag: SubcommandTag::ScriptStop,
+        grammar: &END,
+        summary: "stop the running script",
+    },
+];
+
+const SCRIPT_GRAMMAR: Node = Node::Subcommands(&SCRIPT_SUBCOMMANDS);
+
+const MODE_CHOICES: [ChoiceBranch; 2] = [
+    ChoiceBranch {
+        keyword: "text",
+        tag: ChoiceTag::ModeText,
+        value: ValueSpec::None,
+        doc: "OK/ERR lines for people",
+        next: &END,
+    },
+    ChoiceBranch {
+        keyword: "json",
+        tag: ChoiceTag::ModeJson,
+        value: ValueSpec::None,
+        doc: "one JSON object per line for scripts",
+        next: &END,
+    },
+];
+
+const MODE_GRAMMAR: Node = Node::OptionalChoice {
+    choices: &MODE_CHOICES,
+    default: None,
+};
+
+const EVENT_CATEGORIES_GRAMMAR: Node = Node::Argument {
+    label: "categories",
+    tag: ChoiceTag::EventCategories,
+    value: ValueSpec::Categories,
+    required: false,
+    doc: "strap, command, sequence, bridge, power (default all)",
+    next: &END,
+};
+
+const EVENTS_CHOICES: [ChoiceBranch; 2] = [
+    ChoiceBranch {
+        keyword: "on",
+        tag: ChoiceTag::EventsOn,
+        value: ValueSpec::None,
+        doc: "print events from these categories",
+        next: &EVENT_CATEGORIES_GRAMMAR,
+    },
+    ChoiceBranch {
+        keyword: "off",
+        tag: ChoiceTag::EventsOff,
+        value: ValueSpec::None,
+        doc: "stop printing events from these categories",
+        next: &EVENT_CATEGORIES_GRAMMAR,
+    },
+];
+
+const EVENTS_GRAMMAR: Node = Node::OptionalChoice {
+    choices: &EVENTS_CHOICES,
+    default: None,
+};
+
+const WAIT_TIMEOUT_SUGGESTIONS: [&str; 3] = ["timeout=10s", "timeout=60s", "timeout=300s"];
+
+const WAIT_OPTION_CHOICES: [ChoiceBranch; 1] = [ChoiceBranch {
+    keyword: "timeout",
+    tag: ChoiceTag::WaitTimeout,
+    value: ValueSpec::Assignment(OptionSpec {
+        kind: OptionKind::Duration {
+            min: Duration::ZERO,
+            max: Duration::from_secs(3600),
+        },
+        suggestions: &WAIT_TIMEOUT_SUGGESTIONS,
+    }),
+    doc: "give up after this long (default 60s)",
+    next: &END,
+}];
+
+const WAIT_OPTIONS: Node = Node::OptionalChoice {
+    choices: &WAIT_OPTION_CHOICES,
+    default: None,
+};
+
+const WAIT_COMPLETE_GRAMMAR: Node = Node::Argument {
+    label: "request tag",
+    tag: ChoiceTag::WaitTag,
+    value: ValueSpec::Count,
+    required: false,
+    doc: "tag of the request that queued the sequence (default any)",
+    next: &WAIT_OPTIONS,
+};
+
+const WAIT_EVENT_GRAMMAR: Node = Node::Argument {
+    label: "event name",
+    tag: ChoiceTag::WaitEventName,
+    value: ValueSpec::EventName,
+    required: true,
+    doc: "telemetry event such as strap-released",
+    next: &WAIT_OPTIONS,
+};
+
+const WAIT_SUBCOMMANDS: [SubcommandBranch; 3] = [
+    SubcommandBranch {
+        name: "complete",
+        tag: SubcommandTag::WaitComplete,
+        grammar: &WAIT_COMPLETE_GRAMMAR,
+        summary: "wait for a strap sequence to end",
+    },
+    SubcommandBranch {
+        name: "idle",
+        tag: SubcommandTag::WaitIdle,
+        grammar: &WAIT_OPTIONS,
+        summary: "wait until no sequence is running or queued",
+    },
+    SubcommandBranch {
+        name: "event",
+        tag: SubcommandTag::WaitEvent,
+        grammar: &WAIT_EVENT_GRAMMAR,
+        summary: "wait for a telemetry event",
+    },
+];
+
+const WAIT_GRAMMAR: Node = Node::Subcommands(&WAIT_SUBCOMMANDS);
+
+const MACRO_DEFINE_CHOICES: [ChoiceBranch; 1] = [ChoiceBranch {
+    keyword: "continue-on-error",
+    tag: ChoiceTag::MacroContinueOnError,
+    value: ValueSpec::None,
+    doc: "keep going after a command fails",
+    next: &END,
+}];
+
+const MACRO_DEFINE_OPTIONS: Node = Node::OptionalChoice {
+    choices: &MACRO_DEFINE_CHOICES,
+    default: None,
+};
+
+const MACRO_BODY_GRAMMAR: Node = Node::Argument {
+    label: "quoted commands",
+    tag: ChoiceTag::MacroBody,
+    value: ValueSpec::Text,
+    required: true,
+    doc: "commands separated by `;`",
+    next: &MACRO_DEFINE_OPTIONS,
+};
+
+const MACRO_DEFINE_GRAMMAR: Node = Node::Argument {
+    label: "macro name",
+    tag: ChoiceTag::MacroName,
+    value: ValueSpec::Name,
+    required: true,
+    doc: "name to store the commands under",
+    next: &MACRO_BODY_GRAMMAR,
+};
+
+const MACRO_NAME_GRAMMAR: Node = Node::Argument {
+    label: "macro name",
+    tag: ChoiceTag::MacroName,
+    value: ValueSpec::Name,
+    required: true,
+    doc: "stored macro name",
+    next: &END,
+};
+
+const MACRO_SUBCOMMANDS: [SubcommandBranch; 3] = [
+    SubcommandBranch {
+        name: "define",
+        tag: SubcommandTag::MacroDefine,
+        grammar: &MACRO_DEFINE_GRAMMAR,
+        summary: "store a command chain under a name",
+    },
+    SubcommandBranch {
+        name: "run",
+        tag: SubcommandTag::MacroRun,
+        grammar: &MACRO_NAME_GRAMMAR,
+        summary: "run a stored macro",
+    },
+    SubcommandBranch {
+        name: "list",
+        tag: SubcommandTag::MacroList,
+        grammar: &END,
+        summary: "show stored macros",
+    },
+];
+
+const MACRO_GRAMMAR: Node = Node::Subcommands(&MACRO_SUBCOMMANDS);
+
 const HELP_GRAMMAR: Node = Node::Topic {
     topics: HelpTopics::Commands,
     next: &END,
 };
 
-const COMMANDS: [CommandSpec; 5] = [
+const COMMANDS: [CommandSpec; 14] = [
     CommandSpec {
         name: "reboot",
         tag: CommandTag::Reboot,
         grammar: &REBOOT_GRAMMAR,
         help: HelpTopics::None,
+        summary: "queue the normal reboot sequence",
     },
     CommandSpec {
         name: "recovery",
         tag: CommandTag::Recovery,
         grammar: &RECOVERY_GRAMMAR,
         help: HelpTopics::None,
+        summary: "manage recovery strap flows",
     },
     CommandSpec {
         name: "fault",
         tag: CommandTag::Fault,
         grammar: &FAULT_GRAMMAR,
         help: HelpTopics::None,
+        summary: "attempt the fault recovery sequence",
+    },
+    CommandSpec {
+        name: "power",
+        tag: CommandTag::Power,
+        grammar: &POWER_GRAMMAR,
+        help: HelpTopics::None,
+        summary: "rail statistics and the brown-out log",
+    },
+    CommandSpec {
+        name: "watchdog",
+        tag: CommandTag::Watchdog,
+        grammar: &WATCHDOG_GRAMMAR,
+        help: HelpTopics::None,
+        summary: "configure the boot supervisor",
+    },
+    CommandSpec {
+        name: "console",
+        tag: CommandTag::Console,
+        grammar: &CONSOLE_GRAMMAR,
+        help: HelpTopics::None,
+        summary: "Jetson console scrollback and control",
+    },
+    CommandSpec {
+        name: "bridge",
+        tag: CommandTag::Bridge,
+        grammar: &BRIDGE_GRAMMAR,
+        help: HelpTopics::None,
+        summary: "bridge traffic and error counters",
+    },
+    CommandSpec {
+        name: "script",
+        tag: CommandTag::Script,
+        grammar: &SCRIPT_GRAMMAR,
+        help: HelpTopics::None,
+        summary: "expect-style console scripts",
     },
     CommandSpec {
         name: "status",
         tag: CommandTag::Status,
         grammar: &END,
         help: HelpTopics::None,
+        summary: "display orchestrator state",
+    },
+    CommandSpec {
+        name: "mode",
+        tag: CommandTag::Mode,
+        grammar: &MODE_GRAMMAR,
+        help: HelpTopics::None,
+        summary: "show or switch the response format",
+    },
+    CommandSpec {
+        name: "events",
+        tag: CommandTag::Events,
+        grammar: &EVENTS_GRAMMAR,
+        help: HelpTopics::None,
+        summary: "print telemetry events as they happen",
+    },
+    CommandSpec {
+        name: "wait",
+        tag: CommandTag::Wait,
+        grammar: &WAIT_GRAMMAR,
+        help: HelpTopics::None,
+        summary: "hold input until a sequence or event",
+    },
+    CommandSpec {
+        name: "macro",
+        tag: CommandTag::Macro,
+        grammar: &MACRO_GRAMMAR,
+        help: HelpTopics::None,
+        summary: "stored command chains",
     },
     CommandSpec {
         name: "help",
         tag: CommandTag::Help,
         grammar: &HELP_GRAMMAR,
         help: HelpTopics::Commands,
+        summary: "show help for a command",
     },
 ];
 
@@ -216,8 +1007,17 @@ pub fn command(tag: CommandTag) -> &'static CommandSpec {
         CommandTag::Reboot => &COMMANDS[0],
         CommandTag::Recovery => &COMMANDS[1],
         CommandTag::Fault => &COMMANDS[2],
-        CommandTag::Status => &COMMANDS[3],
-        CommandTag::Help => &COMMANDS[4],
+        CommandTag::Power => &COMMANDS[3],
+        CommandTag::Watchdog => &COMMANDS[4],
+        CommandTag::Console => &COMMANDS[5],
+        CommandTag::Bridge => &COMMANDS[6],
+        CommandTag::Script => &COMMANDS[7],
+        CommandTag::Status => &COMMANDS[8],
+        CommandTag::Mode => &COMMANDS[9],
+        CommandTag::Events => &COMMANDS[10],
+        CommandTag::Wait => &COMMANDS[11],
+        CommandTag::Macro => &COMMANDS[12],
+        CommandTag::Help => &COMMANDS[13],
     }
 }
 
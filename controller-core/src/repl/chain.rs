@@ -0,0 +1,314 @@
+//! `;`-separated command chains shared by the firmware and emulator REPLs.
+//!
+//! A typed line such as `recovery enter; wait complete; status` and the body
+//! of a stored macro both run as a chain: one command at a time, stopping at
+//! the first error unless the chain was started with `continue-on-error`. A
+//! chain may be suspended between commands (a `wait` holds the session), so
+//! [`CommandChain`] keeps its own copy of the text. A macro run from a typed
+//! line nests one level deep; macros cannot run other macros.
+
+use core::fmt;
+use core::ops::Range;
+
+use heapless::{String, Vec};
+
+use super::editor::EDITOR_LINE_CAPACITY;
+use super::grammar;
+use crate::orchestrator::RequestTag;
+
+/// Longest chain text: a full editor line or a macro body.
+pub const CHAIN_TEXT_CAPACITY: usize = EDITOR_LINE_CAPACITY;
+/// Commands accepted in one chain.
+pub const MAX_CHAIN_COMMANDS: usize = 8;
+/// Chains active at once: a typed line and the macro it runs.
+pub const MAX_CHAIN_DEPTH: usize = 2;
+
+/// Byte ranges of the commands in a chain.
+pub type CommandSpans = Vec<Range<usize>, MAX_CHAIN_COMMANDS>;
+
+/// Reasons a chain cannot be started.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChainError {
+    /// The text does not fit [`CHAIN_TEXT_CAPACITY`].
+    TooLong,
+    /// More than [`MAX_CHAIN_COMMANDS`] commands.
+    TooManyCommands,
+    /// A macro tried to run another macro.
+    Nested,
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainError::TooLong => write!(f, "longer than {CHAIN_TEXT_CAPACITY} bytes"),
+            ChainError::TooManyCommands => {
+                write!(f, "more than {MAX_CHAIN_COMMANDS} commands")
+            }
+            ChainError::Nested => f.write_str("macros cannot run other macros"),
+        }
+    }
+}
+
+/// Splits `line` at each `;` outside quoted strings, skipping empty commands.
+///
+/// The line is scanned directly rather than lexed, so a chain is not limited
+/// by the parser's token count; an unterminated quote runs to the end of the
+/// line and is left for the parser to report.
+///
+/// # Errors
+/// Returns [`ChainError::TooManyCommands`] for more than
+/// [`MAX_CHAIN_COMMANDS`] commands.
+pub fn split(line: &str) -> Result<CommandSpans, ChainError> {
+    let mut spans = CommandSpans::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (index, byte) in line.bytes().enumerate() {
+        match byte {
+            _ if escaped => escaped = false,
+            b'\\' if quoted => escaped = true,
+            b'"' => quoted = !quoted,
+            b';' if !quoted => {
+                push_span(&mut spans, line, start..index)?;
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    push_span(&mut spans, line, start..line.len())?;
+    Ok(spans)
+}
+
+fn push_span(spans: &mut CommandSpans, line: &str, span: Range<usize>) -> Result<(), ChainError> {
+    if line[span.clone()].trim().is_empty() {
+        return Ok(());
+    }
+    spans.push(span).map_err(|_| ChainError::TooManyCommands)
+}
+
+/// Next command of a chain and the tag its replies carry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChainCommand<'a> {
+    pub text: &'a str,
+    /// The command's own `@tag`, or the tag of the line that started the chain.
+    pub tag: Option<RequestTag>,
+}
+
+struct ChainFrame {
+    text: String<CHAIN_TEXT_CAPACITY>,
+    commands: CommandSpans,
+    next: usize,
+    continue_on_error: bool,
+    tag: Option<RequestTag>,
+}
+
+impl ChainFrame {
+    fn is_done(&self) -> bool {
+        self.next >= self.commands.len()
+    }
+}
+
+/// Commands still to run for a session, innermost chain last.
+pub struct CommandChain {
+    frames: Vec<ChainFrame, MAX_CHAIN_DEPTH>,
+}
+
+impl Default for CommandChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandChain {
+    /// Creates an empty chain.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { frames: Vec::new() }
+    }
+
+    /// Returns `true` when no commands are left.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.iter().all(ChainFrame::is_done)
+    }
+
+    /// Replaces whatever is left with the commands of a typed line and
+    /// returns how many there are. Replies default to the line's `tag`.
+    ///
+    /// # Errors
+    /// Returns a [`ChainError`] when the line holds too many commands.
+    pub fn start(&mut self, line: &str, tag: Option<RequestTag>) -> Result<usize, ChainError> {
+        self.frames.clear();
+        self.push(line, false, tag)
+    }
+
+    /// Queues the commands of a macro ahead of the rest of the line that
+    /// runs it and returns how many there are. Replies default to `tag`.
+    ///
+    /// The line stays on the stack even when the macro is its last command,
+    /// so a macro that runs a macro is always rejected.
+    ///
+    /// # Errors
+    /// Returns a [`ChainError`] when the text is too long, holds too many
+    /// commands, or would nest a macro inside a macro.
+    pub fn push(
+        &mut self,
+        text: &str,
+        continue_on_error: bool,
+        tag: Option<RequestTag>,
+    ) -> Result<usize, ChainError> {
+        if self.frames.is_full() {
+            return Err(ChainError::Nested);
+        }
+        let text = String::try_from(text).map_err(|_| ChainError::TooLong)?;
+        let commands = split(&text)?;
+        let count = commands.len();
+        self.frames
+            .push(ChainFrame {
+                text,
+                commands,
+                next: 0,
+                continue_on_error,
+                tag,
+            })
+            .map_err(|_| ChainError::Nested)?;
+        Ok(count)
+    }
+
+    /// Takes the next command to run.
+    pub fn next_command(&mut self) -> Option<ChainCommand<'_>> {
+        self.prune();
+        let frame = self.frames.last_mut()?;
+        let span = frame.commands[frame.next].clone();
+        frame.next += 1;
+        let text = frame.text.as_str()[span].trim();
+        Some(ChainCommand {
+            text,
+            tag: grammar::request_tag(text).or(frame.tag),
+        })
+    }
+
+    /// Records that the last command failed: chains without
+    /// `continue-on-error` are dropped, and a failed macro counts as a failure
+    /// of the chain that ran it.
+    pub fn fail(&mut self) {
+        while self
+            .frames
+            .last()
+            .is_some_and(|frame| !frame.continue_on_error)
+        {
+            self.frames.pop();
+        }
+    }
+
+    /// Drops every remaining command.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    fn prune(&mut self) {
+        while self.frames.last().is_some_and(ChainFrame::is_done) {
+            self.frames.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(line: &str) -> std::vec::Vec<&str> {
+        split(line)
+            .expect("line splits")
+            .iter()
+            .map(|span| line[span.clone()].trim())
+            .collect()
+    }
+
+    #[test]
+    fn splits_outside_quotes_and_skips_empty_commands() {
+        assert_eq!(
+            commands("recovery enter; wait complete;status"),
+            ["recovery enter", "wait complete", "status"]
+        );
+        assert_eq!(
+            commands(r#"console send "a;b"; ; status;"#),
+            [r#"console send "a;b""#, "status"]
+        );
+        assert_eq!(
+            commands(r#"console send "a\";b"; status"#),
+            [r#"console send "a\";b""#, "status"]
+        );
+        assert_eq!(split("s;s;s;s;s;s;s;s;s"), Err(ChainError::TooManyCommands));
+    }
+
+    #[test]
+    fn splits_chains_longer_than_the_token_limit() {
+        let line = "power history; bridge stats reset; watchdog boot=90s; \
+                    watchdog silence=30s; watchdog limit=2; recovery now timeout=5s; \
+                    wait complete timeout=10s; console tail 5";
+        let spans = split(line).expect("line splits");
+        assert!(grammar::lex(line).is_err());
+        assert_eq!(spans.len(), 8);
+        assert_eq!(line[spans[7].clone()].trim(), "console tail 5");
+    }
+
+    #[test]
+    fn stops_at_the_first_failure_unless_told_otherwise() {
+        let mut chain = CommandChain::new();
+        let tag = Some(RequestTag::new(7));
+        assert_eq!(chain.start("status; macro run boot; mode", tag), Ok(3));
+
+        let first = chain.next_command().expect("first command");
+        assert_eq!(first.text, "status");
+        assert_eq!(first.tag, tag);
+        assert_eq!(
+            chain.next_command().map(|command| command.text),
+            Some("macro run boot")
+        );
+
+        assert_eq!(chain.push("reboot now; @9 status", true, tag), Ok(2));
+        assert_eq!(chain.push("status", false, tag), Err(ChainError::Nested));
+        assert_eq!(
+            chain.next_command().map(|command| command.text),
+            Some("reboot now")
+        );
+        chain.fail();
+        let tagged = chain.next_command().expect("macro keeps going");
+        assert_eq!(tagged.text, "@9 status");
+        assert_eq!(tagged.tag, Some(RequestTag::new(9)));
+        assert_eq!(
+            chain.next_command().map(|command| command.text),
+            Some("mode")
+        );
+        chain.fail();
+        assert!(chain.is_empty());
+        assert!(chain.next_command().is_none());
+    }
+
+    #[test]
+    fn a_failed_macro_stops_the_line_that_ran_it() {
+        let mut chain = CommandChain::new();
+        assert_eq!(chain.start("macro run boot; status", None), Ok(2));
+        let _ = chain.next_command();
+        assert_eq!(chain.push("reboot now; status", false, None), Ok(2));
+        let _ = chain.next_command();
+        chain.fail();
+        assert!(chain.is_empty());
+    }
+
+    #[test]
+    fn a_macro_cannot_run_a_macro_even_as_its_last_command() {
+        let mut chain = CommandChain::new();
+        assert_eq!(chain.start("macro run loop", None), Ok(1));
+        let _ = chain.next_command();
+        assert_eq!(chain.push("macro run loop", false, None), Ok(1));
+        let _ = chain.next_command();
+        assert_eq!(
+            chain.push("macro run loop", false, None),
+            Err(ChainError::Nested)
+        );
+
+        assert_eq!(chain.start("status", None), Ok(1));
+    }
+}
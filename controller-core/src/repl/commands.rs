@@ -7,12 +7,24 @@
 use core::ops::Add;
 use core::time::Duration;
 
+use crate::bridge::autobaud::BaudSetting;
+use crate::bridge::inject::ConsoleText;
+use crate::bridge::line_coding::SysRqKey;
+use crate::bridge::scrollback::{DEFAULT_TAIL_LINES, ScrollbackSelection};
+use crate::bridge::stats::BridgeStatsSnapshot;
 use crate::orchestrator::{
-    CommandFlags, CommandQueueProducer, CommandSource, ScheduleError, SequenceScheduler,
+    CommandFlags, CommandQueueProducer, CommandSource, RequestTag, ScheduleError, SequenceScheduler,
 };
+use crate::power::{BrownOutReport, RailStatsSnapshot};
 use crate::sequences::{StrapSequenceKind, fault::FAULT_RECOVERY_MAX_RETRIES};
+use crate::supervisor::{WatchdogStatus, WatchdogUpdate};
 
-use super::grammar::{self, Command, RebootCommand, RecoveryCommand};
+use super::grammar::{
+    self, BridgeCommand, Command, ConsoleCommand, EventsCommand, MacroCommand, PatternCommand,
+    PowerCommand, RebootCommand, RecoveryCommand, ScriptCommand, WaitCommand, WatchdogCommand,
+};
+use super::help::HelpPage;
+use super::json::ResponseMode;
 use super::status::{NoStatusProvider, StatusProvider, StatusSnapshot};
 
 /// Command execution successes.
@@ -21,7 +33,23 @@ pub enum CommandOutcome<Instant> {
     Reboot(RebootAck<Instant>),
     Recovery(RecoveryAck<Instant>),
     Fault(FaultAck<Instant>),
+    Power(PowerReport),
+    Watchdog(WatchdogStatus),
+    Console(ConsoleAction),
+    Bridge(BridgeStatsReport),
+    /// Script library work handed back to the caller, which owns the engine.
+    Script(ScriptCommand),
     Status(StatusSnapshot),
+    /// `mode [text|json]`; the caller owns the session's response format.
+    Mode(Option<ResponseMode>),
+    /// `events ...`; the caller owns the session's event subscription.
+    Events(EventsCommand),
+    /// `wait ...`; the caller holds the session until the condition holds.
+    Wait(WaitCommand),
+    /// `macro ...`; the caller owns the macro library and command chain.
+    Macro(MacroCommand),
+    /// Page to print for `help [topic]`.
+    Help(HelpPage),
 }
 
 /// Summary returned after queueing a reboot command.
@@ -29,6 +57,8 @@ pub enum CommandOutcome<Instant> {
 pub struct RebootAck<Instant> {
     pub requested_at: Instant,
     pub start_after: Option<Duration>,
+    /// The caller should stop the Jetson at its bootloader prompt.
+    pub to_bootloader: bool,
 }
 
 /// Summary returned after queueing a recovery command.
@@ -47,6 +77,43 @@ pub struct FaultAck<Instant> {
     pub retry_budget: u8,
 }
 
+/// Console work handed back to the caller, which owns the scrollback and UART.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConsoleAction {
+    /// Print the selected scrollback lines.
+    Show(ScrollbackSelection),
+    /// Send a break followed by the sysrq key.
+    SysRq(SysRqKey),
+    /// Type decoded text into the console, then Enter when `newline` is set.
+    Send { text: ConsoleText, newline: bool },
+    /// Bridge the REPL port to the console until the operator types `~.`.
+    Attach,
+    /// Reframe the console UART at a fixed rate or start a rate search.
+    Baud(BaudSetting),
+    /// Change or list the marker patterns the console matcher watches for.
+    Pattern(PatternCommand),
+}
+
+/// Power-rail data returned by the `power` command family.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PowerReport {
+    /// Rail statistics as they stood before an optional reset.
+    Stats {
+        snapshot: RailStatsSnapshot,
+        reset: bool,
+    },
+    /// Brown-out episodes recorded since boot.
+    History(BrownOutReport),
+}
+
+/// Bridge counters returned by `bridge stats`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BridgeStatsReport {
+    /// Counters as they stood before an optional reset.
+    pub snapshot: BridgeStatsSnapshot,
+    pub reset: bool,
+}
+
 /// Errors surfaced while executing a command.
 #[derive(Debug, PartialEq)]
 pub enum CommandError<E, Instant> {
@@ -180,7 +247,8 @@ where
     S: SequenceEnqueuer,
     P: StatusProvider<S::Instant>,
 {
-    /// Parses and executes a REPL command.
+    /// Parses and executes a REPL command. A request tag is passed on to any
+    /// strap sequence the command queues.
     ///
     /// # Errors
     /// Returns a [`CommandError`] when the input fails to parse or when the
@@ -194,56 +262,140 @@ where
     where
         S::Instant: Copy,
     {
-        let command = grammar::parse(line)?;
-        self.dispatch(command, now, source)
+        let request = grammar::parse_request(line)?;
+        self.dispatch(request.command, request.tag, now, source)
     }
 
     fn dispatch(
         &mut self,
         command: Command<'_>,
+        tag: Option<RequestTag>,
         now: S::Instant,
         source: CommandSource,
     ) -> CommandResult<S> {
+        let flags = CommandFlags {
+            tag,
+            ..CommandFlags::default()
+        };
         match command {
             Command::Reboot(action) => self
-                .handle_reboot(action, now, source)
+                .handle_reboot(action, flags, now, source)
                 .map(CommandOutcome::Reboot)
                 .map_err(CommandError::Schedule),
             Command::Recovery(action) => self
-                .handle_recovery(action, now, source)
+                .handle_recovery(action, flags, now, source)
                 .map(CommandOutcome::Recovery)
                 .map_err(CommandError::Schedule),
-            Command::Fault(action) => {
-                let retries = match action.retries {
-                    Some(0) => return Err(CommandError::Unsupported("fault retries must be 1-3")),
-                    Some(value) if value > FAULT_RECOVERY_MAX_RETRIES => {
-                        return Err(CommandError::Unsupported("fault retries must be 1-3"));
-                    }
-                    other => other,
-                };
-
-                self.handle_fault(retries, now, source)
-                    .map(CommandOutcome::Fault)
-                    .map_err(CommandError::Schedule)
+            Command::Fault(action) => self
+                .handle_fault(action.retries, flags, now, source)
+                .map(CommandOutcome::Fault)
+                .map_err(CommandError::Schedule),
+            Command::Power(action) => self.handle_power(action, now).map(CommandOutcome::Power),
+            Command::Watchdog(action) => self
+                .handle_watchdog(action, now)
+                .map(CommandOutcome::Watchdog),
+            Command::Console(action) => Ok(CommandOutcome::Console(match action {
+                ConsoleCommand::Tail { lines } => ConsoleAction::Show(ScrollbackSelection::Tail(
+                    lines.unwrap_or(DEFAULT_TAIL_LINES),
+                )),
+                ConsoleCommand::Since { run } => {
+                    ConsoleAction::Show(ScrollbackSelection::SinceRun(run))
+                }
+                ConsoleCommand::SysRq { key } => ConsoleAction::SysRq(key),
+                ConsoleCommand::Send { text, newline } => ConsoleAction::Send { text, newline },
+                ConsoleCommand::Attach => ConsoleAction::Attach,
+                ConsoleCommand::Baud { setting } => ConsoleAction::Baud(setting),
+                ConsoleCommand::Pattern(command) => ConsoleAction::Pattern(command),
+            })),
+            Command::Bridge(BridgeCommand::Stats { reset }) => {
+                let snapshot = self
+                    .status
+                    .bridge_stats()
+                    .ok_or(CommandError::Unsupported("bridge stats"))?;
+                if reset {
+                    self.status.reset_bridge_stats();
+                }
+                Ok(CommandOutcome::Bridge(BridgeStatsReport {
+                    snapshot,
+                    reset,
+                }))
             }
+            Command::Script(action) => Ok(CommandOutcome::Script(action)),
             Command::Status => self
                 .status
                 .snapshot(now)
                 .map(CommandOutcome::Status)
                 .ok_or(CommandError::Unsupported("status")),
-            Command::Help(_) => Err(CommandError::Unsupported("help")),
+            Command::Mode(mode) => Ok(CommandOutcome::Mode(mode)),
+            Command::Events(action) => Ok(CommandOutcome::Events(action)),
+            Command::Wait(wait) => Ok(CommandOutcome::Wait(wait)),
+            Command::Macro(action) => Ok(CommandOutcome::Macro(action)),
+            Command::Help(help) => Ok(CommandOutcome::Help(HelpPage::for_topic(help.topic))),
         }
     }
 
+    fn handle_power(
+        &mut self,
+        action: PowerCommand,
+        now: S::Instant,
+    ) -> Result<PowerReport, CommandError<S::Error, S::Instant>> {
+        match action {
+            PowerCommand::Stats { reset } => {
+                let snapshot = self
+                    .status
+                    .rail_stats()
+                    .ok_or(CommandError::Unsupported("power stats"))?;
+                if reset {
+                    self.status.reset_rail_stats();
+                }
+                Ok(PowerReport::Stats { snapshot, reset })
+            }
+            PowerCommand::History => self
+                .status
+                .brown_out_history(now)
+                .map(PowerReport::History)
+                .ok_or(CommandError::Unsupported("power history")),
+        }
+    }
+
+    fn handle_watchdog(
+        &mut self,
+        action: WatchdogCommand,
+        now: S::Instant,
+    ) -> Result<WatchdogStatus, CommandError<S::Error, S::Instant>> {
+        let update = match action {
+            WatchdogCommand::Status => {
+                return self
+                    .status
+                    .watchdog_status(now)
+                    .ok_or(CommandError::Unsupported("watchdog"));
+            }
+            WatchdogCommand::Enable => WatchdogUpdate::Enable,
+            WatchdogCommand::Disable => WatchdogUpdate::Disable,
+            WatchdogCommand::BootDeadline(deadline) => WatchdogUpdate::BootDeadline(deadline),
+            WatchdogCommand::SilenceLimit(limit) => {
+                WatchdogUpdate::SilenceLimit((!limit.is_zero()).then_some(limit))
+            }
+            WatchdogCommand::PanicGrace(grace) => {
+                WatchdogUpdate::PanicGrace((!grace.is_zero()).then_some(grace))
+            }
+            WatchdogCommand::RecoveryLimit(limit) => WatchdogUpdate::MaxRecoveries(limit),
+        };
+
+        self.status
+            .update_watchdog(update, now)
+            .ok_or(CommandError::Unsupported("watchdog"))
+    }
+
     fn handle_reboot(
         &mut self,
         action: RebootCommand,
+        mut flags: CommandFlags,
         now: S::Instant,
         source: CommandSource,
     ) -> RebootResult<S> {
-        let mut flags = CommandFlags::default();
         let start_after = match action {
-            RebootCommand::Now => None,
+            RebootCommand::Now | RebootCommand::ToBootloader => None,
             RebootCommand::Delay(duration) if duration.is_zero() => None,
             RebootCommand::Delay(duration) => {
                 flags.start_after = Some(duration);
@@ -257,22 +409,25 @@ where
         Ok(RebootAck {
             requested_at: now,
             start_after,
+            to_bootloader: action == RebootCommand::ToBootloader,
         })
     }
 
     fn handle_recovery(
         &mut self,
         action: RecoveryCommand,
+        flags: CommandFlags,
         now: S::Instant,
         source: CommandSource,
     ) -> RecoveryResult<S> {
         let (sequence, flags) = match action {
-            RecoveryCommand::Enter => (StrapSequenceKind::RecoveryEntry, CommandFlags::default()),
-            RecoveryCommand::Exit => (StrapSequenceKind::NormalReboot, CommandFlags::default()),
-            RecoveryCommand::Now => {
+            RecoveryCommand::Enter => (StrapSequenceKind::RecoveryEntry, flags),
+            RecoveryCommand::Exit => (StrapSequenceKind::NormalReboot, flags),
+            RecoveryCommand::Now { timeout } => {
                 let flags = CommandFlags {
                     force_recovery: true,
-                    ..CommandFlags::default()
+                    bridge_timeout: timeout,
+                    ..flags
                 };
                 (StrapSequenceKind::RecoveryImmediate, flags)
             }
@@ -291,12 +446,12 @@ where
     fn handle_fault(
         &mut self,
         retry_override: Option<u8>,
+        mut flags: CommandFlags,
         now: S::Instant,
         source: CommandSource,
     ) -> FaultResult<S> {
         let retry_budget = retry_override.unwrap_or(FAULT_RECOVERY_MAX_RETRIES);
 
-        let mut flags = CommandFlags::default();
         if retry_override.is_some() {
             flags.retry_override = Some(retry_budget);
         }
@@ -315,11 +470,13 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::orchestrator::{CommandEnqueueError, CommandQueueProducer};
+    use crate::orchestrator::{BridgeHoldConfig, CommandEnqueueError, CommandQueueProducer};
     use crate::orchestrator::{CommandSource, SequenceCommand};
+    use crate::repl::catalog::{self, CommandTag};
     use crate::sequences::{
         fault_recovery_template, recovery_entry_template, recovery_immediate_template,
     };
+    use crate::telemetry::TelemetryInstant;
     use core::convert::TryFrom;
     use core::ops::Add;
     use core::time::Duration;
@@ -344,6 +501,12 @@ mod tests {
         }
     }
 
+    impl TelemetryInstant for MockInstant {
+        fn saturating_duration_since(&self, earlier: Self) -> Duration {
+            Duration::from_micros(self.0.saturating_sub(earlier.0))
+        }
+    }
+
     #[derive(Clone)]
     struct MockQueue {
         capacity: usize,
@@ -421,6 +584,7 @@ mod tests {
             CommandOutcome::Reboot(RebootAck {
                 requested_at: now,
                 start_after: None,
+                to_bootloader: false,
             })
         );
 
@@ -445,6 +609,7 @@ mod tests {
             CommandOutcome::Reboot(RebootAck {
                 requested_at: now,
                 start_after: Some(Duration::from_millis(250)),
+                to_bootloader: false,
             })
         );
 
@@ -456,6 +621,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reboot_to_bootloader_queues_normal_reboot() {
+        let mut executor = executor_with_capacity(4);
+        let now = MockInstant::micros(3_000);
+
+        let outcome = executor
+            .execute("reboot to-bootloader", now, CommandSource::UsbHost)
+            .expect("dispatch should succeed");
+
+        assert_eq!(
+            outcome,
+            CommandOutcome::Reboot(RebootAck {
+                requested_at: now,
+                start_after: None,
+                to_bootloader: true,
+            })
+        );
+        let commands = executor.scheduler().producer().commands();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].kind, StrapSequenceKind::NormalReboot);
+    }
+
     #[test]
     fn unsupported_command_is_reported() {
         let mut executor = executor_with_capacity(4);
@@ -484,6 +671,248 @@ mod tests {
         assert!(matches!(outcome, CommandOutcome::Status(_)));
     }
 
+    #[test]
+    fn help_command_returns_catalog_page() {
+        let mut executor = executor_with_capacity(4);
+        let now = MockInstant::micros(0);
+        let outcome = executor
+            .execute("help watchdog", now, CommandSource::UsbHost)
+            .expect("help should succeed");
+        assert_eq!(
+            outcome,
+            CommandOutcome::Help(HelpPage::Command(catalog::command(CommandTag::Watchdog)))
+        );
+        let outcome = executor
+            .execute("help", now, CommandSource::UsbHost)
+            .expect("help should succeed");
+        assert_eq!(outcome, CommandOutcome::Help(HelpPage::Index));
+    }
+
+    #[test]
+    fn power_stats_reads_then_resets_provider() {
+        use crate::power::RailStatistics;
+
+        struct RailProvider {
+            stats: RailStatistics,
+        }
+
+        impl StatusProvider<MockInstant> for RailProvider {
+            fn snapshot(&mut self, _now: MockInstant) -> Option<StatusSnapshot> {
+                None
+            }
+
+            fn rail_stats(&mut self) -> Option<RailStatsSnapshot> {
+                Some(self.stats.snapshot())
+            }
+
+            fn reset_rail_stats(&mut self) {
+                self.stats.reset();
+            }
+        }
+
+        let mut stats = RailStatistics::new();
+        stats.record(Some(3300));
+        let mut executor = executor_with_capacity(4).with_status_provider(RailProvider { stats });
+        let now = MockInstant::micros(0);
+
+        let outcome = executor
+            .execute("power stats reset", now, CommandSource::UsbHost)
+            .expect("power stats should succeed");
+        match outcome {
+            CommandOutcome::Power(PowerReport::Stats { snapshot, reset }) => {
+                assert!(reset);
+                assert_eq!(snapshot.overall.samples(), 1);
+            }
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+        assert_eq!(
+            executor
+                .status_provider_mut()
+                .stats
+                .snapshot()
+                .overall
+                .samples(),
+            0
+        );
+
+        let error = executor_with_capacity(4)
+            .execute("power stats", now, CommandSource::UsbHost)
+            .expect_err("missing provider should be unsupported");
+        assert_eq!(error, CommandError::Unsupported("power stats"));
+    }
+
+    #[test]
+    fn power_history_reports_provider_episodes() {
+        struct HistoryProvider;
+
+        impl StatusProvider<MockInstant> for HistoryProvider {
+            fn snapshot(&mut self, _now: MockInstant) -> Option<StatusSnapshot> {
+                None
+            }
+
+            fn brown_out_history(&mut self, _now: MockInstant) -> Option<BrownOutReport> {
+                Some(BrownOutReport {
+                    total: 3,
+                    ..BrownOutReport::default()
+                })
+            }
+        }
+
+        let now = MockInstant::micros(0);
+        let outcome = executor_with_capacity(4)
+            .with_status_provider(HistoryProvider)
+            .execute("power history", now, CommandSource::UsbHost)
+            .expect("power history should succeed");
+        match outcome {
+            CommandOutcome::Power(PowerReport::History(report)) => assert_eq!(report.total, 3),
+            other => panic!("unexpected outcome: {other:?}"),
+        }
+
+        let error = executor_with_capacity(4)
+            .execute("power history", now, CommandSource::UsbHost)
+            .expect_err("missing provider should be unsupported");
+        assert_eq!(error, CommandError::Unsupported("power history"));
+    }
+
+    #[test]
+    fn watchdog_commands_update_provider() {
+        use crate::supervisor::{BootWatchdog, WatchdogConfig, WatchdogPhase};
+        struct WatchdogProvider {
+            watchdog: BootWatchdog<MockInstant>,
+        }
+
+        impl StatusProvider<MockInstant> for WatchdogProvider {
+            fn snapshot(&mut self, _now: MockInstant) -> Option<StatusSnapshot> {
+                None
+            }
+
+            fn watchdog_status(&mut self, now: MockInstant) -> Option<WatchdogStatus> {
+                Some(self.watchdog.status(now))
+            }
+
+            fn update_watchdog(
+                &mut self,
+                update: WatchdogUpdate,
+                now: MockInstant,
+            ) -> Option<WatchdogStatus> {
+                self.watchdog.apply(update, now);
+                Some(self.watchdog.status(now))
+            }
+        }
+
+        let provider = WatchdogProvider {
+            watchdog: BootWatchdog::new(WatchdogConfig::new()),
+        };
+        let mut executor = executor_with_capacity(4).with_status_provider(provider);
+        let now = MockInstant::micros(0);
+
+        let outcome = executor
+            .execute("watchdog on", now, CommandSource::UsbHost)
+            .expect("watchdog on should succeed");
+        let CommandOutcome::Watchdog(status) = outcome else {
+            panic!("unexpected outcome: {outcome:?}");
+        };
+        assert_eq!(status.phase, WatchdogPhase::Running);
+
+        let outcome = executor
+            .execute("watchdog silence=0s", now, CommandSource::UsbHost)
+            .expect("watchdog silence should succeed");
+        let CommandOutcome::Watchdog(status) = outcome else {
+            panic!("unexpected outcome: {outcome:?}");
+        };
+        assert_eq!(status.silence_limit_ms, None);
+
+        let outcome = executor
+            .execute("watchdog panic=30s", now, CommandSource::UsbHost)
+            .expect("watchdog panic should succeed");
+        let CommandOutcome::Watchdog(status) = outcome else {
+            panic!("unexpected outcome: {outcome:?}");
+        };
+        assert_eq!(status.panic_grace_ms, Some(30_000));
+
+        let error = executor
+            .execute("watchdog boot=0s", now, CommandSource::UsbHost)
+            .expect_err("zero boot deadline should be rejected");
+        assert!(matches!(error, CommandError::Parse(_)), "{error:?}");
+
+        let error = executor_with_capacity(4)
+            .execute("watchdog", now, CommandSource::UsbHost)
+            .expect_err("missing provider should be unsupported");
+        assert_eq!(error, CommandError::Unsupported("watchdog"));
+    }
+
+    #[test]
+    fn console_commands_select_scrollback_lines() {
+        let mut executor = executor_with_capacity(4);
+        let now = MockInstant::micros(0);
+
+        let outcome = executor
+            .execute("console tail", now, CommandSource::UsbHost)
+            .expect("tail should succeed");
+        assert_eq!(
+            outcome,
+            CommandOutcome::Console(ConsoleAction::Show(ScrollbackSelection::Tail(
+                DEFAULT_TAIL_LINES
+            )))
+        );
+
+        let outcome = executor
+            .execute("console tail 5", now, CommandSource::UsbHost)
+            .expect("tail with count should succeed");
+        assert_eq!(
+            outcome,
+            CommandOutcome::Console(ConsoleAction::Show(ScrollbackSelection::Tail(5)))
+        );
+
+        let outcome = executor
+            .execute("console since 3", now, CommandSource::UsbHost)
+            .expect("since should succeed");
+        assert_eq!(
+            outcome,
+            CommandOutcome::Console(ConsoleAction::Show(ScrollbackSelection::SinceRun(3)))
+        );
+
+        let outcome = executor
+            .execute("console sysrq b", now, CommandSource::UsbHost)
+            .expect("sysrq should succeed");
+        let CommandOutcome::Console(ConsoleAction::SysRq(key)) = outcome else {
+            panic!("expected sysrq action, got {outcome:?}");
+        };
+        assert_eq!(key.byte(), b'b');
+
+        let outcome = executor
+            .execute(r#"console send "\r" newline"#, now, CommandSource::UsbHost)
+            .expect("send should succeed");
+        let CommandOutcome::Console(ConsoleAction::Send { text, newline }) = outcome else {
+            panic!("expected send action, got {outcome:?}");
+        };
+        assert_eq!(text.as_slice(), b"\r");
+        assert!(newline);
+    }
+
+    #[test]
+    fn script_commands_are_returned_to_caller() {
+        let mut executor = executor_with_capacity(4);
+        let now = MockInstant::micros(0);
+        let outcome = executor
+            .execute("script run login", now, CommandSource::UsbHost)
+            .expect("script run should parse");
+        let CommandOutcome::Script(ScriptCommand::Run { name }) = outcome else {
+            panic!("expected script run, got {outcome:?}");
+        };
+        assert_eq!(name.as_str(), "login");
+
+        let outcome = executor
+            .execute("script list", now, CommandSource::UsbHost)
+            .expect("script list should parse");
+        assert_eq!(outcome, CommandOutcome::Script(ScriptCommand::List));
+        assert!(
+            executor
+                .execute("script run", now, CommandSource::UsbHost)
+                .is_err()
+        );
+    }
+
     #[test]
     fn parse_error_is_returned() {
         let mut executor = executor_with_capacity(4);
@@ -507,6 +936,31 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn request_tag_is_threaded_into_sequence_flags() {
+        let mut executor = executor_with_capacity(4);
+        let now = MockInstant::micros(4_000);
+
+        executor
+            .execute("@17 reboot now", now, CommandSource::UsbHost)
+            .expect("tagged reboot should succeed");
+        executor
+            .execute("@18 recovery now timeout=5s", now, CommandSource::UsbHost)
+            .expect("tagged recovery should succeed");
+        executor
+            .execute("fault recover", now, CommandSource::UsbHost)
+            .expect("untagged fault should succeed");
+
+        let commands = executor.scheduler().producer().commands();
+        assert_eq!(commands[0].flags.tag, Some(RequestTag::new(17)));
+        assert_eq!(commands[1].flags.tag, Some(RequestTag::new(18)));
+        assert_eq!(
+            commands[1].flags.bridge_timeout,
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(commands[2].flags.tag, None);
+    }
+
     #[test]
     fn recovery_enter_enqueues_recovery_entry() {
         let mut executor = executor_with_capacity(4);
@@ -544,7 +998,7 @@ mod tests {
             CommandOutcome::Recovery(RecoveryAck {
                 requested_at: now,
                 sequence: StrapSequenceKind::RecoveryImmediate,
-                command: RecoveryCommand::Now,
+                command: RecoveryCommand::Now { timeout: None },
             })
         );
 
@@ -553,6 +1007,26 @@ mod tests {
         assert_eq!(commands[0].kind, StrapSequenceKind::RecoveryImmediate);
     }
 
+    #[test]
+    fn recovery_now_timeout_overrides_bridge_wait() {
+        let mut executor = executor_with_capacity(4);
+        let now = MockInstant::micros(6_500);
+
+        executor
+            .execute("recovery now timeout=30s", now, CommandSource::UsbHost)
+            .expect("dispatch should succeed");
+
+        let commands = executor.scheduler().producer().commands();
+        assert_eq!(
+            commands[0].flags.bridge_timeout,
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            BridgeHoldConfig::for_flags(&commands[0].flags).timeout(),
+            Duration::from_secs(30)
+        );
+    }
+
     #[test]
     fn recovery_exit_reuses_normal_reboot_sequence() {
         let mut executor = executor_with_capacity(4);
@@ -633,9 +1107,9 @@ mod tests {
             .execute("fault recover retries=5", now, CommandSource::UsbHost)
             .expect_err("retry override exceeding template should fail");
 
-        assert_eq!(
-            error,
-            CommandError::Unsupported("fault retries must be 1-3")
-        );
+        let CommandError::Parse(error) = error else {
+            panic!("unexpected error: {error:?}");
+        };
+        assert_eq!(std::format!("{error}"), "retries must be 1-3 at 22..23");
     }
 }
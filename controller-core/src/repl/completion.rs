@@ -4,9 +4,12 @@
 //! current buffer contents and cursor position without pulling in `std`.
 
 use super::catalog::{
-    self, ChoiceBranch, DefaultChoice, HelpTopics, Node, SubcommandBranch, ValueSpec,
+    self, ChoiceBranch, DefaultChoice, HelpTopics, Node, OptionKind, OptionSpec, SubcommandBranch,
+    ValueSpec,
 };
 use super::grammar::{self, Token, TokenKind};
+use crate::bridge::console::ConsoleMarker;
+use crate::telemetry::{EventCategory, TelemetryEventKind};
 use heapless::Vec as HeaplessVec;
 
 const MAX_SUGGESTIONS: usize = 16;
@@ -80,7 +83,11 @@ impl CompletionEngine {
         }
 
         let expectation = determine_expectation(leading_tokens.as_slice());
-        let candidates = collect_candidates(expectation);
+        // After a number where a duration goes, complete its unit (`5` -> `5m`).
+        let (prefix_start, prefix, candidates) = match duration_unit_start(expectation, prefix) {
+            Some(offset) => (prefix_start + offset, &prefix[offset..], unit_candidates()),
+            None => (prefix_start, prefix, collect_candidates(expectation)),
+        };
         if candidates.is_empty() {
             return CompletionResult {
                 replacement: None,
@@ -152,12 +159,21 @@ fn collect_candidates(
         CompletionExpectation::Choices(choices) => {
             for choice in choices {
                 match choice.value {
-                    ValueSpec::IntegerAssignment { suggestions } => {
-                        for suggestion in suggestions {
+                    ValueSpec::Assignment(option) => {
+                        for suggestion in option.suggestions {
                             let _ = options.push(*suggestion);
                         }
                     }
-                    ValueSpec::None | ValueSpec::Duration => {
+                    ValueSpec::None
+                    | ValueSpec::Duration
+                    | ValueSpec::Count
+                    | ValueSpec::Key
+                    | ValueSpec::Text
+                    | ValueSpec::Name
+                    | ValueSpec::Baud
+                    | ValueSpec::Categories
+                    | ValueSpec::EventName
+                    | ValueSpec::Marker => {
                         let _ = options.push(choice.keyword);
                     }
                 }
@@ -169,20 +185,101 @@ fn collect_candidates(
             }
         }
         CompletionExpectation::Topic(HelpTopics::None)
-        | CompletionExpectation::Value(ValueSpec::None | ValueSpec::Duration)
+        | CompletionExpectation::Value(
+            ValueSpec::None
+            | ValueSpec::Duration
+            | ValueSpec::Count
+            | ValueSpec::Key
+            | ValueSpec::Text
+            | ValueSpec::Name
+            | ValueSpec::Baud,
+        )
         | CompletionExpectation::None => {}
-        CompletionExpectation::Value(ValueSpec::IntegerAssignment { suggestions }) => {
-            for suggestion in suggestions {
+        CompletionExpectation::Value(ValueSpec::Assignment(option)) => {
+            for suggestion in option.suggestions {
                 let _ = options.push(*suggestion);
             }
         }
+        CompletionExpectation::Value(ValueSpec::Categories) => {
+            for category in EventCategory::ALL {
+                let _ = options.push(category.name());
+            }
+        }
+        CompletionExpectation::Value(ValueSpec::EventName) => {
+            for name in TelemetryEventKind::NAMES {
+                let _ = options.push(name);
+            }
+        }
+        CompletionExpectation::Value(ValueSpec::Marker) => {
+            for marker in ConsoleMarker::ALL {
+                let _ = options.push(marker.name());
+            }
+        }
     }
 
     options
 }
 
+/// Returns where the unit of a duration being typed starts in `prefix`, when
+/// the expected value is a duration and `prefix` holds a number awaiting one.
+fn duration_unit_start(expectation: CompletionExpectation, prefix: &str) -> Option<usize> {
+    let value_start = match expectation {
+        CompletionExpectation::Value(spec) if accepts_duration(spec) => 0,
+        // `timeout=5` is still one word, completed against the choices.
+        CompletionExpectation::Choices(choices) => {
+            let (keyword, _) = prefix.split_once('=')?;
+            let is_duration = choices.iter().any(|choice| {
+                choice.keyword.eq_ignore_ascii_case(keyword) && accepts_duration(choice.value)
+            });
+            if !is_duration {
+                return None;
+            }
+            keyword.len() + 1
+        }
+        _ => return None,
+    };
+    let value = &prefix[value_start..];
+    let unit_start = value
+        .trim_end_matches(|ch: char| ch.is_ascii_alphabetic())
+        .len();
+    value[..unit_start]
+        .ends_with(|ch: char| ch.is_ascii_digit())
+        .then_some(value_start + unit_start)
+}
+
+fn accepts_duration(spec: ValueSpec) -> bool {
+    matches!(
+        spec,
+        ValueSpec::Duration
+            | ValueSpec::Assignment(OptionSpec {
+                kind: OptionKind::Duration { .. },
+                ..
+            })
+    )
+}
+
+fn unit_candidates() -> HeaplessVec<&'static str, MAX_SUGGESTIONS> {
+    catalog::DURATION_UNITS
+        .iter()
+        .map(|(unit, _)| *unit)
+        .collect()
+}
+
 fn determine_expectation(tokens: &[Token<'_>]) -> CompletionExpectation {
     let tokens = trim_trailing_eol(tokens);
+    // Only the last command of a `;` chain is being completed.
+    let tokens = match tokens
+        .iter()
+        .rposition(|token| token.kind == TokenKind::Semicolon)
+    {
+        Some(index) => &tokens[index + 1..],
+        None => tokens,
+    };
+    // A request tag (`@17`) comes before the command keyword.
+    let tokens = match tokens.split_first() {
+        Some((first, rest)) if first.kind == TokenKind::Tag => rest,
+        _ => tokens,
+    };
     if tokens.is_empty() {
         return CompletionExpectation::Root;
     }
@@ -223,6 +320,22 @@ fn evaluate_node(node: &'static Node, tokens: &[Token<'_>]) -> CompletionExpecta
             evaluate_optional_choice(choices, *default, tokens)
         }
         Node::Subcommands(branches) => evaluate_subcommands(branches, tokens),
+        Node::Argument {
+            value,
+            required,
+            next,
+            ..
+        } => match evaluate_value(*value, tokens) {
+            ValueProgress::Advance(remaining) => evaluate_node(next, remaining),
+            // An optional argument may be skipped in favour of what follows it.
+            ValueProgress::Need(spec) if !*required && **next != Node::End => {
+                match evaluate_node(next, tokens) {
+                    CompletionExpectation::None => CompletionExpectation::Value(spec),
+                    other => other,
+                }
+            }
+            ValueProgress::Need(spec) => CompletionExpectation::Value(spec),
+        },
         Node::Topic { topics, next } => evaluate_topic(*topics, next, tokens),
     }
 }
@@ -327,9 +440,54 @@ fn evaluate_value<'src, 'slice>(
             Some((token, _)) if token.kind == TokenKind::Eol => ValueProgress::Need(spec),
             _ => ValueProgress::Need(spec),
         },
-        ValueSpec::IntegerAssignment { .. } => match tokens.split_first() {
+        ValueSpec::Key => match tokens.split_first() {
+            Some((token, rest)) if matches!(token.kind, TokenKind::Ident | TokenKind::Integer) => {
+                ValueProgress::Advance(rest)
+            }
+            _ => ValueProgress::Need(spec),
+        },
+        ValueSpec::Count => match tokens.split_first() {
+            Some((token, rest)) if token.kind == TokenKind::Integer => ValueProgress::Advance(rest),
+            _ => ValueProgress::Need(spec),
+        },
+        ValueSpec::Text => match tokens.split_first() {
+            Some((token, rest)) if token.kind == TokenKind::QuotedString => {
+                ValueProgress::Advance(rest)
+            }
+            _ => ValueProgress::Need(spec),
+        },
+        ValueSpec::Baud => match tokens.split_first() {
+            Some((token, rest)) if matches!(token.kind, TokenKind::Ident | TokenKind::Integer) => {
+                ValueProgress::Advance(rest)
+            }
+            _ => ValueProgress::Need(spec),
+        },
+        ValueSpec::Name | ValueSpec::EventName | ValueSpec::Marker => match tokens.split_first() {
+            Some((token, rest)) if token.kind == TokenKind::Ident => ValueProgress::Advance(rest),
+            _ => ValueProgress::Need(spec),
+        },
+        ValueSpec::Categories => {
+            let mut tokens = tokens;
+            loop {
+                let Some((token, rest)) = tokens.split_first() else {
+                    return ValueProgress::Need(spec);
+                };
+                if token.kind != TokenKind::Ident {
+                    return ValueProgress::Need(spec);
+                }
+                match rest.split_first() {
+                    Some((separator, after)) if separator.kind == TokenKind::Comma => {
+                        tokens = after;
+                    }
+                    _ => return ValueProgress::Advance(rest),
+                }
+            }
+        }
+        ValueSpec::Assignment(option) => match tokens.split_first() {
             Some((token, rest)) if token.kind == TokenKind::Equals => match rest.split_first() {
-                Some((value_token, remaining)) if value_token.kind == TokenKind::Integer => {
+                Some((value_token, remaining))
+                    if value_token.kind == assigned_kind(option.kind) =>
+                {
                     ValueProgress::Advance(remaining)
                 }
                 Some((value_token, _)) if value_token.kind == TokenKind::Eol => {
@@ -343,12 +501,22 @@ fn evaluate_value<'src, 'slice>(
     }
 }
 
+fn assigned_kind(kind: OptionKind) -> TokenKind {
+    match kind {
+        OptionKind::Integer { .. } => TokenKind::Integer,
+        OptionKind::Duration { .. } => TokenKind::Duration,
+        OptionKind::Enum(_) => TokenKind::Ident,
+        OptionKind::Text => TokenKind::QuotedString,
+    }
+}
+
 fn token_start(buffer: &str) -> usize {
     let mut index = buffer.len();
     let bytes = buffer.as_bytes();
     while index > 0 {
         let byte = bytes[index - 1];
-        if byte == b' ' || byte == b'\t' {
+        // Category lists (`strap,power`) complete one item at a time.
+        if matches!(byte, b' ' | b'\t' | b',' | b';') {
             break;
         }
         index -= 1;
@@ -500,6 +668,97 @@ mod tests {
         assert_eq!(options.as_slice(), ["reboot"]);
     }
 
+    #[test]
+    fn completes_after_a_request_tag() {
+        let engine = CompletionEngine::new();
+        let (replacement, _) = expect_options(engine.complete("@7 reb", 6));
+        let replacement = replacement.expect("expected replacement");
+        assert_eq!(replacement.start, 3);
+        assert_eq!(replacement.value, "reboot");
+
+        let (_, options) = expect_options(engine.complete("@7 recovery n", 13));
+        assert_eq!(options.as_slice(), ["now"]);
+    }
+
+    #[test]
+    fn completes_event_categories_one_at_a_time() {
+        let engine = CompletionEngine::new();
+        let (_, options) = expect_options(engine.complete("events on ", 10));
+        assert_eq!(
+            options.as_slice(),
+            ["strap", "command", "sequence", "bridge", "power"]
+        );
+
+        let (replacement, _) = expect_options(engine.complete("events on strap,se", 18));
+        let replacement = replacement.expect("expected replacement");
+        assert_eq!(replacement.start, 16);
+        assert_eq!(replacement.value, "sequence");
+        assert!(!replacement.append_space);
+    }
+
+    #[test]
+    fn completes_wait_event_names() {
+        let engine = CompletionEngine::new();
+        let (replacement, _) = expect_options(engine.complete("wait event strap-r", 18));
+        let replacement = replacement.expect("expected replacement");
+        assert_eq!(replacement.start, 11);
+        assert_eq!(replacement.value, "strap-released");
+
+        let (_, options) = expect_options(engine.complete("wait ", 5));
+        assert_eq!(options.as_slice(), ["complete", "idle", "event"]);
+    }
+
+    #[test]
+    fn completes_console_pattern_markers() {
+        let engine = CompletionEngine::new();
+        let (replacement, _) = expect_options(engine.complete("console pattern add log", 23));
+        let replacement = replacement.expect("expected replacement");
+        assert_eq!(replacement.start, 20);
+        assert_eq!(replacement.value, "login-prompt");
+
+        let (_, options) = expect_options(engine.complete("console pattern ", 16));
+        assert_eq!(options.as_slice(), ["add", "remove", "list"]);
+    }
+
+    #[test]
+    fn offers_duration_units_after_a_number() {
+        let engine = CompletionEngine::new();
+        let (replacement, options) = expect_options(engine.complete("reboot delay 15", 15));
+        assert!(replacement.is_none());
+        assert_eq!(options.as_slice(), ["h", "m", "s", "ms", "us"]);
+
+        let (_, options) = expect_options(engine.complete("reboot delay 1m", 15));
+        assert_eq!(options.as_slice(), ["m", "ms"]);
+
+        let (replacement, _) = expect_options(engine.complete("recovery now timeout=1m3u", 25));
+        let replacement = replacement.expect("expected unit replacement");
+        assert_eq!((replacement.start, replacement.end), (24, 25));
+        assert_eq!(replacement.value, "us");
+        assert!(!replacement.append_space);
+
+        let (_, options) = expect_options(engine.complete("wait idle timeout=", 18));
+        assert!(options.iter().all(|option| option.starts_with("timeout=")));
+        assert!(
+            engine
+                .complete("fault recover retries=2", 23)
+                .options
+                .iter()
+                .all(|option| option.starts_with("retries="))
+        );
+    }
+
+    #[test]
+    fn completes_the_last_command_of_a_chain() {
+        let engine = CompletionEngine::new();
+        let (replacement, _) = expect_options(engine.complete("status;reb", 10));
+        let replacement = replacement.expect("expected replacement");
+        assert_eq!(replacement.start, 7);
+        assert_eq!(replacement.value, "reboot");
+
+        let (_, options) = expect_options(engine.complete("status; macro ", 14));
+        assert_eq!(options.as_slice(), ["define", "run", "list"]);
+    }
+
     #[test]
     fn does_not_append_space_for_status_command() {
         let engine = CompletionEngine::new();
@@ -0,0 +1,627 @@
+//! Line editor shared by the firmware REPL and the emulator.
+//!
+//! [`KeyDecoder`] turns the raw bytes a VT100-style terminal sends into
+//! [`EditKey`]s (arrow and Home/End escape sequences, Emacs-style control
+//! keys), and [`LineEditor`] applies them to a bounded line with a cursor and
+//! an up/down history ring. Front-ends that already receive decoded keys, such
+//! as the emulator, call [`LineEditor::apply`] directly; byte streams go
+//! through [`LineEditor::feed`]. The editor never writes to the terminal
+//! itself: it reports what changed through [`EditEvent`] and offers
+//! [`LineEditor::write_redraw`] for terminals driven over a byte stream.
+
+use core::fmt;
+
+use heapless::{Deque, Vec};
+
+use super::completion::Replacement;
+
+/// Default number of bytes accepted on one line (excluding the terminator).
+pub const EDITOR_LINE_CAPACITY: usize = 96;
+/// Default number of submitted lines kept for up/down recall.
+pub const EDITOR_HISTORY_DEPTH: usize = 8;
+
+const ESC: u8 = 0x1b;
+
+/// Editing operations understood by [`LineEditor`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditKey {
+    /// Printable ASCII character to insert at the cursor.
+    Char(u8),
+    /// Backspace / DEL: remove the character before the cursor.
+    Backspace,
+    /// Delete / Ctrl-D: remove the character under the cursor.
+    Delete,
+    /// Left arrow / Ctrl-B.
+    Left,
+    /// Right arrow / Ctrl-F.
+    Right,
+    /// Home / Ctrl-A.
+    Home,
+    /// End / Ctrl-E.
+    End,
+    /// Up arrow / Ctrl-P: recall the previous history entry.
+    Up,
+    /// Down arrow / Ctrl-N: recall the next history entry.
+    Down,
+    /// Ctrl-W: remove the word before the cursor.
+    DeleteWord,
+    /// Ctrl-U: remove everything before the cursor.
+    KillToStart,
+    /// Ctrl-K: remove everything from the cursor on.
+    KillToEnd,
+    /// Ctrl-C: abandon the line.
+    Cancel,
+    /// Tab: ask for completion at the cursor.
+    Tab,
+    /// CR or LF: submit the line.
+    Enter,
+}
+
+impl EditKey {
+    /// Maps a single byte outside an escape sequence to a key.
+    ///
+    /// Returns `None` for bytes the editor does not accept.
+    #[must_use]
+    pub const fn from_byte(byte: u8) -> Option<Self> {
+        Some(match byte {
+            b' '..=b'~' => Self::Char(byte),
+            0x08 | 0x7f => Self::Backspace,
+            0x04 => Self::Delete,
+            0x02 => Self::Left,
+            0x06 => Self::Right,
+            0x01 => Self::Home,
+            0x05 => Self::End,
+            0x10 => Self::Up,
+            0x0e => Self::Down,
+            0x17 => Self::DeleteWord,
+            0x15 => Self::KillToStart,
+            0x0b => Self::KillToEnd,
+            0x03 => Self::Cancel,
+            b'\t' => Self::Tab,
+            b'\r' | b'\n' => Self::Enter,
+            _ => return None,
+        })
+    }
+}
+
+/// Result of decoding one input byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decoded {
+    /// A complete key.
+    Key(EditKey),
+    /// The byte was consumed as part of an escape sequence or a CRLF pair.
+    Pending,
+    /// The byte (or the sequence it ended) is not supported.
+    Rejected,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DecoderState {
+    Ground,
+    Escape,
+    /// `ESC [` followed by the first numeric parameter seen so far.
+    Csi(u16),
+    /// Further CSI parameters after a `;`; only the first one matters.
+    CsiTail(u16),
+    /// `ESC O`, used by terminals in application cursor mode.
+    Ss3,
+}
+
+/// VT100 input decoder.
+#[derive(Clone, Debug)]
+pub struct KeyDecoder {
+    state: DecoderState,
+    after_cr: bool,
+}
+
+impl KeyDecoder {
+    /// Creates a decoder in its ground state.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: DecoderState::Ground,
+            after_cr: false,
+        }
+    }
+
+    /// Feeds one byte from the terminal.
+    pub fn push(&mut self, byte: u8) -> Decoded {
+        let after_cr = core::mem::replace(&mut self.after_cr, byte == b'\r');
+        match self.state {
+            DecoderState::Ground => {
+                if byte == ESC {
+                    self.state = DecoderState::Escape;
+                    return Decoded::Pending;
+                }
+                if byte == b'\n' && after_cr {
+                    return Decoded::Pending;
+                }
+                EditKey::from_byte(byte).map_or(Decoded::Rejected, Decoded::Key)
+            }
+            DecoderState::Escape => match byte {
+                b'[' => {
+                    self.state = DecoderState::Csi(0);
+                    Decoded::Pending
+                }
+                b'O' => {
+                    self.state = DecoderState::Ss3;
+                    Decoded::Pending
+                }
+                _ => {
+                    // A lone ESC (or an Alt chord): drop the ESC and treat
+                    // the byte as ordinary input.
+                    self.state = DecoderState::Ground;
+                    self.after_cr = false;
+                    self.push(byte)
+                }
+            },
+            DecoderState::Csi(param) => match byte {
+                b'0'..=b'9' => {
+                    let digit = u16::from(byte - b'0');
+                    self.state = DecoderState::Csi(param.saturating_mul(10).saturating_add(digit));
+                    Decoded::Pending
+                }
+                b';' => {
+                    self.state = DecoderState::CsiTail(param);
+                    Decoded::Pending
+                }
+                _ => self.finish_csi(param, byte),
+            },
+            DecoderState::CsiTail(param) => match byte {
+                b'0'..=b'9' | b';' => Decoded::Pending,
+                _ => self.finish_csi(param, byte),
+            },
+            DecoderState::Ss3 => {
+                self.state = DecoderState::Ground;
+                cursor_key(byte).map_or(Decoded::Rejected, Decoded::Key)
+            }
+        }
+    }
+
+    fn finish_csi(&mut self, param: u16, byte: u8) -> Decoded {
+        if !(0x40..=0x7e).contains(&byte) {
+            // Not a final byte; keep waiting for one.
+            return Decoded::Pending;
+        }
+        self.state = DecoderState::Ground;
+        let key = if byte == b'~' {
+            match param {
+                1 | 7 => Some(EditKey::Home),
+                4 | 8 => Some(EditKey::End),
+                3 => Some(EditKey::Delete),
+                _ => None,
+            }
+        } else {
+            cursor_key(byte)
+        };
+        key.map_or(Decoded::Rejected, Decoded::Key)
+    }
+}
+
+impl Default for KeyDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const fn cursor_key(byte: u8) -> Option<EditKey> {
+    match byte {
+        b'A' => Some(EditKey::Up),
+        b'B' => Some(EditKey::Down),
+        b'C' => Some(EditKey::Right),
+        b'D' => Some(EditKey::Left),
+        b'H' => Some(EditKey::Home),
+        b'F' => Some(EditKey::End),
+        _ => None,
+    }
+}
+
+/// What the front-end should do after a key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditEvent {
+    /// Nothing visible changed.
+    Pending,
+    /// The line or cursor changed; redraw the input row.
+    Redraw,
+    /// The key does nothing here; ring the terminal bell.
+    Bell,
+    /// Tab: run completion at [`LineEditor::cursor`].
+    Complete,
+    /// Enter: collect the line with [`LineEditor::take_line`].
+    Submit,
+    /// Ctrl-C abandoned the line; move to a fresh row.
+    Cancelled,
+    /// The line outgrew the buffer; input is dropped until the next Enter.
+    Overflow,
+}
+
+/// Errors raised while changing the line programmatically.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LineError {
+    /// The change would not fit in the line buffer.
+    Overflow,
+}
+
+/// Bounded single-line editor with an `H`-entry history ring.
+#[derive(Clone, Debug)]
+pub struct LineEditor<const N: usize = EDITOR_LINE_CAPACITY, const H: usize = EDITOR_HISTORY_DEPTH>
+{
+    decoder: KeyDecoder,
+    line: Vec<u8, N>,
+    cursor: usize,
+    history: Deque<Vec<u8, N>, H>,
+    /// History entry on display, counted back from the newest (0).
+    recall: Option<usize>,
+    /// Line being typed before history browsing started.
+    draft: Vec<u8, N>,
+    discarding: bool,
+}
+
+impl<const N: usize, const H: usize> LineEditor<N, H> {
+    /// Creates an empty editor with no history.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            decoder: KeyDecoder::new(),
+            line: Vec::new(),
+            cursor: 0,
+            history: Deque::new(),
+            recall: None,
+            draft: Vec::new(),
+            discarding: false,
+        }
+    }
+
+    /// Returns the current line.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        // Only printable ASCII is ever inserted.
+        core::str::from_utf8(&self.line).unwrap_or_default()
+    }
+
+    /// Returns the cursor position as a byte offset into the line.
+    #[must_use]
+    pub const fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns `true` when the line is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.line.is_empty()
+    }
+
+    /// Returns the number of lines kept for recall.
+    #[must_use]
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Decodes one byte from a terminal and applies the resulting key.
+    pub fn feed(&mut self, byte: u8) -> EditEvent {
+        match self.decoder.push(byte) {
+            Decoded::Key(key) => self.apply(key),
+            Decoded::Pending => EditEvent::Pending,
+            Decoded::Rejected if self.discarding => EditEvent::Pending,
+            Decoded::Rejected => EditEvent::Bell,
+        }
+    }
+
+    /// Applies one decoded key.
+    pub fn apply(&mut self, key: EditKey) -> EditEvent {
+        if self.discarding {
+            if matches!(key, EditKey::Enter | EditKey::Cancel) {
+                self.discarding = false;
+                self.clear();
+            }
+            return EditEvent::Pending;
+        }
+
+        match key {
+            EditKey::Char(byte) => self.insert(byte),
+            EditKey::Backspace => self.remove_before(1),
+            EditKey::Delete => {
+                if self.cursor == self.line.len() {
+                    return EditEvent::Bell;
+                }
+                self.cursor += 1;
+                self.remove_before(1)
+            }
+            EditKey::Left => self.move_to(self.cursor.checked_sub(1)),
+            EditKey::Right => {
+                self.move_to(Some(self.cursor + 1).filter(|&at| at <= self.line.len()))
+            }
+            EditKey::Home => self.move_to(Some(0)),
+            EditKey::End => self.move_to(Some(self.line.len())),
+            EditKey::Up => self.recall_older(),
+            EditKey::Down => self.recall_newer(),
+            EditKey::DeleteWord => self.remove_before(self.word_start_distance()),
+            EditKey::KillToStart => self.remove_before(self.cursor),
+            EditKey::KillToEnd => {
+                if self.cursor == self.line.len() {
+                    return EditEvent::Bell;
+                }
+                self.line.truncate(self.cursor);
+                EditEvent::Redraw
+            }
+            EditKey::Cancel => {
+                self.clear();
+                EditEvent::Cancelled
+            }
+            EditKey::Tab => EditEvent::Complete,
+            EditKey::Enter => EditEvent::Submit,
+        }
+    }
+
+    /// Returns the submitted line, records it in the history, and clears the editor.
+    ///
+    /// Blank lines are returned but not recorded, and a line equal to the
+    /// newest entry is not recorded twice.
+    pub fn take_line(&mut self) -> Vec<u8, N> {
+        let line = core::mem::take(&mut self.line);
+        self.clear();
+        let blank = line.iter().all(u8::is_ascii_whitespace);
+        if !blank && self.history.back() != Some(&line) {
+            if self.history.is_full() {
+                self.history.pop_front();
+            }
+            let _ = self.history.push_back(line.clone());
+        }
+        line
+    }
+
+    /// Applies a completion and leaves the cursor after the inserted text.
+    ///
+    /// # Errors
+    /// Returns [`LineError::Overflow`] if the range is outside the line or the
+    /// completed line would not fit; the line is left unchanged.
+    pub fn complete(&mut self, replacement: &Replacement) -> Result<(), LineError> {
+        let Replacement {
+            start,
+            end,
+            value,
+            append_space,
+        } = *replacement;
+        if start > end || end > self.line.len() {
+            return Err(LineError::Overflow);
+        }
+        let space = usize::from(append_space);
+        let length = self.line.len() - (end - start) + value.len() + space;
+        if length > N {
+            return Err(LineError::Overflow);
+        }
+
+        let mut line: Vec<u8, N> = Vec::new();
+        let tail = &self.line[end..];
+        let _ = line.extend_from_slice(&self.line[..start]);
+        let _ = line.extend_from_slice(value.as_bytes());
+        if append_space {
+            let _ = line.push(b' ');
+        }
+        let cursor = line.len();
+        let _ = line.extend_from_slice(tail);
+        self.line = line;
+        self.cursor = cursor;
+        Ok(())
+    }
+
+    /// Writes the VT100 bytes that repaint the input row: a carriage return,
+    /// erase-line, the line, and a cursor move back to the editing position.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn write_redraw<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(writer, "\r\x1b[K{}", self.as_str())?;
+        let back = self.line.len() - self.cursor;
+        if back > 0 {
+            write!(writer, "\x1b[{back}D")?;
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        self.line.clear();
+        self.cursor = 0;
+        self.recall = None;
+        self.draft.clear();
+    }
+
+    fn insert(&mut self, byte: u8) -> EditEvent {
+        if self.line.insert(self.cursor, byte).is_err() {
+            self.discarding = true;
+            return EditEvent::Overflow;
+        }
+        self.cursor += 1;
+        EditEvent::Redraw
+    }
+
+    fn remove_before(&mut self, count: usize) -> EditEvent {
+        if count == 0 || count > self.cursor {
+            return EditEvent::Bell;
+        }
+        let start = self.cursor - count;
+        self.line.copy_within(self.cursor.., start);
+        self.line.truncate(self.line.len() - count);
+        self.cursor = start;
+        EditEvent::Redraw
+    }
+
+    fn move_to(&mut self, position: Option<usize>) -> EditEvent {
+        match position {
+            Some(position) if position != self.cursor => {
+                self.cursor = position;
+                EditEvent::Redraw
+            }
+            _ => EditEvent::Bell,
+        }
+    }
+
+    /// Distance back to the start of the word before the cursor, skipping
+    /// any spaces directly before it.
+    fn word_start_distance(&self) -> usize {
+        let before = &self.line[..self.cursor];
+        let spaces = before
+            .iter()
+            .rev()
+            .take_while(|byte| **byte == b' ')
+            .count();
+        let word = before[..before.len() - spaces]
+            .iter()
+            .rev()
+            .take_while(|byte| **byte != b' ')
+            .count();
+        spaces + word
+    }
+
+    fn recall_older(&mut self) -> EditEvent {
+        let next = self.recall.map_or(0, |index| index + 1);
+        if next >= self.history.len() {
+            return EditEvent::Bell;
+        }
+        if self.recall.is_none() {
+            self.draft = self.line.clone();
+        }
+        self.show_entry(Some(next));
+        EditEvent::Redraw
+    }
+
+    fn recall_newer(&mut self) -> EditEvent {
+        match self.recall {
+            None => EditEvent::Bell,
+            Some(0) => {
+                self.line = core::mem::take(&mut self.draft);
+                self.cursor = self.line.len();
+                self.recall = None;
+                EditEvent::Redraw
+            }
+            Some(index) => {
+                self.show_entry(Some(index - 1));
+                EditEvent::Redraw
+            }
+        }
+    }
+
+    fn show_entry(&mut self, recall: Option<usize>) {
+        let entry = recall.and_then(|index| self.history.iter().rev().nth(index));
+        if let Some(entry) = entry {
+            self.line = entry.clone();
+            self.cursor = self.line.len();
+            self.recall = recall;
+        }
+    }
+}
+
+impl<const N: usize, const H: usize> Default for LineEditor<N, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feed(editor: &mut LineEditor<16, 2>, bytes: &[u8]) -> EditEvent {
+        let mut last = EditEvent::Pending;
+        for &byte in bytes {
+            last = editor.feed(byte);
+        }
+        last
+    }
+
+    #[test]
+    fn decodes_cursor_keys_and_edits_mid_line() {
+        let mut editor = LineEditor::<16, 2>::new();
+        feed(&mut editor, b"rebot");
+        // Left twice (CSI and SS3 forms), insert, then Home/End variants.
+        assert_eq!(feed(&mut editor, b"\x1b[D\x1bOD"), EditEvent::Redraw);
+        feed(&mut editor, b"o");
+        assert_eq!((editor.as_str(), editor.cursor()), ("reboot", 4));
+        feed(&mut editor, b"\x1b[1~");
+        assert_eq!(editor.cursor(), 0);
+        feed(&mut editor, b"\x1b[1;5F");
+        assert_eq!(editor.cursor(), 6);
+        assert_eq!(feed(&mut editor, b"\x1b[3~"), EditEvent::Bell);
+        assert_eq!(feed(&mut editor, b"\x1b[Z"), EditEvent::Bell);
+
+        let mut redraw = std::string::String::new();
+        feed(&mut editor, b"\x01\x06");
+        editor.write_redraw(&mut redraw).unwrap();
+        assert_eq!(redraw, "\r\x1b[Kreboot\x1b[5D");
+
+        assert_eq!(feed(&mut editor, b"\r"), EditEvent::Submit);
+        assert_eq!(editor.take_line().as_slice(), b"reboot");
+        // The LF of a CRLF ending is swallowed rather than submitting again.
+        assert_eq!(feed(&mut editor, b"\n"), EditEvent::Pending);
+        assert!(editor.is_empty());
+    }
+
+    #[test]
+    fn control_keys_delete_words_and_ranges() {
+        let mut editor = LineEditor::<16, 2>::new();
+        feed(&mut editor, b"power stats  ");
+        assert_eq!(feed(&mut editor, b"\x17"), EditEvent::Redraw);
+        assert_eq!(editor.as_str(), "power ");
+        feed(&mut editor, b"history\x02\x02\x0b");
+        assert_eq!(editor.as_str(), "power histo");
+        feed(&mut editor, b"\x02\x15");
+        assert_eq!((editor.as_str(), editor.cursor()), ("o", 0));
+        assert_eq!(feed(&mut editor, b"\x7f"), EditEvent::Bell);
+        assert_eq!(feed(&mut editor, b"\x04"), EditEvent::Redraw);
+        assert!(editor.is_empty());
+    }
+
+    #[test]
+    fn history_ring_recalls_and_restores_the_draft() {
+        let mut editor = LineEditor::<16, 2>::new();
+        for line in [
+            &b"status\r"[..],
+            b"status\r",
+            b"   \r",
+            b"bridge stats\r",
+            b"help\r",
+        ] {
+            feed(&mut editor, line);
+            editor.take_line();
+        }
+        assert_eq!(editor.history_len(), 2);
+
+        feed(&mut editor, b"rec");
+        feed(&mut editor, b"\x1b[A");
+        assert_eq!(editor.as_str(), "help");
+        feed(&mut editor, b"\x10");
+        assert_eq!(editor.as_str(), "bridge stats");
+        assert_eq!(feed(&mut editor, b"\x1b[A"), EditEvent::Bell);
+        feed(&mut editor, b"\x1b[B\x0e");
+        assert_eq!((editor.as_str(), editor.cursor()), ("rec", 3));
+        assert_eq!(feed(&mut editor, b"\x1b[B"), EditEvent::Bell);
+    }
+
+    #[test]
+    fn overflow_discards_until_enter_and_completion_inserts_at_cursor() {
+        let mut editor = LineEditor::<16, 2>::new();
+        assert_eq!(
+            feed(&mut editor, b"console send \"abcdef"),
+            EditEvent::Pending
+        );
+        assert_eq!(feed(&mut editor, b"\r"), EditEvent::Pending);
+        assert!(editor.is_empty());
+        assert_eq!(editor.history_len(), 0);
+
+        feed(&mut editor, b"rec now\x01\x06\x06\x06");
+        let replacement = Replacement {
+            start: 0,
+            end: 3,
+            value: "recovery",
+            append_space: false,
+        };
+        editor.complete(&replacement).unwrap();
+        assert_eq!((editor.as_str(), editor.cursor()), ("recovery now", 8));
+        let too_long = Replacement {
+            value: "recoveryrecovery",
+            ..replacement
+        };
+        assert_eq!(editor.complete(&too_long), Err(LineError::Overflow));
+        assert_eq!(editor.as_str(), "recovery now");
+    }
+}
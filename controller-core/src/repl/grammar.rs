@@ -7,9 +7,20 @@
 //! `winnow` combinators over those tokens to build structured command values.
 
 use super::catalog::{
-    self, ChoiceBranch, ChoiceTag, CommandTag, DefaultChoice, HelpTopics, Node, SubcommandBranch,
-    SubcommandTag, ValueSpec,
+    self, ChoiceBranch, ChoiceTag, CommandTag, DURATION_UNITS, DefaultChoice, HelpTopics, Node,
+    OptionKind, SubcommandBranch, SubcommandTag, ValueSpec,
 };
+use super::json::ResponseMode;
+use super::suggest::Suggestions;
+use super::wait::WaitCondition;
+use crate::bridge::autobaud::BaudSetting;
+use crate::bridge::console::{ConsoleMarker, PatternId};
+use crate::bridge::inject::{self, ConsoleText, TextError};
+use crate::bridge::line_coding::SysRqKey;
+use crate::orchestrator::RequestTag;
+use crate::script::{ScriptName, script_name};
+use crate::telemetry::{EventCategory, EventFilter, TelemetryEventKind};
+
 use core::fmt;
 use core::ops::Range;
 use core::time::Duration;
@@ -25,12 +36,14 @@ use winnow::stream::Stream;
 /// Maximum number of tokens produced per REPL line. Commands remain short and bounded.
 pub const MAX_TOKENS: usize = 32;
 const MAX_CACHE_RECORDS: usize = MAX_TOKENS * 2;
+/// Most `key=value` options a single command accepts.
+const MAX_OPTIONS: usize = 4;
 
 /// Lexical token kinds recognized by the REPL grammar.
 #[derive(RegalLexer, Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub enum TokenKind {
-    /// Duration literal ending in `ms` or `s`.
-    #[regex(r"[0-9]+(?:ms|s)", priority = 2)]
+    /// Duration literal such as `250us`, `1.5s`, or `1h30m`.
+    #[regex(r"(?:[0-9]+(?:\.[0-9]+)?(?:us|ms|s|m|h))+", priority = 2)]
     Duration,
     /// Unsuffixed integer literal.
     #[regex(r"[0-9]+")]
@@ -38,15 +51,24 @@ pub enum TokenKind {
     /// Identifier or keyword (case-insensitive match performed later).
     #[regex(r"[A-Za-z][A-Za-z0-9-]*")]
     Ident,
+    /// Request tag prefix such as `@17`.
+    #[regex(r"@[0-9]+")]
+    Tag,
     /// CLI-style flag placeholder (future extension).
     #[regex(r"-{1,2}[A-Za-z][A-Za-z0-9-]*")]
     Flag,
+    /// Double-quoted string; a backslash escapes the next character.
+    #[regex(r#""(?:[^"\\\r\n]|\\[^\r\n])*""#)]
+    QuotedString,
     /// Equals sign for key/value assignments.
     #[token("=")]
     Equals,
     /// Comma separator.
     #[token(",")]
     Comma,
+    /// Separates chained commands (`recovery enter; status`).
+    #[token(";")]
+    Semicolon,
     /// Inline whitespace is ignored.
     #[regex(r"[ \t]+", skip)]
     Whitespace,
@@ -67,9 +89,12 @@ impl fmt::Display for TokenKind {
             TokenKind::Duration => "duration literal",
             TokenKind::Integer => "integer literal",
             TokenKind::Ident => "identifier",
+            TokenKind::Tag => "request tag",
             TokenKind::Flag => "flag",
+            TokenKind::QuotedString => "quoted string",
             TokenKind::Equals => "equals sign",
             TokenKind::Comma => "comma",
+            TokenKind::Semicolon => "semicolon",
             TokenKind::Whitespace => "whitespace",
             TokenKind::Eol => "end-of-line marker",
             TokenKind::Error => "unsupported token",
@@ -130,6 +155,25 @@ pub enum GrammarErrorKind {
         span: Range<usize>,
         lexeme: HeaplessString<32>,
     },
+    InvalidText {
+        span: Range<usize>,
+        error: TextError,
+    },
+    /// An option value of the wrong type or outside its bounds.
+    InvalidOption {
+        option: &'static str,
+        expected: OptionKind,
+        span: Range<usize>,
+    },
+    /// A word the catalog does not allow at this position, with the allowed
+    /// words closest to it.
+    UnknownKeyword {
+        /// What was expected: `command`, `subcommand`, `keyword`, or `option`.
+        category: &'static str,
+        word: HeaplessString<32>,
+        suggestions: Suggestions,
+        span: Range<usize>,
+    },
 }
 
 impl fmt::Display for GrammarErrorKind {
@@ -163,6 +207,26 @@ impl fmt::Display for GrammarErrorKind {
             GrammarErrorKind::InvalidToken { span, lexeme } => {
                 write!(f, "unsupported token `{lexeme}` at {}", SpanDisplay(span))
             }
+            GrammarErrorKind::InvalidText { span, error } => {
+                write!(f, "invalid quoted string at {}: {error}", SpanDisplay(span))
+            }
+            GrammarErrorKind::InvalidOption {
+                option,
+                expected,
+                span,
+            } => write!(f, "{option} must be {expected} at {}", SpanDisplay(span)),
+            GrammarErrorKind::UnknownKeyword {
+                category,
+                word,
+                suggestions,
+                ..
+            } => {
+                write!(f, "unknown {category} '{word}'")?;
+                if !suggestions.is_empty() {
+                    write!(f, " (did you mean {suggestions}?)")?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -217,21 +281,67 @@ impl GrammarError {
         }
     }
 
-    fn invalid_token(token: &Token<'_>) -> Self {
-        let mut lexeme = HeaplessString::<32>::new();
-        for ch in token.lexeme.chars() {
-            if lexeme.push(ch).is_err() {
-                break;
-            }
+    fn invalid_text(token: &Token<'_>, error: TextError) -> Self {
+        GrammarError {
+            kind: GrammarErrorKind::InvalidText {
+                span: token.span.clone(),
+                error,
+            },
+        }
+    }
+
+    fn invalid_option(option: &'static str, expected: OptionKind, token: &Token<'_>) -> Self {
+        GrammarError {
+            kind: GrammarErrorKind::InvalidOption {
+                option,
+                expected,
+                span: token.span.clone(),
+            },
         }
+    }
 
+    fn invalid_token(token: &Token<'_>) -> Self {
         GrammarError {
             kind: GrammarErrorKind::InvalidToken {
                 span: token.span.clone(),
-                lexeme,
+                lexeme: truncated_lexeme(token),
+            },
+        }
+    }
+
+    fn unknown_keyword(
+        category: &'static str,
+        token: &Token<'_>,
+        allowed: impl IntoIterator<Item = &'static str>,
+    ) -> Self {
+        GrammarError {
+            kind: GrammarErrorKind::UnknownKeyword {
+                category,
+                word: truncated_lexeme(token),
+                suggestions: Suggestions::closest(token.lexeme, allowed),
+                span: token.span.clone(),
             },
         }
     }
+
+    /// Returns the words suggested for an unknown keyword, if any.
+    #[must_use]
+    pub fn suggestions(&self) -> &[&'static str] {
+        match &self.kind {
+            GrammarErrorKind::UnknownKeyword { suggestions, .. } => suggestions.as_slice(),
+            _ => &[],
+        }
+    }
+}
+
+fn truncated_lexeme(token: &Token<'_>) -> HeaplessString<32> {
+    let mut lexeme = HeaplessString::new();
+    for ch in token.lexeme.chars() {
+        if lexeme.push(ch).is_err() {
+            break;
+        }
+    }
+    lexeme
 }
 
 type Input<'src, 'slice> = &'slice [Token<'src>];
@@ -279,13 +389,30 @@ impl fmt::Display for ParseError {
     }
 }
 
+/// A command line with its optional request tag (`@17 reboot now`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Request<'a> {
+    pub tag: Option<RequestTag>,
+    pub command: Command<'a>,
+}
+
 /// Structured commands produced by the parser.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Command<'a> {
     Reboot(RebootCommand),
     Recovery(RecoveryCommand),
     Fault(FaultCommand),
+    Power(PowerCommand),
+    Watchdog(WatchdogCommand),
+    Console(ConsoleCommand),
+    Bridge(BridgeCommand),
+    Script(ScriptCommand),
     Status,
+    /// Switch the response format, or report it when `None`.
+    Mode(Option<ResponseMode>),
+    Events(EventsCommand),
+    Wait(WaitCommand),
+    Macro(MacroCommand),
     Help(HelpCommand<'a>),
 }
 
@@ -293,13 +420,18 @@ pub enum Command<'a> {
 pub enum RebootCommand {
     Now,
     Delay(Duration),
+    /// Reboot and stop at the bootloader prompt.
+    ToBootloader,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RecoveryCommand {
     Enter,
     Exit,
-    Now,
+    /// Hold REC until console activity, or `timeout` (default when `None`).
+    Now {
+        timeout: Option<Duration>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -307,6 +439,119 @@ pub struct FaultCommand {
     pub retries: Option<u8>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PowerCommand {
+    Stats { reset: bool },
+    History,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchdogCommand {
+    Status,
+    Enable,
+    Disable,
+    BootDeadline(Duration),
+    SilenceLimit(Duration),
+    PanicGrace(Duration),
+    RecoveryLimit(u8),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConsoleCommand {
+    Tail {
+        lines: Option<u32>,
+    },
+    Since {
+        run: u32,
+    },
+    SysRq {
+        key: SysRqKey,
+    },
+    Send {
+        text: ConsoleText,
+        newline: bool,
+    },
+    /// Hand the REPL port over to the Jetson console until the escape.
+    Attach,
+    /// Switch the console UART to a fixed rate or search for one.
+    Baud {
+        setting: BaudSetting,
+    },
+    /// Change or list the console marker patterns.
+    Pattern(PatternCommand),
+}
+
+/// `console pattern` changes to the marker pattern table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternCommand {
+    Add {
+        marker: ConsoleMarker,
+        text: ConsoleText,
+    },
+    Remove {
+        id: PatternId,
+    },
+    List,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BridgeCommand {
+    Stats { reset: bool },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ScriptCommand {
+    Load { name: ScriptName },
+    List,
+    Run { name: ScriptName },
+    Stop,
+}
+
+/// Changes to the telemetry categories a session prints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventsCommand {
+    /// Report the current selection.
+    Show,
+    /// Start printing the given categories.
+    On(EventFilter),
+    /// Stop printing the given categories.
+    Off(EventFilter),
+}
+
+impl EventsCommand {
+    /// Applies the change to `current` and returns the new selection.
+    #[must_use]
+    pub const fn apply(self, current: EventFilter) -> EventFilter {
+        match self {
+            EventsCommand::Show => current,
+            EventsCommand::On(filter) => current.union(filter),
+            EventsCommand::Off(filter) => current.difference(filter),
+        }
+    }
+}
+
+/// Hold the session until `condition` holds or `timeout` passes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaitCommand {
+    pub condition: WaitCondition,
+    /// `timeout=`; [`DEFAULT_WAIT_TIMEOUT`](super::wait::DEFAULT_WAIT_TIMEOUT) when `None`.
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MacroCommand {
+    /// Store `body`, a `;`-separated chain still to be checked by the library.
+    Define {
+        name: ScriptName,
+        body: ConsoleText,
+        continue_on_error: bool,
+    },
+    Run {
+        name: ScriptName,
+    },
+    List,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HelpCommand<'a> {
     pub topic: Option<&'a str>,
@@ -390,12 +635,21 @@ fn map_incremental_error(error: &IncrementalError) -> LexError {
     }
 }
 
-/// Parse a REPL command from the provided line.
+/// Parse a REPL command from the provided line, ignoring any request tag.
 ///
 /// # Errors
 /// Returns a [`ParseError`] when the line is empty, fails lexical analysis, or
 /// does not match the grammar.
 pub fn parse(line: &str) -> Result<Command<'_>, ParseError> {
+    parse_request(line).map(|request| request.command)
+}
+
+/// Parse a REPL command line that may start with a request tag.
+///
+/// # Errors
+/// Returns a [`ParseError`] when the line is empty, fails lexical analysis, or
+/// does not match the grammar.
+pub fn parse_request(line: &str) -> Result<Request<'_>, ParseError> {
     let tokens = lex(line).map_err(ParseError::Lex)?;
 
     for token in &tokens {
@@ -404,8 +658,14 @@ pub fn parse(line: &str) -> Result<Command<'_>, ParseError> {
         }
     }
 
-    let (command, mut rest) =
-        parse_tokens_partial(tokens.as_slice()).map_err(ParseError::Grammar)?;
+    let (tag, tokens) = match tokens.split_first() {
+        Some((token, rest)) if token.kind == TokenKind::Tag => {
+            (Some(parse_tag(token).map_err(ParseError::Grammar)?), rest)
+        }
+        _ => (None, tokens.as_slice()),
+    };
+
+    let (command, mut rest) = parse_tokens_partial(tokens).map_err(ParseError::Grammar)?;
 
     while let Some((token, remaining)) = rest.split_first() {
         if token.kind == TokenKind::Eol {
@@ -418,7 +678,18 @@ pub fn parse(line: &str) -> Result<Command<'_>, ParseError> {
         }
     }
 
-    Ok(command)
+    Ok(Request { tag, command })
+}
+
+/// Returns the request tag at the start of `line`, even when the command
+/// after it does not parse, so errors can be tagged as well.
+#[must_use]
+pub fn request_tag(line: &str) -> Option<RequestTag> {
+    let tokens = lex(line).ok()?;
+    tokens
+        .first()
+        .filter(|token| token.kind == TokenKind::Tag)
+        .and_then(|token| parse_tag(token).ok())
 }
 
 fn command<'src, 'slice>() -> impl Parser<Input<'src, 'slice>, Command<'src>, ErrMode<GrammarError>>
@@ -430,14 +701,15 @@ where
         let command_token = expect_kind(TokenKind::Ident, "command keyword").parse_next(input)?;
 
         if let Some(spec) = catalog::find(command_token.lexeme) {
-            let mut state = CommandState::new(spec.tag);
+            let mut state = ParseState::new(spec.tag);
             parse_node(spec.grammar, input, &mut state)?;
             state.finish()
         } else {
             *input = snapshot;
-            Err(ErrMode::Backtrack(GrammarError::unexpected(
-                "command keyword",
-                Some(&command_token),
+            Err(ErrMode::Backtrack(GrammarError::unknown_keyword(
+                "command",
+                &command_token,
+                catalog::commands().iter().map(|spec| spec.name),
             )))
         }
     }
@@ -446,7 +718,7 @@ where
 fn parse_node<'src, 'slice>(
     node: &'static Node,
     input: &mut Input<'src, 'slice>,
-    state: &mut CommandState<'src>,
+    state: &mut ParseState<'src>,
 ) -> Result<(), ErrMode<GrammarError>>
 where
     'src: 'slice,
@@ -457,6 +729,25 @@ where
             parse_optional_choice(input, choices, *default, state)
         }
         Node::Subcommands(branches) => parse_subcommands(input, branches, state),
+        Node::Argument {
+            label,
+            tag,
+            value,
+            required,
+            next,
+            ..
+        } => {
+            let snapshot = *input;
+            match parse_argument(input, label, *tag, *value, *required, state) {
+                // An optional argument followed by options is skipped when
+                // the next token cannot start it (`wait complete timeout=5s`).
+                Err(ErrMode::Backtrack(_)) if !*required && **next != Node::End => {
+                    *input = snapshot;
+                }
+                result => result?,
+            }
+            parse_node(next, input, state)
+        }
         Node::Topic { topics, next } => {
             parse_topic(*topics, input, state)?;
             parse_node(next, input, state)
@@ -468,7 +759,7 @@ fn parse_optional_choice<'src, 'slice>(
     input: &mut Input<'src, 'slice>,
     choices: &'static [ChoiceBranch],
     default: Option<DefaultChoice>,
-    state: &mut CommandState<'src>,
+    state: &mut ParseState<'src>,
 ) -> Result<(), ErrMode<GrammarError>>
 where
     'src: 'slice,
@@ -479,15 +770,16 @@ where
                 *input = rest;
                 parse_choice_branch(input, branch, state)
             } else {
-                Err(ErrMode::Backtrack(GrammarError::unexpected(
-                    choice_expected_label(choices),
-                    Some(token),
+                Err(ErrMode::Backtrack(GrammarError::unknown_keyword(
+                    choice_category(choices),
+                    token,
+                    choices.iter().map(|choice| choice.keyword),
                 )))
             }
         }
         Some((token, _)) if token.kind == TokenKind::Eol => {
             if let Some(default_choice) = default {
-                state.apply_default_choice(default_choice.tag)?;
+                state.command.apply_default_choice(default_choice.tag)?;
                 parse_node(default_choice.next, input, state)
             } else {
                 Ok(())
@@ -499,7 +791,7 @@ where
         ))),
         None => {
             if let Some(default_choice) = default {
-                state.apply_default_choice(default_choice.tag)?;
+                state.command.apply_default_choice(default_choice.tag)?;
                 parse_node(default_choice.next, input, state)
             } else {
                 Ok(())
@@ -511,20 +803,24 @@ where
 fn parse_choice_branch<'src, 'slice>(
     input: &mut Input<'src, 'slice>,
     branch: &'static ChoiceBranch,
-    state: &mut CommandState<'src>,
+    state: &mut ParseState<'src>,
 ) -> Result<(), ErrMode<GrammarError>>
 where
     'src: 'slice,
 {
-    let value = parse_value(input, branch.value)?;
-    state.apply_choice(branch.tag, value)?;
+    let value = parse_value(input, branch.keyword, branch.value)?;
+    if matches!(branch.value, ValueSpec::Assignment(_)) {
+        state.options.set(branch.tag, value)?;
+    } else {
+        state.command.apply_choice(branch.tag, value)?;
+    }
     parse_node(branch.next, input, state)
 }
 
 fn parse_subcommands<'src, 'slice>(
     input: &mut Input<'src, 'slice>,
     branches: &'static [SubcommandBranch],
-    state: &mut CommandState<'src>,
+    state: &mut ParseState<'src>,
 ) -> Result<(), ErrMode<GrammarError>>
 where
     'src: 'slice,
@@ -536,12 +832,13 @@ where
                 .find(|candidate| candidate.name.eq_ignore_ascii_case(token.lexeme))
             {
                 *input = rest;
-                state.set_subcommand(branch.tag);
+                state.command.set_subcommand(branch.tag);
                 parse_node(branch.grammar, input, state)
             } else {
-                Err(ErrMode::Backtrack(GrammarError::unexpected(
-                    branches.first().map_or("subcommand", |branch| branch.name),
-                    Some(token),
+                Err(ErrMode::Backtrack(GrammarError::unknown_keyword(
+                    "subcommand",
+                    token,
+                    branches.iter().map(|branch| branch.name),
                 )))
             }
         }
@@ -562,19 +859,44 @@ where
     }
 }
 
+fn parse_argument<'src, 'slice>(
+    input: &mut Input<'src, 'slice>,
+    label: &'static str,
+    tag: ChoiceTag,
+    spec: ValueSpec,
+    required: bool,
+    state: &mut ParseState<'src>,
+) -> Result<(), ErrMode<GrammarError>>
+where
+    'src: 'slice,
+{
+    let present = input
+        .first()
+        .is_some_and(|token| token.kind != TokenKind::Eol);
+    if !present && !required {
+        return Ok(());
+    }
+
+    let value = parse_value(input, label, spec).map_err(|error| match error {
+        ErrMode::Backtrack(_) => ErrMode::Backtrack(GrammarError::unexpected(label, input.first())),
+        other => other,
+    })?;
+    state.command.apply_choice(tag, value)
+}
+
 fn parse_topic<'src, 'slice>(
     _topics: HelpTopics,
     input: &mut Input<'src, 'slice>,
-    state: &mut CommandState<'src>,
+    state: &mut ParseState<'src>,
 ) -> Result<(), ErrMode<GrammarError>>
 where
     'src: 'slice,
 {
-    state.set_topic(None);
+    state.command.set_topic(None);
 
     match input.split_first() {
         Some((token, rest)) if token.kind == TokenKind::Ident => {
-            state.set_topic(Some(token.lexeme));
+            state.command.set_topic(Some(token.lexeme));
             *input = rest;
             Ok(())
         }
@@ -587,8 +909,11 @@ where
     }
 }
 
+/// Parses the value `spec` describes; `name` is the keyword or argument label
+/// it belongs to, used to report invalid option values.
 fn parse_value<'src, 'slice>(
     input: &mut Input<'src, 'slice>,
+    name: &'static str,
     spec: ValueSpec,
 ) -> Result<ChoiceValue, ErrMode<GrammarError>>
 where
@@ -601,11 +926,115 @@ where
             let duration = parse_duration(&duration_token).map_err(ErrMode::Cut)?;
             Ok(ChoiceValue::Duration(duration))
         }
-        ValueSpec::IntegerAssignment { .. } => {
+        ValueSpec::Assignment(option) => {
             let _ = expect_kind(TokenKind::Equals, "=").parse_next(input)?;
+            match input.split_first() {
+                Some((token, rest)) if token.kind != TokenKind::Eol => {
+                    let value = parse_option(name, option.kind, token).map_err(ErrMode::Cut)?;
+                    *input = rest;
+                    Ok(value)
+                }
+                token => Err(ErrMode::Backtrack(GrammarError::unexpected(
+                    name,
+                    token.map(|(token, _)| token),
+                ))),
+            }
+        }
+        ValueSpec::Count => {
             let integer_token = expect_kind(TokenKind::Integer, "integer").parse_next(input)?;
-            let value = parse_integer(&integer_token).map_err(ErrMode::Cut)?;
-            Ok(ChoiceValue::Integer(value))
+            let value = parse_count(&integer_token).map_err(ErrMode::Cut)?;
+            Ok(ChoiceValue::Count(value))
+        }
+        ValueSpec::Key => match input.split_first() {
+            Some((token, rest)) if matches!(token.kind, TokenKind::Ident | TokenKind::Integer) => {
+                let key = match token.lexeme.as_bytes() {
+                    [byte] => SysRqKey::new(*byte),
+                    _ => None,
+                }
+                .ok_or_else(|| ErrMode::Cut(GrammarError::unexpected("key", Some(token))))?;
+                *input = rest;
+                Ok(ChoiceValue::Key(key))
+            }
+            Some((token, _)) => Err(ErrMode::Backtrack(GrammarError::unexpected(
+                "key",
+                Some(token),
+            ))),
+            None => Err(ErrMode::Backtrack(GrammarError::unexpected("key", None))),
+        },
+        ValueSpec::Text => {
+            let text_token =
+                expect_kind(TokenKind::QuotedString, "quoted string").parse_next(input)?;
+            let text = parse_text(&text_token).map_err(ErrMode::Cut)?;
+            Ok(ChoiceValue::Text(text))
+        }
+        ValueSpec::Baud => match input.split_first() {
+            Some((token, rest)) if token.kind == TokenKind::Ident => {
+                if !token.lexeme.eq_ignore_ascii_case("auto") {
+                    return Err(ErrMode::Backtrack(GrammarError::unexpected(
+                        "baud rate or auto",
+                        Some(token),
+                    )));
+                }
+                *input = rest;
+                Ok(ChoiceValue::Baud(BaudSetting::Auto))
+            }
+            Some((token, rest)) if token.kind == TokenKind::Integer => {
+                let setting = parse_count(token)
+                    .ok()
+                    .and_then(BaudSetting::fixed)
+                    .ok_or_else(|| {
+                        ErrMode::Cut(GrammarError::unexpected("supported baud rate", Some(token)))
+                    })?;
+                *input = rest;
+                Ok(ChoiceValue::Baud(setting))
+            }
+            Some((token, _)) => Err(ErrMode::Backtrack(GrammarError::unexpected(
+                "baud rate or auto",
+                Some(token),
+            ))),
+            None => Err(ErrMode::Backtrack(GrammarError::unexpected(
+                "baud rate or auto",
+                None,
+            ))),
+        },
+        ValueSpec::Categories => {
+            let mut filter = EventFilter::NONE;
+            loop {
+                let token = expect_kind(TokenKind::Ident, "event category").parse_next(input)?;
+                let category = EventCategory::from_name(token.lexeme).ok_or_else(|| {
+                    ErrMode::Cut(GrammarError::unexpected("event category", Some(&token)))
+                })?;
+                filter = filter.with(category);
+                match input.split_first() {
+                    Some((token, rest)) if token.kind == TokenKind::Comma => *input = rest,
+                    _ => return Ok(ChoiceValue::Categories(filter)),
+                }
+            }
+        }
+        ValueSpec::EventName => {
+            let name_token = expect_kind(TokenKind::Ident, "event name").parse_next(input)?;
+            let name = TelemetryEventKind::find_name(name_token.lexeme).ok_or_else(|| {
+                ErrMode::Cut(GrammarError::unexpected("event name", Some(&name_token)))
+            })?;
+            Ok(ChoiceValue::EventName(name))
+        }
+        ValueSpec::Marker => {
+            let marker_token = expect_kind(TokenKind::Ident, "console marker").parse_next(input)?;
+            let marker = ConsoleMarker::from_name(marker_token.lexeme).ok_or_else(|| {
+                ErrMode::Cut(GrammarError::unknown_keyword(
+                    "marker",
+                    &marker_token,
+                    ConsoleMarker::ALL.iter().map(|marker| marker.name()),
+                ))
+            })?;
+            Ok(ChoiceValue::Marker(marker))
+        }
+        ValueSpec::Name => {
+            let name_token = expect_kind(TokenKind::Ident, "script name").parse_next(input)?;
+            let name = script_name(name_token.lexeme).ok_or_else(|| {
+                ErrMode::Cut(GrammarError::unexpected("short name", Some(&name_token)))
+            })?;
+            Ok(ChoiceValue::Name(name))
         }
     }
 }
@@ -616,15 +1045,98 @@ fn find_choice(choices: &'static [ChoiceBranch], lexeme: &str) -> Option<&'stati
         .find(|choice| choice.keyword.eq_ignore_ascii_case(lexeme))
 }
 
+/// Names the words of a choice list in errors: `option` when every choice is
+/// a `key=value` option, `keyword` otherwise.
+fn choice_category(choices: &'static [ChoiceBranch]) -> &'static str {
+    if choices
+        .iter()
+        .all(|choice| matches!(choice.value, ValueSpec::Assignment(_)))
+    {
+        "option"
+    } else {
+        "keyword"
+    }
+}
+
 fn choice_expected_label(choices: &'static [ChoiceBranch]) -> &'static str {
     choices.first().map_or("keyword", |choice| choice.keyword)
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 enum ChoiceValue {
     None,
     Duration(Duration),
-    Integer(u8),
+    Integer(u32),
+    /// One of an [`OptionKind::Enum`]'s words.
+    Word(&'static str),
+    Count(u32),
+    Key(SysRqKey),
+    Text(ConsoleText),
+    Name(ScriptName),
+    Baud(BaudSetting),
+    Categories(EventFilter),
+    EventName(&'static str),
+    Marker(ConsoleMarker),
+}
+
+/// Everything collected while walking a command's grammar.
+struct ParseState<'a> {
+    command: CommandState<'a>,
+    /// `key=value` options, read back when the command is built.
+    options: OptionValues,
+}
+
+impl<'a> ParseState<'a> {
+    fn new(tag: CommandTag) -> Self {
+        Self {
+            command: CommandState::new(tag),
+            options: OptionValues::default(),
+        }
+    }
+
+    fn finish(self) -> Result<Command<'a>, ErrMode<GrammarError>> {
+        self.command.finish(&self.options)
+    }
+}
+
+/// Validated `key=value` options by the tag of the branch that declared them.
+#[derive(Default)]
+struct OptionValues {
+    values: HeaplessVec<(ChoiceTag, ChoiceValue), MAX_OPTIONS>,
+}
+
+impl OptionValues {
+    fn set(&mut self, tag: ChoiceTag, value: ChoiceValue) -> Result<(), ErrMode<GrammarError>> {
+        if let Some((_, slot)) = self.values.iter_mut().find(|(seen, _)| *seen == tag) {
+            *slot = value;
+            return Ok(());
+        }
+        self.values
+            .push((tag, value))
+            .map_err(|_| ErrMode::Cut(GrammarError::unexpected("fewer options", None)))
+    }
+
+    fn get(&self, tag: ChoiceTag) -> Option<&ChoiceValue> {
+        self.values
+            .iter()
+            .find(|(seen, _)| *seen == tag)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns an integer option; the catalog's bounds keep it within `T`.
+    fn integer<T: TryFrom<u32>>(&self, tag: ChoiceTag) -> Option<T> {
+        match self.get(tag) {
+            Some(ChoiceValue::Integer(value)) => T::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    fn duration(&self, tag: ChoiceTag) -> Option<Duration> {
+        match self.get(tag) {
+            Some(ChoiceValue::Duration(duration)) => Some(*duration),
+            _ => None,
+        }
+    }
 }
 
 enum CommandState<'a> {
@@ -636,9 +1148,49 @@ enum CommandState<'a> {
     },
     Fault {
         subcommand: Option<SubcommandTag>,
-        retries: Option<u8>,
+    },
+    Power {
+        subcommand: Option<SubcommandTag>,
+        reset: bool,
+    },
+    Watchdog {
+        action: Option<WatchdogCommand>,
+    },
+    Console {
+        subcommand: Option<SubcommandTag>,
+        count: Option<u32>,
+        key: Option<SysRqKey>,
+        text: Option<ConsoleText>,
+        newline: bool,
+        baud: Option<BaudSetting>,
+        marker: Option<ConsoleMarker>,
+    },
+    Bridge {
+        subcommand: Option<SubcommandTag>,
+        reset: bool,
+    },
+    Script {
+        subcommand: Option<SubcommandTag>,
+        name: Option<ScriptName>,
     },
     Status,
+    Mode {
+        mode: Option<ResponseMode>,
+    },
+    Events {
+        action: EventsCommand,
+    },
+    Wait {
+        subcommand: Option<SubcommandTag>,
+        tag: Option<u32>,
+        event: Option<&'static str>,
+    },
+    Macro {
+        subcommand: Option<SubcommandTag>,
+        name: Option<ScriptName>,
+        body: Option<ConsoleText>,
+        continue_on_error: bool,
+    },
     Help {
         topic: Option<&'a str>,
     },
@@ -649,11 +1201,45 @@ impl<'a> CommandState<'a> {
         match tag {
             CommandTag::Reboot => CommandState::Reboot { action: None },
             CommandTag::Recovery => CommandState::Recovery { action: None },
-            CommandTag::Fault => CommandState::Fault {
+            CommandTag::Fault => CommandState::Fault { subcommand: None },
+            CommandTag::Power => CommandState::Power {
                 subcommand: None,
-                retries: None,
+                reset: false,
+            },
+            CommandTag::Watchdog => CommandState::Watchdog { action: None },
+            CommandTag::Console => CommandState::Console {
+                subcommand: None,
+                count: None,
+                key: None,
+                text: None,
+                newline: false,
+                baud: None,
+                marker: None,
+            },
+            CommandTag::Bridge => CommandState::Bridge {
+                subcommand: None,
+                reset: false,
+            },
+            CommandTag::Script => CommandState::Script {
+                subcommand: None,
+                name: None,
             },
             CommandTag::Status => CommandState::Status,
+            CommandTag::Mode => CommandState::Mode { mode: None },
+            CommandTag::Events => CommandState::Events {
+                action: EventsCommand::Show,
+            },
+            CommandTag::Wait => CommandState::Wait {
+                subcommand: None,
+                tag: None,
+                event: None,
+            },
+            CommandTag::Macro => CommandState::Macro {
+                subcommand: None,
+                name: None,
+                body: None,
+                continue_on_error: false,
+            },
             CommandTag::Help => CommandState::Help { topic: None },
         }
     }
@@ -676,6 +1262,10 @@ impl<'a> CommandState<'a> {
                 *action = Some(RebootCommand::Delay(duration));
                 Ok(())
             }
+            (CommandState::Reboot { action }, ChoiceTag::RebootToBootloader, _) => {
+                *action = Some(RebootCommand::ToBootloader);
+                Ok(())
+            }
             (CommandState::Recovery { action }, ChoiceTag::RecoveryEnter, _) => {
                 *action = Some(RecoveryCommand::Enter);
                 Ok(())
@@ -685,15 +1275,82 @@ impl<'a> CommandState<'a> {
                 Ok(())
             }
             (CommandState::Recovery { action }, ChoiceTag::RecoveryNow, _) => {
-                *action = Some(RecoveryCommand::Now);
+                *action = Some(RecoveryCommand::Now { timeout: None });
+                Ok(())
+            }
+            (CommandState::Power { reset, .. }, ChoiceTag::PowerStatsReset, _)
+            | (CommandState::Bridge { reset, .. }, ChoiceTag::BridgeStatsReset, _) => {
+                *reset = true;
+                Ok(())
+            }
+            (watchdog @ CommandState::Watchdog { .. }, tag, value) => {
+                watchdog.apply_watchdog_choice(tag, value)
+            }
+            (console @ CommandState::Console { .. }, tag, value) => {
+                console.apply_console_choice(tag, value)
+            }
+            (
+                CommandState::Script { name, .. },
+                ChoiceTag::ScriptName,
+                ChoiceValue::Name(value),
+            ) => {
+                *name = Some(value);
+                Ok(())
+            }
+            (CommandState::Mode { mode }, ChoiceTag::ModeText, _) => {
+                *mode = Some(ResponseMode::Text);
+                Ok(())
+            }
+            (CommandState::Mode { mode }, ChoiceTag::ModeJson, _) => {
+                *mode = Some(ResponseMode::Json);
+                Ok(())
+            }
+            (CommandState::Events { action }, ChoiceTag::EventsOn, _) => {
+                *action = EventsCommand::On(EventFilter::ALL);
+                Ok(())
+            }
+            (CommandState::Events { action }, ChoiceTag::EventsOff, _) => {
+                *action = EventsCommand::Off(EventFilter::ALL);
+                Ok(())
+            }
+            (
+                CommandState::Events {
+                    action: EventsCommand::On(filter) | EventsCommand::Off(filter),
+                },
+                ChoiceTag::EventCategories,
+                ChoiceValue::Categories(value),
+            ) => {
+                *filter = value;
+                Ok(())
+            }
+            (CommandState::Wait { tag, .. }, ChoiceTag::WaitTag, ChoiceValue::Count(value)) => {
+                *tag = Some(value);
+                Ok(())
+            }
+            (
+                CommandState::Wait { event, .. },
+                ChoiceTag::WaitEventName,
+                ChoiceValue::EventName(name),
+            ) => {
+                *event = Some(name);
+                Ok(())
+            }
+            (CommandState::Macro { name, .. }, ChoiceTag::MacroName, ChoiceValue::Name(value)) => {
+                *name = Some(value);
+                Ok(())
+            }
+            (CommandState::Macro { body, .. }, ChoiceTag::MacroBody, ChoiceValue::Text(value)) => {
+                *body = Some(value);
                 Ok(())
             }
             (
-                CommandState::Fault { retries, .. },
-                ChoiceTag::FaultRetries,
-                ChoiceValue::Integer(value),
+                CommandState::Macro {
+                    continue_on_error, ..
+                },
+                ChoiceTag::MacroContinueOnError,
+                _,
             ) => {
-                *retries = Some(value);
+                *continue_on_error = true;
                 Ok(())
             }
             (_, unexpected_tag, _) => {
@@ -703,37 +1360,167 @@ impl<'a> CommandState<'a> {
         }
     }
 
-    fn apply_default_choice(&mut self, tag: ChoiceTag) -> Result<(), ErrMode<GrammarError>> {
-        self.apply_choice(tag, ChoiceValue::None)
-    }
-
-    fn set_subcommand(&mut self, tag: SubcommandTag) {
-        if let CommandState::Fault { subcommand, .. } = self {
-            *subcommand = Some(tag);
-        } else {
-            unreachable!("subcommands only apply to fault commands");
-        }
-    }
-
-    fn set_topic(&mut self, topic: Option<&'a str>) {
-        if let CommandState::Help { topic: slot } = self {
-            *slot = topic;
+    fn apply_watchdog_choice(
+        &mut self,
+        tag: ChoiceTag,
+        value: ChoiceValue,
+    ) -> Result<(), ErrMode<GrammarError>> {
+        match (self, tag, value) {
+            (CommandState::Watchdog { action }, ChoiceTag::WatchdogStatus, _) => {
+                *action = Some(WatchdogCommand::Status);
+                Ok(())
+            }
+            (CommandState::Watchdog { action }, ChoiceTag::WatchdogOn, _) => {
+                *action = Some(WatchdogCommand::Enable);
+                Ok(())
+            }
+            (CommandState::Watchdog { action }, ChoiceTag::WatchdogOff, _) => {
+                *action = Some(WatchdogCommand::Disable);
+                Ok(())
+            }
+            _ => Err(ErrMode::Backtrack(GrammarError::unexpected("choice", None))),
         }
     }
 
-    fn finish(self) -> Result<Command<'a>, ErrMode<GrammarError>> {
-        match self {
-            CommandState::Reboot {
-                action: Some(command),
+    fn apply_console_choice(
+        &mut self,
+        tag: ChoiceTag,
+        value: ChoiceValue,
+    ) -> Result<(), ErrMode<GrammarError>> {
+        match (self, tag, value) {
+            (
+                CommandState::Console { count, .. },
+                ChoiceTag::ConsoleLines | ChoiceTag::ConsoleRun | ChoiceTag::ConsolePattern,
+                ChoiceValue::Count(value),
+            ) => {
+                *count = Some(value);
+                Ok(())
+            }
+            (CommandState::Console { key, .. }, ChoiceTag::ConsoleKey, ChoiceValue::Key(value)) => {
+                *key = Some(value);
+                Ok(())
+            }
+            (
+                CommandState::Console { text, .. },
+                ChoiceTag::ConsoleText,
+                ChoiceValue::Text(value),
+            ) => {
+                *text = Some(value);
+                Ok(())
+            }
+            (CommandState::Console { newline, .. }, ChoiceTag::ConsoleNewline, _) => {
+                *newline = true;
+                Ok(())
+            }
+            (
+                CommandState::Console { baud, .. },
+                ChoiceTag::ConsoleBaud,
+                ChoiceValue::Baud(value),
+            ) => {
+                *baud = Some(value);
+                Ok(())
+            }
+            (
+                CommandState::Console { marker, .. },
+                ChoiceTag::ConsoleMarker,
+                ChoiceValue::Marker(value),
+            ) => {
+                *marker = Some(value);
+                Ok(())
+            }
+            _ => Err(ErrMode::Backtrack(GrammarError::unexpected("choice", None))),
+        }
+    }
+
+    fn apply_default_choice(&mut self, tag: ChoiceTag) -> Result<(), ErrMode<GrammarError>> {
+        self.apply_choice(tag, ChoiceValue::None)
+    }
+
+    fn set_subcommand(&mut self, tag: SubcommandTag) {
+        match self {
+            CommandState::Fault { subcommand, .. }
+            | CommandState::Power { subcommand, .. }
+            | CommandState::Console { subcommand, .. }
+            | CommandState::Bridge { subcommand, .. }
+            | CommandState::Script { subcommand, .. }
+            | CommandState::Wait { subcommand, .. }
+            | CommandState::Macro { subcommand, .. } => {
+                *subcommand = Some(tag);
+            }
+            _ => unreachable!(
+                "subcommands only apply to fault, power, console, bridge, script, wait, and macro commands"
+            ),
+        }
+    }
+
+    fn set_topic(&mut self, topic: Option<&'a str>) {
+        if let CommandState::Help { topic: slot } = self {
+            *slot = topic;
+        }
+    }
+
+    fn finish(self, options: &OptionValues) -> Result<Command<'a>, ErrMode<GrammarError>> {
+        match self {
+            CommandState::Reboot {
+                action: Some(command),
             } => Ok(Command::Reboot(command)),
+            CommandState::Recovery {
+                action: Some(RecoveryCommand::Now { .. }),
+            } => Ok(Command::Recovery(RecoveryCommand::Now {
+                timeout: options.duration(ChoiceTag::RecoveryTimeout),
+            })),
             CommandState::Recovery {
                 action: Some(command),
             } => Ok(Command::Recovery(command)),
             CommandState::Fault {
                 subcommand: Some(SubcommandTag::FaultRecover),
-                retries,
-            } => Ok(Command::Fault(FaultCommand { retries })),
+            } => Ok(Command::Fault(FaultCommand {
+                retries: options.integer(ChoiceTag::FaultRetries),
+            })),
+            CommandState::Power {
+                subcommand: Some(SubcommandTag::PowerStats),
+                reset,
+            } => Ok(Command::Power(PowerCommand::Stats { reset })),
+            CommandState::Power {
+                subcommand: Some(SubcommandTag::PowerHistory),
+                ..
+            } => Ok(Command::Power(PowerCommand::History)),
+            CommandState::Watchdog {
+                action: Some(command),
+            } => Ok(Command::Watchdog(command)),
+            CommandState::Console {
+                subcommand,
+                count,
+                key,
+                text,
+                newline,
+                baud,
+                marker,
+            } => finish_console(subcommand, count, key, text, newline, baud, marker),
+            CommandState::Bridge {
+                subcommand: Some(SubcommandTag::BridgeStats),
+                reset,
+            } => Ok(Command::Bridge(BridgeCommand::Stats { reset })),
+            CommandState::Script { subcommand, name } => finish_script(subcommand, name),
             CommandState::Status => Ok(Command::Status),
+            CommandState::Mode { mode } => Ok(Command::Mode(mode)),
+            CommandState::Events { action } => Ok(Command::Events(action)),
+            CommandState::Wait {
+                subcommand,
+                tag,
+                event,
+            } => finish_wait(
+                subcommand,
+                tag,
+                event,
+                options.duration(ChoiceTag::WaitTimeout),
+            ),
+            CommandState::Macro {
+                subcommand,
+                name,
+                body,
+                continue_on_error,
+            } => finish_macro(subcommand, name, body, continue_on_error),
             CommandState::Help { topic } => Ok(Command::Help(HelpCommand { topic })),
             CommandState::Reboot { action: None } => Err(ErrMode::Backtrack(
                 GrammarError::unexpected("reboot argument", None),
@@ -741,16 +1528,183 @@ impl<'a> CommandState<'a> {
             CommandState::Recovery { action: None } => Err(ErrMode::Backtrack(
                 GrammarError::unexpected("recovery argument", None),
             )),
-            CommandState::Fault {
-                subcommand: None, ..
-            } => Err(ErrMode::Backtrack(GrammarError::unexpected(
+            CommandState::Watchdog { action: None } => watchdog_option(options)
+                .map(Command::Watchdog)
+                .ok_or_else(|| {
+                    ErrMode::Backtrack(GrammarError::unexpected("watchdog argument", None))
+                }),
+            CommandState::Fault { .. } => Err(ErrMode::Backtrack(GrammarError::unexpected(
                 "fault subcommand",
                 None,
             ))),
+            CommandState::Power { .. } => Err(ErrMode::Backtrack(GrammarError::unexpected(
+                "power subcommand",
+                None,
+            ))),
+            CommandState::Bridge { .. } => Err(ErrMode::Backtrack(GrammarError::unexpected(
+                "bridge subcommand",
+                None,
+            ))),
         }
     }
 }
 
+/// Builds the `watchdog` command from whichever `key=value` option was given.
+fn watchdog_option(options: &OptionValues) -> Option<WatchdogCommand> {
+    options
+        .duration(ChoiceTag::WatchdogBoot)
+        .map(WatchdogCommand::BootDeadline)
+        .or_else(|| {
+            options
+                .duration(ChoiceTag::WatchdogSilence)
+                .map(WatchdogCommand::SilenceLimit)
+        })
+        .or_else(|| {
+            options
+                .duration(ChoiceTag::WatchdogPanic)
+                .map(WatchdogCommand::PanicGrace)
+        })
+        .or_else(|| {
+            options
+                .integer(ChoiceTag::WatchdogLimit)
+                .map(WatchdogCommand::RecoveryLimit)
+        })
+}
+
+fn finish_console<'a>(
+    subcommand: Option<SubcommandTag>,
+    count: Option<u32>,
+    key: Option<SysRqKey>,
+    text: Option<ConsoleText>,
+    newline: bool,
+    baud: Option<BaudSetting>,
+    marker: Option<ConsoleMarker>,
+) -> Result<Command<'a>, ErrMode<GrammarError>> {
+    let command = match (subcommand, count, key, text, baud, marker) {
+        (Some(SubcommandTag::ConsoleTail), lines, ..) => ConsoleCommand::Tail { lines },
+        (Some(SubcommandTag::ConsoleSince), Some(run), ..) => ConsoleCommand::Since { run },
+        (Some(SubcommandTag::ConsoleSysRq), _, Some(key), ..) => ConsoleCommand::SysRq { key },
+        (Some(SubcommandTag::ConsoleSend), _, _, Some(text), ..) => {
+            ConsoleCommand::Send { text, newline }
+        }
+        (Some(SubcommandTag::ConsoleAttach), ..) => ConsoleCommand::Attach,
+        (Some(SubcommandTag::ConsoleBaud), _, _, _, Some(setting), _) => {
+            ConsoleCommand::Baud { setting }
+        }
+        (Some(SubcommandTag::ConsolePatternAdd), _, _, Some(text), _, Some(marker)) => {
+            ConsoleCommand::Pattern(PatternCommand::Add { marker, text })
+        }
+        (Some(SubcommandTag::ConsolePatternRemove), Some(id), ..) => {
+            let Ok(id) = PatternId::try_from(id) else {
+                return Err(ErrMode::Backtrack(GrammarError::unexpected(
+                    "pattern id",
+                    None,
+                )));
+            };
+            ConsoleCommand::Pattern(PatternCommand::Remove { id })
+        }
+        (Some(SubcommandTag::ConsolePatternList), ..) => {
+            ConsoleCommand::Pattern(PatternCommand::List)
+        }
+        (subcommand, ..) => {
+            let expected = match subcommand {
+                Some(SubcommandTag::ConsoleSince) => "run id",
+                Some(SubcommandTag::ConsoleSysRq) => "sysrq key",
+                Some(SubcommandTag::ConsoleSend) => "quoted text",
+                Some(SubcommandTag::ConsoleBaud) => "baud rate or auto",
+                Some(SubcommandTag::ConsolePatternAdd) => "console marker",
+                Some(SubcommandTag::ConsolePatternRemove) => "pattern id",
+                Some(SubcommandTag::ConsolePattern) => "pattern subcommand",
+                _ => "console subcommand",
+            };
+            return Err(ErrMode::Backtrack(GrammarError::unexpected(expected, None)));
+        }
+    };
+    Ok(Command::Console(command))
+}
+
+fn finish_wait<'a>(
+    subcommand: Option<SubcommandTag>,
+    tag: Option<u32>,
+    event: Option<&'static str>,
+    timeout: Option<Duration>,
+) -> Result<Command<'a>, ErrMode<GrammarError>> {
+    let condition = match (subcommand, event) {
+        (Some(SubcommandTag::WaitComplete), _) => WaitCondition::Complete(tag.map(RequestTag::new)),
+        (Some(SubcommandTag::WaitIdle), _) => WaitCondition::Idle,
+        (Some(SubcommandTag::WaitEvent), Some(name)) => WaitCondition::Event(name),
+        (subcommand, _) => {
+            let expected = match subcommand {
+                Some(SubcommandTag::WaitEvent) => "event name",
+                _ => "wait subcommand",
+            };
+            return Err(ErrMode::Backtrack(GrammarError::unexpected(expected, None)));
+        }
+    };
+    Ok(Command::Wait(WaitCommand { condition, timeout }))
+}
+
+fn finish_macro<'a>(
+    subcommand: Option<SubcommandTag>,
+    name: Option<ScriptName>,
+    body: Option<ConsoleText>,
+    continue_on_error: bool,
+) -> Result<Command<'a>, ErrMode<GrammarError>> {
+    let command = match (subcommand, name, body) {
+        (Some(SubcommandTag::MacroDefine), Some(name), Some(body)) => MacroCommand::Define {
+            name,
+            body,
+            continue_on_error,
+        },
+        (Some(SubcommandTag::MacroRun), Some(name), _) => MacroCommand::Run { name },
+        (Some(SubcommandTag::MacroList), _, _) => MacroCommand::List,
+        (Some(SubcommandTag::MacroDefine), Some(_), None) => {
+            return Err(ErrMode::Backtrack(GrammarError::unexpected(
+                "quoted commands",
+                None,
+            )));
+        }
+        (Some(SubcommandTag::MacroDefine | SubcommandTag::MacroRun), None, _) => {
+            return Err(ErrMode::Backtrack(GrammarError::unexpected(
+                "macro name",
+                None,
+            )));
+        }
+        _ => {
+            return Err(ErrMode::Backtrack(GrammarError::unexpected(
+                "macro subcommand",
+                None,
+            )));
+        }
+    };
+    Ok(Command::Macro(command))
+}
+
+fn finish_script<'a>(
+    subcommand: Option<SubcommandTag>,
+    name: Option<ScriptName>,
+) -> Result<Command<'a>, ErrMode<GrammarError>> {
+    let command = match (subcommand, name) {
+        (Some(SubcommandTag::ScriptLoad), Some(name)) => ScriptCommand::Load { name },
+        (Some(SubcommandTag::ScriptRun), Some(name)) => ScriptCommand::Run { name },
+        (Some(SubcommandTag::ScriptList), _) => ScriptCommand::List,
+        (Some(SubcommandTag::ScriptStop), _) => ScriptCommand::Stop,
+        (Some(SubcommandTag::ScriptLoad | SubcommandTag::ScriptRun), None) => {
+            return Err(ErrMode::Backtrack(GrammarError::unexpected(
+                "script name",
+                None,
+            )));
+        }
+        _ => {
+            return Err(ErrMode::Backtrack(GrammarError::unexpected(
+                "script subcommand",
+                None,
+            )));
+        }
+    };
+    Ok(Command::Script(command))
+}
+
 fn expect_kind<'src, 'slice>(
     kind: TokenKind,
     label: &'static str,
@@ -771,28 +1725,139 @@ where
     }
 }
 
-fn parse_integer(token: &Token<'_>) -> Result<u8, GrammarError> {
+/// Validates the value of the `name=` option against its declared kind.
+fn parse_option(
+    name: &'static str,
+    kind: OptionKind,
+    token: &Token<'_>,
+) -> Result<ChoiceValue, GrammarError> {
+    let invalid = || GrammarError::invalid_option(name, kind, token);
+    match (kind, token.kind) {
+        (OptionKind::Integer { min, max }, TokenKind::Integer) => token
+            .lexeme
+            .parse::<u32>()
+            .ok()
+            .filter(|value| (min..=max).contains(value))
+            .map(ChoiceValue::Integer)
+            .ok_or_else(invalid),
+        (OptionKind::Duration { min, max }, TokenKind::Duration) => {
+            let duration = parse_duration(token)?;
+            if (min..=max).contains(&duration) {
+                Ok(ChoiceValue::Duration(duration))
+            } else {
+                Err(invalid())
+            }
+        }
+        (OptionKind::Enum(words), TokenKind::Ident) => words
+            .iter()
+            .find(|word| word.eq_ignore_ascii_case(token.lexeme))
+            .copied()
+            .map(ChoiceValue::Word)
+            .ok_or_else(invalid),
+        (OptionKind::Text, TokenKind::QuotedString) => parse_text(token).map(ChoiceValue::Text),
+        _ => Err(invalid()),
+    }
+}
+
+fn parse_count(token: &Token<'_>) -> Result<u32, GrammarError> {
     token
         .lexeme
-        .parse::<u8>()
+        .parse::<u32>()
         .map_err(|_| GrammarError::invalid_integer(token))
 }
 
-fn parse_duration(token: &Token<'_>) -> Result<Duration, GrammarError> {
-    let text = token.lexeme;
-    if let Some(rest) = text.strip_suffix("ms") {
-        let millis = rest
-            .parse::<u32>()
-            .map_err(|_| GrammarError::invalid_duration(token))?;
-        Ok(Duration::from_millis(millis.into()))
-    } else if let Some(rest) = text.strip_suffix('s') {
-        let seconds = rest
-            .parse::<u32>()
-            .map_err(|_| GrammarError::invalid_duration(token))?;
-        Ok(Duration::from_secs(seconds.into()))
-    } else {
-        Err(GrammarError::invalid_duration(token))
+fn parse_tag(token: &Token<'_>) -> Result<RequestTag, GrammarError> {
+    token
+        .lexeme
+        .strip_prefix('@')
+        .and_then(|digits| digits.parse::<u32>().ok())
+        .map(RequestTag::new)
+        .ok_or_else(|| GrammarError::invalid_integer(token))
+}
+
+fn parse_text(token: &Token<'_>) -> Result<ConsoleText, GrammarError> {
+    let raw = token
+        .lexeme
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or_else(|| GrammarError::invalid_token(token))?;
+    inject::unescape(raw).map_err(|error| GrammarError::invalid_text(token, error))
+}
+
+/// Parses a duration made of one or more `<number><unit>` parts, largest
+/// unit first (`1h30m`, `1.5s`). Only the last part may have a fraction.
+pub(crate) fn parse_duration(token: &Token<'_>) -> Result<Duration, GrammarError> {
+    let invalid = || GrammarError::invalid_duration(token);
+    let mut rest = token.lexeme;
+    let mut total = 0_u64;
+    let mut previous_unit = u64::MAX;
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    while !rest.is_empty() {
+        let (whole, tail) = split_digits(rest);
+        let (fraction, tail) = match tail.strip_prefix('.') {
+            Some(tail) => {
+                let (digits, tail) = split_digits(tail);
+                if digits.is_empty() {
+                    return Err(invalid());
+                }
+                (Some(digits), tail)
+            }
+            None => (None, tail),
+        };
+        let unit_len = tail
+            .find(|ch: char| ch.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        let unit_nanos = DURATION_UNITS
+            .iter()
+            .find(|(name, _)| *name == unit)
+            .map(|(_, nanos)| *nanos)
+            .ok_or_else(invalid)?;
+
+        if whole.is_empty()
+            || unit_nanos >= previous_unit
+            || (fraction.is_some() && !tail.is_empty())
+        {
+            return Err(invalid());
+        }
+        total = part_nanos(whole, fraction.unwrap_or_default(), unit_nanos)
+            .and_then(|nanos| total.checked_add(nanos))
+            .ok_or_else(invalid)?;
+        previous_unit = unit_nanos;
+        rest = tail;
+    }
+
+    Ok(Duration::from_nanos(total))
+}
+
+fn split_digits(text: &str) -> (&str, &str) {
+    let end = text
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(text.len());
+    text.split_at(end)
+}
+
+/// Nanoseconds in `whole.fraction` units of `unit_nanos`, or `None` on
+/// overflow or a fraction finer than a nanosecond.
+fn part_nanos(whole: &str, fraction: &str, unit_nanos: u64) -> Option<u64> {
+    let mut nanos = whole
+        .bytes()
+        .try_fold(0_u64, |value, digit| {
+            value.checked_mul(10)?.checked_add(u64::from(digit - b'0'))
+        })?
+        .checked_mul(unit_nanos)?;
+    let mut scale = unit_nanos;
+    for digit in fraction.bytes() {
+        if scale % 10 != 0 {
+            return None;
+        }
+        scale /= 10;
+        nanos = nanos.checked_add(u64::from(digit - b'0') * scale)?;
     }
+    Some(nanos)
 }
 
 #[cfg(test)]
@@ -806,6 +1871,10 @@ mod tests {
     #[test]
     fn parses_reboot_now() {
         assert_eq!(parse_ok("reboot now"), Command::Reboot(RebootCommand::Now));
+        assert_eq!(
+            parse_ok("reboot to-bootloader"),
+            Command::Reboot(RebootCommand::ToBootloader)
+        );
     }
 
     #[test]
@@ -828,6 +1897,50 @@ mod tests {
         }
     }
 
+    fn duration(text: &str) -> Result<Duration, GrammarError> {
+        let tokens = lex(text).expect("duration lexes");
+        assert_eq!(tokens.len(), 1, "{text} is one token");
+        assert_eq!(tokens[0].kind, TokenKind::Duration);
+        parse_duration(&tokens[0])
+    }
+
+    #[test]
+    fn parses_compound_and_fractional_durations() {
+        assert_eq!(duration("250us"), Ok(Duration::from_micros(250)));
+        assert_eq!(duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(duration("2h"), Ok(Duration::from_secs(7200)));
+        assert_eq!(duration("1m30s"), Ok(Duration::from_secs(90)));
+        assert_eq!(duration("1h2m3s4ms5us"), Ok(Duration::new(3723, 4_005_000)));
+        assert_eq!(duration("1.25us"), Ok(Duration::from_nanos(1250)));
+        assert_eq!(
+            parse_ok("reboot delay 1m30s"),
+            Command::Reboot(RebootCommand::Delay(Duration::from_secs(90)))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_and_overflowing_durations() {
+        for text in [
+            "30s1m",
+            "1s1s",
+            "1.5m30s",
+            "1.0000001ms",
+            "99999999h",
+            "18446744073709551616us",
+        ] {
+            assert!(
+                matches!(
+                    duration(text),
+                    Err(GrammarError {
+                        kind: GrammarErrorKind::InvalidDuration { .. }
+                    })
+                ),
+                "{text} should be rejected"
+            );
+        }
+    }
+
     #[test]
     fn parses_recovery_variants() {
         assert_eq!(
@@ -840,8 +1953,15 @@ mod tests {
         );
         assert_eq!(
             parse_ok("recovery now"),
-            Command::Recovery(RecoveryCommand::Now)
+            Command::Recovery(RecoveryCommand::Now { timeout: None })
         );
+        assert_eq!(
+            parse_ok("recovery now timeout=30s"),
+            Command::Recovery(RecoveryCommand::Now {
+                timeout: Some(Duration::from_secs(30))
+            })
+        );
+        assert!(parse("recovery now timeout=30").is_err());
     }
 
     #[test]
@@ -857,11 +1977,462 @@ mod tests {
         }
     }
 
+    #[test]
+    fn rejects_options_outside_their_declared_bounds() {
+        let message = |line: &str| match parse(line) {
+            Err(error) => std::format!("{error}"),
+            Ok(command) => panic!("unexpected command: {command:?}"),
+        };
+        assert_eq!(
+            message("fault recover retries=5"),
+            "retries must be 1-3 at 22..23"
+        );
+        assert_eq!(
+            message("fault recover retries=0"),
+            "retries must be 1-3 at 22..23"
+        );
+        assert_eq!(message("watchdog limit=11"), "limit must be 0-10 at 15..17");
+        assert_eq!(message("watchdog boot=0s"), "boot must be 1s-1h at 14..16");
+        assert_eq!(
+            message("watchdog silence=25h"),
+            "silence must be 0s-24h at 17..20"
+        );
+        assert_eq!(
+            message("recovery now timeout=3601s"),
+            "timeout must be 0s-1h at 21..26"
+        );
+        assert_eq!(
+            message("recovery now timeout=30"),
+            "timeout must be 0s-1h at 21..23"
+        );
+        assert_eq!(
+            parse_ok("watchdog limit=0"),
+            Command::Watchdog(WatchdogCommand::RecoveryLimit(0))
+        );
+    }
+
+    #[test]
+    fn validates_enum_and_text_options() {
+        let option = |kind, line: &str| {
+            let tokens = lex(line).expect("value lexes");
+            parse_option("level", kind, &tokens[0])
+        };
+        let levels = OptionKind::Enum(&["low", "high"]);
+        assert_eq!(option(levels, "HIGH"), Ok(ChoiceValue::Word("high")));
+        assert_eq!(
+            option(levels, "medium").map_err(|error| std::format!("{error}")),
+            Err("level must be one of low, high at 0..6".into())
+        );
+        assert!(matches!(
+            option(OptionKind::Text, r#""a\"b""#),
+            Ok(ChoiceValue::Text(text)) if text.as_slice() == br#"a"b"#
+        ));
+        assert!(option(OptionKind::Text, "bare").is_err());
+    }
+
+    #[test]
+    fn catalog_option_suggestions_are_valid_values() {
+        fn check(node: &'static Node) {
+            match node {
+                Node::End => {}
+                Node::OptionalChoice { choices, default } => {
+                    for choice in *choices {
+                        if let ValueSpec::Assignment(option) = choice.value {
+                            for suggestion in option.suggestions {
+                                let value = suggestion
+                                    .strip_prefix(choice.keyword)
+                                    .and_then(|rest| rest.strip_prefix('='))
+                                    .expect("suggestion spells the option");
+                                let tokens = lex(value).expect("suggestion lexes");
+                                assert!(
+                                    parse_option(choice.keyword, option.kind, &tokens[0]).is_ok(),
+                                    "{suggestion} is outside {}",
+                                    option.kind
+                                );
+                            }
+                        }
+                        check(choice.next);
+                    }
+                    if let Some(default) = default {
+                        check(default.next);
+                    }
+                }
+                Node::Subcommands(branches) => {
+                    for branch in *branches {
+                        check(branch.grammar);
+                    }
+                }
+                Node::Argument { next, .. } | Node::Topic { next, .. } => check(next),
+            }
+        }
+
+        for spec in catalog::commands() {
+            check(spec.grammar);
+        }
+    }
+
+    #[test]
+    fn parses_power_subcommands() {
+        assert_eq!(
+            parse_ok("power stats"),
+            Command::Power(PowerCommand::Stats { reset: false })
+        );
+        assert_eq!(
+            parse_ok("power stats reset"),
+            Command::Power(PowerCommand::Stats { reset: true })
+        );
+        assert_eq!(
+            parse_ok("power history"),
+            Command::Power(PowerCommand::History)
+        );
+    }
+
+    #[test]
+    fn parses_bridge_stats() {
+        assert_eq!(
+            parse_ok("bridge stats"),
+            Command::Bridge(BridgeCommand::Stats { reset: false })
+        );
+        assert_eq!(
+            parse_ok("bridge stats reset"),
+            Command::Bridge(BridgeCommand::Stats { reset: true })
+        );
+        assert!(parse("bridge").is_err());
+    }
+
+    #[test]
+    fn parses_watchdog_variants() {
+        assert_eq!(
+            parse_ok("watchdog"),
+            Command::Watchdog(WatchdogCommand::Status)
+        );
+        assert_eq!(
+            parse_ok("watchdog on"),
+            Command::Watchdog(WatchdogCommand::Enable)
+        );
+        assert_eq!(
+            parse_ok("watchdog off"),
+            Command::Watchdog(WatchdogCommand::Disable)
+        );
+        assert_eq!(
+            parse_ok("watchdog boot=240s"),
+            Command::Watchdog(WatchdogCommand::BootDeadline(Duration::from_secs(240)))
+        );
+        assert_eq!(
+            parse_ok("watchdog silence=0s"),
+            Command::Watchdog(WatchdogCommand::SilenceLimit(Duration::ZERO))
+        );
+        assert_eq!(
+            parse_ok("watchdog panic=30s"),
+            Command::Watchdog(WatchdogCommand::PanicGrace(Duration::from_secs(30)))
+        );
+        assert_eq!(
+            parse_ok("watchdog limit=5"),
+            Command::Watchdog(WatchdogCommand::RecoveryLimit(5))
+        );
+    }
+
+    #[test]
+    fn parses_console_scrollback_requests() {
+        assert_eq!(
+            parse_ok("console tail"),
+            Command::Console(ConsoleCommand::Tail { lines: None })
+        );
+        assert_eq!(
+            parse_ok("console tail 50"),
+            Command::Console(ConsoleCommand::Tail { lines: Some(50) })
+        );
+        assert_eq!(
+            parse_ok("console since 3"),
+            Command::Console(ConsoleCommand::Since { run: 3 })
+        );
+        assert!(parse("console since").is_err());
+        let Some(key) = SysRqKey::new(b's') else {
+            panic!("s is a valid sysrq key");
+        };
+        assert_eq!(
+            parse_ok("console sysrq S"),
+            Command::Console(ConsoleCommand::SysRq { key })
+        );
+        assert!(parse("console sysrq").is_err());
+        assert!(parse("console sysrq sub").is_err());
+        assert!(parse("console tail 5s").is_err());
+        assert_eq!(
+            parse_ok("console attach"),
+            Command::Console(ConsoleCommand::Attach)
+        );
+        assert!(parse("console attach now").is_err());
+
+        assert_eq!(
+            parse_ok("console baud auto"),
+            Command::Console(ConsoleCommand::Baud {
+                setting: BaudSetting::Auto
+            })
+        );
+        assert_eq!(
+            parse_ok("console baud 921600"),
+            Command::Console(ConsoleCommand::Baud {
+                setting: BaudSetting::Fixed(921_600)
+            })
+        );
+        assert!(parse("console baud").is_err());
+        assert!(parse("console baud fast").is_err());
+        assert!(parse("console baud 5000000").is_err());
+    }
+
+    #[test]
+    fn parses_console_send_with_escapes() {
+        let Ok(text) = inject::unescape("root") else {
+            panic!("plain text should decode");
+        };
+        assert_eq!(
+            parse_ok(r#"console send "root" newline"#),
+            Command::Console(ConsoleCommand::Send {
+                text,
+                newline: true,
+            })
+        );
+
+        let Command::Console(ConsoleCommand::Send { text, newline }) =
+            parse_ok(r#"console send "say \"hi\"\x03""#)
+        else {
+            panic!("expected console send");
+        };
+        assert_eq!(text.as_slice(), b"say \"hi\"\x03");
+        assert!(!newline);
+
+        match parse(r#"console send "bad\q""#) {
+            Err(ParseError::Grammar(err)) => assert!(matches!(
+                err.kind,
+                GrammarErrorKind::InvalidText {
+                    error: TextError::Escape { offset: 3 },
+                    ..
+                }
+            )),
+            other => panic!("expected escape error, got {other:?}"),
+        }
+        assert!(parse("console send root").is_err());
+        assert!(parse(r#"console send "unterminated"#).is_err());
+        assert!(parse("console send").is_err());
+    }
+
+    #[test]
+    fn parses_console_pattern_commands() {
+        let Ok(text) = inject::unescape("Welcome to") else {
+            panic!("plain text should decode");
+        };
+        assert_eq!(
+            parse_ok(r#"console pattern add Login-Prompt "Welcome to""#),
+            Command::Console(ConsoleCommand::Pattern(PatternCommand::Add {
+                marker: ConsoleMarker::LoginPrompt,
+                text,
+            }))
+        );
+        assert_eq!(
+            parse_ok("console pattern remove 7"),
+            Command::Console(ConsoleCommand::Pattern(PatternCommand::Remove { id: 7 }))
+        );
+        assert_eq!(
+            parse_ok("console pattern list"),
+            Command::Console(ConsoleCommand::Pattern(PatternCommand::List))
+        );
+
+        match parse(r#"console pattern add login "x""#) {
+            Err(ParseError::Grammar(err)) => assert!(
+                std::format!("{err}").starts_with("unknown marker 'login'"),
+                "{err}"
+            ),
+            other => panic!("expected unknown marker, got {other:?}"),
+        }
+        assert!(parse("console pattern").is_err());
+        assert!(parse("console pattern add custom").is_err());
+        assert!(parse(r#"console pattern add "x""#).is_err());
+        assert!(parse("console pattern remove").is_err());
+        assert!(parse("console pattern remove 300").is_err());
+        assert!(parse("console pattern list 3").is_err());
+    }
+
+    #[test]
+    fn parses_script_commands() {
+        let Some(name) = script_name("login") else {
+            panic!("name fits");
+        };
+        assert_eq!(
+            parse_ok("script load login"),
+            Command::Script(ScriptCommand::Load { name: name.clone() })
+        );
+        assert_eq!(
+            parse_ok("SCRIPT run login"),
+            Command::Script(ScriptCommand::Run { name })
+        );
+        assert_eq!(
+            parse_ok("script list"),
+            Command::Script(ScriptCommand::List)
+        );
+        assert_eq!(
+            parse_ok("script stop"),
+            Command::Script(ScriptCommand::Stop)
+        );
+        assert!(parse("script load").is_err());
+        assert!(parse("script run a-name-well-past-sixteen").is_err());
+        assert!(parse("script").is_err());
+    }
+
+    #[test]
+    fn parses_macro_commands() {
+        let Some(name) = script_name("boot") else {
+            panic!("name fits");
+        };
+        let Command::Macro(MacroCommand::Define {
+            name: defined,
+            body,
+            continue_on_error,
+        }) = parse_ok(r#"macro define boot "recovery enter; wait complete; status""#)
+        else {
+            panic!("expected macro define");
+        };
+        assert_eq!(defined, name);
+        assert_eq!(body.as_slice(), b"recovery enter; wait complete; status");
+        assert!(!continue_on_error);
+
+        let Command::Macro(MacroCommand::Define {
+            continue_on_error, ..
+        }) = parse_ok(r#"macro define boot "status" continue-on-error"#)
+        else {
+            panic!("expected macro define");
+        };
+        assert!(continue_on_error);
+
+        assert_eq!(
+            parse_ok("MACRO run boot"),
+            Command::Macro(MacroCommand::Run { name })
+        );
+        assert_eq!(parse_ok("macro list"), Command::Macro(MacroCommand::List));
+        assert!(parse("macro define boot").is_err());
+        assert!(parse("macro define boot status").is_err());
+        assert!(parse("macro run").is_err());
+        assert!(parse("macro").is_err());
+    }
+
+    #[test]
+    fn rejects_chained_commands_in_one_parse() {
+        assert!(parse("status; status").is_err());
+    }
+
     #[test]
     fn parses_status() {
         assert_eq!(parse_ok("status"), Command::Status);
     }
 
+    #[test]
+    fn parses_mode() {
+        assert_eq!(parse_ok("mode"), Command::Mode(None));
+        assert_eq!(
+            parse_ok("MODE json"),
+            Command::Mode(Some(ResponseMode::Json))
+        );
+        assert_eq!(
+            parse_ok("mode text"),
+            Command::Mode(Some(ResponseMode::Text))
+        );
+        assert!(parse("mode yaml").is_err());
+    }
+
+    #[test]
+    fn parses_events() {
+        use crate::telemetry::{EventCategory, EventFilter};
+
+        assert_eq!(parse_ok("events"), Command::Events(EventsCommand::Show));
+        assert_eq!(
+            parse_ok("events on"),
+            Command::Events(EventsCommand::On(EventFilter::ALL))
+        );
+        let selected = EventFilter::NONE
+            .with(EventCategory::Strap)
+            .with(EventCategory::Sequence);
+        assert_eq!(
+            parse_ok("events on strap, SEQUENCE"),
+            Command::Events(EventsCommand::On(selected))
+        );
+        assert_eq!(
+            parse_ok("events off bridge"),
+            Command::Events(EventsCommand::Off(
+                EventFilter::NONE.with(EventCategory::Bridge)
+            ))
+        );
+        assert_eq!(
+            EventsCommand::Off(selected).apply(EventFilter::ALL),
+            EventFilter::NONE
+                .with(EventCategory::Command)
+                .with(EventCategory::Bridge)
+                .with(EventCategory::Power)
+        );
+        assert!(parse("events on console").is_err());
+        assert!(parse("events on strap,").is_err());
+        assert!(parse("events maybe").is_err());
+    }
+
+    #[test]
+    fn parses_wait() {
+        assert_eq!(
+            parse_ok("wait complete"),
+            Command::Wait(WaitCommand {
+                condition: WaitCondition::Complete(None),
+                timeout: None,
+            })
+        );
+        assert_eq!(
+            parse_ok("wait complete 17 timeout=5s"),
+            Command::Wait(WaitCommand {
+                condition: WaitCondition::Complete(Some(RequestTag::new(17))),
+                timeout: Some(Duration::from_secs(5)),
+            })
+        );
+        assert_eq!(
+            parse_ok("wait complete timeout=500ms"),
+            Command::Wait(WaitCommand {
+                condition: WaitCondition::Complete(None),
+                timeout: Some(Duration::from_millis(500)),
+            })
+        );
+        assert_eq!(
+            parse_ok("wait idle"),
+            Command::Wait(WaitCommand {
+                condition: WaitCondition::Idle,
+                timeout: None,
+            })
+        );
+        assert_eq!(
+            parse_ok("wait event Strap-Released timeout=30s"),
+            Command::Wait(WaitCommand {
+                condition: WaitCondition::Event("strap-released"),
+                timeout: Some(Duration::from_secs(30)),
+            })
+        );
+        assert!(parse("wait").is_err());
+        assert!(parse("wait event").is_err());
+        assert!(parse("wait event reboot").is_err());
+        assert!(parse("wait complete soon").is_err());
+    }
+
+    #[test]
+    fn parses_request_tags() {
+        let request = parse_request("@17 reboot now").expect("tagged command should parse");
+        assert_eq!(request.tag, Some(RequestTag::new(17)));
+        assert_eq!(request.command, Command::Reboot(RebootCommand::Now));
+        assert_eq!(parse_request("status").expect("parses").tag, None);
+        assert_eq!(parse_ok("@3 status"), Command::Status);
+
+        assert!(parse("@17").is_err());
+        assert!(parse("reboot @17 now").is_err());
+        assert!(parse("@99999999999 status").is_err());
+        assert_eq!(
+            request_tag("@42 reboot sideways"),
+            Some(RequestTag::new(42))
+        );
+        assert_eq!(request_tag("reboot now"), None);
+    }
+
     #[test]
     fn parses_help_topic() {
         assert_eq!(
@@ -882,6 +2453,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn suggests_close_keywords_for_unknown_words() {
+        let message = |line: &str| match parse(line) {
+            Err(error) => std::format!("{error}"),
+            Ok(command) => panic!("unexpected command: {command:?}"),
+        };
+        assert_eq!(
+            message("rebot now"),
+            "unknown command 'rebot' (did you mean 'reboot'?)"
+        );
+        assert_eq!(
+            message("reboot nwo"),
+            "unknown keyword 'nwo' (did you mean 'now'?)"
+        );
+        assert_eq!(
+            message("fault recovr"),
+            "unknown subcommand 'recovr' (did you mean 'recover'?)"
+        );
+        assert_eq!(
+            message("recovery now timout=5s"),
+            "unknown option 'timout' (did you mean 'timeout'?)"
+        );
+        assert_eq!(message("frobnicate"), "unknown command 'frobnicate'");
+
+        match parse("@4 stauts") {
+            Err(ParseError::Grammar(error)) => {
+                assert_eq!(error.suggestions(), ["status"]);
+                assert!(matches!(
+                    error.kind,
+                    GrammarErrorKind::UnknownKeyword { span, .. } if span == (3..9)
+                ));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
     #[test]
     fn supports_case_insensitive_keywords() {
         assert_eq!(parse_ok("ReBoOt Now"), Command::Reboot(RebootCommand::Now));
@@ -0,0 +1,436 @@
+//! `help` pages generated from the command catalog.
+//!
+//! Summaries and option docs live next to the grammar in [`catalog`], and the
+//! usage synopses are produced by walking the same [`Node`] tree the parser
+//! uses, so help cannot drift from what the REPL accepts. [`HelpPage`] renders
+//! one line at a time into any [`fmt::Write`] sink, which lets the firmware
+//! stream pages through a fixed-size line buffer.
+
+use core::fmt::{self, Write as _};
+
+use heapless::String;
+
+use super::catalog::{
+    self, ChoiceBranch, CommandSpec, HelpTopics, Node, OptionKind, SubcommandBranch, ValueSpec,
+};
+
+/// Longest unknown topic echoed back; longer names are truncated.
+pub const HELP_TOPIC_CAPACITY: usize = 16;
+/// Buffer size that holds every line of every help page.
+pub const HELP_LINE_CAPACITY: usize = 128;
+
+/// Help output selected by `help [topic]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HelpPage {
+    /// Every command with its synopsis and summary.
+    Index,
+    /// Synopsis and option docs for one command.
+    Command(&'static CommandSpec),
+    /// The topic does not name a command.
+    Unknown(String<HELP_TOPIC_CAPACITY>),
+}
+
+impl HelpPage {
+    /// Resolves the topic given to `help` (case insensitive).
+    #[must_use]
+    pub fn for_topic(topic: Option<&str>) -> Self {
+        let Some(topic) = topic else {
+            return Self::Index;
+        };
+        if let Some(spec) = catalog::find(topic) {
+            return Self::Command(spec);
+        }
+        let mut name = String::new();
+        for ch in topic.chars() {
+            if name.push(ch).is_err() {
+                break;
+            }
+        }
+        Self::Unknown(name)
+    }
+
+    /// Returns the number of lines on the page.
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        let mut walker = Walker::new(usize::MAX);
+        self.walk(&mut walker);
+        walker.seen
+    }
+
+    /// Writes line `index` of the page without a line terminator.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if `index` is past the end of the page or the
+    /// writer runs out of space.
+    pub fn write_line<W: fmt::Write>(&self, index: usize, writer: &mut W) -> fmt::Result {
+        let mut walker = Walker::new(index);
+        self.walk(&mut walker);
+        let line = walker.found.ok_or(fmt::Error)?;
+        line.write(self, writer)
+    }
+
+    fn walk(&self, walker: &mut Walker) {
+        match self {
+            Self::Index => {
+                walker.emit(Line::Text("Available commands:"));
+                for spec in catalog::commands() {
+                    walker.emit(Line::Command { spec, indent: 2 });
+                }
+                walker.emit(Line::Text("Type `help <topic>` for a specific command."));
+            }
+            Self::Command(spec) => {
+                walker.emit(Line::Command { spec, indent: 0 });
+                walk_node(spec.grammar, 1, walker);
+            }
+            Self::Unknown(_) => {
+                walker.emit(Line::UnknownTopic);
+                walker.emit(Line::TopicList);
+            }
+        }
+    }
+}
+
+/// One rendered line, located by [`Walker`].
+#[derive(Clone, Copy)]
+enum Line {
+    Text(&'static str),
+    Command {
+        spec: &'static CommandSpec,
+        indent: usize,
+    },
+    Subcommand {
+        branch: &'static SubcommandBranch,
+        depth: usize,
+    },
+    Choice {
+        branch: &'static ChoiceBranch,
+        default: bool,
+        depth: usize,
+    },
+    Argument {
+        label: &'static str,
+        required: bool,
+        doc: &'static str,
+        depth: usize,
+    },
+    Topic {
+        depth: usize,
+    },
+    UnknownTopic,
+    TopicList,
+}
+
+impl Line {
+    fn write<W: fmt::Write>(self, page: &HelpPage, writer: &mut W) -> fmt::Result {
+        match self {
+            Line::Text(text) => writer.write_str(text),
+            Line::Command { spec, indent } => write!(
+                writer,
+                "{:indent$}{}{} - {}",
+                "",
+                spec.name,
+                Synopsis(spec.grammar),
+                spec.summary
+            ),
+            Line::Subcommand { branch, depth } => write!(
+                writer,
+                "{:width$}{}{} - {}",
+                "",
+                branch.name,
+                Synopsis(branch.grammar),
+                branch.summary,
+                width = depth * 2
+            ),
+            Line::Choice {
+                branch,
+                default,
+                depth,
+            } => {
+                write!(
+                    writer,
+                    "{:width$}{}{} - {}",
+                    "",
+                    branch.keyword,
+                    ValueHint(branch.value),
+                    branch.doc,
+                    width = depth * 2
+                )?;
+                if default {
+                    writer.write_str(" (default)")?;
+                }
+                Ok(())
+            }
+            Line::Argument {
+                label,
+                required,
+                doc,
+                depth,
+            } => {
+                write!(writer, "{:width$}", "", width = depth * 2)?;
+                if required {
+                    write!(writer, "<{label}> - {doc}")
+                } else {
+                    write!(writer, "[<{label}>] - {doc}")
+                }
+            }
+            Line::Topic { depth } => write!(
+                writer,
+                "{:width$}[<command>] - command to describe",
+                "",
+                width = depth * 2
+            ),
+            Line::UnknownTopic => {
+                let HelpPage::Unknown(topic) = page else {
+                    return Err(fmt::Error);
+                };
+                write!(writer, "No help available for `{topic}`.")
+            }
+            Line::TopicList => {
+                writer.write_str("Available topics: ")?;
+                for (index, spec) in catalog::commands().iter().enumerate() {
+                    if index > 0 {
+                        writer.write_str(", ")?;
+                    }
+                    writer.write_str(spec.name)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Counts lines while walking a page and keeps the one at `target`.
+struct Walker {
+    target: usize,
+    seen: usize,
+    found: Option<Line>,
+}
+
+impl Walker {
+    const fn new(target: usize) -> Self {
+        Self {
+            target,
+            seen: 0,
+            found: None,
+        }
+    }
+
+    fn emit(&mut self, line: Line) {
+        if self.seen == self.target {
+            self.found = Some(line);
+        }
+        self.seen += 1;
+    }
+}
+
+fn walk_node(node: &'static Node, depth: usize, walker: &mut Walker) {
+    match node {
+        Node::End => {}
+        Node::OptionalChoice { choices, default } => {
+            for branch in *choices {
+                let default = default.is_some_and(|choice| choice.tag == branch.tag);
+                walker.emit(Line::Choice {
+                    branch,
+                    default,
+                    depth,
+                });
+                walk_node(branch.next, depth + 1, walker);
+            }
+        }
+        Node::Subcommands(branches) => {
+            for branch in *branches {
+                walker.emit(Line::Subcommand { branch, depth });
+                walk_node(branch.grammar, depth + 1, walker);
+            }
+        }
+        Node::Argument {
+            label,
+            required,
+            doc,
+            next,
+            ..
+        } => {
+            walker.emit(Line::Argument {
+                label,
+                required: *required,
+                doc,
+                depth,
+            });
+            walk_node(next, depth, walker);
+        }
+        Node::Topic { topics, next } => {
+            if *topics == HelpTopics::Commands {
+                walker.emit(Line::Topic { depth });
+            }
+            walk_node(next, depth, walker);
+        }
+    }
+}
+
+/// Usage synopsis for everything after a keyword, e.g. ` [now|delay <duration>]`.
+struct Synopsis(&'static Node);
+
+impl fmt::Display for Synopsis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Node::End => Ok(()),
+            Node::OptionalChoice { choices, .. } => {
+                f.write_str(" [")?;
+                for (index, branch) in choices.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char('|')?;
+                    }
+                    write!(
+                        f,
+                        "{}{}{}",
+                        branch.keyword,
+                        ValueHint(branch.value),
+                        Synopsis(branch.next)
+                    )?;
+                }
+                f.write_char(']')
+            }
+            Node::Subcommands(branches) => {
+                f.write_str(" <")?;
+                for (index, branch) in branches.iter().enumerate() {
+                    if index > 0 {
+                        f.write_char('|')?;
+                    }
+                    f.write_str(branch.name)?;
+                }
+                f.write_char('>')
+            }
+            Node::Argument {
+                label,
+                required,
+                next,
+                ..
+            } => {
+                if *required {
+                    write!(f, " <{label}>")?;
+                } else {
+                    write!(f, " [<{label}>]")?;
+                }
+                Synopsis(next).fmt(f)
+            }
+            Node::Topic { topics, next } => {
+                if *topics == HelpTopics::Commands {
+                    f.write_str(" [<command>]")?;
+                }
+                Synopsis(next).fmt(f)
+            }
+        }
+    }
+}
+
+/// Placeholder for the value following a keyword, e.g. ` <duration>` or
+/// `=<1-3>`.
+struct ValueHint(ValueSpec);
+
+impl fmt::Display for ValueHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let option = match self.0 {
+            ValueSpec::Assignment(option) => option,
+            ValueSpec::None => return Ok(()),
+            ValueSpec::Duration => return f.write_str(" <duration>"),
+            ValueSpec::Count => return f.write_str(" <n>"),
+            ValueSpec::Key => return f.write_str(" <key>"),
+            ValueSpec::Text => return f.write_str(" \"<text>\""),
+            ValueSpec::Name => return f.write_str(" <name>"),
+            ValueSpec::Baud => return f.write_str(" auto|<rate>"),
+            ValueSpec::Categories => return f.write_str(" <categories>"),
+            ValueSpec::EventName => return f.write_str(" <event>"),
+            ValueSpec::Marker => return f.write_str(" <marker>"),
+        };
+        match option.kind {
+            OptionKind::Integer { .. } | OptionKind::Duration { .. } => {
+                write!(f, "=<{}>", option.kind)
+            }
+            OptionKind::Enum(words) => {
+                f.write_str("=<")?;
+                for (index, word) in words.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str("|")?;
+                    }
+                    f.write_str(word)?;
+                }
+                f.write_str(">")
+            }
+            OptionKind::Text => f.write_str("=\"<text>\""),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::repl::catalog::CommandTag;
+
+    fn render(page: &HelpPage) -> std::vec::Vec<std::string::String> {
+        (0..page.line_count())
+            .map(|index| {
+                let mut line = std::string::String::new();
+                page.write_line(index, &mut line).expect("line renders");
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn command_page_walks_the_grammar() {
+        let page = HelpPage::for_topic(Some("RECOVERY"));
+        assert_eq!(
+            page,
+            HelpPage::Command(catalog::command(CommandTag::Recovery))
+        );
+        assert_eq!(
+            render(&page),
+            [
+                "recovery [enter|exit|now [timeout=<0s-1h>]] - manage recovery strap flows",
+                "  enter - reset into forced recovery with REC held (default)",
+                "  exit - leave recovery with a normal reset",
+                "  now - hold REC until the console starts talking",
+                "    timeout=<0s-1h> - release REC anyway after this long (0s waits forever)",
+            ]
+        );
+
+        let lines = render(&HelpPage::for_topic(Some("console")));
+        assert_eq!(
+            lines[0],
+            "console <tail|since|sysrq|send|attach|baud|pattern> - Jetson console scrollback and control"
+        );
+        assert!(lines.contains(
+            &"    add <marker> <quoted text> - report a marker whenever the text appears".into()
+        ));
+        assert!(
+            lines.contains(&"  send <quoted text> [newline] - type text into the console".into())
+        );
+        assert!(lines.contains(&"    [<line count>] - lines to print (default 20)".into()));
+    }
+
+    #[test]
+    fn index_and_unknown_topics_list_every_command() {
+        let index = render(&HelpPage::for_topic(None));
+        assert_eq!(index.len(), catalog::commands().len() + 2);
+        assert_eq!(index[14], "  help [<command>] - show help for a command");
+
+        let page = HelpPage::for_topic(Some("rebooot-the-whole-thing"));
+        let lines = render(&page);
+        assert_eq!(lines[0], "No help available for `rebooot-the-whol`.");
+        assert!(lines[1].starts_with("Available topics: reboot, recovery, fault"));
+        let mut buffer = std::string::String::new();
+        assert!(page.write_line(2, &mut buffer).is_err());
+    }
+
+    #[test]
+    fn every_line_fits_the_line_capacity() {
+        let mut pages = std::vec![HelpPage::Index];
+        pages.extend(catalog::commands().iter().map(HelpPage::Command));
+        for page in &pages {
+            for line in render(page) {
+                assert!(line.len() <= HELP_LINE_CAPACITY, "{line}");
+            }
+        }
+    }
+}
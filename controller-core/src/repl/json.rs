@@ -0,0 +1,1451 @@
+//! Machine-readable REPL responses for `mode json`.
+//!
+//! In JSON mode every reply, error, and asynchronous event is one JSON object
+//! on its own line, tagged by a `type` member. The writers here stream
+//! straight into any [`fmt::Write`] sink without allocating, so the firmware
+//! and the emulator emit the same bytes for the same outcome. Platform-only
+//! narration, such as the emulator's sequence walkthrough, has no JSON form.
+
+use core::fmt::{self, Write as _};
+use core::time::Duration;
+
+use crate::bridge::autobaud::{AutobaudReport, BaudSetting};
+use crate::bridge::autoboot::AutobootReport;
+use crate::bridge::console::{ConsoleMarker, PatternId};
+use crate::bridge::inject::Escaped;
+use crate::bridge::line_coding::{SYSRQ_BREAK, SysRqKey};
+use crate::bridge::stats::{BridgeDirectionStats, BridgeStatsSnapshot};
+use crate::orchestrator::{
+    DEFAULT_BRIDGE_ACTIVITY_TIMEOUT, QueueErrorKind, ScheduleErrorInfo, SequenceCompletion,
+    schedule_error_info,
+};
+use crate::power::{BrownOutReport, RAIL_HISTOGRAM_BUCKETS, RailStatsSnapshot};
+use crate::script::{ScriptName, ScriptReport, ScriptSummary};
+use crate::sequences::{StrapSequenceKind, strap_by_id};
+use crate::supervisor::WatchdogStatus;
+use crate::telemetry::{EventCategory, EventFilter, TelemetryNotice, TelemetryPayload};
+
+use super::chain::ChainError;
+use super::commands::{
+    BridgeStatsReport, CommandError, CommandOutcome, FaultAck, PowerReport, RebootAck, RecoveryAck,
+};
+use super::grammar::{ParseError, RecoveryCommand};
+use super::macros::Macro;
+use super::status::{DebugLinkState, StatusSnapshot};
+use super::wait::{WaitOutcome, WaitReport, WaitTimeout};
+
+/// Buffer size that holds every object produced by this module.
+pub const JSON_LINE_CAPACITY: usize = 512;
+
+/// Response format selected with `mode`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResponseMode {
+    /// `OK ...` / `ERR ...` lines meant for people.
+    #[default]
+    Text,
+    /// One JSON object per line.
+    Json,
+}
+
+impl ResponseMode {
+    /// Returns `true` in JSON mode.
+    #[must_use]
+    pub const fn is_json(self) -> bool {
+        matches!(self, ResponseMode::Json)
+    }
+}
+
+impl fmt::Display for ResponseMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ResponseMode::Text => "text",
+            ResponseMode::Json => "json",
+        })
+    }
+}
+
+/// Writes one JSON object a member at a time.
+///
+/// Members appear in call order; [`Self::finish`] closes the object.
+pub struct JsonObject<'w, W: fmt::Write> {
+    writer: &'w mut W,
+    empty: bool,
+}
+
+impl<'w, W: fmt::Write> JsonObject<'w, W> {
+    /// Opens an object whose first member is `"type":kind`.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn new(writer: &'w mut W, kind: &str) -> Result<Self, fmt::Error> {
+        let mut object = Self::open(writer)?;
+        object.str("type", kind)?;
+        Ok(object)
+    }
+
+    fn open(writer: &'w mut W) -> Result<Self, fmt::Error> {
+        writer.write_char('{')?;
+        Ok(Self {
+            writer,
+            empty: true,
+        })
+    }
+
+    fn key(&mut self, key: &str) -> fmt::Result {
+        if !self.empty {
+            self.writer.write_char(',')?;
+        }
+        self.empty = false;
+        write_string(self.writer, key)?;
+        self.writer.write_char(':')
+    }
+
+    /// Adds a string member.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn str(&mut self, key: &str, value: &str) -> Result<&mut Self, fmt::Error> {
+        self.display(key, value)
+    }
+
+    /// Adds a string member holding the [`fmt::Display`] form of `value`.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn display(
+        &mut self,
+        key: &str,
+        value: impl fmt::Display,
+    ) -> Result<&mut Self, fmt::Error> {
+        self.key(key)?;
+        self.writer.write_char('"')?;
+        write!(Escaper(&mut *self.writer), "{value}")?;
+        self.writer.write_char('"')?;
+        Ok(self)
+    }
+
+    /// Adds a string member, or `null` when `value` is `None`.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn opt_display(
+        &mut self,
+        key: &str,
+        value: Option<impl fmt::Display>,
+    ) -> Result<&mut Self, fmt::Error> {
+        match value {
+            Some(value) => self.display(key, value),
+            None => self.null(key),
+        }
+    }
+
+    /// Adds an unsigned integer member.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn uint(&mut self, key: &str, value: impl Into<u64>) -> Result<&mut Self, fmt::Error> {
+        self.key(key)?;
+        write!(self.writer, "{}", value.into())?;
+        Ok(self)
+    }
+
+    /// Adds an unsigned integer member, or `null` when `value` is `None`.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn opt_uint(
+        &mut self,
+        key: &str,
+        value: Option<impl Into<u64>>,
+    ) -> Result<&mut Self, fmt::Error> {
+        match value {
+            Some(value) => self.uint(key, value),
+            None => self.null(key),
+        }
+    }
+
+    /// Adds a count member.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn count(&mut self, key: &str, value: usize) -> Result<&mut Self, fmt::Error> {
+        self.uint(key, u64::try_from(value).unwrap_or(u64::MAX))
+    }
+
+    /// Adds a duration member in whole milliseconds.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn millis(&mut self, key: &str, value: Duration) -> Result<&mut Self, fmt::Error> {
+        self.uint(key, u64::try_from(value.as_millis()).unwrap_or(u64::MAX))
+    }
+
+    /// Adds a duration member in milliseconds, or `null` when `value` is `None`.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn opt_millis(
+        &mut self,
+        key: &str,
+        value: Option<Duration>,
+    ) -> Result<&mut Self, fmt::Error> {
+        match value {
+            Some(value) => self.millis(key, value),
+            None => self.null(key),
+        }
+    }
+
+    /// Adds a boolean member.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn bool(&mut self, key: &str, value: bool) -> Result<&mut Self, fmt::Error> {
+        self.key(key)?;
+        self.writer
+            .write_str(if value { "true" } else { "false" })?;
+        Ok(self)
+    }
+
+    /// Adds a `null` member.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn null(&mut self, key: &str) -> Result<&mut Self, fmt::Error> {
+        self.key(key)?;
+        self.writer.write_str("null")?;
+        Ok(self)
+    }
+
+    /// Adds an array of unsigned integers.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn uint_array<T: Into<u64>>(
+        &mut self,
+        key: &str,
+        values: impl IntoIterator<Item = T>,
+    ) -> Result<&mut Self, fmt::Error> {
+        self.key(key)?;
+        self.writer.write_char('[')?;
+        for (index, value) in values.into_iter().enumerate() {
+            if index > 0 {
+                self.writer.write_char(',')?;
+            }
+            write!(self.writer, "{}", value.into())?;
+        }
+        self.writer.write_char(']')?;
+        Ok(self)
+    }
+
+    /// Adds an array of strings.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn str_array<'v>(
+        &mut self,
+        key: &str,
+        values: impl IntoIterator<Item = &'v str>,
+    ) -> Result<&mut Self, fmt::Error> {
+        self.key(key)?;
+        self.writer.write_char('[')?;
+        for (index, value) in values.into_iter().enumerate() {
+            if index > 0 {
+                self.writer.write_char(',')?;
+            }
+            write_string(self.writer, value)?;
+        }
+        self.writer.write_char(']')?;
+        Ok(self)
+    }
+
+    /// Adds a nested object filled in by `body`.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if `body` or the writer fails.
+    pub fn object(
+        &mut self,
+        key: &str,
+        body: impl FnOnce(&mut JsonObject<'_, W>) -> fmt::Result,
+    ) -> Result<&mut Self, fmt::Error> {
+        self.key(key)?;
+        let mut nested = JsonObject::open(&mut *self.writer)?;
+        body(&mut nested)?;
+        nested.finish()?;
+        Ok(self)
+    }
+
+    /// Closes the object.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn finish(self) -> fmt::Result {
+        self.writer.write_char('}')
+    }
+}
+
+/// Renders a [`CommandOutcome`] as JSON lines.
+///
+/// Console, script, and mode outcomes depend on state the platform owns, so
+/// they produce no lines here; the platform reports them with [`Reply`].
+#[derive(Clone, Copy, Debug)]
+pub struct JsonFormatter<'a, Instant> {
+    outcome: &'a CommandOutcome<Instant>,
+}
+
+impl<'a, Instant> JsonFormatter<'a, Instant> {
+    /// Creates a new formatter for the provided outcome.
+    #[must_use]
+    pub const fn new(outcome: &'a CommandOutcome<Instant>) -> Self {
+        Self { outcome }
+    }
+
+    /// Number of lines produced by [`Self::write_line`].
+    #[must_use]
+    pub fn line_count(&self) -> usize {
+        match self.outcome {
+            CommandOutcome::Power(PowerReport::History(report)) => 1 + report.episodes.len(),
+            CommandOutcome::Help(page) => page.line_count(),
+            CommandOutcome::Console(_)
+            | CommandOutcome::Script(_)
+            | CommandOutcome::Mode(_)
+            | CommandOutcome::Events(_)
+            | CommandOutcome::Wait(_)
+            | CommandOutcome::Macro(_) => 0,
+            _ => 1,
+        }
+    }
+
+    /// Writes line `index` without a line terminator.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space or `index` is
+    /// past [`Self::line_count`].
+    pub fn write_line<W: fmt::Write>(&self, writer: &mut W, index: usize) -> fmt::Result {
+        if index >= self.line_count() {
+            return Err(fmt::Error);
+        }
+        match self.outcome {
+            CommandOutcome::Reboot(ack) => write_reboot(writer, ack),
+            CommandOutcome::Recovery(ack) => write_recovery(writer, ack),
+            CommandOutcome::Fault(ack) => write_fault(writer, ack),
+            CommandOutcome::Power(PowerReport::Stats { snapshot, reset }) => {
+                write_rail_stats(writer, snapshot, *reset)
+            }
+            CommandOutcome::Power(PowerReport::History(report)) => {
+                write_brown_out_line(writer, report, index)
+            }
+            CommandOutcome::Watchdog(status) => write_watchdog(writer, status),
+            CommandOutcome::Bridge(report) => write_bridge_stats(writer, report),
+            CommandOutcome::Status(snapshot) => write_status(writer, snapshot),
+            CommandOutcome::Help(page) => {
+                let mut object = JsonObject::new(writer, "help")?;
+                object.key("text")?;
+                object.writer.write_char('"')?;
+                page.write_line(index, &mut Escaper(&mut *object.writer))?;
+                object.writer.write_char('"')?;
+                object.finish()
+            }
+            CommandOutcome::Console(_)
+            | CommandOutcome::Script(_)
+            | CommandOutcome::Mode(_)
+            | CommandOutcome::Events(_)
+            | CommandOutcome::Wait(_)
+            | CommandOutcome::Macro(_) => Err(fmt::Error),
+        }
+    }
+}
+
+fn write_reboot<W: fmt::Write, I>(writer: &mut W, ack: &RebootAck<I>) -> fmt::Result {
+    let mut object = JsonObject::new(writer, "ok")?;
+    object
+        .str("command", "reboot")?
+        .bool("to_bootloader", ack.to_bootloader)?
+        .opt_millis("start_after_ms", ack.start_after)?;
+    object.finish()
+}
+
+fn write_recovery<W: fmt::Write, I>(writer: &mut W, ack: &RecoveryAck<I>) -> fmt::Result {
+    let (action, timeout) = match ack.command {
+        RecoveryCommand::Enter => ("enter", None),
+        RecoveryCommand::Exit => ("exit", None),
+        RecoveryCommand::Now { timeout } => (
+            "now",
+            Some(timeout.unwrap_or(DEFAULT_BRIDGE_ACTIVITY_TIMEOUT)),
+        ),
+    };
+    let mut object = JsonObject::new(writer, "ok")?;
+    object
+        .str("command", "recovery")?
+        .str("action", action)?
+        .display("sequence", ack.sequence)?
+        .opt_millis("timeout_ms", timeout)?;
+    object.finish()
+}
+
+fn write_fault<W: fmt::Write, I>(writer: &mut W, ack: &FaultAck<I>) -> fmt::Result {
+    let mut object = JsonObject::new(writer, "ok")?;
+    object
+        .str("command", "fault")?
+        .str("action", "recover")?
+        .display("sequence", ack.sequence)?
+        .uint("retries", ack.retry_budget)?;
+    object.finish()
+}
+
+fn write_rail_stats<W: fmt::Write>(
+    writer: &mut W,
+    snapshot: &RailStatsSnapshot,
+    reset: bool,
+) -> fmt::Result {
+    let overall = &snapshot.overall;
+    let histogram = &snapshot.histogram;
+    let mut object = JsonObject::new(writer, "power-stats")?;
+    object
+        .uint("samples", overall.samples())?
+        .uint("invalid", snapshot.invalid_samples)?
+        .opt_uint("min_mv", overall.min_mv())?
+        .opt_uint("max_mv", overall.max_mv())?
+        .opt_uint("mean_mv", overall.mean_mv())?
+        .opt_uint("pp_mv", overall.peak_to_peak_mv())?
+        .uint("window", snapshot.window_samples)?
+        .opt_uint("window_pp_mv", snapshot.last_window_pp_mv)?
+        .opt_uint("max_window_pp_mv", snapshot.max_window_pp_mv)?
+        .object("histogram", |object| {
+            // Bucket `i` spans edges `i - 1` to `i`; the outer buckets are open.
+            let edges = (0..RAIL_HISTOGRAM_BUCKETS - 1)
+                .filter_map(|index| histogram.bucket_bounds(index).1);
+            object
+                .uint_array("edges_mv", edges)?
+                .uint_array("counts", histogram.counts().iter().copied())?;
+            Ok(())
+        })?;
+    let capture = match (snapshot.active_capture, snapshot.last_capture) {
+        (Some(capture), _) => Some((capture, "active")),
+        (None, Some(capture)) if capture.within_ripple_limit() => Some((capture, "pass")),
+        (None, Some(capture)) => Some((capture, "fail")),
+        (None, None) => None,
+    };
+    match capture {
+        Some((capture, state)) => object.object("capture", |object| {
+            object
+                .display("sequence", capture.kind)?
+                .str("state", state)?
+                .uint("samples", capture.summary.samples())?
+                .opt_uint("pp_mv", capture.summary.peak_to_peak_mv())?;
+            Ok(())
+        })?,
+        None => object.null("capture")?,
+    };
+    object.bool("reset", reset)?;
+    object.finish()
+}
+
+/// Writes the brown-out log: one `brown-out` object per episode, oldest
+/// first, then a `brown-outs` summary.
+fn write_brown_out_line<W: fmt::Write>(
+    writer: &mut W,
+    report: &BrownOutReport,
+    index: usize,
+) -> fmt::Result {
+    if let Some(episode) = report.episodes.get(index) {
+        let mut object = JsonObject::new(writer, "brown-out")?;
+        object
+            .uint("age_ms", episode.age_ms)?
+            .opt_uint("min_mv", episode.min_mv)?
+            .opt_uint("duration_ms", episode.duration_ms)?
+            .opt_display("sequence", episode.sequence)?
+            .opt_uint("step", episode.step)?
+            .bool("retried", episode.retried)?;
+        return object.finish();
+    }
+    let mut object = JsonObject::new(writer, "brown-outs")?;
+    object
+        .uint("total", report.total)?
+        .count("shown", report.episodes.len())?;
+    object.finish()
+}
+
+fn write_watchdog<W: fmt::Write>(writer: &mut W, status: &WatchdogStatus) -> fmt::Result {
+    let mut object = JsonObject::new(writer, "watchdog")?;
+    object
+        .display("phase", status.phase)?
+        .opt_uint("remaining_ms", status.remaining_ms)?
+        .uint("boot_deadline_ms", status.boot_deadline_ms)?
+        .opt_display("marker", status.expected_marker)?
+        .opt_uint("silence_limit_ms", status.silence_limit_ms)?
+        .opt_uint("panic_grace_ms", status.panic_grace_ms)?
+        .uint("max_recoveries", status.max_recoveries)?
+        .uint("consecutive", status.consecutive)?
+        .uint("trips", status.trips)?
+        .opt_display("last_reason", status.last_reason)?;
+    object.finish()
+}
+
+fn write_bridge_stats<W: fmt::Write>(writer: &mut W, report: &BridgeStatsReport) -> fmt::Result {
+    let snapshot: &BridgeStatsSnapshot = &report.snapshot;
+    let mut object = JsonObject::new(writer, "bridge-stats")?;
+    object
+        .object("rx", |object| direction(object, &snapshot.jetson_to_usb))?
+        .object("tx", |object| direction(object, &snapshot.usb_to_jetson))?
+        .object("errors", |object| {
+            object
+                .uint("framing", snapshot.framing_errors)?
+                .uint("noise", snapshot.noise_errors)?
+                .uint("overrun", snapshot.overrun_errors)?
+                .uint("parity", snapshot.parity_errors)?
+                .uint("uart_write", snapshot.uart_write_failures)?
+                .uint("usb_write", snapshot.usb_write_failures)?;
+            Ok(())
+        })?
+        .bool("reset", report.reset)?;
+    object.finish()
+}
+
+fn direction<W: fmt::Write>(
+    object: &mut JsonObject<'_, W>,
+    stats: &BridgeDirectionStats,
+) -> fmt::Result {
+    object
+        .uint("bytes", stats.bytes)?
+        .uint("frames", stats.frames)?
+        .uint("dropped", stats.dropped)?
+        .uint("queue_full", stats.queue_full)?;
+    Ok(())
+}
+
+fn write_status<W: fmt::Write>(writer: &mut W, snapshot: &StatusSnapshot) -> fmt::Result {
+    let bridge = &snapshot.bridge;
+    let mut object = JsonObject::new(writer, "status")?;
+    object
+        .object("straps", |object| {
+            for sample in &snapshot.strap_levels {
+                let state = if sample.level.is_asserted() {
+                    "asserted"
+                } else {
+                    "released"
+                };
+                object.str(strap_by_id(sample.id).name, state)?;
+            }
+            Ok(())
+        })?
+        .opt_uint("vdd_mv", snapshot.vdd_mv)?
+        .str(
+            "control_link",
+            if snapshot.control_link_attached {
+                "attached"
+            } else {
+                "lost"
+            },
+        )?
+        .str(
+            "debug",
+            match snapshot.debug_link {
+                DebugLinkState::Connected => "connected",
+                DebugLinkState::Disconnected => "disconnected",
+                DebugLinkState::Unknown => "unknown",
+            },
+        )?
+        .object("bridge", |object| {
+            object
+                .bool("waiting", bridge.waiting_for_activity)?
+                .opt_millis("rx_idle_ms", bridge.jetson_to_usb_idle)?
+                .opt_millis("tx_idle_ms", bridge.usb_to_jetson_idle)?
+                .display("uart", bridge.line_coding)?
+                .display("baud_source", bridge.baud_source)?
+                .display("release", bridge.release)?
+                .uint("rx_bytes", bridge.stats.jetson_to_usb.bytes)?
+                .uint("tx_bytes", bridge.stats.usb_to_jetson.bytes)?
+                .uint("errors", bridge.stats.error_count())?
+                .uint("dropped", bridge.stats.dropped_frames())?;
+            Ok(())
+        })?;
+    object.finish()
+}
+
+/// Replies for work the platform carries out itself.
+#[derive(Clone, Copy, Debug)]
+pub enum Reply<'a> {
+    /// Response format now in effect.
+    Mode(ResponseMode),
+    /// Event categories now printed.
+    Events(EventFilter),
+    /// One rendered scrollback line.
+    ConsoleLine(&'a str),
+    /// Closes a scrollback listing.
+    ConsoleShown {
+        lines: u32,
+        run: u32,
+        dropped: u32,
+    },
+    ConsoleSysRq(SysRqKey),
+    ConsoleSent {
+        bytes: usize,
+    },
+    ConsoleAttached,
+    ConsoleBaud(BaudSetting),
+    /// One `console pattern list` entry.
+    ConsolePattern {
+        id: PatternId,
+        marker: ConsoleMarker,
+        text: &'a [u8],
+    },
+    /// Closes a `console pattern list` listing.
+    ConsolePatternsListed {
+        count: usize,
+    },
+    ConsolePatternAdded {
+        id: PatternId,
+        marker: ConsoleMarker,
+    },
+    ConsolePatternRemoved(PatternId),
+    ScriptLoading(&'a ScriptName),
+    ScriptLoaded {
+        name: &'a ScriptName,
+        steps: u8,
+    },
+    /// One `script list` entry.
+    ScriptEntry(&'a ScriptSummary),
+    /// Closes a `script list` listing.
+    ScriptListed {
+        count: usize,
+    },
+    ScriptStarted(&'a ScriptName),
+    /// A `wait` whose condition held.
+    Wait(&'a WaitReport),
+    MacroDefined(&'a Macro),
+    /// One `macro list` entry.
+    MacroEntry(&'a Macro),
+    /// Closes a `macro list` listing.
+    MacroListed {
+        count: usize,
+    },
+    MacroStarted(&'a Macro),
+}
+
+impl Reply<'_> {
+    /// Writes the reply without a line terminator.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn write<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        let mut object = match self {
+            Reply::ConsoleLine(text) => {
+                let mut object = JsonObject::new(writer, "console-line")?;
+                object.str("text", text)?;
+                return object.finish();
+            }
+            Reply::ConsolePattern { id, marker, text } => {
+                let mut object = JsonObject::new(writer, "console-pattern")?;
+                object
+                    .uint("id", *id)?
+                    .display("marker", marker)?
+                    .display("text", Escaped(text))?;
+                return object.finish();
+            }
+            Reply::ScriptEntry(summary) => {
+                let mut object = JsonObject::new(writer, "script")?;
+                object
+                    .display("name", &summary.name)?
+                    .uint("steps", summary.steps)?
+                    .opt_uint("running_line", summary.running_line)?;
+                return object.finish();
+            }
+            Reply::MacroEntry(entry) => {
+                let mut object = JsonObject::new(writer, "macro")?;
+                object
+                    .display("name", &entry.name)?
+                    .count("commands", entry.commands)?
+                    .bool("continue_on_error", entry.continue_on_error)?
+                    .str("body", &entry.body)?;
+                return object.finish();
+            }
+            _ => JsonObject::new(writer, "ok")?,
+        };
+        match self {
+            Reply::Mode(mode) => {
+                object.str("command", "mode")?.display("mode", mode)?;
+            }
+            Reply::Events(filter) => {
+                object
+                    .str("command", "events")?
+                    .str_array("categories", filter.categories().map(EventCategory::name))?;
+            }
+            Reply::ConsoleShown {
+                lines,
+                run,
+                dropped,
+            } => {
+                object
+                    .str("command", "console")?
+                    .str("action", "show")?
+                    .uint("lines", *lines)?
+                    .uint("run", *run)?
+                    .uint("dropped", *dropped)?;
+            }
+            Reply::ConsoleSysRq(key) => {
+                object
+                    .str("command", "console")?
+                    .str("action", "sysrq")?
+                    .display("key", key)?
+                    .millis("break_ms", SYSRQ_BREAK)?;
+            }
+            Reply::ConsoleSent { bytes } => {
+                object
+                    .str("command", "console")?
+                    .str("action", "send")?
+                    .count("bytes", *bytes)?;
+            }
+            Reply::ConsoleAttached => {
+                object
+                    .str("command", "console")?
+                    .str("action", "attach")?
+                    .str("escape", "~.")?;
+            }
+            Reply::ConsoleBaud(setting) => {
+                object.str("command", "console")?.str("action", "baud")?;
+                match setting {
+                    BaudSetting::Auto => object.null("baud")?.bool("searching", true)?,
+                    BaudSetting::Fixed(baud) => {
+                        object.uint("baud", *baud)?.bool("searching", false)?
+                    }
+                };
+            }
+            Reply::ConsolePatternsListed { count } => {
+                object
+                    .str("command", "console")?
+                    .str("action", "pattern-list")?
+                    .count("count", *count)?;
+            }
+            Reply::ConsolePatternAdded { id, marker } => {
+                object
+                    .str("command", "console")?
+                    .str("action", "pattern-add")?
+                    .uint("id", *id)?
+                    .display("marker", marker)?;
+            }
+            Reply::ConsolePatternRemoved(id) => {
+                object
+                    .str("command", "console")?
+                    .str("action", "pattern-remove")?
+                    .uint("id", *id)?;
+            }
+            Reply::ScriptLoading(name) => {
+                object
+                    .str("command", "script")?
+                    .str("action", "load")?
+                    .display("name", name)?;
+            }
+            Reply::ScriptLoaded { name, steps } => {
+                object
+                    .str("command", "script")?
+                    .str("action", "loaded")?
+                    .display("name", name)?
+                    .uint("steps", *steps)?;
+            }
+            Reply::ScriptListed { count } => {
+                object
+                    .str("command", "script")?
+                    .str("action", "list")?
+                    .count("count", *count)?;
+            }
+            Reply::ScriptStarted(name) => {
+                object
+                    .str("command", "script")?
+                    .str("action", "run")?
+                    .display("name", name)?;
+            }
+            Reply::Wait(report) => write_wait(&mut object, report)?,
+            Reply::MacroDefined(entry) => {
+                object
+                    .str("command", "macro")?
+                    .str("action", "define")?
+                    .display("name", &entry.name)?
+                    .count("commands", entry.commands)?
+                    .bool("continue_on_error", entry.continue_on_error)?;
+            }
+            Reply::MacroListed { count } => {
+                object
+                    .str("command", "macro")?
+                    .str("action", "list")?
+                    .count("count", *count)?;
+            }
+            Reply::MacroStarted(entry) => {
+                object
+                    .str("command", "macro")?
+                    .str("action", "run")?
+                    .display("name", &entry.name)?
+                    .count("commands", entry.commands)?;
+            }
+            Reply::ConsoleLine(_)
+            | Reply::ConsolePattern { .. }
+            | Reply::ScriptEntry(_)
+            | Reply::MacroEntry(_) => {}
+        }
+        object.finish()
+    }
+}
+
+/// Failures reported as `{"type":"error","code":...}`.
+#[derive(Clone, Copy)]
+pub enum ErrorReply<'a> {
+    Syntax(&'a ParseError),
+    Unsupported(&'a str),
+    /// The command queue rejected the request.
+    Busy(QueueErrorKind),
+    MissingTemplate(StrapSequenceKind),
+    Cooldown {
+        sequence: StrapSequenceKind,
+        ready_in: Duration,
+    },
+    /// Script library or engine error.
+    Script(&'a dyn fmt::Display),
+    /// The platform cannot carry out the named command.
+    Unavailable(&'a str),
+    LineTooLong,
+    InvalidUtf8,
+    /// A reply did not fit the platform's line buffer.
+    LineOverflow,
+    /// A `wait` ran out of time.
+    Timeout(&'a WaitTimeout),
+    /// A `;` chain could not be started.
+    Chain(ChainError),
+    /// Macro library error.
+    Macro(&'a dyn fmt::Display),
+    /// Console pattern table error.
+    Pattern(&'a dyn fmt::Display),
+}
+
+impl<'a> ErrorReply<'a> {
+    /// Describes an executor error. `ready_in` converts a cooldown deadline
+    /// into the time left from now.
+    #[must_use]
+    pub fn from_command_error<E, Instant: Copy>(
+        error: &'a CommandError<E, Instant>,
+        ready_in: impl FnOnce(Instant) -> Duration,
+    ) -> Self {
+        match error {
+            CommandError::Parse(parse) => ErrorReply::Syntax(parse),
+            CommandError::Unsupported(topic) => ErrorReply::Unsupported(topic),
+            CommandError::Schedule(error) => match schedule_error_info(error) {
+                ScheduleErrorInfo::Queue(kind) => ErrorReply::Busy(kind),
+                ScheduleErrorInfo::MissingTemplate(kind) => ErrorReply::MissingTemplate(kind),
+                ScheduleErrorInfo::CooldownActive { kind, ready_at } => ErrorReply::Cooldown {
+                    sequence: kind,
+                    ready_in: ready_in(ready_at),
+                },
+            },
+        }
+    }
+
+    /// Writes the error without a line terminator.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn write<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        let mut object = JsonObject::new(writer, "error")?;
+        match self {
+            ErrorReply::Syntax(parse) => {
+                object.str("code", "syntax")?.display("message", parse)?;
+                if let ParseError::Grammar(error) = parse
+                    && !error.suggestions().is_empty()
+                {
+                    object.str_array("suggestions", error.suggestions().iter().copied())?;
+                }
+            }
+            ErrorReply::Unsupported(topic) => {
+                object.str("code", "unsupported")?.str("message", topic)?;
+            }
+            ErrorReply::Busy(kind) => {
+                let reason = match kind {
+                    QueueErrorKind::QueueFull => "queue-full",
+                    QueueErrorKind::QueueDisconnected => "queue-disconnected",
+                    QueueErrorKind::QueueOther => "queue-error",
+                };
+                object.str("code", "busy")?.str("reason", reason)?;
+            }
+            ErrorReply::MissingTemplate(kind) => {
+                object
+                    .str("code", "missing-template")?
+                    .display("sequence", kind)?;
+            }
+            ErrorReply::Cooldown { sequence, ready_in } => {
+                object
+                    .str("code", "cooldown")?
+                    .display("sequence", sequence)?
+                    .millis("ready_in_ms", *ready_in)?;
+            }
+            ErrorReply::Script(error) => {
+                object.str("code", "script")?.display("message", error)?;
+            }
+            ErrorReply::Unavailable(command) => {
+                object.str("code", "unavailable")?.str("command", command)?;
+            }
+            ErrorReply::LineTooLong => {
+                object.str("code", "line-too-long")?;
+            }
+            ErrorReply::InvalidUtf8 => {
+                object.str("code", "invalid-utf8")?;
+            }
+            ErrorReply::LineOverflow => {
+                object.str("code", "line-overflow")?;
+            }
+            ErrorReply::Timeout(timeout) => {
+                object
+                    .str("code", "timeout")?
+                    .display("condition", timeout.condition)?
+                    .millis("waited_ms", timeout.waited)?;
+            }
+            ErrorReply::Chain(error) => {
+                object.str("code", "chain")?.display("message", error)?;
+            }
+            ErrorReply::Macro(error) => {
+                object.str("code", "macro")?.display("message", error)?;
+            }
+            ErrorReply::Pattern(error) => {
+                object.str("code", "pattern")?.display("message", error)?;
+            }
+        }
+        object.finish()
+    }
+}
+
+/// Reports that arrive after the command that started them.
+#[derive(Clone, Copy, Debug)]
+pub enum Event<'a> {
+    /// A `reboot to-bootloader` run ended.
+    Bootloader(&'a AutobootReport),
+    /// A `console baud auto` search ended.
+    Autobaud(&'a AutobaudReport),
+    /// A script expect finished or the run ended.
+    Script(&'a ScriptReport),
+    /// The operator typed the `console attach` escape.
+    ConsoleDetached,
+    /// The strap sequence of a tagged request ended.
+    Done(&'a SequenceCompletion),
+    /// A telemetry event from a category selected with `events`.
+    Telemetry(&'a TelemetryNotice),
+}
+
+impl Event<'_> {
+    /// Writes the event without a line terminator.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer runs out of space.
+    pub fn write<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        let mut object = JsonObject::new(writer, "event")?;
+        match self {
+            Event::Bootloader(report) => {
+                object
+                    .str("event", "bootloader")?
+                    .display("result", report.result)?
+                    .bool("ok", report.result.is_success())?
+                    .millis("elapsed_ms", report.elapsed)?
+                    .uint("keys", report.keys_sent)?;
+            }
+            Event::Autobaud(report) => {
+                object
+                    .str("event", "autobaud")?
+                    .display("result", report.result)?
+                    .bool("ok", report.result.is_success())?
+                    .opt_uint("baud", report.best.map(|best| best.baud))?
+                    .opt_uint("score", report.best.map(|best| best.score()))?
+                    .opt_uint("errors", report.best.map(|best| best.errors))?
+                    .millis("elapsed_ms", report.elapsed)?;
+            }
+            Event::Script(report) => {
+                object
+                    .str("event", "script")?
+                    .display("name", &report.name)?
+                    .display("result", report.result)?
+                    .bool("final", report.result.is_final())?
+                    .uint("line", report.line)?
+                    .millis("elapsed_ms", report.elapsed)?;
+            }
+            Event::ConsoleDetached => {
+                object.str("event", "console-detached")?;
+            }
+            Event::Done(done) => {
+                object
+                    .str("event", "done")?
+                    .display("sequence", done.kind)?
+                    .str("result", done.result.label())?
+                    .bool("ok", done.result.is_success())?
+                    .opt_display("reason", done.result.reason())?
+                    .millis("elapsed_ms", done.elapsed)?;
+            }
+            Event::Telemetry(notice) => write_telemetry(&mut object, notice)?,
+        }
+        object.finish()
+    }
+}
+
+fn write_wait<W: fmt::Write>(object: &mut JsonObject<'_, W>, report: &WaitReport) -> fmt::Result {
+    object.str("command", "wait")?;
+    match &report.outcome {
+        WaitOutcome::Complete(done) => {
+            object
+                .str("condition", "complete")?
+                .display("sequence", done.kind)?
+                .str("result", done.result.label())?
+                .bool("ok", done.result.is_success())?
+                .opt_display("reason", done.result.reason())?
+                .millis("elapsed_ms", done.elapsed)?;
+        }
+        WaitOutcome::Idle => {
+            object.str("condition", "idle")?;
+        }
+        WaitOutcome::Event(notice) => {
+            object
+                .str("condition", "event")?
+                .uint("id", notice.id)?
+                .display("kind", notice.event)?
+                .millis("t_ms", notice.uptime)?;
+        }
+    }
+    object.millis("waited_ms", report.waited)?;
+    Ok(())
+}
+
+fn write_telemetry<W: fmt::Write>(
+    object: &mut JsonObject<'_, W>,
+    notice: &TelemetryNotice,
+) -> fmt::Result {
+    object
+        .str("event", "telemetry")?
+        .uint("id", notice.id)?
+        .opt_display("category", notice.event.category())?
+        .display("kind", notice.event)?
+        .millis("t_ms", notice.uptime)?;
+    match notice.details {
+        TelemetryPayload::Strap(details) => {
+            object.opt_millis("delta_ms", details.elapsed_since_previous)?;
+        }
+        TelemetryPayload::Command(details) => {
+            object
+                .uint("depth", details.queue_depth)?
+                .opt_millis("waited_ms", details.pending_for)?;
+        }
+        TelemetryPayload::Sequence(details) => {
+            object
+                .str("outcome", details.outcome.label())?
+                .opt_millis("duration_ms", details.duration)?
+                .opt_display("reason", details.fault.map(|fault| fault.reason))?;
+        }
+        TelemetryPayload::BrownOut(details) => {
+            object
+                .opt_uint("min_mv", details.min_mv)?
+                .opt_millis("duration_ms", details.duration)?
+                .bool("retried", details.retried)?;
+        }
+        TelemetryPayload::Console(details) => {
+            object.uint("line", details.line)?;
+        }
+        TelemetryPayload::Panic(excerpt) => {
+            object
+                .uint("line", excerpt.line)?
+                .display("text", excerpt)?;
+        }
+        TelemetryPayload::None | TelemetryPayload::Script(_) | TelemetryPayload::Autoboot(_) => {}
+    }
+    Ok(())
+}
+
+fn write_string<W: fmt::Write>(writer: &mut W, value: &str) -> fmt::Result {
+    writer.write_char('"')?;
+    Escaper(&mut *writer).write_str(value)?;
+    writer.write_char('"')
+}
+
+/// Adapter that escapes everything written through it for a JSON string.
+struct Escaper<'a, W>(&'a mut W);
+
+impl<W: fmt::Write> fmt::Write for Escaper<'_, W> {
+    fn write_str(&mut self, value: &str) -> fmt::Result {
+        let mut start = 0;
+        for (index, ch) in value.char_indices() {
+            let escape = match ch {
+                '"' => "\\\"",
+                '\\' => "\\\\",
+                '\n' => "\\n",
+                '\r' => "\\r",
+                '\t' => "\\t",
+                ch if u32::from(ch) < 0x20 => "",
+                _ => continue,
+            };
+            self.0.write_str(&value[start..index])?;
+            if escape.is_empty() {
+                write!(self.0, "\\u{:04x}", u32::from(ch))?;
+            } else {
+                self.0.write_str(escape)?;
+            }
+            start = index + ch.len_utf8();
+        }
+        self.0.write_str(&value[start..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::bridge::ConsoleMarker;
+    use crate::bridge::stats::BridgeStatistics;
+    use crate::power::{BrownOutSummary, RailStatistics, SequenceRailCapture};
+    use crate::repl::grammar::parse;
+    use crate::repl::help::HelpPage;
+    use crate::supervisor::WatchdogPhase;
+    use crate::telemetry::FaultRecoveryReason;
+
+    fn render<I>(outcome: &CommandOutcome<I>) -> std::vec::Vec<std::string::String> {
+        let formatter = JsonFormatter::new(outcome);
+        (0..formatter.line_count())
+            .map(|index| {
+                let mut line = std::string::String::new();
+                formatter
+                    .write_line(&mut line, index)
+                    .expect("line renders");
+                line
+            })
+            .collect()
+    }
+
+    #[test]
+    fn acks_use_a_stable_schema() {
+        let reboot: CommandOutcome<u32> = CommandOutcome::Reboot(RebootAck {
+            requested_at: 5,
+            start_after: Some(Duration::from_secs(2)),
+            to_bootloader: false,
+        });
+        assert_eq!(
+            render(&reboot),
+            [r#"{"type":"ok","command":"reboot","to_bootloader":false,"start_after_ms":2000}"#]
+        );
+
+        let recovery: CommandOutcome<u32> = CommandOutcome::Recovery(RecoveryAck {
+            requested_at: 5,
+            sequence: StrapSequenceKind::RecoveryEntry,
+            command: RecoveryCommand::Now { timeout: None },
+        });
+        assert_eq!(
+            render(&recovery),
+            [
+                r#"{"type":"ok","command":"recovery","action":"now","sequence":"recovery-entry","timeout_ms":10000}"#
+            ]
+        );
+
+        let mut line = std::string::String::new();
+        Reply::ConsoleBaud(BaudSetting::Auto)
+            .write(&mut line)
+            .expect("reply renders");
+        assert_eq!(
+            line,
+            r#"{"type":"ok","command":"console","action":"baud","baud":null,"searching":true}"#
+        );
+
+        line.clear();
+        Reply::ConsolePattern {
+            id: 5,
+            marker: ConsoleMarker::Custom,
+            text: b"ready\t>",
+        }
+        .write(&mut line)
+        .expect("reply renders");
+        assert_eq!(
+            line,
+            r#"{"type":"console-pattern","id":5,"marker":"custom","text":"ready\\t>"}"#
+        );
+    }
+
+    #[test]
+    fn done_events_carry_the_sequence_result() {
+        use crate::orchestrator::{
+            CommandFlags, CommandSource, RequestTag, SequenceCommand, SequenceError, SequenceResult,
+        };
+        use crate::telemetry::test_support::MillisInstant;
+
+        let mut command = SequenceCommand::new(
+            StrapSequenceKind::NormalReboot,
+            MillisInstant(100),
+            CommandSource::UsbHost,
+        );
+        let failed = SequenceResult::Failed(SequenceError::BrownOutDetected);
+        assert_eq!(
+            SequenceCompletion::for_command(&command, failed, MillisInstant(900)).tag,
+            None
+        );
+
+        command.flags = CommandFlags {
+            tag: Some(RequestTag::new(17)),
+            ..CommandFlags::default()
+        };
+        let done = SequenceCompletion::for_command(&command, failed, MillisInstant(1_320));
+        assert_eq!(done.tag, Some(RequestTag::new(17)));
+        assert_eq!(
+            std::format!("DONE {done}"),
+            "DONE normal-reboot failed reason=brown-out elapsed=1220ms"
+        );
+        let mut line = std::string::String::new();
+        Event::Done(&done).write(&mut line).expect("event renders");
+        assert_eq!(
+            line,
+            r#"{"type":"event","event":"done","sequence":"normal-reboot","result":"failed","ok":false,"reason":"brown-out","elapsed_ms":1220}"#
+        );
+    }
+
+    #[test]
+    fn telemetry_events_and_subscriptions_render() {
+        use crate::sequences::StrapId;
+        use crate::telemetry::{
+            EventCategory, StrapTelemetry, TelemetryEventKind, TelemetryNotice,
+        };
+
+        let notice = TelemetryNotice {
+            id: 4,
+            uptime: Duration::from_millis(1_250),
+            event: TelemetryEventKind::StrapReleased(StrapId::Rec),
+            details: TelemetryPayload::Strap(StrapTelemetry::new(
+                StrapId::Rec,
+                crate::sequences::StrapAction::ReleaseHigh,
+                Some(Duration::from_millis(40)),
+            )),
+        };
+        let mut line = std::string::String::new();
+        Event::Telemetry(&notice)
+            .write(&mut line)
+            .expect("event renders");
+        assert_eq!(
+            line,
+            r#"{"type":"event","event":"telemetry","id":4,"category":"strap","kind":"strap-released REC*","t_ms":1250,"delta_ms":40}"#
+        );
+
+        line.clear();
+        let filter = EventFilter::NONE
+            .with(EventCategory::Strap)
+            .with(EventCategory::Power);
+        Reply::Events(filter)
+            .write(&mut line)
+            .expect("reply renders");
+        assert_eq!(
+            line,
+            r#"{"type":"ok","command":"events","categories":["strap","power"]}"#
+        );
+    }
+
+    #[test]
+    fn wait_replies_render() {
+        use crate::orchestrator::{RequestTag, SequenceOutcome, SequenceResult};
+        use crate::repl::wait::{WaitCondition, WaitOutcome, WaitReport, WaitTimeout};
+
+        let report = WaitReport {
+            outcome: WaitOutcome::Complete(SequenceCompletion {
+                tag: Some(RequestTag::new(17)),
+                kind: StrapSequenceKind::NormalReboot,
+                result: SequenceResult::Finished(SequenceOutcome::Completed),
+                elapsed: Duration::from_millis(1_220),
+            }),
+            waited: Duration::from_millis(800),
+        };
+        let mut line = std::string::String::new();
+        Reply::Wait(&report)
+            .write(&mut line)
+            .expect("reply renders");
+        assert_eq!(
+            line,
+            r#"{"type":"ok","command":"wait","condition":"complete","sequence":"normal-reboot","result":"completed","ok":true,"reason":null,"elapsed_ms":1220,"waited_ms":800}"#
+        );
+
+        line.clear();
+        let timeout = WaitTimeout {
+            condition: WaitCondition::Event("power-stable"),
+            waited: Duration::from_secs(5),
+        };
+        ErrorReply::Timeout(&timeout)
+            .write(&mut line)
+            .expect("error renders");
+        assert_eq!(
+            line,
+            r#"{"type":"error","code":"timeout","condition":"event power-stable","waited_ms":5000}"#
+        );
+    }
+
+    #[test]
+    fn macro_replies_render() {
+        use crate::repl::chain::ChainError;
+        use crate::repl::macros::MacroLibrary;
+        use crate::script::script_name;
+
+        let mut library = MacroLibrary::new();
+        let name = script_name("boot").expect("name fits");
+        let stored = library
+            .define(name, b"recovery enter; wait complete; status", true)
+            .expect("macro stores");
+        let mut line = std::string::String::new();
+        Reply::MacroEntry(stored)
+            .write(&mut line)
+            .expect("reply renders");
+        assert_eq!(
+            line,
+            r#"{"type":"macro","name":"boot","commands":3,"continue_on_error":true,"body":"recovery enter; wait complete; status"}"#
+        );
+
+        line.clear();
+        Reply::MacroStarted(stored)
+            .write(&mut line)
+            .expect("reply renders");
+        assert_eq!(
+            line,
+            r#"{"type":"ok","command":"macro","action":"run","name":"boot","commands":3}"#
+        );
+
+        line.clear();
+        ErrorReply::Chain(ChainError::TooManyCommands)
+            .write(&mut line)
+            .expect("error renders");
+        assert_eq!(
+            line,
+            r#"{"type":"error","code":"chain","message":"more than 8 commands"}"#
+        );
+    }
+
+    #[test]
+    fn errors_carry_a_code_and_escaped_message() {
+        let error = parse("reboot \"x\"").expect_err("quoted text is not a reboot argument");
+        let error: CommandError<(), u32> = CommandError::Parse(error);
+        let mut line = std::string::String::new();
+        ErrorReply::from_command_error(&error, |_| Duration::ZERO)
+            .write(&mut line)
+            .expect("error renders");
+        assert_eq!(
+            line,
+            r#"{"type":"error","code":"syntax","message":"expected now, found quoted string at 7..10"}"#
+        );
+
+        line.clear();
+        let error = parse("rebot now").expect_err("misspelled command");
+        ErrorReply::Syntax(&error)
+            .write(&mut line)
+            .expect("error renders");
+        assert_eq!(
+            line,
+            r#"{"type":"error","code":"syntax","message":"unknown command 'rebot' (did you mean 'reboot'?)","suggestions":["reboot"]}"#
+        );
+
+        line.clear();
+        ErrorReply::Script(&"name \"a\\b\"\n")
+            .write(&mut line)
+            .expect("error renders");
+        assert_eq!(
+            line,
+            r#"{"type":"error","code":"script","message":"name \"a\\b\"\n"}"#
+        );
+
+        line.clear();
+        ErrorReply::Cooldown {
+            sequence: StrapSequenceKind::NormalReboot,
+            ready_in: Duration::from_millis(1500),
+        }
+        .write(&mut line)
+        .expect("error renders");
+        assert_eq!(
+            line,
+            r#"{"type":"error","code":"cooldown","sequence":"normal-reboot","ready_in_ms":1500}"#
+        );
+
+        line.clear();
+        Event::ConsoleDetached
+            .write(&mut line)
+            .expect("event renders");
+        assert_eq!(line, r#"{"type":"event","event":"console-detached"}"#);
+    }
+
+    #[test]
+    fn help_and_history_emit_one_object_per_line() {
+        let help: CommandOutcome<u32> = CommandOutcome::Help(HelpPage::for_topic(Some("nope")));
+        let lines = render(&help);
+        assert_eq!(
+            lines[0],
+            r#"{"type":"help","text":"No help available for `nope`."}"#
+        );
+
+        let mut report = BrownOutReport {
+            total: 4,
+            ..BrownOutReport::default()
+        };
+        report
+            .episodes
+            .push(BrownOutSummary {
+                age_ms: 1200,
+                min_mv: Some(2950),
+                duration_ms: None,
+                sequence: Some(StrapSequenceKind::NormalReboot),
+                step: Some(1),
+                retried: true,
+            })
+            .expect("room for an episode");
+        let history: CommandOutcome<u32> = CommandOutcome::Power(PowerReport::History(report));
+        assert_eq!(
+            render(&history),
+            [
+                r#"{"type":"brown-out","age_ms":1200,"min_mv":2950,"duration_ms":null,"sequence":"normal-reboot","step":1,"retried":true}"#,
+                r#"{"type":"brown-outs","total":4,"shown":1}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn worst_case_objects_fit_the_line_capacity() {
+        let mut statistics = RailStatistics::new();
+        statistics.record(Some(u16::MAX));
+        let mut snapshot = statistics.snapshot();
+        snapshot.invalid_samples = u32::MAX;
+        let capture = SequenceRailCapture {
+            kind: StrapSequenceKind::RecoveryEntry,
+            summary: snapshot.overall,
+        };
+        snapshot.active_capture = Some(capture);
+        let rail: CommandOutcome<u32> = CommandOutcome::Power(PowerReport::Stats {
+            snapshot,
+            reset: true,
+        });
+
+        let mut counters = BridgeStatistics::new().snapshot();
+        counters.framing_errors = u32::MAX;
+        counters.jetson_to_usb.bytes = u32::MAX;
+        let bridge: CommandOutcome<u32> = CommandOutcome::Bridge(BridgeStatsReport {
+            snapshot: counters,
+            reset: false,
+        });
+
+        let watchdog: CommandOutcome<u32> = CommandOutcome::Watchdog(WatchdogStatus {
+            phase: WatchdogPhase::Recovering,
+            boot_deadline_ms: u32::MAX,
+            expected_marker: Some(ConsoleMarker::TegraRecovery),
+            silence_limit_ms: Some(u32::MAX),
+            panic_grace_ms: Some(u32::MAX),
+            max_recoveries: u8::MAX,
+            consecutive: u8::MAX,
+            trips: u32::MAX,
+            last_reason: Some(FaultRecoveryReason::ConsoleWatchdogTimeout),
+            remaining_ms: Some(u32::MAX),
+        });
+
+        let status: CommandOutcome<u32> = CommandOutcome::Status(StatusSnapshot::unknown());
+
+        for outcome in [rail, bridge, watchdog, status] {
+            for line in render(&outcome) {
+                assert!(line.len() <= JSON_LINE_CAPACITY, "{line}");
+            }
+        }
+        let mut help = std::vec![HelpPage::Index];
+        help.extend(
+            crate::repl::catalog::commands()
+                .iter()
+                .map(HelpPage::Command),
+        );
+        for page in help {
+            for line in render::<u32>(&CommandOutcome::Help(page)) {
+                assert!(line.len() <= JSON_LINE_CAPACITY, "{line}");
+            }
+        }
+    }
+}
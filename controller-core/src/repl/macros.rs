@@ -0,0 +1,217 @@
+//! Stored command chains run with `macro run`.
+//!
+//! `macro define <name> "<commands>"` keeps a `;`-separated chain in a small
+//! fixed library owned by the REPL session; `macro run <name>` hands it to
+//! the session's [`CommandChain`](super::chain::CommandChain).
+
+use core::fmt;
+
+use heapless::{String, Vec};
+
+use super::chain::{self, CHAIN_TEXT_CAPACITY, ChainError};
+use crate::script::ScriptName;
+
+/// Macros kept at once.
+pub const MAX_MACROS: usize = 8;
+
+/// One stored macro.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Macro {
+    pub name: ScriptName,
+    pub body: String<CHAIN_TEXT_CAPACITY>,
+    /// Number of commands in `body`.
+    pub commands: usize,
+    /// Keep running after a command fails.
+    pub continue_on_error: bool,
+}
+
+impl fmt::Display for Macro {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "macro {} commands={}", self.name, self.commands)?;
+        if self.continue_on_error {
+            f.write_str(" continue-on-error")?;
+        }
+        write!(f, ": {}", self.body)
+    }
+}
+
+/// Reasons a macro cannot be stored or run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MacroError {
+    /// Every slot holds a different macro.
+    LibraryFull,
+    NotFound,
+    /// The body decodes to bytes that are not UTF-8.
+    InvalidUtf8,
+    /// The body holds no commands.
+    Empty,
+    Chain(ChainError),
+}
+
+impl From<ChainError> for MacroError {
+    fn from(error: ChainError) -> Self {
+        MacroError::Chain(error)
+    }
+}
+
+impl fmt::Display for MacroError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MacroError::LibraryFull => write!(f, "library full ({MAX_MACROS} macros)"),
+            MacroError::NotFound => f.write_str("not found"),
+            MacroError::InvalidUtf8 => f.write_str("body is not UTF-8"),
+            MacroError::Empty => f.write_str("body has no commands"),
+            MacroError::Chain(error) => error.fmt(f),
+        }
+    }
+}
+
+/// Fixed-capacity macro storage.
+#[derive(Clone, Debug, Default)]
+pub struct MacroLibrary {
+    macros: Vec<Macro, MAX_MACROS>,
+}
+
+impl MacroLibrary {
+    /// Creates an empty library.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { macros: Vec::new() }
+    }
+
+    /// Stores `body` under `name`, replacing a macro of the same name, and
+    /// returns it.
+    ///
+    /// # Errors
+    /// Returns a [`MacroError`] when the body is not a valid chain or the
+    /// library has no free slot.
+    pub fn define(
+        &mut self,
+        name: ScriptName,
+        body: &[u8],
+        continue_on_error: bool,
+    ) -> Result<&Macro, MacroError> {
+        let body = core::str::from_utf8(body).map_err(|_| MacroError::InvalidUtf8)?;
+        let commands = chain::split(body)?.len();
+        if commands == 0 {
+            return Err(MacroError::Empty);
+        }
+        let entry = Macro {
+            body: String::try_from(body).map_err(|_| ChainError::TooLong)?,
+            name,
+            commands,
+            continue_on_error,
+        };
+
+        let index = match self.position(&entry.name) {
+            Some(index) => {
+                self.macros[index] = entry;
+                index
+            }
+            None => {
+                self.macros
+                    .push(entry)
+                    .map_err(|_| MacroError::LibraryFull)?;
+                self.macros.len() - 1
+            }
+        };
+        Ok(&self.macros[index])
+    }
+
+    /// Looks up a macro by name (case insensitive).
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Macro> {
+        self.position(name).map(|index| &self.macros[index])
+    }
+
+    /// Iterates over the stored macros in definition order.
+    pub fn entries(&self) -> impl Iterator<Item = &Macro> {
+        self.macros.iter()
+    }
+
+    /// Returns the number of stored macros.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.macros.len()
+    }
+
+    /// Returns `true` when no macros are stored.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.macros.is_empty()
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.macros
+            .iter()
+            .position(|entry| entry.name.eq_ignore_ascii_case(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::script::script_name;
+
+    fn name(text: &str) -> ScriptName {
+        script_name(text).expect("name fits")
+    }
+
+    #[test]
+    fn defines_replaces_and_lists_macros() {
+        let mut library = MacroLibrary::new();
+        let stored = library
+            .define(
+                name("boot"),
+                b"recovery enter; wait complete; status",
+                false,
+            )
+            .expect("macro stores");
+        assert_eq!(
+            std::format!("{stored}"),
+            "macro boot commands=3: recovery enter; wait complete; status"
+        );
+
+        let replaced = library
+            .define(name("BOOT"), b"status", true)
+            .expect("macro replaces");
+        assert_eq!(
+            std::format!("{replaced}"),
+            "macro BOOT commands=1 continue-on-error: status"
+        );
+        assert_eq!(library.len(), 1);
+        assert_eq!(library.get("boot").map(|entry| entry.commands), Some(1));
+        assert!(library.get("other").is_none());
+    }
+
+    #[test]
+    fn rejects_bad_bodies_and_a_full_library() {
+        let mut library = MacroLibrary::new();
+        assert_eq!(
+            library.define(name("empty"), b" ; ", false).map(|_| ()),
+            Err(MacroError::Empty)
+        );
+        assert_eq!(
+            library.define(name("bytes"), b"\xff", false).map(|_| ()),
+            Err(MacroError::InvalidUtf8)
+        );
+        assert_eq!(
+            library
+                .define(name("long"), b"s;s;s;s;s;s;s;s;s", false)
+                .map(|_| ()),
+            Err(MacroError::Chain(ChainError::TooManyCommands))
+        );
+
+        for index in 0..MAX_MACROS {
+            let label = std::format!("m{index}");
+            library
+                .define(name(&label), b"status", false)
+                .expect("slot free");
+        }
+        assert_eq!(
+            library.define(name("extra"), b"status", false).map(|_| ()),
+            Err(MacroError::LibraryFull)
+        );
+    }
+}
@@ -4,7 +4,15 @@
 //! token/parse pipeline that stays compatible with `no_std`.
 
 pub mod catalog;
+pub mod chain;
 pub mod commands;
 pub mod completion;
+pub mod editor;
 pub mod grammar;
+pub mod help;
+pub mod json;
+pub mod macros;
 pub mod status;
+pub mod suggest;
+pub mod tag;
+pub mod wait;
@@ -8,7 +8,13 @@
 use core::fmt;
 use core::time::Duration;
 
+use crate::bridge::ReleaseCriterion;
+use crate::bridge::autobaud::BaudSource;
+use crate::bridge::line_coding::LineCoding;
+use crate::bridge::stats::{BridgeStatistics, BridgeStatsSnapshot};
+use crate::power::{BrownOutReport, RailStatsSnapshot};
 use crate::sequences::{StrapId, strap_by_id};
+use crate::supervisor::{WatchdogStatus, WatchdogUpdate};
 
 /// Logical level reported for a strap line.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -59,6 +65,14 @@ pub struct BridgeActivitySnapshot {
     pub usb_to_jetson_idle: Option<Duration>,
     /// Duration since the controller received traffic from the Jetson.
     pub jetson_to_usb_idle: Option<Duration>,
+    /// UART framing currently applied to the Jetson console.
+    pub line_coding: LineCoding,
+    /// How the console baud rate was chosen.
+    pub baud_source: BaudSource,
+    /// Traffic and error counters since boot or the last `bridge stats reset`.
+    pub stats: BridgeStatsSnapshot,
+    /// Console evidence `recovery now` waits for before releasing REC.
+    pub release: ReleaseCriterion,
 }
 
 impl BridgeActivitySnapshot {
@@ -73,8 +87,40 @@ impl BridgeActivitySnapshot {
             waiting_for_activity,
             usb_to_jetson_idle,
             jetson_to_usb_idle,
+            line_coding: LineCoding::DEFAULT,
+            baud_source: BaudSource::Host,
+            stats: BridgeStatistics::new().snapshot(),
+            release: ReleaseCriterion::AnyByte,
         }
     }
+
+    /// Replaces the reported UART framing.
+    #[must_use]
+    pub const fn with_line_coding(mut self, line_coding: LineCoding) -> Self {
+        self.line_coding = line_coding;
+        self
+    }
+
+    /// Replaces the reported baud rate source.
+    #[must_use]
+    pub const fn with_baud_source(mut self, baud_source: BaudSource) -> Self {
+        self.baud_source = baud_source;
+        self
+    }
+
+    /// Replaces the reported traffic counters.
+    #[must_use]
+    pub const fn with_stats(mut self, stats: BridgeStatsSnapshot) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Replaces the reported REC release criterion.
+    #[must_use]
+    pub const fn with_release_criterion(mut self, release: ReleaseCriterion) -> Self {
+        self.release = release;
+        self
+    }
 }
 
 /// Trait describing the instant arithmetic required for status tracking.
@@ -126,6 +172,10 @@ pub struct BridgeActivityTracker<Instant> {
     waiting_for_activity: bool,
     last_rx: Option<Instant>,
     last_tx: Option<Instant>,
+    line_coding: LineCoding,
+    baud_source: BaudSource,
+    stats: BridgeStatistics,
+    release: ReleaseCriterion,
 }
 
 impl<Instant: Copy> BridgeActivityTracker<Instant> {
@@ -136,9 +186,34 @@ impl<Instant: Copy> BridgeActivityTracker<Instant> {
             waiting_for_activity: false,
             last_rx: None,
             last_tx: None,
+            line_coding: LineCoding::DEFAULT,
+            baud_source: BaudSource::Host,
+            stats: BridgeStatistics::new(),
+            release: ReleaseCriterion::AnyByte,
         }
     }
 
+    /// Records the UART framing now applied to the Jetson console.
+    pub fn set_line_coding(&mut self, line_coding: LineCoding) {
+        self.line_coding = line_coding;
+    }
+
+    /// Returns the UART framing last recorded.
+    #[must_use]
+    pub const fn line_coding(&self) -> LineCoding {
+        self.line_coding
+    }
+
+    /// Records how the console baud rate was chosen.
+    pub fn set_baud_source(&mut self, baud_source: BaudSource) {
+        self.baud_source = baud_source;
+    }
+
+    /// Records the criterion that releases REC during `recovery now`.
+    pub fn set_release_criterion(&mut self, release: ReleaseCriterion) {
+        self.release = release;
+    }
+
     /// Updates the waiting flag.
     pub fn set_waiting_for_activity(&mut self, waiting: bool) {
         self.waiting_for_activity = waiting;
@@ -154,6 +229,12 @@ impl<Instant: Copy> BridgeActivityTracker<Instant> {
         self.last_tx = Some(instant);
     }
 
+    /// Returns a mutable handle to the traffic counters.
+    #[must_use]
+    pub fn stats_mut(&mut self) -> &mut BridgeStatistics {
+        &mut self.stats
+    }
+
     /// Clears the tracked history.
     pub fn reset(&mut self) {
         self.waiting_for_activity = false;
@@ -174,6 +255,10 @@ impl<Instant: Copy> BridgeActivityTracker<Instant> {
             self.last_rx
                 .and_then(|instant| Instant::duration_since(now, instant)),
         )
+        .with_line_coding(self.line_coding)
+        .with_baud_source(self.baud_source)
+        .with_stats(self.stats.snapshot())
+        .with_release_criterion(self.release)
     }
 }
 
@@ -293,6 +378,41 @@ const fn strap_bit(id: StrapId) -> u8 {
 pub trait StatusProvider<Instant> {
     /// Returns a snapshot if the platform can currently provide one.
     fn snapshot(&mut self, now: Instant) -> Option<StatusSnapshot>;
+
+    /// Returns the accumulated power-rail statistics, if the platform tracks them.
+    fn rail_stats(&mut self) -> Option<RailStatsSnapshot> {
+        None
+    }
+
+    /// Clears the accumulated power-rail statistics.
+    fn reset_rail_stats(&mut self) {}
+
+    /// Returns the recorded brown-out episodes with ages relative to `now`.
+    fn brown_out_history(&mut self, _now: Instant) -> Option<BrownOutReport> {
+        None
+    }
+
+    /// Returns the bridge traffic and error counters, if the platform tracks them.
+    fn bridge_stats(&mut self) -> Option<BridgeStatsSnapshot> {
+        None
+    }
+
+    /// Clears the bridge traffic and error counters.
+    fn reset_bridge_stats(&mut self) {}
+
+    /// Returns the boot watchdog state, if the platform supervises boots.
+    fn watchdog_status(&mut self, _now: Instant) -> Option<WatchdogStatus> {
+        None
+    }
+
+    /// Applies a watchdog configuration change and returns the resulting state.
+    fn update_watchdog(
+        &mut self,
+        _update: WatchdogUpdate,
+        _now: Instant,
+    ) -> Option<WatchdogStatus> {
+        None
+    }
 }
 
 /// Placeholder status provider that never reports snapshots.
@@ -366,7 +486,7 @@ impl<'a> StatusFormatter<'a> {
         Ok(())
     }
 
-    /// Writes the bridge line (e.g. `bridge waiting=false rx=+1.2s tx=n/a`).
+    /// Writes the bridge line (e.g. `bridge waiting=false rx=+1.2s tx=n/a uart=115200-8N1 baud=host release=any`).
     ///
     /// # Errors
     /// Returns [`fmt::Error`] if the formatter reports a failure while
@@ -387,8 +507,29 @@ impl<'a> StatusFormatter<'a> {
         writer.write_str("tx=")?;
         write_duration(writer, self.snapshot.bridge.usb_to_jetson_idle)?;
 
+        write!(writer, " uart={}", self.snapshot.bridge.line_coding)?;
+        write!(writer, " baud={}", self.snapshot.bridge.baud_source)?;
+        write!(writer, " release={}", self.snapshot.bridge.release)?;
+
         Ok(())
     }
+
+    /// Writes the bridge counter summary (e.g. `bridge traffic rx-bytes=812 tx-bytes=6 errors=0 dropped=0`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the formatter reports a failure while
+    /// writing the line.
+    pub fn write_bridge_stats_line<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        let stats = &self.snapshot.bridge.stats;
+        write!(
+            writer,
+            "bridge traffic rx-bytes={} tx-bytes={} errors={} dropped={}",
+            stats.jetson_to_usb.bytes,
+            stats.usb_to_jetson.bytes,
+            stats.error_count(),
+            stats.dropped_frames()
+        )
+    }
 }
 
 fn write_duration<W: fmt::Write>(writer: &mut W, duration: Option<Duration>) -> fmt::Result {
@@ -472,5 +613,55 @@ mod tests {
             Some(Duration::from_micros(3))
         );
         assert!(snapshot.bridge.waiting_for_activity);
+        assert_eq!(snapshot.bridge.line_coding, LineCoding::DEFAULT);
+    }
+
+    #[test]
+    fn bridge_line_reports_active_uart_framing() {
+        let mut accumulator = StatusAccumulator::<FakeInstant>::new();
+        let Ok(coding) = LineCoding::from_cdc(1_500_000, 0, 2, 8) else {
+            panic!("8E1 should be accepted");
+        };
+        accumulator.bridge_tracker().set_line_coding(coding);
+        accumulator
+            .bridge_tracker()
+            .set_baud_source(BaudSource::Detected);
+        accumulator
+            .bridge_tracker()
+            .set_release_criterion(ReleaseCriterion::ByteCount {
+                bytes: 16,
+                window: Duration::from_millis(100),
+            });
+
+        let snapshot = accumulator.snapshot(FakeInstant(0));
+        let mut line = heapless::String::<96>::new();
+        let Ok(()) = StatusFormatter::new(&snapshot).write_bridge_line(&mut line) else {
+            panic!("bridge line should fit");
+        };
+        assert_eq!(
+            line.as_str(),
+            "bridge waiting=false rx=n/a tx=n/a uart=1500000-8E1 baud=auto release=bytes:16/100ms"
+        );
+    }
+
+    #[test]
+    fn bridge_stats_line_summarizes_counters() {
+        let mut accumulator = StatusAccumulator::<FakeInstant>::new();
+        {
+            let stats = accumulator.bridge_tracker().stats_mut();
+            stats.record_frame(crate::bridge::BridgeActivityKind::JetsonToUsb, 812);
+            stats.record_frame(crate::bridge::BridgeActivityKind::UsbToJetson, 6);
+            stats.record_fault(crate::bridge::stats::BridgeFault::UartFraming);
+        }
+
+        let snapshot = accumulator.snapshot(FakeInstant(0));
+        let mut line = heapless::String::<96>::new();
+        let Ok(()) = StatusFormatter::new(&snapshot).write_bridge_stats_line(&mut line) else {
+            panic!("stats line should fit");
+        };
+        assert_eq!(
+            line.as_str(),
+            "bridge traffic rx-bytes=812 tx-bytes=6 errors=1 dropped=0"
+        );
     }
 }
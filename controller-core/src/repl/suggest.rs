@@ -0,0 +1,150 @@
+//! "Did you mean" suggestions for mistyped keywords.
+//!
+//! When a word is not one the catalog allows at its position, the parser
+//! reports the allowed words closest to it by edit distance (insertions,
+//! deletions, substitutions, and swaps of adjacent letters, ignoring ASCII
+//! case). Distances are computed with fixed-size rows on the stack, so words
+//! longer than [`MAX_WORD_LEN`] are never matched.
+
+use core::fmt;
+
+use heapless::Vec;
+
+/// Suggestions kept for one unknown word.
+pub const MAX_SUGGESTIONS: usize = 3;
+/// Longest word compared.
+pub const MAX_WORD_LEN: usize = 24;
+
+/// Allowed words closest to an unknown one, in catalog order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Suggestions {
+    words: Vec<&'static str, MAX_SUGGESTIONS>,
+}
+
+impl Suggestions {
+    /// Keeps the `candidates` nearest to `word`, provided they are close
+    /// enough to be a typo: one edit for words of up to four letters, two for
+    /// longer ones.
+    #[must_use]
+    pub fn closest(word: &str, candidates: impl IntoIterator<Item = &'static str>) -> Self {
+        let limit = if word.len() <= 4 { 1 } else { 2 };
+        let mut best = limit;
+        let mut words = Vec::new();
+        for candidate in candidates {
+            let Some(distance) = distance(word, candidate) else {
+                continue;
+            };
+            if distance > best {
+                continue;
+            }
+            if distance < best {
+                best = distance;
+                words.clear();
+            }
+            // Ties beyond the first few are dropped.
+            let _ = words.push(candidate);
+        }
+        Self { words }
+    }
+
+    /// Returns the suggested words.
+    #[must_use]
+    pub fn as_slice(&self) -> &[&'static str] {
+        &self.words
+    }
+
+    /// Returns `true` when nothing was close enough.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Lists the words as `'a'`, `'a' or 'b'`, or `'a', 'b' or 'c'`.
+impl fmt::Display for Suggestions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.words.len();
+        for (index, word) in self.words.iter().enumerate() {
+            if index > 0 {
+                f.write_str(if index + 1 == count { " or " } else { ", " })?;
+            }
+            write!(f, "'{word}'")?;
+        }
+        Ok(())
+    }
+}
+
+/// Optimal string alignment distance between `word` and `candidate`, or
+/// `None` when either is longer than [`MAX_WORD_LEN`].
+fn distance(word: &str, candidate: &str) -> Option<usize> {
+    let (word, candidate) = (word.as_bytes(), candidate.as_bytes());
+    if word.len() > MAX_WORD_LEN || candidate.len() > MAX_WORD_LEN {
+        return None;
+    }
+
+    // Rows for the previous two prefixes of `word` and the current one.
+    let mut before = [0_u8; MAX_WORD_LEN + 1];
+    let mut previous = [0_u8; MAX_WORD_LEN + 1];
+    let mut current = [0_u8; MAX_WORD_LEN + 1];
+    for (slot, value) in previous.iter_mut().zip(0..) {
+        *slot = value;
+    }
+
+    let mut row = 0;
+    for (i, &left) in word.iter().enumerate() {
+        row += 1;
+        current[0] = row;
+        for (j, &right) in candidate.iter().enumerate() {
+            let cost = u8::from(!left.eq_ignore_ascii_case(&right));
+            let mut value = (previous[j + 1] + 1)
+                .min(current[j] + 1)
+                .min(previous[j] + cost);
+            let swapped = i > 0
+                && j > 0
+                && left.eq_ignore_ascii_case(&candidate[j - 1])
+                && word[i - 1].eq_ignore_ascii_case(&right);
+            if swapped {
+                value = value.min(before[j - 1] + 1);
+            }
+            current[j + 1] = value;
+        }
+        before = previous;
+        previous = current;
+    }
+    Some(usize::from(previous[candidate.len()]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn suggest(word: &str, candidates: &[&'static str]) -> std::string::String {
+        std::format!("{}", Suggestions::closest(word, candidates.iter().copied()))
+    }
+
+    #[test]
+    fn measures_edits_and_adjacent_swaps() {
+        assert_eq!(distance("rebot", "reboot"), Some(1));
+        assert_eq!(distance("REBOOT", "reboot"), Some(0));
+        assert_eq!(distance("rebtoo", "reboot"), Some(2));
+        assert_eq!(distance("reobot", "reboot"), Some(1));
+        assert_eq!(distance("", "now"), Some(3));
+        assert_eq!(distance("a".repeat(25).as_str(), "a"), None);
+    }
+
+    #[test]
+    fn keeps_only_the_closest_words_within_the_limit() {
+        let commands = ["reboot", "recovery", "fault", "power", "status"];
+        assert_eq!(suggest("rebot", &commands), "'reboot'");
+        assert_eq!(suggest("stats", &commands), "'status'");
+        assert_eq!(suggest("xyz", &commands), "");
+        // Short words only tolerate one edit.
+        assert_eq!(suggest("fat", &["fault"]), "");
+
+        assert_eq!(suggest("of", &["on", "off", "now"]), "'on' or 'off'");
+        assert_eq!(
+            suggest("ab", &["a", "b", "abc", "abd"]),
+            "'a', 'b' or 'abc'"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+//! Request tags echoed on response lines.
+//!
+//! A host that prefixes a command with `@<n>` gets that tag back on every line
+//! the command produces: `OK@17 ...`, `ERR@17 ...`, and `DONE@17 ...` carry it
+//! after the status word, JSON objects gain a leading `"tag"` member, and any
+//! other report line is prefixed with `@17 `. [`TaggedLine`] splices the tag
+//! into a finished line so the firmware can send the pieces from its frame
+//! buffers and the emulator can format the whole line.
+
+use core::fmt;
+
+use crate::orchestrator::RequestTag;
+
+/// Buffer size that holds the longest [`TagMarker`].
+pub const TAG_MARKER_CAPACITY: usize = 20;
+
+/// Status words that take the tag directly after them.
+const STATUS_WORDS: [&str; 3] = ["OK", "ERR", "DONE"];
+
+/// A response line split around the place its tag goes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TaggedLine<'a> {
+    head: &'a str,
+    marker: TagMarker,
+    tail: &'a str,
+}
+
+impl<'a> TaggedLine<'a> {
+    /// Locates the tag position in `line`; untagged lines pass through as is.
+    #[must_use]
+    pub fn new(line: &'a str, tag: Option<RequestTag>) -> Self {
+        let Some(tag) = tag else {
+            return Self {
+                head: line,
+                marker: TagMarker::None,
+                tail: "",
+            };
+        };
+        if let Some(rest) = line.strip_prefix('{') {
+            return Self {
+                head: "{",
+                marker: TagMarker::Member(tag),
+                tail: rest,
+            };
+        }
+        for word in STATUS_WORDS {
+            if let Some(rest) = line.strip_prefix(word)
+                && (rest.is_empty() || rest.starts_with(' '))
+            {
+                return Self {
+                    head: word,
+                    marker: TagMarker::Suffix(tag),
+                    tail: rest,
+                };
+            }
+        }
+        Self {
+            head: "",
+            marker: TagMarker::Prefix(tag),
+            tail: line,
+        }
+    }
+
+    /// Text before the tag.
+    #[must_use]
+    pub const fn head(&self) -> &'a str {
+        self.head
+    }
+
+    /// The tag as it appears in this line.
+    #[must_use]
+    pub const fn marker(&self) -> TagMarker {
+        self.marker
+    }
+
+    /// Text after the tag.
+    #[must_use]
+    pub const fn tail(&self) -> &'a str {
+        self.tail
+    }
+}
+
+impl fmt::Display for TaggedLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}{}", self.head, self.marker, self.tail)
+    }
+}
+
+/// How a tag is written into a line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagMarker {
+    /// The line is untagged.
+    None,
+    /// `@17` after a status word.
+    Suffix(RequestTag),
+    /// `"tag":17,` at the start of a JSON object.
+    Member(RequestTag),
+    /// `@17 ` before a report line.
+    Prefix(RequestTag),
+}
+
+impl fmt::Display for TagMarker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagMarker::None => Ok(()),
+            TagMarker::Suffix(tag) => write!(f, "@{tag}"),
+            TagMarker::Member(tag) => write!(f, "\"tag\":{tag},"),
+            TagMarker::Prefix(tag) => write!(f, "@{tag} "),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::string::{String, ToString};
+
+    fn tagged(line: &str) -> String {
+        TaggedLine::new(line, Some(RequestTag::new(17))).to_string()
+    }
+
+    #[test]
+    fn tags_status_words_json_and_report_lines() {
+        assert_eq!(tagged("OK reboot"), "OK@17 reboot");
+        assert_eq!(tagged("ERR syntax x"), "ERR@17 syntax x");
+        assert_eq!(tagged("DONE normal-reboot"), "DONE@17 normal-reboot");
+        assert_eq!(tagged("OK"), "OK@17");
+        assert_eq!(tagged(r#"{"type":"ok"}"#), r#"{"tag":17,"type":"ok"}"#);
+        assert_eq!(
+            tagged("straps RESET*=released"),
+            "@17 straps RESET*=released"
+        );
+        assert_eq!(tagged("OKAY then"), "@17 OKAY then");
+        assert_eq!(TaggedLine::new("OK reboot", None).to_string(), "OK reboot");
+    }
+
+    #[test]
+    fn longest_marker_fits_the_capacity() {
+        let tag = RequestTag::new(u32::MAX);
+        for marker in [
+            TagMarker::Suffix(tag),
+            TagMarker::Member(tag),
+            TagMarker::Prefix(tag),
+        ] {
+            assert!(marker.to_string().len() <= TAG_MARKER_CAPACITY);
+        }
+    }
+}
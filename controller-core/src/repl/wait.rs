@@ -0,0 +1,260 @@
+//! Blocking `wait` conditions shared by the firmware and emulator REPLs.
+//!
+//! `wait complete`, `wait idle`, and `wait event` hold the session until a
+//! strap sequence ends, the orchestrator has nothing left to run, or a
+//! telemetry event of the named kind is recorded. [`PendingWait`] keeps the
+//! condition and its deadline; the platform feeds it what it observes and
+//! prints the [`WaitReport`] or [`WaitTimeout`] it hands back.
+
+use core::fmt;
+use core::time::Duration;
+
+use crate::orchestrator::{RequestTag, SequenceCompletion};
+use crate::telemetry::{TelemetryInstant, TelemetryNotice};
+
+/// Longest a `wait` holds the session when no `timeout=` is given.
+pub const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// What a `wait` command waits for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitCondition {
+    /// The next strap sequence to end, or the one queued by the request
+    /// carrying this tag.
+    Complete(Option<RequestTag>),
+    /// No strap sequence running or queued.
+    Idle,
+    /// The next telemetry event with this name from
+    /// [`TelemetryEventKind::NAMES`](crate::telemetry::TelemetryEventKind::NAMES).
+    Event(&'static str),
+}
+
+impl WaitCondition {
+    /// Returns `complete`, `idle`, or `event`.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            WaitCondition::Complete(_) => "complete",
+            WaitCondition::Idle => "idle",
+            WaitCondition::Event(_) => "event",
+        }
+    }
+}
+
+impl fmt::Display for WaitCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaitCondition::Complete(Some(tag)) => write!(f, "complete {}", tag.get()),
+            WaitCondition::Event(name) => write!(f, "event {name}"),
+            WaitCondition::Complete(None) | WaitCondition::Idle => f.write_str(self.label()),
+        }
+    }
+}
+
+/// What ended a wait.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WaitOutcome {
+    Complete(SequenceCompletion),
+    Idle,
+    Event(TelemetryNotice),
+}
+
+/// Successful end of a wait, printed as `OK wait <report>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaitReport {
+    pub outcome: WaitOutcome,
+    /// Time the session was held.
+    pub waited: Duration,
+}
+
+impl fmt::Display for WaitReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.outcome {
+            WaitOutcome::Complete(done) => write!(f, "complete {done}")?,
+            WaitOutcome::Idle => f.write_str("idle")?,
+            WaitOutcome::Event(notice) => write!(
+                f,
+                "event {} t={}ms",
+                notice.event,
+                notice.uptime.as_millis()
+            )?,
+        }
+        write!(f, " waited={}ms", self.waited.as_millis())
+    }
+}
+
+/// A wait that ran out of time, printed as `ERR timeout <condition> waited=<ms>ms`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaitTimeout {
+    pub condition: WaitCondition,
+    pub waited: Duration,
+}
+
+impl fmt::Display for WaitTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} waited={}ms", self.condition, self.waited.as_millis())
+    }
+}
+
+/// A `wait` in progress.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PendingWait<I> {
+    condition: WaitCondition,
+    timeout: Duration,
+    started_at: I,
+}
+
+impl<I: TelemetryInstant + Copy> PendingWait<I> {
+    /// Starts waiting for `condition` at `now`; `timeout` defaults to
+    /// [`DEFAULT_WAIT_TIMEOUT`].
+    #[must_use]
+    pub fn new(condition: WaitCondition, timeout: Option<Duration>, now: I) -> Self {
+        Self {
+            condition,
+            timeout: timeout.unwrap_or(DEFAULT_WAIT_TIMEOUT),
+            started_at: now,
+        }
+    }
+
+    /// Returns the condition being waited for.
+    #[must_use]
+    pub const fn condition(&self) -> WaitCondition {
+        self.condition
+    }
+
+    /// Returns the report when `done` ends a `wait complete`.
+    #[must_use]
+    pub fn on_completion(&self, done: &SequenceCompletion, now: I) -> Option<WaitReport> {
+        match self.condition {
+            WaitCondition::Complete(tag) if tag.is_none() || tag == done.tag => {
+                Some(self.report(WaitOutcome::Complete(*done), now))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the report when `notice` ends a `wait event`.
+    #[must_use]
+    pub fn on_event(&self, notice: &TelemetryNotice, now: I) -> Option<WaitReport> {
+        match self.condition {
+            WaitCondition::Event(name) if notice.event.name() == name => {
+                Some(self.report(WaitOutcome::Event(*notice), now))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the report when the orchestrator being `idle` ends a `wait idle`.
+    #[must_use]
+    pub fn on_idle(&self, idle: bool, now: I) -> Option<WaitReport> {
+        match self.condition {
+            WaitCondition::Idle if idle => Some(self.report(WaitOutcome::Idle, now)),
+            _ => None,
+        }
+    }
+
+    /// Returns the timeout once the wait has run out of time.
+    #[must_use]
+    pub fn expired(&self, now: I) -> Option<WaitTimeout> {
+        let waited = now.saturating_duration_since(self.started_at);
+        (waited >= self.timeout).then_some(WaitTimeout {
+            condition: self.condition,
+            waited,
+        })
+    }
+
+    fn report(&self, outcome: WaitOutcome, now: I) -> WaitReport {
+        WaitReport {
+            outcome,
+            waited: now.saturating_duration_since(self.started_at),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::orchestrator::{SequenceOutcome, SequenceResult};
+    use crate::sequences::{StrapId, StrapSequenceKind};
+    use crate::telemetry::test_support::MillisInstant;
+    use crate::telemetry::{TelemetryEventKind, TelemetryPayload};
+
+    fn completion(tag: Option<u32>) -> SequenceCompletion {
+        SequenceCompletion {
+            tag: tag.map(RequestTag::new),
+            kind: StrapSequenceKind::NormalReboot,
+            result: SequenceResult::Finished(SequenceOutcome::Completed),
+            elapsed: Duration::from_millis(1_220),
+        }
+    }
+
+    #[test]
+    fn complete_waits_match_the_requested_tag() {
+        let any = PendingWait::new(WaitCondition::Complete(None), None, MillisInstant(100));
+        let report = any
+            .on_completion(&completion(None), MillisInstant(900))
+            .expect("any completion ends an untagged wait");
+        assert_eq!(
+            std::format!("{report}"),
+            "complete normal-reboot completed elapsed=1220ms waited=800ms"
+        );
+
+        let tagged = PendingWait::new(
+            WaitCondition::Complete(Some(RequestTag::new(17))),
+            None,
+            MillisInstant(100),
+        );
+        assert!(
+            tagged
+                .on_completion(&completion(Some(4)), MillisInstant(900))
+                .is_none()
+        );
+        assert!(
+            tagged
+                .on_completion(&completion(None), MillisInstant(900))
+                .is_none()
+        );
+        assert!(
+            tagged
+                .on_completion(&completion(Some(17)), MillisInstant(900))
+                .is_some()
+        );
+        assert!(tagged.on_idle(true, MillisInstant(900)).is_none());
+    }
+
+    #[test]
+    fn event_waits_match_by_name_and_time_out() {
+        let pending = PendingWait::new(
+            WaitCondition::Event("strap-released"),
+            Some(Duration::from_secs(2)),
+            MillisInstant(0),
+        );
+        let asserted = TelemetryNotice {
+            id: 3,
+            uptime: Duration::from_millis(400),
+            event: TelemetryEventKind::StrapAsserted(StrapId::Rec),
+            details: TelemetryPayload::None,
+        };
+        let released = TelemetryNotice {
+            event: TelemetryEventKind::StrapReleased(StrapId::Rec),
+            ..asserted
+        };
+        assert!(pending.on_event(&asserted, MillisInstant(400)).is_none());
+        let report = pending
+            .on_event(&released, MillisInstant(400))
+            .expect("matching event ends the wait");
+        assert_eq!(
+            std::format!("{report}"),
+            "event strap-released REC* t=400ms waited=400ms"
+        );
+
+        assert!(pending.expired(MillisInstant(1_999)).is_none());
+        let timeout = pending
+            .expired(MillisInstant(2_000))
+            .expect("deadline reached");
+        assert_eq!(
+            std::format!("{timeout}"),
+            "event strap-released waited=2000ms"
+        );
+    }
+}
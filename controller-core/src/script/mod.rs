@@ -0,0 +1,986 @@
+//! Expect-style console automation scripts.
+//!
+//! A script is a short list of steps loaded through the REPL one line at a
+//! time (`script load <name>` ... `end`) and kept in a small fixed library.
+//! [`ScriptEngine`] runs one script at a time against the Jetson console byte
+//! stream: `send` text and strap sequence steps are handed back to the
+//! platform as [`ScriptEvent`]s, `expect` steps wait for a pattern in the bytes
+//! passed to [`ScriptEngine::feed`], and an expect that times out either
+//! branches to a label or fails the run. Every expect and every finished run
+//! produces a [`ScriptReport`] that the platform records as telemetry.
+//!
+//! One step per line:
+//!
+//! ```text
+//! send "<text>"                       type text (same escapes as `console send`)
+//! expect "<text>" [<duration>] [else <label>]
+//! timeout <duration>                  default wait for later expects
+//! label <name> / goto <name>
+//! reboot | recovery | fault           queue a strap sequence
+//! done | fail                         finish the run early
+//! ```
+
+use core::fmt;
+use core::time::Duration;
+
+use heapless::{String, Vec};
+
+use crate::bridge::inject;
+use crate::power::saturating_millis;
+use crate::repl::grammar::{self, Token, TokenKind};
+use crate::sequences::StrapSequenceKind;
+use crate::telemetry::TelemetryInstant;
+
+/// Number of scripts the library holds.
+pub const MAX_SCRIPTS: usize = 4;
+/// Steps per script, not counting labels.
+pub const MAX_SCRIPT_STEPS: usize = 24;
+/// Labels (and references to them) per script.
+pub const MAX_SCRIPT_LABELS: usize = 8;
+/// Longest `send` or `expect` text after escapes are decoded.
+pub const SCRIPT_TEXT_CAPACITY: usize = 32;
+/// Longest script or label name.
+pub const SCRIPT_NAME_CAPACITY: usize = 16;
+/// Expect timeout used until a script sets its own.
+pub const DEFAULT_EXPECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Text sent to or expected from the console.
+pub type ScriptText = Vec<u8, SCRIPT_TEXT_CAPACITY>;
+/// Script or label name.
+pub type ScriptName = String<SCRIPT_NAME_CAPACITY>;
+
+/// Reasons a script cannot be loaded or started.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ScriptError {
+    /// The line is not a recognised step.
+    Syntax,
+    /// `send`/`expect` text exceeds [`SCRIPT_TEXT_CAPACITY`] or is empty.
+    Text,
+    /// More than [`MAX_SCRIPT_STEPS`] steps.
+    TooManySteps,
+    /// More than [`MAX_SCRIPT_LABELS`] labels or label references.
+    TooManyLabels,
+    /// A label was defined twice.
+    DuplicateLabel,
+    /// A `goto` or `else` names a label that was never defined.
+    UnknownLabel,
+    /// All [`MAX_SCRIPTS`] slots hold other scripts.
+    LibraryFull,
+    /// No script has that name.
+    NotFound,
+    /// A script is already running (or the named one is, when reloading).
+    Busy,
+    /// No `script load` is in progress.
+    NotLoading,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ScriptError::Syntax => "unrecognised step",
+            ScriptError::Text => "text empty or too long",
+            ScriptError::TooManySteps => "too many steps",
+            ScriptError::TooManyLabels => "too many labels",
+            ScriptError::DuplicateLabel => "duplicate label",
+            ScriptError::UnknownLabel => "unknown label",
+            ScriptError::LibraryFull => "library full",
+            ScriptError::NotFound => "no such script",
+            ScriptError::Busy => "script running",
+            ScriptError::NotLoading => "not loading",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Error raised while loading, tagged with the script line (1-based).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct LoadError {
+    pub line: u8,
+    pub error: ScriptError,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+/// Progress reported for each line fed to [`ScriptEngine::load_line`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LoadProgress {
+    /// The line was accepted; more are expected.
+    Pending,
+    /// `end` stored the script.
+    Loaded { name: ScriptName, steps: u8 },
+}
+
+/// How an expect step or a whole run ended.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ScriptResult {
+    /// The expected text arrived.
+    Matched,
+    /// The expect timed out.
+    TimedOut,
+    /// The run reached its last step or `done`.
+    Passed,
+    /// The run hit `fail`, an unhandled timeout, or a runaway loop.
+    Failed,
+    /// The run was stopped from the REPL or by the platform.
+    Stopped,
+}
+
+impl ScriptResult {
+    /// `true` for results that end a run rather than a single expect.
+    #[must_use]
+    pub const fn is_final(self) -> bool {
+        matches!(
+            self,
+            ScriptResult::Passed | ScriptResult::Failed | ScriptResult::Stopped
+        )
+    }
+}
+
+impl fmt::Display for ScriptResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            ScriptResult::Matched => "matched",
+            ScriptResult::TimedOut => "timed-out",
+            ScriptResult::Passed => "passed",
+            ScriptResult::Failed => "failed",
+            ScriptResult::Stopped => "stopped",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Outcome of an expect step or a finished run.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScriptReport {
+    /// Library slot of the script.
+    pub slot: u8,
+    pub name: ScriptName,
+    /// Script line of the step that produced the report.
+    pub line: u8,
+    pub result: ScriptResult,
+    /// Time spent waiting (expects) or since the run started (final results).
+    pub elapsed: Duration,
+}
+
+impl fmt::Display for ScriptReport {
+    /// Formats as `script login matched line=3 elapsed=1200ms`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "script {} {} line={} elapsed={}ms",
+            self.name,
+            self.result,
+            self.line,
+            saturating_millis(self.elapsed)
+        )
+    }
+}
+
+/// Work the platform must carry out for the running script.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScriptEvent {
+    /// Write these bytes to the Jetson console.
+    Send(ScriptText),
+    /// Queue a strap sequence.
+    Sequence(StrapSequenceKind),
+    /// An expect step matched or timed out.
+    Expect(ScriptReport),
+    /// The run ended; the engine is idle again.
+    Finished(ScriptReport),
+}
+
+/// Library entry as shown by `script list`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScriptSummary {
+    pub name: ScriptName,
+    pub steps: u8,
+    /// Script line the run is on, when this script is running.
+    pub running_line: Option<u8>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Op {
+    Send(ScriptText),
+    Expect {
+        pattern: ScriptText,
+        timeout: Option<Duration>,
+        otherwise: Option<u8>,
+    },
+    Timeout(Duration),
+    Goto(u8),
+    Sequence(StrapSequenceKind),
+    Done,
+    Fail,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Step {
+    line: u8,
+    op: Op,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Script {
+    name: ScriptName,
+    steps: Vec<Step, MAX_SCRIPT_STEPS>,
+    end_line: u8,
+}
+
+/// Parsed form of one script line before labels are resolved.
+enum Line {
+    Blank,
+    End,
+    Label(ScriptName),
+    Op { op: Op, target: Option<ScriptName> },
+}
+
+struct ScriptBuilder {
+    name: ScriptName,
+    steps: Vec<Step, MAX_SCRIPT_STEPS>,
+    labels: Vec<(ScriptName, u8), MAX_SCRIPT_LABELS>,
+    references: Vec<(u8, ScriptName), MAX_SCRIPT_LABELS>,
+    line: u8,
+}
+
+impl ScriptBuilder {
+    const fn new(name: ScriptName) -> Self {
+        Self {
+            name,
+            steps: Vec::new(),
+            labels: Vec::new(),
+            references: Vec::new(),
+            line: 0,
+        }
+    }
+
+    fn push(&mut self, line: Line) -> Result<(), ScriptError> {
+        let next = u8::try_from(self.steps.len()).map_err(|_| ScriptError::TooManySteps)?;
+        match line {
+            Line::Blank | Line::End => Ok(()),
+            Line::Label(name) => {
+                if self.labels.iter().any(|(label, _)| *label == name) {
+                    return Err(ScriptError::DuplicateLabel);
+                }
+                self.labels
+                    .push((name, next))
+                    .map_err(|_| ScriptError::TooManyLabels)
+            }
+            Line::Op { op, target } => {
+                if let Some(target) = target {
+                    self.references
+                        .push((next, target))
+                        .map_err(|_| ScriptError::TooManyLabels)?;
+                }
+                self.steps
+                    .push(Step {
+                        line: self.line,
+                        op,
+                    })
+                    .map_err(|_| ScriptError::TooManySteps)
+            }
+        }
+    }
+
+    fn finish(mut self) -> Result<Script, ScriptError> {
+        for (index, target) in &self.references {
+            let (_, position) = self
+                .labels
+                .iter()
+                .find(|(label, _)| label == target)
+                .ok_or(ScriptError::UnknownLabel)?;
+            match &mut self.steps[usize::from(*index)].op {
+                Op::Goto(slot)
+                | Op::Expect {
+                    otherwise: Some(slot),
+                    ..
+                } => *slot = *position,
+                _ => {}
+            }
+        }
+
+        Ok(Script {
+            name: self.name,
+            steps: self.steps,
+            end_line: self.line,
+        })
+    }
+}
+
+struct ExpectWait<TInstant> {
+    since: TInstant,
+    timeout: Duration,
+    matched: bool,
+}
+
+struct ScriptRun<TInstant> {
+    slot: u8,
+    pc: u8,
+    started: TInstant,
+    timeout: Duration,
+    wait: Option<ExpectWait<TInstant>>,
+    failed: bool,
+}
+
+/// Script library plus the interpreter for the one running script.
+pub struct ScriptEngine<TInstant> {
+    scripts: Vec<Script, MAX_SCRIPTS>,
+    loading: Option<ScriptBuilder>,
+    run: Option<ScriptRun<TInstant>>,
+    window: ScriptText,
+}
+
+impl<TInstant> ScriptEngine<TInstant>
+where
+    TInstant: TelemetryInstant,
+{
+    /// Creates an empty library with nothing running.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            scripts: Vec::new(),
+            loading: None,
+            run: None,
+            window: Vec::new(),
+        }
+    }
+
+    /// Starts collecting lines for `name`, discarding any unfinished load.
+    ///
+    /// # Errors
+    /// Returns [`ScriptError::Busy`] while a script with that name is running.
+    pub fn begin_load(&mut self, name: ScriptName) -> Result<(), ScriptError> {
+        if self
+            .running_slot()
+            .is_some_and(|slot| self.slot_of(&name) == Some(slot))
+        {
+            return Err(ScriptError::Busy);
+        }
+        self.loading = Some(ScriptBuilder::new(name));
+        Ok(())
+    }
+
+    /// Returns `true` while `script load` is collecting lines.
+    #[must_use]
+    pub fn is_loading(&self) -> bool {
+        self.loading.is_some()
+    }
+
+    /// Adds one line to the script being loaded; `end` stores it.
+    ///
+    /// Any error abandons the load so a half-read script is never stored.
+    ///
+    /// # Errors
+    /// Returns a [`LoadError`] naming the offending line.
+    pub fn load_line(&mut self, text: &str) -> Result<LoadProgress, LoadError> {
+        let Some(builder) = self.loading.as_mut() else {
+            return Err(LoadError {
+                line: 0,
+                error: ScriptError::NotLoading,
+            });
+        };
+        builder.line = builder.line.saturating_add(1);
+        let line = builder.line;
+        let fail = |error| LoadError { line, error };
+
+        let parsed = match parse_line(text) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                self.loading = None;
+                return Err(fail(error));
+            }
+        };
+        let end = matches!(parsed, Line::End);
+        if let Err(error) = builder.push(parsed) {
+            self.loading = None;
+            return Err(fail(error));
+        }
+        if !end {
+            return Ok(LoadProgress::Pending);
+        }
+
+        let Some(builder) = self.loading.take() else {
+            return Ok(LoadProgress::Pending);
+        };
+        let script = builder.finish().map_err(fail)?;
+        self.store(script).map_err(fail)
+    }
+
+    /// Lists the stored scripts in slot order.
+    pub fn summaries(&self) -> impl Iterator<Item = ScriptSummary> + '_ {
+        let running = self.running_slot();
+        self.scripts.iter().enumerate().map(move |(slot, script)| {
+            let running_line = self
+                .run
+                .as_ref()
+                .filter(|_| running.map(usize::from) == Some(slot))
+                .map(|run| Self::line_at(script, run.pc));
+            ScriptSummary {
+                name: script.name.clone(),
+                steps: u8::try_from(script.steps.len()).unwrap_or(u8::MAX),
+                running_line,
+            }
+        })
+    }
+
+    /// Returns `true` while a script is running.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.run.is_some()
+    }
+
+    /// Starts the named script.
+    ///
+    /// # Errors
+    /// Returns [`ScriptError::Busy`] if a script is already running and
+    /// [`ScriptError::NotFound`] for an unknown name.
+    pub fn start(&mut self, name: &str, now: TInstant) -> Result<(), ScriptError> {
+        if self.run.is_some() {
+            return Err(ScriptError::Busy);
+        }
+        let slot = self.slot_of(name).ok_or(ScriptError::NotFound)?;
+        self.window.clear();
+        self.run = Some(ScriptRun {
+            slot,
+            pc: 0,
+            started: now,
+            timeout: DEFAULT_EXPECT_TIMEOUT,
+            wait: None,
+            failed: false,
+        });
+        Ok(())
+    }
+
+    /// Stops the running script, returning its final report.
+    pub fn stop(&mut self, now: TInstant) -> Option<ScriptReport> {
+        self.finish(ScriptResult::Stopped, now)
+    }
+
+    /// Scans console output for the pending expect.
+    ///
+    /// Output is buffered between expects, so text that arrives just before
+    /// the expect step runs still counts. Returns `true` when the pending
+    /// expect matched and the engine should be polled.
+    pub fn feed(&mut self, bytes: &[u8]) -> bool {
+        let Some(run) = self.run.as_mut() else {
+            return false;
+        };
+        let pattern = match (&run.wait, self.scripts.get(usize::from(run.slot))) {
+            (Some(wait), Some(script)) if !wait.matched => {
+                match script.steps.get(usize::from(run.pc)).map(|step| &step.op) {
+                    Some(Op::Expect { pattern, .. }) => Some(pattern),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        let mut matched = false;
+        for &byte in bytes {
+            if self.window.is_full() {
+                self.window.remove(0);
+            }
+            let _ = self.window.push(byte);
+            if let Some(pattern) = pattern
+                && !matched
+                && self.window.ends_with(pattern)
+            {
+                matched = true;
+                self.window.clear();
+            }
+        }
+
+        if matched && let Some(wait) = run.wait.as_mut() {
+            wait.matched = true;
+        }
+        matched
+    }
+
+    /// Advances the running script and returns the next event, if any.
+    ///
+    /// Call repeatedly until it returns `None`, then again after new console
+    /// output or once time has passed.
+    pub fn poll(&mut self, now: TInstant) -> Option<ScriptEvent> {
+        // Labels and gotos alone never wait, so a loop without an expect
+        // would spin forever; cap the steps taken per call.
+        for _ in 0..=MAX_SCRIPT_STEPS {
+            let run = self.run.as_mut()?;
+            if run.failed {
+                return self
+                    .finish(ScriptResult::Failed, now)
+                    .map(ScriptEvent::Finished);
+            }
+            let script = &self.scripts[usize::from(run.slot)];
+
+            if let Some(wait) = &run.wait {
+                let waited = now.saturating_duration_since(wait.since);
+                let Some(Step {
+                    line,
+                    op: Op::Expect { otherwise, .. },
+                    ..
+                }) = script.steps.get(usize::from(run.pc))
+                else {
+                    run.wait = None;
+                    continue;
+                };
+                let result = if wait.matched {
+                    run.pc += 1;
+                    ScriptResult::Matched
+                } else if waited >= wait.timeout {
+                    match otherwise {
+                        Some(target) => run.pc = *target,
+                        None => run.failed = true,
+                    }
+                    ScriptResult::TimedOut
+                } else {
+                    return None;
+                };
+                run.wait = None;
+                return Some(ScriptEvent::Expect(ScriptReport {
+                    slot: run.slot,
+                    name: script.name.clone(),
+                    line: *line,
+                    result,
+                    elapsed: waited,
+                }));
+            }
+
+            let Some(step) = script.steps.get(usize::from(run.pc)) else {
+                return self
+                    .finish(ScriptResult::Passed, now)
+                    .map(ScriptEvent::Finished);
+            };
+            match &step.op {
+                Op::Send(text) => {
+                    run.pc += 1;
+                    return Some(ScriptEvent::Send(text.clone()));
+                }
+                Op::Sequence(kind) => {
+                    run.pc += 1;
+                    return Some(ScriptEvent::Sequence(*kind));
+                }
+                Op::Expect {
+                    pattern, timeout, ..
+                } => {
+                    let buffered = self
+                        .window
+                        .windows(pattern.len())
+                        .any(|candidate| candidate == pattern.as_slice());
+                    if buffered {
+                        self.window.clear();
+                    }
+                    run.wait = Some(ExpectWait {
+                        since: now,
+                        timeout: timeout.unwrap_or(run.timeout),
+                        matched: buffered,
+                    });
+                }
+                Op::Timeout(timeout) => {
+                    run.timeout = *timeout;
+                    run.pc += 1;
+                }
+                Op::Goto(target) => run.pc = *target,
+                Op::Done => {
+                    return self
+                        .finish(ScriptResult::Passed, now)
+                        .map(ScriptEvent::Finished);
+                }
+                Op::Fail => {
+                    return self
+                        .finish(ScriptResult::Failed, now)
+                        .map(ScriptEvent::Finished);
+                }
+            }
+        }
+
+        self.finish(ScriptResult::Failed, now)
+            .map(ScriptEvent::Finished)
+    }
+
+    fn finish(&mut self, result: ScriptResult, now: TInstant) -> Option<ScriptReport> {
+        let run = self.run.take()?;
+        let script = &self.scripts[usize::from(run.slot)];
+        Some(ScriptReport {
+            slot: run.slot,
+            name: script.name.clone(),
+            line: Self::line_at(script, run.pc),
+            result,
+            elapsed: now.saturating_duration_since(run.started),
+        })
+    }
+
+    fn store(&mut self, script: Script) -> Result<LoadProgress, ScriptError> {
+        let progress = LoadProgress::Loaded {
+            name: script.name.clone(),
+            steps: u8::try_from(script.steps.len()).unwrap_or(u8::MAX),
+        };
+        match self.slot_of(&script.name) {
+            Some(slot) if self.running_slot() == Some(slot) => Err(ScriptError::Busy),
+            Some(slot) => {
+                self.scripts[usize::from(slot)] = script;
+                Ok(progress)
+            }
+            None => {
+                self.scripts
+                    .push(script)
+                    .map_err(|_| ScriptError::LibraryFull)?;
+                Ok(progress)
+            }
+        }
+    }
+
+    fn slot_of(&self, name: &str) -> Option<u8> {
+        self.scripts
+            .iter()
+            .position(|script| script.name.eq_ignore_ascii_case(name))
+            .and_then(|slot| u8::try_from(slot).ok())
+    }
+
+    fn running_slot(&self) -> Option<u8> {
+        self.run.as_ref().map(|run| run.slot)
+    }
+
+    fn line_at(script: &Script, pc: u8) -> u8 {
+        script
+            .steps
+            .get(usize::from(pc))
+            .map_or(script.end_line, |step| step.line)
+    }
+}
+
+impl<TInstant> Default for ScriptEngine<TInstant>
+where
+    TInstant: TelemetryInstant,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Converts a script or label name, rejecting names that do not fit.
+#[must_use]
+pub fn script_name(text: &str) -> Option<ScriptName> {
+    ScriptName::try_from(text).ok()
+}
+
+fn parse_line(text: &str) -> Result<Line, ScriptError> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return Ok(Line::Blank);
+    }
+
+    let tokens = grammar::lex(trimmed).map_err(|_| ScriptError::Syntax)?;
+    let mut words = tokens.iter().filter(|token| token.kind != TokenKind::Eol);
+    let keyword = words.next().ok_or(ScriptError::Syntax)?;
+    if keyword.kind != TokenKind::Ident {
+        return Err(ScriptError::Syntax);
+    }
+    let is = |name: &str| keyword.lexeme.eq_ignore_ascii_case(name);
+
+    let line = if is("send") {
+        let text = quoted_text(words.next())?;
+        op(Op::Send(text))
+    } else if is("expect") {
+        let pattern = quoted_text(words.next())?;
+        let mut timeout = None;
+        let mut otherwise = None;
+        let mut next = words.next();
+        if let Some(token) = next.filter(|token| token.kind == TokenKind::Duration) {
+            timeout = Some(grammar::parse_duration(token).map_err(|_| ScriptError::Syntax)?);
+            next = words.next();
+        }
+        if let Some(token) = next {
+            if !(token.kind == TokenKind::Ident && token.lexeme.eq_ignore_ascii_case("else")) {
+                return Err(ScriptError::Syntax);
+            }
+            otherwise = Some(label_name(words.next())?);
+        }
+        Line::Op {
+            op: Op::Expect {
+                pattern,
+                timeout,
+                otherwise: otherwise.as_ref().map(|_| 0),
+            },
+            target: otherwise,
+        }
+    } else if is("timeout") {
+        let token = words
+            .next()
+            .filter(|token| token.kind == TokenKind::Duration)
+            .ok_or(ScriptError::Syntax)?;
+        op(Op::Timeout(
+            grammar::parse_duration(token).map_err(|_| ScriptError::Syntax)?,
+        ))
+    } else if is("label") {
+        Line::Label(label_name(words.next())?)
+    } else if is("goto") {
+        Line::Op {
+            op: Op::Goto(0),
+            target: Some(label_name(words.next())?),
+        }
+    } else if is("reboot") {
+        op(Op::Sequence(StrapSequenceKind::NormalReboot))
+    } else if is("recovery") {
+        op(Op::Sequence(StrapSequenceKind::RecoveryEntry))
+    } else if is("fault") {
+        op(Op::Sequence(StrapSequenceKind::FaultRecovery))
+    } else if is("done") {
+        op(Op::Done)
+    } else if is("fail") {
+        op(Op::Fail)
+    } else if is("end") {
+        Line::End
+    } else {
+        return Err(ScriptError::Syntax);
+    };
+
+    if words.next().is_some() {
+        return Err(ScriptError::Syntax);
+    }
+    Ok(line)
+}
+
+const fn op(op: Op) -> Line {
+    Line::Op { op, target: None }
+}
+
+fn quoted_text(token: Option<&Token<'_>>) -> Result<ScriptText, ScriptError> {
+    let raw = token
+        .filter(|token| token.kind == TokenKind::QuotedString)
+        .and_then(|token| token.lexeme.strip_prefix('"'))
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or(ScriptError::Syntax)?;
+    let decoded = inject::unescape(raw).map_err(|_| ScriptError::Text)?;
+    if decoded.is_empty() {
+        return Err(ScriptError::Text);
+    }
+    ScriptText::from_slice(&decoded).map_err(|_| ScriptError::Text)
+}
+
+fn label_name(token: Option<&Token<'_>>) -> Result<ScriptName, ScriptError> {
+    token
+        .filter(|token| token.kind == TokenKind::Ident)
+        .and_then(|token| script_name(token.lexeme))
+        .ok_or(ScriptError::Syntax)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::test_support::MillisInstant;
+
+    fn name(text: &str) -> ScriptName {
+        let Some(name) = script_name(text) else {
+            panic!("name should fit");
+        };
+        name
+    }
+
+    fn load(engine: &mut ScriptEngine<MillisInstant>, script: &str, lines: &[&str]) {
+        assert_eq!(engine.begin_load(name(script)), Ok(()));
+        for line in lines {
+            assert!(engine.load_line(line).is_ok(), "line {line:?} should load");
+        }
+    }
+
+    fn report(event: Option<ScriptEvent>) -> ScriptReport {
+        match event {
+            Some(ScriptEvent::Expect(report) | ScriptEvent::Finished(report)) => report,
+            other => panic!("expected a report, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn loads_and_lists_scripts() {
+        let mut engine = ScriptEngine::<MillisInstant>::new();
+        assert_eq!(engine.begin_load(name("login")), Ok(()));
+        assert!(engine.is_loading());
+        assert_eq!(engine.load_line("# comment"), Ok(LoadProgress::Pending));
+        assert_eq!(engine.load_line("send \"\\r\""), Ok(LoadProgress::Pending));
+        assert_eq!(
+            engine.load_line("expect \"login:\" 5s else retry"),
+            Ok(LoadProgress::Pending)
+        );
+        assert_eq!(engine.load_line("label retry"), Ok(LoadProgress::Pending));
+        assert_eq!(engine.load_line("reboot"), Ok(LoadProgress::Pending));
+        assert_eq!(
+            engine.load_line("end"),
+            Ok(LoadProgress::Loaded {
+                name: name("login"),
+                steps: 3,
+            })
+        );
+        assert!(!engine.is_loading());
+
+        let mut summaries = engine.summaries();
+        assert_eq!(
+            summaries.next(),
+            Some(ScriptSummary {
+                name: name("login"),
+                steps: 3,
+                running_line: None,
+            })
+        );
+        assert_eq!(summaries.next(), None);
+    }
+
+    #[test]
+    fn load_errors_abandon_the_script() {
+        let mut engine = ScriptEngine::<MillisInstant>::new();
+        assert_eq!(
+            engine.load_line("send \"x\""),
+            Err(LoadError {
+                line: 0,
+                error: ScriptError::NotLoading,
+            })
+        );
+
+        let _ = engine.begin_load(name("bad"));
+        let _ = engine.load_line("send \"ok\"");
+        assert_eq!(
+            engine.load_line("jump somewhere"),
+            Err(LoadError {
+                line: 2,
+                error: ScriptError::Syntax,
+            })
+        );
+        assert!(!engine.is_loading());
+
+        let _ = engine.begin_load(name("bad"));
+        let _ = engine.load_line("goto nowhere");
+        assert_eq!(
+            engine.load_line("end"),
+            Err(LoadError {
+                line: 2,
+                error: ScriptError::UnknownLabel,
+            })
+        );
+
+        let _ = engine.begin_load(name("bad"));
+        assert_eq!(
+            engine.load_line("send \"0123456789012345678901234567890123\""),
+            Err(LoadError {
+                line: 1,
+                error: ScriptError::Text,
+            })
+        );
+        assert_eq!(engine.summaries().count(), 0);
+    }
+
+    #[test]
+    fn runs_send_expect_and_sequence_steps() {
+        let mut engine = ScriptEngine::<MillisInstant>::new();
+        load(
+            &mut engine,
+            "boot",
+            &["send \"root\\r\"", "expect \"# \" 2s", "recovery", "end"],
+        );
+        assert_eq!(
+            engine.start("missing", MillisInstant(0)),
+            Err(ScriptError::NotFound)
+        );
+        assert_eq!(engine.start("boot", MillisInstant(0)), Ok(()));
+        assert_eq!(
+            engine.start("boot", MillisInstant(0)),
+            Err(ScriptError::Busy)
+        );
+
+        let Some(ScriptEvent::Send(text)) = engine.poll(MillisInstant(0)) else {
+            panic!("first step sends text");
+        };
+        assert_eq!(text.as_slice(), b"root\r");
+        assert_eq!(engine.poll(MillisInstant(10)), None);
+        assert!(!engine.feed(b"Password"));
+        assert!(engine.feed(b"ok\r\nroot@orin:~# "));
+
+        let matched = report(engine.poll(MillisInstant(510)));
+        assert_eq!(matched.result, ScriptResult::Matched);
+        assert_eq!(matched.line, 2);
+        assert_eq!(matched.elapsed, Duration::from_millis(500));
+        assert_eq!(
+            engine.poll(MillisInstant(510)),
+            Some(ScriptEvent::Sequence(StrapSequenceKind::RecoveryEntry))
+        );
+
+        let finished = report(engine.poll(MillisInstant(600)));
+        assert_eq!(finished.result, ScriptResult::Passed);
+        assert_eq!(finished.line, 4);
+        assert_eq!(
+            std::format!("{finished}"),
+            "script boot passed line=4 elapsed=600ms"
+        );
+        assert!(!engine.is_running());
+    }
+
+    #[test]
+    fn timeouts_branch_or_fail() {
+        let mut engine = ScriptEngine::<MillisInstant>::new();
+        load(
+            &mut engine,
+            "retry",
+            &[
+                "timeout 1s",
+                "expect \"login:\" else kick",
+                "done",
+                "label kick",
+                "expect \"never\"",
+                "end",
+            ],
+        );
+        let _ = engine.start("retry", MillisInstant(0));
+        assert_eq!(engine.poll(MillisInstant(0)), None);
+        assert_eq!(engine.poll(MillisInstant(999)), None);
+
+        let branched = report(engine.poll(MillisInstant(1_000)));
+        assert_eq!(branched.result, ScriptResult::TimedOut);
+        assert_eq!(branched.line, 2);
+        assert_eq!(engine.poll(MillisInstant(1_000)), None);
+        assert_eq!(
+            engine
+                .summaries()
+                .next()
+                .and_then(|summary| summary.running_line),
+            Some(5)
+        );
+
+        let timed_out = report(engine.poll(MillisInstant(2_000)));
+        assert_eq!(timed_out.result, ScriptResult::TimedOut);
+        let failed = report(engine.poll(MillisInstant(2_000)));
+        assert_eq!(failed.result, ScriptResult::Failed);
+        assert_eq!(failed.elapsed, Duration::from_secs(2));
+    }
+
+    #[test]
+    fn buffered_output_satisfies_expect_and_loops_are_bounded() {
+        let mut engine = ScriptEngine::<MillisInstant>::new();
+        load(&mut engine, "early", &["expect \"ready\"", "end"]);
+        load(&mut engine, "spin", &["label top", "goto top", "end"]);
+
+        let _ = engine.start("early", MillisInstant(0));
+        engine.feed(b"system ready\r\n");
+        let matched = report(engine.poll(MillisInstant(0)));
+        assert_eq!(matched.result, ScriptResult::Matched);
+        assert_eq!(
+            report(engine.poll(MillisInstant(0))).result,
+            ScriptResult::Passed
+        );
+
+        let _ = engine.start("spin", MillisInstant(0));
+        assert_eq!(
+            report(engine.poll(MillisInstant(0))).result,
+            ScriptResult::Failed
+        );
+
+        let _ = engine.start("early", MillisInstant(0));
+        assert_eq!(engine.begin_load(name("early")), Err(ScriptError::Busy));
+        let stopped = engine.stop(MillisInstant(40));
+        assert_eq!(
+            stopped.map(|report| report.result),
+            Some(ScriptResult::Stopped)
+        );
+        assert_eq!(engine.stop(MillisInstant(40)), None);
+    }
+}
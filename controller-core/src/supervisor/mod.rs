@@ -0,0 +1,797 @@
+//! Boot-hang supervision for unattended controllers.
+//!
+//! [`BootWatchdog`] arms whenever the Jetson is rebooted or powered on and
+//! waits for evidence that it came up: an expected console marker. Once the
+//! boot is confirmed it keeps watching for console silence. A kernel panic banner can optionally trip it as well
+//! once a grace period passes without the Jetson restarting on its own. Each
+//! failure produces a [`WatchdogTrip`] carrying the [`FaultRecoveryReason`],
+//! which the platform turns into a `FaultRecovery` request through
+//! [`schedule_fault_recovery`]. Consecutive automatic
+//! recoveries are capped so a board that never boots is not power-cycled
+//! forever; a confirmed boot resets the streak.
+
+use core::fmt;
+use core::ops::Add;
+use core::time::Duration;
+
+use crate::bridge::console::ConsoleMarker;
+use crate::orchestrator::{
+    CommandFlags, CommandQueueProducer, CommandSource, ScheduleError, SequenceScheduler,
+};
+use crate::power::{saturating_millis, write_span};
+use crate::sequences::StrapSequenceKind;
+use crate::telemetry::{FaultRecoveryReason, TelemetryInstant};
+
+/// Default time allowed between arming and the boot confirmation.
+pub const DEFAULT_BOOT_DEADLINE: Duration = Duration::from_secs(180);
+/// Default console silence tolerated once the Jetson is running.
+pub const DEFAULT_SILENCE_LIMIT: Duration = Duration::from_secs(600);
+/// Default number of automatic recoveries attempted before giving up.
+pub const DEFAULT_MAX_CONSECUTIVE_RECOVERIES: u8 = 3;
+
+/// Thresholds that drive the [`BootWatchdog`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WatchdogConfig {
+    /// Whether the watchdog may trigger recoveries at all.
+    pub enabled: bool,
+    /// Time allowed between arming and the boot confirmation.
+    pub boot_deadline: Duration,
+    /// Console marker that confirms the boot (`None` skips the console check).
+    pub expected_marker: Option<ConsoleMarker>,
+    /// Console silence tolerated while running (`None` disables the check).
+    pub silence_limit: Option<Duration>,
+    /// Wait after a kernel panic before recovering (`None` ignores panics).
+    pub panic_grace: Option<Duration>,
+    /// Automatic recoveries attempted before the watchdog gives up.
+    pub max_recoveries: u8,
+}
+
+impl WatchdogConfig {
+    /// Returns the default configuration; the watchdog starts disabled.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            enabled: false,
+            boot_deadline: DEFAULT_BOOT_DEADLINE,
+            expected_marker: Some(ConsoleMarker::LoginPrompt),
+            silence_limit: Some(DEFAULT_SILENCE_LIMIT),
+            panic_grace: None,
+            max_recoveries: DEFAULT_MAX_CONSECUTIVE_RECOVERIES,
+        }
+    }
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Supervision phase reported by the watchdog.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WatchdogPhase {
+    /// Supervision is switched off.
+    Disabled,
+    /// Enabled but not watching (for example while the Jetson sits in recovery mode).
+    Idle,
+    /// Waiting for the boot confirmation.
+    Booting,
+    /// Boot confirmed; watching for console silence.
+    Running,
+    /// A recovery was requested; waiting for the platform to re-arm.
+    Recovering,
+    /// The consecutive recovery cap was reached.
+    Exhausted,
+}
+
+impl fmt::Display for WatchdogPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            WatchdogPhase::Disabled => "off",
+            WatchdogPhase::Idle => "idle",
+            WatchdogPhase::Booting => "booting",
+            WatchdogPhase::Running => "running",
+            WatchdogPhase::Recovering => "recovering",
+            WatchdogPhase::Exhausted => "exhausted",
+        };
+        f.write_str(label)
+    }
+}
+
+/// Action requested by [`BootWatchdog::poll`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WatchdogTrip {
+    /// Enqueue a fault recovery; `attempt` counts consecutive automatic recoveries.
+    Recover {
+        reason: FaultRecoveryReason,
+        attempt: u8,
+    },
+    /// The recovery cap was reached, so the failure is only reported.
+    Exhausted { reason: FaultRecoveryReason },
+}
+
+/// Configuration change requested through the REPL.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WatchdogUpdate {
+    Enable,
+    Disable,
+    BootDeadline(Duration),
+    SilenceLimit(Option<Duration>),
+    PanicGrace(Option<Duration>),
+    MaxRecoveries(u8),
+}
+
+/// Point-in-time view of the watchdog for the REPL.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct WatchdogStatus {
+    pub phase: WatchdogPhase,
+    pub boot_deadline_ms: u32,
+    pub expected_marker: Option<ConsoleMarker>,
+    pub silence_limit_ms: Option<u32>,
+    pub panic_grace_ms: Option<u32>,
+    pub max_recoveries: u8,
+    pub consecutive: u8,
+    pub trips: u32,
+    pub last_reason: Option<FaultRecoveryReason>,
+    /// Time left before the active check trips, if one is running.
+    pub remaining_ms: Option<u32>,
+}
+
+/// Supervisor that detects a wedged Jetson after reboots and power-on.
+#[derive(Clone, Debug)]
+pub struct BootWatchdog<TInstant> {
+    config: WatchdogConfig,
+    phase: WatchdogPhase,
+    armed_at: Option<TInstant>,
+    last_activity: Option<TInstant>,
+    panic_at: Option<TInstant>,
+    marker_seen: bool,
+    consecutive: u8,
+    trips: u32,
+    last_reason: Option<FaultRecoveryReason>,
+}
+
+impl<TInstant> BootWatchdog<TInstant>
+where
+    TInstant: Copy + TelemetryInstant,
+{
+    /// Creates a watchdog with the provided configuration.
+    #[must_use]
+    pub const fn new(config: WatchdogConfig) -> Self {
+        Self {
+            phase: if config.enabled {
+                WatchdogPhase::Idle
+            } else {
+                WatchdogPhase::Disabled
+            },
+            config,
+            armed_at: None,
+            last_activity: None,
+            panic_at: None,
+            marker_seen: false,
+            consecutive: 0,
+            trips: 0,
+            last_reason: None,
+        }
+    }
+
+    /// Returns the active configuration.
+    #[must_use]
+    pub const fn config(&self) -> &WatchdogConfig {
+        &self.config
+    }
+
+    /// Returns the current supervision phase.
+    #[must_use]
+    pub const fn phase(&self) -> WatchdogPhase {
+        self.phase
+    }
+
+    /// Number of automatic recoveries since the last confirmed boot.
+    #[must_use]
+    pub const fn consecutive_recoveries(&self) -> u8 {
+        self.consecutive
+    }
+
+    /// Starts the boot deadline; call after a reboot, recovery, or power-on.
+    ///
+    /// Ignored while disabled or exhausted.
+    pub fn arm(&mut self, now: TInstant) {
+        if matches!(
+            self.phase,
+            WatchdogPhase::Disabled | WatchdogPhase::Exhausted
+        ) {
+            return;
+        }
+
+        self.phase = WatchdogPhase::Booting;
+        self.armed_at = Some(now);
+        self.last_activity = Some(now);
+        self.panic_at = None;
+        self.marker_seen = false;
+        self.confirm_if_booted();
+    }
+
+    /// Stops watching until the next [`Self::arm`], e.g. while in recovery mode.
+    pub fn park(&mut self) {
+        if matches!(
+            self.phase,
+            WatchdogPhase::Disabled | WatchdogPhase::Exhausted
+        ) {
+            return;
+        }
+
+        self.phase = WatchdogPhase::Idle;
+        self.armed_at = None;
+    }
+
+    /// Notes console traffic from the Jetson.
+    pub fn observe_console(&mut self, now: TInstant) {
+        self.last_activity = Some(now);
+    }
+
+    /// Notes a console marker hit, confirming the boot if it was the expected one.
+    ///
+    /// A kernel panic starts the panic grace period when one is configured
+    /// and the watchdog is watching; a boot banner cancels it because the
+    /// Jetson is already restarting.
+    pub fn observe_marker(&mut self, marker: ConsoleMarker, now: TInstant) {
+        self.observe_console(now);
+        match marker {
+            ConsoleMarker::KernelPanic if self.watching_panics() => {
+                self.panic_at.get_or_insert(now);
+            }
+            ConsoleMarker::BootBanner => self.panic_at = None,
+            _ => {}
+        }
+        if self.config.expected_marker == Some(marker) {
+            self.marker_seen = true;
+            self.confirm_if_booted();
+        }
+    }
+
+    /// Checks the active deadline, returning a trip when it has expired.
+    pub fn poll(&mut self, now: TInstant) -> Option<WatchdogTrip> {
+        if self.panic_expired(now) {
+            self.panic_at = None;
+            return Some(self.trip(FaultRecoveryReason::KernelPanic));
+        }
+
+        match self.phase {
+            WatchdogPhase::Booting => {
+                let armed_at = self.armed_at?;
+                if now.saturating_duration_since(armed_at) >= self.config.boot_deadline {
+                    Some(self.trip(FaultRecoveryReason::BootTimeout))
+                } else {
+                    None
+                }
+            }
+            WatchdogPhase::Running => {
+                let limit = self.config.silence_limit?;
+                let last = self.last_activity?;
+                if now.saturating_duration_since(last) >= limit {
+                    Some(self.trip(FaultRecoveryReason::ConsoleWatchdogTimeout))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Takes back the recovery counted by the last trip when the platform
+    /// could not schedule it, and restarts the boot deadline.
+    pub fn cancel_recovery(&mut self, now: TInstant) {
+        if self.phase != WatchdogPhase::Recovering {
+            return;
+        }
+
+        self.consecutive = self.consecutive.saturating_sub(1);
+        self.arm(now);
+    }
+
+    /// Applies a configuration change.
+    ///
+    /// Enabling clears the recovery streak and starts watching for console
+    /// silence, since the Jetson state is unknown at that point.
+    pub fn apply(&mut self, update: WatchdogUpdate, now: TInstant) {
+        match update {
+            WatchdogUpdate::Enable => {
+                self.config.enabled = true;
+                self.consecutive = 0;
+                self.phase = WatchdogPhase::Running;
+                self.armed_at = None;
+                self.last_activity = Some(now);
+            }
+            WatchdogUpdate::Disable => {
+                self.config.enabled = false;
+                self.phase = WatchdogPhase::Disabled;
+                self.armed_at = None;
+            }
+            WatchdogUpdate::BootDeadline(deadline) => self.config.boot_deadline = deadline,
+            WatchdogUpdate::SilenceLimit(limit) => self.config.silence_limit = limit,
+            WatchdogUpdate::PanicGrace(grace) => self.config.panic_grace = grace,
+            WatchdogUpdate::MaxRecoveries(limit) => self.config.max_recoveries = limit,
+        }
+    }
+
+    /// Builds a status view with remaining time measured from `now`.
+    #[must_use]
+    pub fn status(&self, now: TInstant) -> WatchdogStatus {
+        let panic_remaining = self
+            .panic_deadline()
+            .map(|(grace, at)| remaining(grace, at, now));
+        let remaining = panic_remaining.or(match self.phase {
+            WatchdogPhase::Booting => self
+                .armed_at
+                .map(|armed_at| remaining(self.config.boot_deadline, armed_at, now)),
+            WatchdogPhase::Running => self
+                .config
+                .silence_limit
+                .zip(self.last_activity)
+                .map(|(limit, last)| remaining(limit, last, now)),
+            _ => None,
+        });
+
+        WatchdogStatus {
+            phase: self.phase,
+            boot_deadline_ms: saturating_millis(self.config.boot_deadline),
+            expected_marker: self.config.expected_marker,
+            silence_limit_ms: self.config.silence_limit.map(saturating_millis),
+            panic_grace_ms: self.config.panic_grace.map(saturating_millis),
+            max_recoveries: self.config.max_recoveries,
+            consecutive: self.consecutive,
+            trips: self.trips,
+            last_reason: self.last_reason,
+            remaining_ms: remaining.map(saturating_millis),
+        }
+    }
+
+    /// Grace period and panic time while a panic can still trip the watchdog.
+    fn panic_deadline(&self) -> Option<(Duration, TInstant)> {
+        if !matches!(self.phase, WatchdogPhase::Booting | WatchdogPhase::Running) {
+            return None;
+        }
+        self.config.panic_grace.zip(self.panic_at)
+    }
+
+    fn watching_panics(&self) -> bool {
+        self.config.panic_grace.is_some()
+            && matches!(self.phase, WatchdogPhase::Booting | WatchdogPhase::Running)
+    }
+
+    fn panic_expired(&self, now: TInstant) -> bool {
+        self.panic_deadline()
+            .is_some_and(|(grace, at)| now.saturating_duration_since(at) >= grace)
+    }
+
+    fn confirm_if_booted(&mut self) {
+        if self.phase != WatchdogPhase::Booting {
+            return;
+        }
+
+        if self.config.expected_marker.is_none() || self.marker_seen {
+            self.phase = WatchdogPhase::Running;
+            self.armed_at = None;
+            self.consecutive = 0;
+        }
+    }
+
+    fn trip(&mut self, reason: FaultRecoveryReason) -> WatchdogTrip {
+        self.trips = self.trips.saturating_add(1);
+        self.last_reason = Some(reason);
+        self.armed_at = None;
+
+        if self.consecutive >= self.config.max_recoveries {
+            self.phase = WatchdogPhase::Exhausted;
+            WatchdogTrip::Exhausted { reason }
+        } else {
+            self.consecutive = self.consecutive.saturating_add(1);
+            self.phase = WatchdogPhase::Recovering;
+            WatchdogTrip::Recover {
+                reason,
+                attempt: self.consecutive,
+            }
+        }
+    }
+}
+
+impl<TInstant> Default for BootWatchdog<TInstant>
+where
+    TInstant: Copy + TelemetryInstant,
+{
+    fn default() -> Self {
+        Self::new(WatchdogConfig::new())
+    }
+}
+
+fn remaining<TInstant: TelemetryInstant>(
+    limit: Duration,
+    since: TInstant,
+    now: TInstant,
+) -> Duration {
+    limit.saturating_sub(now.saturating_duration_since(since))
+}
+
+/// Enqueues a watchdog-initiated fault recovery tagged with `reason`.
+///
+/// # Errors
+/// Returns a [`ScheduleError`] when the scheduler rejects the request (for
+/// example because the fault recovery cooldown is still active).
+pub fn schedule_fault_recovery<P, const CAPACITY: usize>(
+    scheduler: &mut SequenceScheduler<P, CAPACITY>,
+    reason: FaultRecoveryReason,
+    requested_at: P::Instant,
+) -> Result<(), ScheduleError<P::Error, P::Instant>>
+where
+    P: CommandQueueProducer,
+    P::Instant: Copy + Ord + Add<Duration, Output = P::Instant>,
+{
+    let flags = CommandFlags {
+        fault_reason: Some(reason),
+        ..CommandFlags::default()
+    };
+
+    scheduler.enqueue_with_flags(
+        StrapSequenceKind::FaultRecovery,
+        requested_at,
+        CommandSource::Watchdog,
+        flags,
+    )
+}
+
+/// Helper that renders a [`WatchdogStatus`] into REPL lines.
+#[derive(Clone, Copy, Debug)]
+pub struct WatchdogFormatter<'a> {
+    status: &'a WatchdogStatus,
+}
+
+impl<'a> WatchdogFormatter<'a> {
+    /// Creates a new formatter for the provided status.
+    #[must_use]
+    pub const fn new(status: &'a WatchdogStatus) -> Self {
+        Self { status }
+    }
+
+    /// Number of lines produced by [`Self::write_line`].
+    #[must_use]
+    pub const fn line_count(&self) -> usize {
+        3
+    }
+
+    /// Writes the line at `index`.
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure or `index` is
+    /// past [`Self::line_count`].
+    pub fn write_line<W: fmt::Write>(&self, writer: &mut W, index: usize) -> fmt::Result {
+        match index {
+            0 => self.write_state_line(writer),
+            1 => self.write_boot_line(writer),
+            2 => self.write_limits_line(writer),
+            _ => Err(fmt::Error),
+        }
+    }
+
+    /// Writes the state line (e.g. `watchdog state=booting due=12.3s last=boot-timeout`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure.
+    pub fn write_state_line<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(writer, "watchdog state={}", self.status.phase)?;
+        if let Some(remaining) = self.status.remaining_ms {
+            writer.write_str(" due=")?;
+            write_span(writer, remaining)?;
+        }
+        if let Some(reason) = self.status.last_reason {
+            write!(writer, " last={reason}")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the boot criteria line (e.g. `watchdog boot=180.0s marker=login-prompt`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure.
+    pub fn write_boot_line<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        writer.write_str("watchdog boot=")?;
+        write_span(writer, self.status.boot_deadline_ms)?;
+        match self.status.expected_marker {
+            Some(marker) => write!(writer, " marker={marker}"),
+            None => writer.write_str(" marker=none"),
+        }
+    }
+
+    /// Writes the limits line (e.g. `watchdog silence=600.0s panic=30.0s recoveries=1/3 trips=4`).
+    ///
+    /// # Errors
+    /// Returns [`fmt::Error`] if the writer reports a failure.
+    pub fn write_limits_line<W: fmt::Write>(&self, writer: &mut W) -> fmt::Result {
+        writer.write_str("watchdog silence=")?;
+        match self.status.silence_limit_ms {
+            Some(limit) => write_span(writer, limit)?,
+            None => writer.write_str("off")?,
+        }
+        writer.write_str(" panic=")?;
+        match self.status.panic_grace_ms {
+            Some(grace) => write_span(writer, grace)?,
+            None => writer.write_str("off")?,
+        }
+        write!(
+            writer,
+            " recoveries={}/{} trips={}",
+            self.status.consecutive, self.status.max_recoveries, self.status.trips
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telemetry::test_support::MillisInstant;
+    use std::string::String;
+
+    fn enabled_watchdog() -> BootWatchdog<MillisInstant> {
+        BootWatchdog::new(WatchdogConfig {
+            enabled: true,
+            boot_deadline: Duration::from_secs(10),
+            silence_limit: Some(Duration::from_secs(5)),
+            max_recoveries: 2,
+            ..WatchdogConfig::new()
+        })
+    }
+
+    #[test]
+    fn disabled_watchdog_never_arms() {
+        let mut watchdog = BootWatchdog::<MillisInstant>::default();
+        watchdog.arm(MillisInstant(0));
+        assert_eq!(watchdog.phase(), WatchdogPhase::Disabled);
+        assert_eq!(watchdog.poll(MillisInstant(1_000_000)), None);
+    }
+
+    #[test]
+    fn expected_marker_confirms_boot() {
+        let mut watchdog = enabled_watchdog();
+        watchdog.arm(MillisInstant(0));
+        watchdog.observe_marker(ConsoleMarker::BootBanner, MillisInstant(1_000));
+        assert_eq!(watchdog.phase(), WatchdogPhase::Booting);
+
+        watchdog.observe_marker(ConsoleMarker::LoginPrompt, MillisInstant(8_000));
+        assert_eq!(watchdog.phase(), WatchdogPhase::Running);
+        assert_eq!(watchdog.poll(MillisInstant(12_000)), None);
+    }
+
+    #[test]
+    fn missing_marker_trips_boot_timeout() {
+        let mut watchdog = enabled_watchdog();
+        watchdog.arm(MillisInstant(0));
+        assert_eq!(watchdog.poll(MillisInstant(9_999)), None);
+        assert_eq!(
+            watchdog.poll(MillisInstant(10_000)),
+            Some(WatchdogTrip::Recover {
+                reason: FaultRecoveryReason::BootTimeout,
+                attempt: 1,
+            })
+        );
+        assert_eq!(watchdog.phase(), WatchdogPhase::Recovering);
+        assert_eq!(watchdog.poll(MillisInstant(20_000)), None);
+    }
+
+    #[test]
+    fn console_silence_trips_while_running() {
+        let mut watchdog = enabled_watchdog();
+        watchdog.arm(MillisInstant(0));
+        watchdog.observe_marker(ConsoleMarker::LoginPrompt, MillisInstant(2_000));
+        watchdog.observe_console(MillisInstant(4_000));
+        assert_eq!(watchdog.poll(MillisInstant(8_999)), None);
+        assert_eq!(
+            watchdog.poll(MillisInstant(9_000)),
+            Some(WatchdogTrip::Recover {
+                reason: FaultRecoveryReason::ConsoleWatchdogTimeout,
+                attempt: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn kernel_panic_trips_after_grace_period() {
+        let mut watchdog = enabled_watchdog();
+        watchdog.apply(
+            WatchdogUpdate::PanicGrace(Some(Duration::from_secs(3))),
+            MillisInstant(0),
+        );
+        watchdog.arm(MillisInstant(0));
+        watchdog.observe_marker(ConsoleMarker::LoginPrompt, MillisInstant(1_000));
+        watchdog.observe_marker(ConsoleMarker::KernelPanic, MillisInstant(2_000));
+        watchdog.observe_marker(ConsoleMarker::KernelPanic, MillisInstant(2_500));
+        assert_eq!(
+            watchdog.status(MillisInstant(4_000)).remaining_ms,
+            Some(1_000)
+        );
+        assert_eq!(watchdog.poll(MillisInstant(4_999)), None);
+        assert_eq!(
+            watchdog.poll(MillisInstant(5_000)),
+            Some(WatchdogTrip::Recover {
+                reason: FaultRecoveryReason::KernelPanic,
+                attempt: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn kernel_panic_is_ignored_without_grace_or_after_restart() {
+        let mut watchdog = enabled_watchdog();
+        watchdog.arm(MillisInstant(0));
+        watchdog.observe_marker(ConsoleMarker::LoginPrompt, MillisInstant(1_000));
+        watchdog.observe_marker(ConsoleMarker::KernelPanic, MillisInstant(2_000));
+        assert_eq!(watchdog.poll(MillisInstant(4_000)), None);
+
+        watchdog.apply(
+            WatchdogUpdate::PanicGrace(Some(Duration::from_secs(3))),
+            MillisInstant(4_000),
+        );
+        watchdog.observe_marker(ConsoleMarker::BootBanner, MillisInstant(4_500));
+        assert_eq!(watchdog.poll(MillisInstant(6_000)), None);
+    }
+
+    #[test]
+    fn earlier_panic_does_not_trip_once_enabled() {
+        let mut watchdog = BootWatchdog::<MillisInstant>::default();
+        watchdog.observe_marker(ConsoleMarker::KernelPanic, MillisInstant(1_000));
+        watchdog.apply(WatchdogUpdate::Enable, MillisInstant(60_000));
+        watchdog.observe_marker(ConsoleMarker::KernelPanic, MillisInstant(61_000));
+        watchdog.apply(
+            WatchdogUpdate::PanicGrace(Some(Duration::from_secs(3))),
+            MillisInstant(62_000),
+        );
+        assert_eq!(watchdog.poll(MillisInstant(62_000)), None);
+        assert_eq!(watchdog.poll(MillisInstant(70_000)), None);
+    }
+
+    #[test]
+    fn consecutive_recoveries_are_capped() {
+        let mut watchdog = enabled_watchdog();
+        let mut now = 0;
+        for attempt in 1..=2 {
+            watchdog.arm(MillisInstant(now));
+            now += 10_000;
+            assert_eq!(
+                watchdog.poll(MillisInstant(now)),
+                Some(WatchdogTrip::Recover {
+                    reason: FaultRecoveryReason::BootTimeout,
+                    attempt,
+                })
+            );
+        }
+
+        watchdog.arm(MillisInstant(now));
+        now += 10_000;
+        assert_eq!(
+            watchdog.poll(MillisInstant(now)),
+            Some(WatchdogTrip::Exhausted {
+                reason: FaultRecoveryReason::BootTimeout,
+            })
+        );
+        assert_eq!(watchdog.phase(), WatchdogPhase::Exhausted);
+
+        watchdog.arm(MillisInstant(now));
+        assert_eq!(watchdog.phase(), WatchdogPhase::Exhausted);
+
+        watchdog.apply(WatchdogUpdate::Enable, MillisInstant(now));
+        assert_eq!(watchdog.phase(), WatchdogPhase::Running);
+        assert_eq!(watchdog.consecutive_recoveries(), 0);
+    }
+
+    #[test]
+    fn confirmed_boot_resets_the_streak() {
+        let mut watchdog = enabled_watchdog();
+        watchdog.arm(MillisInstant(0));
+        let _ = watchdog.poll(MillisInstant(10_000));
+        assert_eq!(watchdog.consecutive_recoveries(), 1);
+
+        watchdog.arm(MillisInstant(20_000));
+        watchdog.observe_marker(ConsoleMarker::LoginPrompt, MillisInstant(25_000));
+        assert_eq!(watchdog.consecutive_recoveries(), 0);
+    }
+
+    #[test]
+    fn schedules_watchdog_fault_recovery() {
+        use crate::orchestrator::{CommandEnqueueError, SequenceCommand};
+        use crate::sequences::fault_recovery_template;
+        use heapless::Vec;
+
+        #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+        struct TickInstant(u64);
+
+        impl Add<Duration> for TickInstant {
+            type Output = Self;
+
+            fn add(self, rhs: Duration) -> Self::Output {
+                Self(self.0 + u64::try_from(rhs.as_millis()).unwrap_or(u64::MAX))
+            }
+        }
+
+        #[derive(Default)]
+        struct Queue(Vec<SequenceCommand<TickInstant>, 2>);
+
+        impl CommandQueueProducer for Queue {
+            type Instant = TickInstant;
+            type Error = ();
+
+            fn try_enqueue(
+                &mut self,
+                command: SequenceCommand<Self::Instant>,
+            ) -> Result<(), CommandEnqueueError<Self::Error>> {
+                self.0
+                    .push(command)
+                    .map_err(|_| CommandEnqueueError::QueueFull)
+            }
+        }
+
+        let mut scheduler: SequenceScheduler<Queue> = SequenceScheduler::new(Queue::default());
+        scheduler
+            .templates_mut()
+            .register(fault_recovery_template())
+            .expect("register fault recovery template");
+
+        schedule_fault_recovery(
+            &mut scheduler,
+            FaultRecoveryReason::BootTimeout,
+            TickInstant(5),
+        )
+        .expect("watchdog recovery should enqueue");
+
+        let command = scheduler.producer().0.first().expect("queued command");
+        assert_eq!(command.kind, StrapSequenceKind::FaultRecovery);
+        assert_eq!(command.source, CommandSource::Watchdog);
+        assert_eq!(
+            command.flags.fault_reason,
+            Some(FaultRecoveryReason::BootTimeout)
+        );
+
+        // Trips rejected by the cooldown must not use up the recovery cap.
+        let mut watchdog = enabled_watchdog();
+        for now in [0, 20_000, 40_000] {
+            watchdog.arm(MillisInstant(now));
+            assert_eq!(
+                watchdog.poll(MillisInstant(now + 10_000)),
+                Some(WatchdogTrip::Recover {
+                    reason: FaultRecoveryReason::BootTimeout,
+                    attempt: 1,
+                })
+            );
+            assert!(matches!(
+                schedule_fault_recovery(
+                    &mut scheduler,
+                    FaultRecoveryReason::BootTimeout,
+                    TickInstant(100),
+                ),
+                Err(ScheduleError::CooldownActive { .. })
+            ));
+            watchdog.cancel_recovery(MillisInstant(now + 10_000));
+            assert_eq!(watchdog.phase(), WatchdogPhase::Booting);
+            assert_eq!(watchdog.consecutive_recoveries(), 0);
+        }
+    }
+
+    #[test]
+    fn formatter_renders_status_lines() {
+        let mut watchdog = enabled_watchdog();
+        watchdog.arm(MillisInstant(0));
+        let status = watchdog.status(MillisInstant(2_500));
+        let formatter = WatchdogFormatter::new(&status);
+
+        let lines: [String; 3] = core::array::from_fn(|index| {
+            let mut line = String::new();
+            formatter.write_line(&mut line, index).expect("format line");
+            line
+        });
+
+        assert_eq!(lines[0], "watchdog state=booting due=7.5s");
+        assert_eq!(lines[1], "watchdog boot=10.0s marker=login-prompt");
+        assert_eq!(
+            lines[2],
+            "watchdog silence=5.0s panic=off recoveries=0/2 trips=0"
+        );
+    }
+}
@@ -13,7 +13,11 @@ use core::{convert::TryFrom, fmt, time::Duration};
 
 use heapless::{HistoryBuf, OldestOrdered, Vec};
 
+use crate::bridge::autoboot::AutobootReport;
+use crate::bridge::console::{ConsoleMarker, PanicExcerpt, PatternId};
 use crate::orchestrator::{EventId, SequenceOutcome};
+use crate::power::BrownOutEpisode;
+use crate::script::{ScriptReport, ScriptResult};
 use crate::sequences::{StrapAction, StrapId, StrapSequenceKind};
 
 /// Maximum length for diagnostics note payloads.
@@ -59,36 +63,65 @@ pub enum TelemetryEventKind {
     CommandStarted(StrapSequenceKind),
     SequenceComplete(StrapSequenceKind),
     UsbDisconnect,
+    BrownOut,
+    ConsoleMarker(ConsoleMarker),
+    KernelPanic,
+    ScriptExpect,
+    ScriptFinished,
+    BootloaderInterrupt,
     Custom(u16),
 }
 
 impl fmt::Display for TelemetryEventKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TelemetryEventKind::StrapAsserted(line) => write!(f, "strap-asserted {line}"),
-            TelemetryEventKind::StrapReleased(line) => write!(f, "strap-released {line}"),
-            TelemetryEventKind::PowerStable => f.write_str("power-stable"),
-            TelemetryEventKind::RecoveryConsoleActivity => f.write_str("recovery-console-activity"),
-            TelemetryEventKind::CommandPending(kind) => write!(f, "command-pending {kind}"),
-            TelemetryEventKind::CommandStarted(kind) => write!(f, "command-started {kind}"),
-            TelemetryEventKind::SequenceComplete(kind) => {
-                write!(f, "sequence-complete {kind}")
+            TelemetryEventKind::StrapAsserted(line) | TelemetryEventKind::StrapReleased(line) => {
+                write!(f, "{} {line}", self.name())
             }
-            TelemetryEventKind::UsbDisconnect => f.write_str("usb-disconnect"),
+            TelemetryEventKind::CommandPending(kind)
+            | TelemetryEventKind::CommandStarted(kind)
+            | TelemetryEventKind::SequenceComplete(kind) => write!(f, "{} {kind}", self.name()),
+            TelemetryEventKind::ConsoleMarker(marker) => write!(f, "{} {marker}", self.name()),
             TelemetryEventKind::Custom(code) => write!(f, "custom({code})"),
+            _ => f.write_str(self.name()),
         }
     }
 }
 
 impl TelemetryEventKind {
+    /// Event names accepted by `wait event`, in catalog order.
+    pub const NAMES: [&'static str; 15] = [
+        "strap-asserted",
+        "strap-released",
+        "power-stable",
+        "recovery-console-activity",
+        "command-pending",
+        "command-started",
+        "sequence-complete",
+        "usb-disconnect",
+        "brown-out",
+        "console-marker",
+        "kernel-panic",
+        "script-expect",
+        "script-finished",
+        "bootloader-interrupt",
+        "custom",
+    ];
+
     const STRAP_ASSERT_BASE: u16 = 0x0000;
     const STRAP_RELEASE_BASE: u16 = 0x0004;
     const POWER_STABLE_CODE: u16 = 0x0008;
     const RECOVERY_ACTIVITY_CODE: u16 = 0x0009;
     const USB_DISCONNECT_CODE: u16 = 0x000A;
+    const BROWN_OUT_CODE: u16 = 0x000B;
+    const KERNEL_PANIC_CODE: u16 = 0x000C;
+    const SCRIPT_EXPECT_CODE: u16 = 0x000D;
+    const SCRIPT_FINISHED_CODE: u16 = 0x000E;
+    const BOOTLOADER_INTERRUPT_CODE: u16 = 0x000F;
     const COMMAND_PENDING_BASE: u16 = 0x0010;
     const COMMAND_STARTED_BASE: u16 = 0x0014;
     const SEQUENCE_COMPLETE_BASE: u16 = 0x0018;
+    const CONSOLE_MARKER_BASE: u16 = 0x0020;
 
     /// Encodes the event into a compact transport-friendly discriminant.
     #[must_use]
@@ -108,6 +141,14 @@ impl TelemetryEventKind {
                 Self::SEQUENCE_COMPLETE_BASE + sequence_index(kind)
             }
             TelemetryEventKind::UsbDisconnect => Self::USB_DISCONNECT_CODE,
+            TelemetryEventKind::BrownOut => Self::BROWN_OUT_CODE,
+            TelemetryEventKind::ConsoleMarker(marker) => {
+                Self::CONSOLE_MARKER_BASE + console_marker_index(marker)
+            }
+            TelemetryEventKind::KernelPanic => Self::KERNEL_PANIC_CODE,
+            TelemetryEventKind::ScriptExpect => Self::SCRIPT_EXPECT_CODE,
+            TelemetryEventKind::ScriptFinished => Self::SCRIPT_FINISHED_CODE,
+            TelemetryEventKind::BootloaderInterrupt => Self::BOOTLOADER_INTERRUPT_CODE,
             TelemetryEventKind::Custom(code) => code,
         }
     }
@@ -119,6 +160,11 @@ impl TelemetryEventKind {
             Self::POWER_STABLE_CODE => TelemetryEventKind::PowerStable,
             Self::RECOVERY_ACTIVITY_CODE => TelemetryEventKind::RecoveryConsoleActivity,
             Self::USB_DISCONNECT_CODE => TelemetryEventKind::UsbDisconnect,
+            Self::BROWN_OUT_CODE => TelemetryEventKind::BrownOut,
+            Self::KERNEL_PANIC_CODE => TelemetryEventKind::KernelPanic,
+            Self::SCRIPT_EXPECT_CODE => TelemetryEventKind::ScriptExpect,
+            Self::SCRIPT_FINISHED_CODE => TelemetryEventKind::ScriptFinished,
+            Self::BOOTLOADER_INTERRUPT_CODE => TelemetryEventKind::BootloaderInterrupt,
             value if (Self::STRAP_ASSERT_BASE..Self::STRAP_RELEASE_BASE).contains(&value) => {
                 let offset = value - Self::STRAP_ASSERT_BASE;
                 strap_from_index(offset).map_or(TelemetryEventKind::Custom(value), |line| {
@@ -154,9 +200,218 @@ impl TelemetryEventKind {
                     TelemetryEventKind::SequenceComplete(kind)
                 })
             }
+            value
+                if (Self::CONSOLE_MARKER_BASE..Self::CONSOLE_MARKER_BASE + 8).contains(&value) =>
+            {
+                let offset = value - Self::CONSOLE_MARKER_BASE;
+                console_marker_from_index(offset)
+                    .map_or(TelemetryEventKind::Custom(value), |marker| {
+                        TelemetryEventKind::ConsoleMarker(marker)
+                    })
+            }
             other => TelemetryEventKind::Custom(other),
         }
     }
+
+    /// Returns the event name without its strap, sequence, or marker, such as
+    /// `strap-released`.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        let index = match self {
+            TelemetryEventKind::StrapAsserted(_) => 0,
+            TelemetryEventKind::StrapReleased(_) => 1,
+            TelemetryEventKind::PowerStable => 2,
+            TelemetryEventKind::RecoveryConsoleActivity => 3,
+            TelemetryEventKind::CommandPending(_) => 4,
+            TelemetryEventKind::CommandStarted(_) => 5,
+            TelemetryEventKind::SequenceComplete(_) => 6,
+            TelemetryEventKind::UsbDisconnect => 7,
+            TelemetryEventKind::BrownOut => 8,
+            TelemetryEventKind::ConsoleMarker(_) => 9,
+            TelemetryEventKind::KernelPanic => 10,
+            TelemetryEventKind::ScriptExpect => 11,
+            TelemetryEventKind::ScriptFinished => 12,
+            TelemetryEventKind::BootloaderInterrupt => 13,
+            TelemetryEventKind::Custom(_) => 14,
+        };
+        Self::NAMES[index]
+    }
+
+    /// Looks up an event name from [`Self::NAMES`] (case-insensitive).
+    #[must_use]
+    pub fn find_name(name: &str) -> Option<&'static str> {
+        Self::NAMES
+            .iter()
+            .copied()
+            .find(|candidate| candidate.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the `events` category the event is reported under, or `None`
+    /// for events the REPL already reports on its own (scripts, bootloader
+    /// interrupts) and custom codes.
+    #[must_use]
+    pub const fn category(self) -> Option<EventCategory> {
+        match self {
+            TelemetryEventKind::StrapAsserted(_) | TelemetryEventKind::StrapReleased(_) => {
+                Some(EventCategory::Strap)
+            }
+            TelemetryEventKind::CommandPending(_) | TelemetryEventKind::CommandStarted(_) => {
+                Some(EventCategory::Command)
+            }
+            TelemetryEventKind::SequenceComplete(_) => Some(EventCategory::Sequence),
+            TelemetryEventKind::RecoveryConsoleActivity
+            | TelemetryEventKind::UsbDisconnect
+            | TelemetryEventKind::ConsoleMarker(_)
+            | TelemetryEventKind::KernelPanic => Some(EventCategory::Bridge),
+            TelemetryEventKind::PowerStable | TelemetryEventKind::BrownOut => {
+                Some(EventCategory::Power)
+            }
+            TelemetryEventKind::ScriptExpect
+            | TelemetryEventKind::ScriptFinished
+            | TelemetryEventKind::BootloaderInterrupt
+            | TelemetryEventKind::Custom(_) => None,
+        }
+    }
+}
+
+/// Groups of telemetry events a REPL session can subscribe to with `events`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EventCategory {
+    /// Strap lines asserted or released.
+    Strap,
+    /// Strap commands waiting in or leaving the queue.
+    Command,
+    /// Strap sequences that ran to the end.
+    Sequence,
+    /// Console activity, markers, panics, and USB disconnects.
+    Bridge,
+    /// Rail stability and brown-outs.
+    Power,
+}
+
+impl EventCategory {
+    /// Every category, in the order `events` lists them.
+    pub const ALL: [EventCategory; 5] = [
+        EventCategory::Strap,
+        EventCategory::Command,
+        EventCategory::Sequence,
+        EventCategory::Bridge,
+        EventCategory::Power,
+    ];
+
+    /// Keyword used for the category on the command line.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            EventCategory::Strap => "strap",
+            EventCategory::Command => "command",
+            EventCategory::Sequence => "sequence",
+            EventCategory::Bridge => "bridge",
+            EventCategory::Power => "power",
+        }
+    }
+
+    /// Looks up a category by keyword (case insensitive).
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.name().eq_ignore_ascii_case(name))
+    }
+
+    const fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+impl fmt::Display for EventCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Set of [`EventCategory`] values selected with `events`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct EventFilter(u8);
+
+impl EventFilter {
+    /// No events are reported.
+    pub const NONE: Self = Self(0);
+    /// Every category is reported.
+    pub const ALL: Self = Self(0b1_1111);
+
+    /// Rebuilds a filter from [`Self::bits`], ignoring unknown bits.
+    #[must_use]
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    /// Returns the filter as a bit mask, for sharing through an atomic.
+    #[must_use]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the filter with `category` added.
+    #[must_use]
+    pub const fn with(self, category: EventCategory) -> Self {
+        Self(self.0 | category.bit())
+    }
+
+    /// Returns the categories present in either filter.
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Returns the categories of `self` that are not in `other`.
+    #[must_use]
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Returns `true` when `category` is selected.
+    #[must_use]
+    pub const fn contains(self, category: EventCategory) -> bool {
+        self.0 & category.bit() != 0
+    }
+
+    /// Returns `true` when no category is selected.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` when `event` belongs to a selected category.
+    #[must_use]
+    pub const fn matches(self, event: TelemetryEventKind) -> bool {
+        match event.category() {
+            Some(category) => self.contains(category),
+            None => false,
+        }
+    }
+
+    /// Iterates over the selected categories.
+    pub fn categories(self) -> impl Iterator<Item = EventCategory> {
+        EventCategory::ALL
+            .into_iter()
+            .filter(move |category| self.contains(*category))
+    }
+}
+
+impl fmt::Display for EventFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("off");
+        }
+        for (index, category) in self.categories().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(category.name())?;
+        }
+        Ok(())
+    }
 }
 
 /// Payloads carried alongside telemetry events.
@@ -170,6 +425,16 @@ pub enum TelemetryPayload {
     Command(CommandTelemetry),
     /// Summary of a completed strap sequence.
     Sequence(SequenceTelemetry),
+    /// Summary of a brown-out episode once the rail recovered.
+    BrownOut(BrownOutTelemetry),
+    /// Console pattern that produced a marker event.
+    Console(ConsoleTelemetry),
+    /// Console line that announced a kernel panic.
+    Panic(PanicExcerpt),
+    /// Outcome of a script expect step or run.
+    Script(ScriptTelemetry),
+    /// Outcome of a `reboot to-bootloader` run.
+    Autoboot(AutobootReport),
 }
 
 impl TelemetryPayload {
@@ -252,6 +517,68 @@ impl SequenceTelemetry {
     }
 }
 
+/// Brown-out episode payload.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BrownOutTelemetry {
+    pub min_mv: Option<u16>,
+    pub duration: Option<Duration>,
+    pub sequence: Option<StrapSequenceKind>,
+    pub step: Option<u8>,
+    pub retried: bool,
+}
+
+impl BrownOutTelemetry {
+    /// Captures the details of a brown-out episode.
+    #[must_use]
+    pub const fn from_episode<TInstant>(episode: &BrownOutEpisode<TInstant>) -> Self {
+        Self {
+            min_mv: episode.min_mv,
+            duration: episode.duration,
+            sequence: episode.sequence,
+            step: episode.step,
+            retried: episode.retried,
+        }
+    }
+}
+
+/// Console marker payload.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ConsoleTelemetry {
+    pub pattern: PatternId,
+    /// Zero-based console line index the marker appeared on.
+    pub line: u32,
+}
+
+impl ConsoleTelemetry {
+    #[must_use]
+    pub const fn new(pattern: PatternId, line: u32) -> Self {
+        Self { pattern, line }
+    }
+}
+
+/// Script expect or run outcome payload.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ScriptTelemetry {
+    /// Library slot of the script.
+    pub slot: u8,
+    /// Script line of the step that produced the report.
+    pub line: u8,
+    pub result: ScriptResult,
+    pub elapsed: Duration,
+}
+
+impl ScriptTelemetry {
+    #[must_use]
+    pub const fn from_report(report: &ScriptReport) -> Self {
+        Self {
+            slot: report.slot,
+            line: report.line,
+            result: report.result,
+            elapsed: report.elapsed,
+        }
+    }
+}
+
 /// Total number of telemetry entries retained in memory.
 pub const TELEMETRY_RING_CAPACITY: usize = 128;
 
@@ -273,6 +600,75 @@ where
     pub details: TelemetryPayload,
 }
 
+/// Telemetry record prepared for the REPL event stream, with its timestamp
+/// turned into time since power-on so it no longer depends on the platform
+/// instant type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TelemetryNotice {
+    pub id: EventId,
+    pub uptime: Duration,
+    pub event: TelemetryEventKind,
+    pub details: TelemetryPayload,
+}
+
+impl TelemetryNotice {
+    /// Copies `record`, stamping it with `uptime`.
+    #[must_use]
+    pub const fn new<TInstant: Copy>(record: &TelemetryRecord<TInstant>, uptime: Duration) -> Self {
+        Self {
+            id: record.id,
+            uptime,
+            event: record.event,
+            details: record.details,
+        }
+    }
+}
+
+impl fmt::Display for TelemetryNotice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "event {} t={}ms", self.event, self.uptime.as_millis())?;
+        match self.details {
+            TelemetryPayload::Strap(details) => {
+                if let Some(elapsed) = details.elapsed_since_previous {
+                    write!(f, " delta={}ms", elapsed.as_millis())?;
+                }
+            }
+            TelemetryPayload::Command(details) => {
+                write!(f, " depth={}", details.queue_depth)?;
+                if let Some(waited) = details.pending_for {
+                    write!(f, " waited={}ms", waited.as_millis())?;
+                }
+            }
+            TelemetryPayload::Sequence(details) => {
+                write!(f, " outcome={}", details.outcome.label())?;
+                if let Some(duration) = details.duration {
+                    write!(f, " duration={}ms", duration.as_millis())?;
+                }
+                if let Some(fault) = details.fault {
+                    write!(f, " reason={} retries={}", fault.reason, fault.retries)?;
+                }
+            }
+            TelemetryPayload::BrownOut(details) => {
+                if let Some(min_mv) = details.min_mv {
+                    write!(f, " min={min_mv}mV")?;
+                }
+                if let Some(duration) = details.duration {
+                    write!(f, " duration={}ms", duration.as_millis())?;
+                }
+                if details.retried {
+                    f.write_str(" retried")?;
+                }
+            }
+            TelemetryPayload::Console(details) => write!(f, " line={}", details.line)?,
+            TelemetryPayload::Panic(excerpt) => write!(f, " line={}", excerpt.line)?,
+            TelemetryPayload::None
+            | TelemetryPayload::Script(_)
+            | TelemetryPayload::Autoboot(_) => {}
+        }
+        Ok(())
+    }
+}
+
 /// Telemetry ring buffer type alias.
 pub type TelemetryRing<TInstant, const CAPACITY: usize = TELEMETRY_RING_CAPACITY> =
     HistoryBuf<TelemetryRecord<TInstant>, CAPACITY>;
@@ -400,6 +796,87 @@ where
             timestamp,
         )
     }
+
+    /// Records the completion of a fault recovery run along with its trigger.
+    pub fn record_fault_recovery_completion(
+        &mut self,
+        outcome: SequenceOutcome,
+        started_at: Option<TInstant>,
+        timestamp: TInstant,
+        events_recorded: usize,
+        fault: FaultRecoveryTelemetry,
+    ) -> EventId {
+        let duration = started_at.map(|start| timestamp.saturating_duration_since(start));
+        let payload = TelemetryPayload::Sequence(
+            SequenceTelemetry::new(outcome, duration, truncate_count(events_recorded))
+                .with_fault(fault),
+        );
+
+        self.record(
+            TelemetryEventKind::SequenceComplete(StrapSequenceKind::FaultRecovery),
+            payload,
+            timestamp,
+        )
+    }
+
+    /// Records a brown-out episode, typically at the moment the rail recovered.
+    pub fn record_brown_out(
+        &mut self,
+        episode: &BrownOutEpisode<TInstant>,
+        timestamp: TInstant,
+    ) -> EventId {
+        let payload = TelemetryPayload::BrownOut(BrownOutTelemetry::from_episode(episode));
+        self.record(TelemetryEventKind::BrownOut, payload, timestamp)
+    }
+
+    /// Records a console marker reported by the bridge pattern matcher.
+    pub fn record_console_marker(
+        &mut self,
+        marker: ConsoleMarker,
+        pattern: PatternId,
+        line: u32,
+        timestamp: TInstant,
+    ) -> EventId {
+        let payload = TelemetryPayload::Console(ConsoleTelemetry::new(pattern, line));
+        self.record(
+            TelemetryEventKind::ConsoleMarker(marker),
+            payload,
+            timestamp,
+        )
+    }
+
+    /// Records a kernel panic along with the console line that reported it.
+    pub fn record_kernel_panic(&mut self, excerpt: PanicExcerpt, timestamp: TInstant) -> EventId {
+        self.record(
+            TelemetryEventKind::KernelPanic,
+            TelemetryPayload::Panic(excerpt),
+            timestamp,
+        )
+    }
+
+    /// Records a script expect result or the end of a script run.
+    pub fn record_script(&mut self, report: &ScriptReport, timestamp: TInstant) -> EventId {
+        let event = if report.result.is_final() {
+            TelemetryEventKind::ScriptFinished
+        } else {
+            TelemetryEventKind::ScriptExpect
+        };
+        let payload = TelemetryPayload::Script(ScriptTelemetry::from_report(report));
+        self.record(event, payload, timestamp)
+    }
+
+    /// Records how a `reboot to-bootloader` run ended.
+    pub fn record_bootloader_interrupt(
+        &mut self,
+        report: AutobootReport,
+        timestamp: TInstant,
+    ) -> EventId {
+        self.record(
+            TelemetryEventKind::BootloaderInterrupt,
+            TelemetryPayload::Autoboot(report),
+            timestamp,
+        )
+    }
 }
 
 impl<TInstant, const CAPACITY: usize> Default for TelemetryRecorder<TInstant, CAPACITY>
@@ -476,6 +953,10 @@ pub enum FaultRecoveryReason {
     ControlLinkLost,
     /// Console watchdog timed out waiting for Jetson UART activity.
     ConsoleWatchdogTimeout,
+    /// Boot supervisor saw no sign of life before its deadline.
+    BootTimeout,
+    /// The Jetson kernel panicked and did not restart within the grace period.
+    KernelPanic,
     /// Implementation-specific extension.
     Custom(u8),
 }
@@ -485,6 +966,8 @@ impl FaultRecoveryReason {
     const BROWN_OUT_CODE: u8 = 0x01;
     const CONTROL_LINK_LOST_CODE: u8 = 0x02;
     const CONSOLE_WATCHDOG_CODE: u8 = 0x03;
+    const BOOT_TIMEOUT_CODE: u8 = 0x04;
+    const KERNEL_PANIC_CODE: u8 = 0x05;
 
     /// Encodes the reason into a compact numeric discriminant.
     #[must_use]
@@ -494,6 +977,8 @@ impl FaultRecoveryReason {
             FaultRecoveryReason::BrownOutDetected => Self::BROWN_OUT_CODE,
             FaultRecoveryReason::ControlLinkLost => Self::CONTROL_LINK_LOST_CODE,
             FaultRecoveryReason::ConsoleWatchdogTimeout => Self::CONSOLE_WATCHDOG_CODE,
+            FaultRecoveryReason::BootTimeout => Self::BOOT_TIMEOUT_CODE,
+            FaultRecoveryReason::KernelPanic => Self::KERNEL_PANIC_CODE,
             FaultRecoveryReason::Custom(code) => code,
         }
     }
@@ -506,6 +991,8 @@ impl FaultRecoveryReason {
             Self::BROWN_OUT_CODE => FaultRecoveryReason::BrownOutDetected,
             Self::CONTROL_LINK_LOST_CODE => FaultRecoveryReason::ControlLinkLost,
             Self::CONSOLE_WATCHDOG_CODE => FaultRecoveryReason::ConsoleWatchdogTimeout,
+            Self::BOOT_TIMEOUT_CODE => FaultRecoveryReason::BootTimeout,
+            Self::KERNEL_PANIC_CODE => FaultRecoveryReason::KernelPanic,
             other => FaultRecoveryReason::Custom(other),
         }
     }
@@ -517,6 +1004,20 @@ impl FaultRecoveryReason {
     }
 }
 
+impl fmt::Display for FaultRecoveryReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FaultRecoveryReason::ManualRequest => f.write_str("manual"),
+            FaultRecoveryReason::BrownOutDetected => f.write_str("brown-out"),
+            FaultRecoveryReason::ControlLinkLost => f.write_str("link-lost"),
+            FaultRecoveryReason::ConsoleWatchdogTimeout => f.write_str("console-silent"),
+            FaultRecoveryReason::BootTimeout => f.write_str("boot-timeout"),
+            FaultRecoveryReason::KernelPanic => f.write_str("kernel-panic"),
+            FaultRecoveryReason::Custom(code) => write!(f, "custom({code:#04x})"),
+        }
+    }
+}
+
 const fn strap_index(line: StrapId) -> u16 {
     match line {
         StrapId::Reset => 0,
@@ -555,6 +1056,45 @@ fn sequence_from_index(index: u16) -> Option<StrapSequenceKind> {
     }
 }
 
+const fn console_marker_index(marker: ConsoleMarker) -> u16 {
+    match marker {
+        ConsoleMarker::BootBanner => 0,
+        ConsoleMarker::LoginPrompt => 1,
+        ConsoleMarker::KernelPanic => 2,
+        ConsoleMarker::TegraRecovery => 3,
+        ConsoleMarker::Custom => 4,
+    }
+}
+
+fn console_marker_from_index(index: u16) -> Option<ConsoleMarker> {
+    match index {
+        0 => Some(ConsoleMarker::BootBanner),
+        1 => Some(ConsoleMarker::LoginPrompt),
+        2 => Some(ConsoleMarker::KernelPanic),
+        3 => Some(ConsoleMarker::TegraRecovery),
+        4 => Some(ConsoleMarker::Custom),
+        _ => None,
+    }
+}
+
+/// Fixtures shared by unit tests across the crate.
+#[cfg(test)]
+pub(crate) mod test_support {
+    use core::time::Duration;
+
+    use super::TelemetryInstant;
+
+    /// Instant counted in whole milliseconds.
+    #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd)]
+    pub(crate) struct MillisInstant(pub u64);
+
+    impl TelemetryInstant for MillisInstant {
+        fn saturating_duration_since(&self, earlier: Self) -> Duration {
+            Duration::from_millis(self.0.saturating_sub(earlier.0))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -582,6 +1122,8 @@ mod tests {
             (FaultRecoveryReason::BrownOutDetected, 0x01),
             (FaultRecoveryReason::ControlLinkLost, 0x02),
             (FaultRecoveryReason::ConsoleWatchdogTimeout, 0x03),
+            (FaultRecoveryReason::BootTimeout, 0x04),
+            (FaultRecoveryReason::KernelPanic, 0x05),
             (FaultRecoveryReason::Custom(0xA5), 0xA5),
         ];
 
@@ -772,4 +1314,212 @@ mod tests {
             _ => panic!("expected sequence payload"),
         }
     }
+
+    #[test]
+    fn records_brown_out_episode() {
+        use crate::power::BrownOutHistory;
+
+        let mut history = BrownOutHistory::<MicrosInstant>::new();
+        history.record_brown_out(
+            MicrosInstant::from_micros(1_000),
+            Some(2_900),
+            Some(StrapSequenceKind::FaultRecovery),
+            Some(3),
+        );
+        history.mark_retry(true);
+        let recovered_at = MicrosInstant::from_micros(6_000);
+        let episode = history.close(recovered_at).expect("episode should close");
+
+        let mut recorder = TelemetryRecorder::<MicrosInstant>::new();
+        recorder.record_brown_out(&episode, recovered_at);
+
+        let record = recorder.latest().copied().unwrap();
+        assert_eq!(record.event, TelemetryEventKind::BrownOut);
+        assert_eq!(
+            TelemetryEventKind::from_raw(record.event.to_raw()),
+            TelemetryEventKind::BrownOut
+        );
+        match record.details {
+            TelemetryPayload::BrownOut(details) => {
+                assert_eq!(details.min_mv, Some(2_900));
+                assert_eq!(details.duration, Some(Duration::from_micros(5_000)));
+                assert_eq!(details.sequence, Some(StrapSequenceKind::FaultRecovery));
+                assert_eq!(details.step, Some(3));
+                assert!(details.retried);
+            }
+            _ => panic!("expected brown-out payload"),
+        }
+    }
+
+    #[test]
+    fn console_marker_round_trips_through_raw_codes() {
+        let mut recorder = TelemetryRecorder::<MicrosInstant>::new();
+        recorder.record_console_marker(
+            ConsoleMarker::KernelPanic,
+            3,
+            42,
+            MicrosInstant::from_micros(9_000),
+        );
+
+        let record = recorder.latest().copied().unwrap();
+        assert_eq!(
+            record.event,
+            TelemetryEventKind::ConsoleMarker(ConsoleMarker::KernelPanic)
+        );
+        assert_eq!(
+            TelemetryEventKind::from_raw(record.event.to_raw()),
+            record.event
+        );
+        assert_eq!(
+            record.details,
+            TelemetryPayload::Console(ConsoleTelemetry::new(3, 42))
+        );
+        assert_eq!(
+            TelemetryEventKind::from_raw(0x0027),
+            TelemetryEventKind::Custom(0x0027)
+        );
+    }
+
+    #[test]
+    fn records_kernel_panic_excerpt() {
+        let mut recorder = TelemetryRecorder::<MicrosInstant>::new();
+        let excerpt = PanicExcerpt::new(7, b"[ 3.2] Kernel panic - not syncing: Oops", false);
+        recorder.record_kernel_panic(excerpt, MicrosInstant::from_micros(4_000));
+
+        let record = recorder.latest().copied().unwrap();
+        assert_eq!(record.event, TelemetryEventKind::KernelPanic);
+        assert_eq!(
+            TelemetryEventKind::from_raw(record.event.to_raw()),
+            TelemetryEventKind::KernelPanic
+        );
+        let TelemetryPayload::Panic(details) = record.details else {
+            panic!("expected panic payload");
+        };
+        assert_eq!(details.line, 7);
+        assert_eq!(details.as_bytes(), b"Kernel panic - not syncing: Oops");
+    }
+
+    #[test]
+    fn records_script_outcomes() {
+        let mut recorder = TelemetryRecorder::<MicrosInstant>::new();
+        let Some(name) = crate::script::script_name("login") else {
+            panic!("name fits");
+        };
+        let mut report = ScriptReport {
+            slot: 1,
+            name,
+            line: 3,
+            result: ScriptResult::Matched,
+            elapsed: Duration::from_millis(250),
+        };
+        recorder.record_script(&report, MicrosInstant::from_micros(1_000));
+        report.result = ScriptResult::Passed;
+        recorder.record_script(&report, MicrosInstant::from_micros(2_000));
+
+        let mut records = recorder.oldest_first();
+        let Some(expect) = records.next() else {
+            panic!("expect record");
+        };
+        assert_eq!(expect.event, TelemetryEventKind::ScriptExpect);
+        assert_eq!(
+            TelemetryEventKind::from_raw(expect.event.to_raw()),
+            TelemetryEventKind::ScriptExpect
+        );
+        let Some(finished) = records.next() else {
+            panic!("finished record");
+        };
+        assert_eq!(
+            TelemetryEventKind::from_raw(finished.event.to_raw()),
+            TelemetryEventKind::ScriptFinished
+        );
+        assert_eq!(
+            finished.details,
+            TelemetryPayload::Script(ScriptTelemetry {
+                slot: 1,
+                line: 3,
+                result: ScriptResult::Passed,
+                elapsed: Duration::from_millis(250),
+            })
+        );
+    }
+
+    #[test]
+    fn records_bootloader_interrupt() {
+        let mut recorder = TelemetryRecorder::<MicrosInstant>::new();
+        let report = AutobootReport {
+            result: crate::bridge::autoboot::AutobootResult::Reached,
+            elapsed: Duration::from_secs(8),
+            keys_sent: 3,
+        };
+        recorder.record_bootloader_interrupt(report, MicrosInstant::from_micros(9_000));
+
+        let record = recorder.latest().copied().unwrap();
+        assert_eq!(
+            TelemetryEventKind::from_raw(record.event.to_raw()),
+            TelemetryEventKind::BootloaderInterrupt
+        );
+        assert_eq!(record.details, TelemetryPayload::Autoboot(report));
+    }
+
+    #[test]
+    fn event_filters_select_categories() {
+        let filter = EventFilter::NONE
+            .with(EventCategory::Strap)
+            .with(EventCategory::Power);
+        assert_eq!(std::format!("{filter}"), "strap,power");
+        assert_eq!(std::format!("{}", EventFilter::NONE), "off");
+        assert!(filter.matches(TelemetryEventKind::StrapReleased(StrapId::Rec)));
+        assert!(filter.matches(TelemetryEventKind::BrownOut));
+        assert!(!filter.matches(TelemetryEventKind::SequenceComplete(
+            StrapSequenceKind::NormalReboot
+        )));
+        assert!(!EventFilter::ALL.matches(TelemetryEventKind::ScriptFinished));
+        assert_eq!(
+            EventFilter::ALL.difference(filter),
+            EventFilter::NONE
+                .with(EventCategory::Command)
+                .with(EventCategory::Sequence)
+                .with(EventCategory::Bridge)
+        );
+        assert_eq!(EventFilter::from_bits(filter.bits()), filter);
+        assert_eq!(
+            EventCategory::from_name("Bridge"),
+            Some(EventCategory::Bridge)
+        );
+        assert_eq!(EventCategory::from_name("console"), None);
+    }
+
+    #[test]
+    fn notices_render_event_lines() {
+        let mut recorder = TelemetryRecorder::<MicrosInstant>::new();
+        recorder.record_strap_transition(
+            StrapId::Rec,
+            StrapAction::AssertLow,
+            MicrosInstant::from_micros(1_000),
+        );
+        recorder.record_strap_transition(
+            StrapId::Rec,
+            StrapAction::ReleaseHigh,
+            MicrosInstant::from_micros(51_000),
+        );
+        let record = recorder.latest().expect("record stored");
+        let notice = TelemetryNotice::new(record, Duration::from_micros(51_000));
+        assert_eq!(
+            std::format!("{notice}"),
+            "event strap-released REC* t=51ms delta=50ms"
+        );
+
+        recorder.record_command_started(
+            StrapSequenceKind::FaultRecovery,
+            2,
+            MicrosInstant::from_micros(60_000),
+            MicrosInstant::from_micros(80_000),
+        );
+        let record = recorder.latest().expect("record stored");
+        let notice = TelemetryNotice::new(record, Duration::from_millis(80));
+        assert_eq!(
+            std::format!("{notice}"),
+            "event command-started fault-recovery t=80ms depth=2 waited=20ms"
+        );
+    }
 }
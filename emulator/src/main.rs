@@ -5,7 +5,7 @@ use std::env;
 use std::io::{self, Write};
 use std::process;
 
-use controller_core::repl::completion::Replacement;
+use controller_core::repl::editor::{EditEvent, EditKey, LineEditor};
 use crossterm::cursor;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use crossterm::terminal::{self, ClearType};
@@ -30,27 +30,20 @@ fn main() -> io::Result<()> {
 
     display_banner(&mut stdout)?;
 
-    let mut buffer = String::new();
-    let mut cursor_index = 0usize;
+    let mut editor: LineEditor = LineEditor::new();
 
-    render_prompt(&mut stdout, &buffer, cursor_index)?;
+    render_prompt(&mut stdout, &editor)?;
 
     loop {
         let event = event::read()?;
         match event {
             Event::Key(key) if matches!(key.kind, KeyEventKind::Press | KeyEventKind::Repeat) => {
-                if handle_key_event(
-                    key,
-                    &mut session,
-                    &mut stdout,
-                    &mut buffer,
-                    &mut cursor_index,
-                )? {
+                if handle_key_event(key, &mut session, &mut stdout, &mut editor)? {
                     break;
                 }
             }
             Event::Resize(_, _) => {
-                render_prompt(&mut stdout, &buffer, cursor_index)?;
+                render_prompt(&mut stdout, &editor)?;
             }
             _ => {}
         }
@@ -63,8 +56,7 @@ fn handle_key_event(
     key: KeyEvent,
     session: &mut Session,
     stdout: &mut io::StdoutLock<'_>,
-    buffer: &mut String,
-    cursor_index: &mut usize,
+    editor: &mut LineEditor,
 ) -> io::Result<bool> {
     if key.modifiers.contains(KeyModifiers::CONTROL) {
         match key.code {
@@ -73,83 +65,83 @@ fn handle_key_event(
                 stdout.flush()?;
                 return Ok(true);
             }
-            KeyCode::Char('d') => {
-                if buffer.is_empty() {
-                    write!(stdout, "\r\nSession closed.\r\n")?;
-                    stdout.flush()?;
-                    return Ok(true);
-                }
+            KeyCode::Char('d') if editor.is_empty() => {
+                write!(stdout, "\r\nSession closed.\r\n")?;
+                stdout.flush()?;
+                return Ok(true);
             }
             _ => {}
         }
     }
+    if key.code == KeyCode::Esc {
+        write!(stdout, "\r\nSession closed.\r\n")?;
+        stdout.flush()?;
+        return Ok(true);
+    }
 
-    match key.code {
-        KeyCode::Enter => handle_enter(session, stdout, buffer, cursor_index),
-        KeyCode::Tab | KeyCode::BackTab => {
-            handle_completion(session, stdout, buffer, cursor_index)?;
-            Ok(false)
-        }
-        KeyCode::Backspace => {
-            if *cursor_index > 0 {
-                let mut chars = buffer[..*cursor_index].char_indices();
-                let (idx, _) = chars.next_back().unwrap();
-                buffer.replace_range(idx..*cursor_index, "");
-                *cursor_index = idx;
-                render_prompt(stdout, buffer, *cursor_index)?;
-            } else {
-                beep(stdout)?;
-            }
-            Ok(false)
-        }
-        KeyCode::Char(ch) => {
-            if key
-                .modifiers
-                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
-            {
-                return Ok(false);
-            }
-            if ch.is_ascii() && ch != '\n' && ch != '\r' && ch != '\t' {
-                buffer.insert(*cursor_index, ch);
-                *cursor_index += ch.len_utf8();
-                render_prompt(stdout, buffer, *cursor_index)?;
-            } else {
-                beep(stdout)?;
-            }
-            Ok(false)
+    let Some(edit) = edit_key(key) else {
+        beep(stdout)?;
+        return Ok(false);
+    };
+
+    match editor.apply(edit) {
+        EditEvent::Pending => {}
+        EditEvent::Redraw => render_prompt(stdout, editor)?,
+        EditEvent::Bell => beep(stdout)?,
+        EditEvent::Complete => handle_completion(session, stdout, editor)?,
+        EditEvent::Submit => return handle_enter(session, stdout, editor),
+        EditEvent::Cancelled => {
+            write!(stdout, "^C\r\n")?;
+            render_prompt(stdout, editor)?;
         }
-        KeyCode::Esc => {
-            write!(stdout, "\r\nSession closed.\r\n")?;
-            stdout.flush()?;
-            Ok(true)
+        EditEvent::Overflow => {
+            write!(stdout, "\r\n{}\r\n", session.line_too_long())?;
+            render_prompt(stdout, editor)?;
         }
-        KeyCode::Left
-        | KeyCode::Right
-        | KeyCode::Up
-        | KeyCode::Down
-        | KeyCode::Home
-        | KeyCode::End => {
-            beep(stdout)?;
-            Ok(false)
+    }
+    Ok(false)
+}
+
+/// Maps a terminal key to the shared editor's keys; control chords go
+/// through the same byte table the firmware decodes.
+fn edit_key(key: KeyEvent) -> Option<EditKey> {
+    match key.code {
+        KeyCode::Char(ch) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            let byte = u8::try_from(ch).ok()?;
+            EditKey::from_byte(byte.to_ascii_lowercase() & 0x1f)
         }
-        _ => Ok(false),
+        KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::ALT) => None,
+        KeyCode::Char(ch) => u8::try_from(ch)
+            .ok()
+            .filter(|byte| byte.is_ascii_graphic() || *byte == b' ')
+            .map(EditKey::Char),
+        KeyCode::Enter => Some(EditKey::Enter),
+        KeyCode::Tab | KeyCode::BackTab => Some(EditKey::Tab),
+        KeyCode::Backspace => Some(EditKey::Backspace),
+        KeyCode::Delete => Some(EditKey::Delete),
+        KeyCode::Left => Some(EditKey::Left),
+        KeyCode::Right => Some(EditKey::Right),
+        KeyCode::Up => Some(EditKey::Up),
+        KeyCode::Down => Some(EditKey::Down),
+        KeyCode::Home => Some(EditKey::Home),
+        KeyCode::End => Some(EditKey::End),
+        _ => None,
     }
 }
 
 fn handle_enter(
     session: &mut Session,
     stdout: &mut io::StdoutLock<'_>,
-    buffer: &mut String,
-    cursor_index: &mut usize,
+    editor: &mut LineEditor,
 ) -> io::Result<bool> {
     write!(stdout, "\r\n")?;
     stdout.flush()?;
 
-    let trimmed = buffer.trim();
+    let line = editor.take_line();
+    let text = String::from_utf8_lossy(&line);
+    let trimmed = text.trim();
     if trimmed.is_empty() {
-        buffer.clear();
-        *cursor_index = 0;
-        render_prompt(stdout, buffer, *cursor_index)?;
+        render_prompt(stdout, editor)?;
         return Ok(false);
     }
 
@@ -165,26 +157,25 @@ fn handle_enter(
     }
     stdout.flush()?;
 
-    buffer.clear();
-    *cursor_index = 0;
-    render_prompt(stdout, buffer, *cursor_index)?;
+    render_prompt(stdout, editor)?;
     Ok(false)
 }
 
 fn handle_completion(
     session: &mut Session,
     stdout: &mut io::StdoutLock<'_>,
-    buffer: &mut String,
-    cursor_index: &mut usize,
+    editor: &mut LineEditor,
 ) -> io::Result<()> {
-    let response = session.handle_completion(buffer, *cursor_index)?;
+    let response = session.handle_completion(editor.as_str(), editor.cursor())?;
     match response {
         CompletionResponse::NoMatches => {
             beep(stdout)?;
         }
         CompletionResponse::Applied { replacement } => {
-            apply_replacement(buffer, cursor_index, &replacement);
-            render_prompt(stdout, buffer, *cursor_index)?;
+            if editor.complete(&replacement).is_err() {
+                beep(stdout)?;
+            }
+            render_prompt(stdout, editor)?;
         }
         CompletionResponse::Suggestions { options } => {
             write!(stdout, "\r\n")?;
@@ -192,36 +183,21 @@ fn handle_completion(
                 write!(stdout, "  {option}\r\n")?;
             }
             stdout.flush()?;
-            render_prompt(stdout, buffer, *cursor_index)?;
+            render_prompt(stdout, editor)?;
         }
     }
     Ok(())
 }
 
-fn apply_replacement(buffer: &mut String, cursor_index: &mut usize, replacement: &Replacement) {
-    let clamped_start = replacement.start.min(buffer.len());
-    let clamped_end = replacement.end.min(buffer.len());
-    buffer.replace_range(clamped_start..clamped_end, replacement.value);
-    let mut new_cursor = clamped_start + replacement.value.len();
-    if replacement.append_space {
-        buffer.insert(new_cursor, ' ');
-        new_cursor += 1;
-    }
-    *cursor_index = new_cursor;
-}
-
-fn render_prompt(
-    stdout: &mut io::StdoutLock<'_>,
-    buffer: &str,
-    cursor_index: usize,
-) -> io::Result<()> {
+fn render_prompt(stdout: &mut io::StdoutLock<'_>, editor: &LineEditor) -> io::Result<()> {
+    let buffer = editor.as_str();
     queue!(
         stdout,
         cursor::MoveToColumn(0),
         terminal::Clear(ClearType::CurrentLine)
     )?;
     write!(stdout, "> {buffer}")?;
-    let base = u16::try_from(buffer[..cursor_index].chars().count()).unwrap_or(u16::MAX);
+    let base = u16::try_from(editor.cursor()).unwrap_or(u16::MAX);
     let cursor_column = base.saturating_add(2);
     queue!(stdout, cursor::MoveToColumn(cursor_column))?;
     stdout.flush()
@@ -6,25 +6,59 @@ use std::path::Path;
 use std::rc::Rc;
 use std::time::{Duration, Instant as HostInstant};
 
+use controller_core::bridge::BridgeActivityKind;
+use controller_core::bridge::autobaud::{BaudSetting, BaudSource};
+use controller_core::bridge::console::{ConsoleMatcher, PatternError};
+use controller_core::bridge::inject::Escaped;
+use controller_core::bridge::line_coding::SYSRQ_BREAK;
+use controller_core::bridge::scrollback::ConsoleScrollback;
+use controller_core::bridge::stats::{BridgeStatsFormatter, BridgeStatsSnapshot};
+use controller_core::orchestrator::RequestTag;
 use controller_core::orchestrator::{
-    CommandEnqueueError, CommandQueueProducer, CommandSource, QueueErrorKind, ScheduleError,
-    ScheduleErrorInfo, SequenceCommand, SequenceScheduler, register_default_templates,
-    schedule_error_info,
+    BridgeHoldConfig, CommandEnqueueError, CommandQueueProducer, CommandSource, QueueErrorKind,
+    ScheduleError, ScheduleErrorInfo, SequenceCommand, SequenceCompletion, SequenceOutcome,
+    SequenceResult, SequenceScheduler, register_default_templates, schedule_error_info,
 };
+use controller_core::power::{
+    BrownOutFormatter, BrownOutReport, RailStatistics, RailStatsFormatter, RailStatsSnapshot,
+};
+use controller_core::repl::chain::CommandChain;
 use controller_core::repl::commands::{
-    CommandError, CommandExecutor, CommandOutcome, FaultAck, RebootAck, RecoveryAck,
+    BridgeStatsReport, CommandError, CommandExecutor, CommandOutcome, ConsoleAction, FaultAck,
+    PowerReport, RebootAck, RecoveryAck,
 };
 use controller_core::repl::completion::{CompletionEngine, CompletionResult, Replacement};
-use controller_core::repl::grammar::RecoveryCommand;
+use controller_core::repl::grammar::{
+    self, EventsCommand, MacroCommand, PatternCommand, RecoveryCommand, ScriptCommand, WaitCommand,
+};
+use controller_core::repl::help::HelpPage;
+use controller_core::repl::json::{ErrorReply, Event, JsonFormatter, Reply, ResponseMode};
+use controller_core::repl::macros::{MacroError, MacroLibrary};
 use controller_core::repl::status::{
     StatusAccumulator, StatusFormatter, StatusInstant, StatusProvider, StatusSnapshot,
 };
+use controller_core::repl::tag::TaggedLine;
+use controller_core::repl::wait::{DEFAULT_WAIT_TIMEOUT, PendingWait, WaitTimeout};
+use controller_core::script::{LoadProgress, ScriptEngine, ScriptEvent, ScriptName};
 use controller_core::sequences::fault::FAULT_RECOVERY_MAX_RETRIES;
 use controller_core::sequences::{
     SequenceTemplate, StepCompletion, StrapAction, StrapSequenceKind, StrapStep,
 };
+use controller_core::supervisor::{
+    BootWatchdog, WatchdogFormatter, WatchdogStatus, WatchdogUpdate,
+};
+use controller_core::telemetry::{
+    EventFilter, TelemetryInstant, TelemetryNotice, TelemetryRecorder,
+};
 
 const DEFAULT_QUEUE_DEPTH: usize = 4;
+const EMULATED_VDD_MV: u16 = 3300;
+/// Virtual clock step used when dry-running a script.
+const SCRIPT_DRY_RUN_TICK: Duration = Duration::from_millis(100);
+/// Virtual time after which a dry run is abandoned.
+const SCRIPT_DRY_RUN_LIMIT: Duration = Duration::from_secs(3600);
+/// Completions and telemetry kept for a later `wait`.
+const WAIT_HISTORY_DEPTH: usize = 32;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 struct HostStatusInstant(HostInstant);
@@ -41,30 +75,13 @@ impl StatusInstant for HostStatusInstant {
     }
 }
 
-type HostExecutor = CommandExecutor<SequenceScheduler<HostQueue>, EmulatorStatusProvider>;
+impl TelemetryInstant for HostStatusInstant {
+    fn saturating_duration_since(&self, earlier: Self) -> Duration {
+        self.0.saturating_duration_since(earlier.0)
+    }
+}
 
-pub const HELP_TOPICS: &[(&str, &str)] = &[
-    (
-        "reboot",
-        "reboot [now|delay <duration>]  - queue the normal reboot sequence",
-    ),
-    (
-        "recovery",
-        "recovery [enter|exit|now]    - manage recovery strap flows",
-    ),
-    (
-        "fault",
-        "fault recover [retries=<1-3>]   - attempt the fault recovery sequence",
-    ),
-    (
-        "status",
-        "status                        - display orchestrator state",
-    ),
-    (
-        "help",
-        "help [topic]                    - show help for a command",
-    ),
-];
+type HostExecutor = CommandExecutor<SequenceScheduler<HostQueue>, EmulatorStatusProvider>;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum TranscriptProfile {
@@ -123,6 +140,30 @@ pub struct Session {
     command_count: usize,
     completion: CompletionEngine,
     status: Rc<RefCell<StatusAccumulator<HostStatusInstant>>>,
+    rail: Rc<RefCell<RailStatistics>>,
+    console: Box<ConsoleScrollback<HostStatusInstant>>,
+    /// Marker patterns managed with `console pattern`; nothing feeds them.
+    patterns: ConsoleMatcher,
+    scripts: Box<ScriptEngine<HostStatusInstant>>,
+    mode: ResponseMode,
+    /// Completion events for tagged sequences, sent after the reply.
+    completions: Vec<SequenceCompletion>,
+    /// Simulated telemetry for the strap sequences run so far.
+    telemetry: Box<TelemetryRecorder<HostStatusInstant>>,
+    /// Telemetry categories selected with `events`.
+    events: EventFilter,
+    /// Subscribed telemetry recorded by the current command, sent after the reply.
+    notices: Vec<TelemetryNotice>,
+    /// Sequence ends not yet consumed by a `wait complete`, oldest first.
+    finished: VecDeque<SequenceCompletion>,
+    /// Telemetry not yet consumed by a `wait event`, oldest first.
+    recorded: VecDeque<TelemetryNotice>,
+    /// Commands of the current `;` chain still to run.
+    chain: CommandChain,
+    /// Macros stored with `macro define`.
+    macros: MacroLibrary,
+    /// Set when the command being run reports an error.
+    failed: bool,
 }
 
 impl Session {
@@ -135,10 +176,11 @@ impl Session {
             register_default_templates(templates).expect("register default sequence templates");
         }
         let mut status_state = StatusAccumulator::<HostStatusInstant>::new();
-        status_state.set_vdd_sample(Some(3300));
+        status_state.set_vdd_sample(Some(EMULATED_VDD_MV));
         status_state.set_control_link_attached(true);
         let status = Rc::new(RefCell::new(status_state));
-        let provider = EmulatorStatusProvider::new(status.clone());
+        let rail = Rc::new(RefCell::new(RailStatistics::new()));
+        let provider = EmulatorStatusProvider::new(status.clone(), rail.clone());
         let executor = CommandExecutor::new(scheduler).with_status_provider(provider);
 
         Ok(Self {
@@ -148,6 +190,20 @@ impl Session {
             command_count: 0,
             completion: CompletionEngine::new(),
             status,
+            rail,
+            console: Box::new(ConsoleScrollback::new()),
+            patterns: ConsoleMatcher::with_default_markers(),
+            scripts: Box::new(ScriptEngine::new()),
+            mode: ResponseMode::Text,
+            completions: Vec::new(),
+            telemetry: Box::new(TelemetryRecorder::new()),
+            events: EventFilter::NONE,
+            notices: Vec::new(),
+            finished: VecDeque::new(),
+            recorded: VecDeque::new(),
+            chain: CommandChain::new(),
+            macros: MacroLibrary::new(),
+            failed: false,
         })
     }
 
@@ -161,39 +217,96 @@ impl Session {
         self.transcript
             .append_line(elapsed, TranscriptRole::Host, trimmed)?;
 
-        if trimmed.eq_ignore_ascii_case("help") {
-            return self.handle_help(None, elapsed);
+        let lines = if self.scripts.is_loading() {
+            self.handle_script_line(trimmed)
+        } else {
+            self.run_chain(trimmed)
+        };
+        self.record_output(elapsed, &lines)?;
+        Ok(lines)
+    }
+
+    /// Runs the `;`-separated commands of a line, and any macro they start,
+    /// stopping at the first error unless the macro continues on errors.
+    fn run_chain(&mut self, line: &str) -> Vec<String> {
+        let tag = grammar::request_tag(line);
+        if let Err(error) = self.chain.start(line, tag) {
+            let text = self.error(format!("ERR chain {error}"), ErrorReply::Chain(error));
+            return vec![TaggedLine::new(&text, tag).to_string()];
         }
-        if let Some(rest) = trimmed.strip_prefix("help ") {
-            return self.handle_help(Some(rest.trim()), elapsed);
+        let mut lines = Vec::new();
+        while let Some(command) = self.chain.next_command() {
+            let tag = command.tag;
+            let text = command.text.to_string();
+            self.failed = false;
+            let output = if self.scripts.is_loading() {
+                self.handle_script_line(&text)
+            } else {
+                self.execute(&text, tag)
+            };
+            lines.extend(
+                output
+                    .iter()
+                    .map(|line| TaggedLine::new(line, tag).to_string()),
+            );
+            if self.failed {
+                self.chain.fail();
+            }
         }
+        lines
+    }
 
+    fn execute(&mut self, line: &str, tag: Option<RequestTag>) -> Vec<String> {
         let now = HostInstant::now();
-        match self.executor.execute(trimmed, now, CommandSource::UsbHost) {
-            Ok(CommandOutcome::Reboot(ack)) => self.handle_reboot(&ack, elapsed),
-            Ok(CommandOutcome::Recovery(ack)) => self.handle_recovery(&ack, elapsed),
-            Ok(CommandOutcome::Fault(ack)) => self.handle_fault(&ack, elapsed),
-            Ok(CommandOutcome::Status(snapshot)) => self.handle_status(snapshot, elapsed),
-            Err(CommandError::Parse(err)) => {
-                let message = format!("ERR syntax {err}");
-                let lines = vec![message];
-                self.record_output(elapsed, &lines)?;
-                Ok(lines)
-            }
-            Err(CommandError::Unsupported(topic)) => {
-                let message = format!("ERR unsupported {topic} (pending implementation)");
-                let lines = vec![message];
-                self.record_output(elapsed, &lines)?;
-                Ok(lines)
-            }
-            Err(CommandError::Schedule(err)) => {
-                let detail = describe_schedule_error(&err, self.started_at);
-                let message = format!("ERR schedule {detail}");
-                let lines = vec![message];
-                self.record_output(elapsed, &lines)?;
-                Ok(lines)
+        let mut lines = match self.executor.execute(line, now, CommandSource::UsbHost) {
+            Ok(CommandOutcome::Console(action)) => self.handle_console(action),
+            Ok(CommandOutcome::Script(action)) => self.handle_script(action),
+            Ok(CommandOutcome::Mode(mode)) => self.handle_mode(mode),
+            Ok(CommandOutcome::Events(action)) => self.handle_events(action),
+            Ok(CommandOutcome::Wait(command)) => self.handle_wait(command, now),
+            Ok(CommandOutcome::Macro(action)) => self.handle_macro(action, tag),
+            Ok(outcome) if self.mode.is_json() => self.handle_json(&outcome),
+            Ok(CommandOutcome::Reboot(ack)) => self.handle_reboot(&ack),
+            Ok(CommandOutcome::Recovery(ack)) => self.handle_recovery(&ack),
+            Ok(CommandOutcome::Fault(ack)) => self.handle_fault(&ack),
+            Ok(CommandOutcome::Power(report)) => handle_power(&report),
+            Ok(CommandOutcome::Watchdog(status)) => format_watchdog_lines(&status),
+            Ok(CommandOutcome::Bridge(report)) => handle_bridge(&report),
+            Ok(CommandOutcome::Status(snapshot)) => format_status_lines(&snapshot),
+            Ok(CommandOutcome::Help(page)) => handle_help(&page),
+            Err(err) => {
+                self.failed = true;
+                if self.mode.is_json() {
+                    let error = ErrorReply::from_command_error(&err, |ready_at| {
+                        ready_at.saturating_duration_since(now)
+                    });
+                    vec![json_line(|line| error.write(line))]
+                } else {
+                    match err {
+                        CommandError::Parse(err) => vec![format!("ERR syntax {err}")],
+                        CommandError::Unsupported(topic) => {
+                            vec![format!("ERR unsupported {topic} (pending implementation)")]
+                        }
+                        CommandError::Schedule(err) => {
+                            let detail = describe_schedule_error(&err, self.started_at);
+                            vec![format!("ERR schedule {detail}")]
+                        }
+                    }
+                }
             }
+        };
+        for notice in std::mem::take(&mut self.notices) {
+            lines.push(self.event(notice.to_string(), Event::Telemetry(&notice)));
         }
+        for done in std::mem::take(&mut self.completions) {
+            lines.push(self.event(format!("DONE {done}"), Event::Done(&done)));
+        }
+        lines
+    }
+
+    /// Reply for a line that outgrew the editor.
+    pub fn line_too_long(&mut self) -> String {
+        self.error("ERR line-too-long".to_string(), ErrorReply::LineTooLong)
     }
 
     pub fn handle_completion(
@@ -241,55 +354,238 @@ impl Session {
         Ok(CompletionResponse::Suggestions { options })
     }
 
-    fn handle_help(&mut self, topic: Option<&str>, elapsed: Duration) -> io::Result<Vec<String>> {
-        let mut lines = Vec::new();
-        match topic {
-            Some(target) if !target.is_empty() => {
-                if let Some((_, detail)) = HELP_TOPICS
-                    .iter()
-                    .find(|(name, _)| name.eq_ignore_ascii_case(target))
-                {
-                    lines.push((*detail).to_string());
-                } else {
-                    lines.push(format!("No help available for `{target}`."));
-                    lines.push(format!("Available topics: {}", help_topic_list()));
-                }
+    /// Switches the response format, replying in the format now in effect.
+    fn handle_mode(&mut self, mode: Option<ResponseMode>) -> Vec<String> {
+        if let Some(mode) = mode {
+            self.mode = mode;
+        }
+        vec![self.reply(format!("OK mode {}", self.mode), Reply::Mode(self.mode))]
+    }
+
+    /// Updates the telemetry categories printed after each command.
+    fn handle_events(&mut self, action: EventsCommand) -> Vec<String> {
+        self.events = action.apply(self.events);
+        vec![self.reply(
+            format!("OK events {}", self.events),
+            Reply::Events(self.events),
+        )]
+    }
+
+    /// Answers a `wait` from what earlier commands already produced, since
+    /// emulated sequences finish before their reply is printed. A condition
+    /// that never held reports a timeout straight away.
+    fn handle_wait(&mut self, command: WaitCommand, now: HostInstant) -> Vec<String> {
+        let now = HostStatusInstant::from(now);
+        let wait = PendingWait::new(command.condition, command.timeout, now);
+        let report = wait.on_idle(true, now).or_else(|| {
+            let index = self
+                .finished
+                .iter()
+                .position(|done| wait.on_completion(done, now).is_some())?;
+            let done = self.finished.drain(..=index).next_back()?;
+            wait.on_completion(&done, now)
+        });
+        let report = report.or_else(|| {
+            let index = self
+                .recorded
+                .iter()
+                .position(|notice| wait.on_event(notice, now).is_some())?;
+            let notice = self.recorded.drain(..=index).next_back()?;
+            wait.on_event(&notice, now)
+        });
+        match report {
+            Some(report) => vec![self.reply(format!("OK wait {report}"), Reply::Wait(&report))],
+            None => {
+                let timeout = WaitTimeout {
+                    condition: command.condition,
+                    waited: command.timeout.unwrap_or(DEFAULT_WAIT_TIMEOUT),
+                };
+                vec![self.error(
+                    format!("ERR timeout {timeout}"),
+                    ErrorReply::Timeout(&timeout),
+                )]
             }
-            _ => {
-                lines.push("Available commands:".to_string());
-                for (_, detail) in HELP_TOPICS {
-                    lines.push(format!("  {detail}"));
-                }
-                lines.push("Type `help <topic>` for a specific command.".to_string());
+        }
+    }
+
+    /// Stores, runs, or lists macros. A started macro's commands run next in
+    /// the chain of the line that ran it.
+    fn handle_macro(&mut self, action: MacroCommand, tag: Option<RequestTag>) -> Vec<String> {
+        let result = match action {
+            MacroCommand::Define {
+                name,
+                body,
+                continue_on_error,
+            } => self
+                .macros
+                .define(name, &body, continue_on_error)
+                .cloned()
+                .map(|entry| {
+                    self.reply(
+                        format!("OK macro define {} commands={}", entry.name, entry.commands),
+                        Reply::MacroDefined(&entry),
+                    )
+                }),
+            MacroCommand::Run { name } => match self.macros.get(&name).cloned() {
+                Some(entry) => match self.chain.push(&entry.body, entry.continue_on_error, tag) {
+                    Ok(_) => Ok(self.reply(
+                        format!("OK macro run {} commands={}", entry.name, entry.commands),
+                        Reply::MacroStarted(&entry),
+                    )),
+                    Err(err) => Err(MacroError::from(err)),
+                },
+                None => Err(MacroError::NotFound),
+            },
+            MacroCommand::List => {
+                let mut lines: Vec<String> = self
+                    .macros
+                    .entries()
+                    .map(|entry| self.reply(entry.to_string(), Reply::MacroEntry(entry)))
+                    .collect();
+                let count = lines.len();
+                lines.push(self.reply(format!("OK macros={count}"), Reply::MacroListed { count }));
+                return lines;
             }
+        };
+        match result {
+            Ok(line) => vec![line],
+            Err(err) => vec![self.error(format!("ERR macro {err}"), ErrorReply::Macro(&err))],
         }
+    }
 
-        self.record_output(elapsed, &lines)?;
-        Ok(lines)
+    /// Keeps the newest telemetry record for `wait event` and queues it for
+    /// printing when its category is subscribed.
+    fn publish_telemetry(&mut self) {
+        let Some(record) = self.telemetry.latest() else {
+            return;
+        };
+        let uptime = record
+            .timestamp
+            .0
+            .saturating_duration_since(self.started_at);
+        let notice = TelemetryNotice::new(record, uptime);
+        if self.recorded.len() == WAIT_HISTORY_DEPTH {
+            self.recorded.pop_front();
+        }
+        self.recorded.push_back(notice);
+        if self.events.matches(notice.event) {
+            self.notices.push(notice);
+        }
     }
 
-    fn handle_reboot(
+    /// Records the telemetry a firmware run of `template` would produce,
+    /// with each strap transition at its scheduled offset.
+    fn record_sequence_telemetry(
         &mut self,
-        ack: &RebootAck<HostInstant>,
-        elapsed: Duration,
-    ) -> io::Result<Vec<String>> {
+        sequence: StrapSequenceKind,
+        template: &SequenceTemplate,
+        queue_depth: usize,
+        requested_at: HostInstant,
+        started_at: HostInstant,
+    ) {
+        self.telemetry.record_command_started(
+            sequence,
+            queue_depth,
+            requested_at.into(),
+            started_at.into(),
+        );
+        self.publish_telemetry();
+        let mut at = started_at;
+        for step in template.steps() {
+            self.telemetry
+                .record_strap_transition(step.line, step.action, at.into());
+            self.publish_telemetry();
+            at += step.hold_duration();
+        }
+        self.telemetry.record_sequence_completion(
+            sequence,
+            SequenceOutcome::Completed,
+            Some(started_at.into()),
+            at.into(),
+            template.step_count() + 2,
+        );
+        self.publish_telemetry();
+    }
+
+    /// Renders an outcome in JSON mode. Strap sequences still run so the
+    /// emulated state matches text mode, but their narration is dropped.
+    fn handle_json(&mut self, outcome: &CommandOutcome<HostInstant>) -> Vec<String> {
+        match outcome {
+            CommandOutcome::Reboot(ack) => {
+                self.handle_reboot(ack);
+            }
+            CommandOutcome::Recovery(ack) => {
+                self.handle_recovery(ack);
+            }
+            CommandOutcome::Fault(ack) => {
+                self.handle_fault(ack);
+            }
+            _ => {}
+        }
+        let formatter = JsonFormatter::new(outcome);
+        (0..formatter.line_count())
+            .map(|index| json_line(|line| formatter.write_line(line, index)))
+            .collect()
+    }
+
+    /// Picks the text or JSON form of a reply for the current mode.
+    fn reply(&self, text: String, reply: Reply<'_>) -> String {
+        if self.mode.is_json() {
+            json_line(|line| reply.write(line))
+        } else {
+            text
+        }
+    }
+
+    /// Picks the text or JSON form of an event for the current mode.
+    fn event(&self, text: String, event: Event<'_>) -> String {
+        if self.mode.is_json() {
+            json_line(|line| event.write(line))
+        } else {
+            text
+        }
+    }
+
+    /// Picks the text or JSON form of an error for the current mode and
+    /// marks the command as failed.
+    fn error(&mut self, text: String, error: ErrorReply<'_>) -> String {
+        self.failed = true;
+        if self.mode.is_json() {
+            json_line(|line| error.write(line))
+        } else {
+            text
+        }
+    }
+
+    fn handle_reboot(&mut self, ack: &RebootAck<HostInstant>) -> Vec<String> {
         let start_delay = ack.start_after.unwrap_or(Duration::ZERO);
-        let label = "reboot";
+        let to_bootloader = ack.to_bootloader;
+        let label = if to_bootloader {
+            "reboot to-bootloader"
+        } else {
+            "reboot"
+        };
         self.handle_sequence(
             label,
             StrapSequenceKind::NormalReboot,
             ack.requested_at,
             start_delay,
-            elapsed,
-            |summary| SequenceNarration::new(default_ack(summary)),
+            |summary| {
+                let head = default_ack(summary);
+                if to_bootloader {
+                    // No Jetson console here, so there is no countdown to interrupt.
+                    SequenceNarration::with_notes(
+                        head,
+                        vec!["bootloader interrupt skipped (emulator has no console)".to_string()],
+                    )
+                } else {
+                    SequenceNarration::new(head)
+                }
+            },
         )
     }
 
-    fn handle_recovery(
-        &mut self,
-        ack: &RecoveryAck<HostInstant>,
-        elapsed: Duration,
-    ) -> io::Result<Vec<String>> {
+    fn handle_recovery(&mut self, ack: &RecoveryAck<HostInstant>) -> Vec<String> {
         match ack.command {
             RecoveryCommand::Enter => {
                 let label = "recovery enter";
@@ -298,7 +594,6 @@ impl Session {
                     ack.sequence,
                     ack.requested_at,
                     Duration::ZERO,
-                    elapsed,
                     |summary| SequenceNarration::new(default_ack(summary)),
                 )
             }
@@ -309,18 +604,17 @@ impl Session {
                     ack.sequence,
                     ack.requested_at,
                     Duration::ZERO,
-                    elapsed,
                     |summary| SequenceNarration::new(default_ack(summary)),
                 )
             }
-            RecoveryCommand::Now => {
+            RecoveryCommand::Now { timeout } => {
                 let label = "recovery now";
+                let timeout = timeout.map_or_else(BridgeHoldConfig::default, BridgeHoldConfig::new);
                 self.handle_sequence(
                     label,
                     ack.sequence,
                     ack.requested_at,
                     Duration::ZERO,
-                    elapsed,
                     |summary| {
                         let head = format!(
                             "OK recovery waiting-for-console seq={} at=+{}ms cooldown={} ready=+{}ms queue-depth={}",
@@ -331,8 +625,10 @@ impl Session {
                             summary.queue_depth,
                         );
                         let notes = vec![
-                            "monitoring for console activity on bridge (timeout 10s fallback)"
-                                .to_string(),
+                            format!(
+                                "monitoring for console activity on bridge (timeout {} fallback)",
+                                format_timeout(timeout.timeout())
+                            ),
                             "emulator parity: REC releases automatically once activity is detected"
                                 .to_string(),
                         ];
@@ -343,11 +639,7 @@ impl Session {
         }
     }
 
-    fn handle_fault(
-        &mut self,
-        ack: &FaultAck<HostInstant>,
-        elapsed: Duration,
-    ) -> io::Result<Vec<String>> {
+    fn handle_fault(&mut self, ack: &FaultAck<HostInstant>) -> Vec<String> {
         let default_budget = {
             let scheduler = self.executor.scheduler();
             scheduler
@@ -363,7 +655,6 @@ impl Session {
             ack.sequence,
             ack.requested_at,
             Duration::ZERO,
-            elapsed,
             |summary| {
                 let head = format!(
                     "OK fault recover seq={} at=+{}ms retries={}",
@@ -382,14 +673,222 @@ impl Session {
         )
     }
 
-    fn handle_status(
-        &mut self,
-        snapshot: StatusSnapshot,
-        elapsed: Duration,
-    ) -> io::Result<Vec<String>> {
-        let lines = format_status_lines(&snapshot);
-        self.record_output(elapsed, &lines)?;
-        Ok(lines)
+    fn handle_console(&mut self, action: ConsoleAction) -> Vec<String> {
+        let selection = match action {
+            ConsoleAction::Show(selection) => selection,
+            ConsoleAction::SysRq(key) => {
+                let text = format!(
+                    "OK console sysrq {key} break={}",
+                    format_duration_short(SYSRQ_BREAK)
+                );
+                return vec![self.reply(text, Reply::ConsoleSysRq(key))];
+            }
+            ConsoleAction::Send { text, newline } => {
+                let bytes = text.len() + usize::from(newline);
+                {
+                    let mut status = self.status.borrow_mut();
+                    let bridge = status.bridge_tracker();
+                    bridge.record_tx(HostStatusInstant::from(HostInstant::now()));
+                    bridge
+                        .stats_mut()
+                        .record_frame(BridgeActivityKind::UsbToJetson, bytes);
+                }
+                let text = format!("OK console send bytes={bytes}");
+                return vec![self.reply(text, Reply::ConsoleSent { bytes })];
+            }
+            ConsoleAction::Attach => {
+                let text = "ERR console attach unavailable (emulator has no console)".to_string();
+                return vec![self.error(text, ErrorReply::Unavailable("console attach"))];
+            }
+            ConsoleAction::Baud(setting @ BaudSetting::Fixed(baud)) => {
+                {
+                    let mut status = self.status.borrow_mut();
+                    let bridge = status.bridge_tracker();
+                    let coding = bridge.line_coding().with_baud(baud);
+                    bridge.set_line_coding(coding);
+                    bridge.set_baud_source(BaudSource::Manual);
+                }
+                let text = format!("OK console baud {baud}");
+                return vec![self.reply(text, Reply::ConsoleBaud(setting))];
+            }
+            ConsoleAction::Baud(BaudSetting::Auto) => {
+                let text =
+                    "ERR console baud auto unavailable (emulator has no console)".to_string();
+                return vec![self.error(text, ErrorReply::Unavailable("console baud auto"))];
+            }
+            ConsoleAction::Pattern(command) => return self.handle_console_pattern(command),
+        };
+        let origin = HostStatusInstant::from(self.started_at);
+        let mut lines = Vec::new();
+        for seq in self.console.select(selection) {
+            if let Some(line) = self.console.line(seq) {
+                let mut text = String::new();
+                line.write_to(&mut text, origin)
+                    .expect("format scrollback line");
+                lines.push(text);
+            }
+        }
+        let shown = u32::try_from(lines.len()).unwrap_or(u32::MAX);
+        let run = self.console.current_run();
+        let dropped = self.console.evicted_lines();
+        if self.mode.is_json() {
+            lines = lines
+                .iter()
+                .map(|text| json_line(|line| Reply::ConsoleLine(text).write(line)))
+                .collect();
+        }
+        lines.push(self.reply(
+            format!("OK console lines={shown} run={run} dropped={dropped}"),
+            Reply::ConsoleShown {
+                lines: shown,
+                run,
+                dropped,
+            },
+        ));
+        lines
+    }
+
+    fn handle_script(&mut self, action: ScriptCommand) -> Vec<String> {
+        match action {
+            ScriptCommand::Load { name } => match self.scripts.begin_load(name.clone()) {
+                Ok(()) => vec![self.reply(
+                    format!("OK script load {name} (finish with `end`)"),
+                    Reply::ScriptLoading(&name),
+                )],
+                Err(err) => vec![self.error(format!("ERR script {err}"), ErrorReply::Script(&err))],
+            },
+            ScriptCommand::List => {
+                let mut lines: Vec<String> = self
+                    .scripts
+                    .summaries()
+                    .map(|summary| {
+                        self.reply(
+                            format!("script {} steps={}", summary.name, summary.steps),
+                            Reply::ScriptEntry(&summary),
+                        )
+                    })
+                    .collect();
+                let count = lines.len();
+                lines
+                    .push(self.reply(format!("OK scripts={count}"), Reply::ScriptListed { count }));
+                lines
+            }
+            ScriptCommand::Run { name } => self.dry_run_script(&name),
+            ScriptCommand::Stop => vec![self.error(
+                "ERR script not running".to_string(),
+                ErrorReply::Script(&"not running"),
+            )],
+        }
+    }
+
+    fn handle_console_pattern(&mut self, command: PatternCommand) -> Vec<String> {
+        let result = match command {
+            PatternCommand::Add { marker, text } => {
+                self.patterns.register(marker, &text).map(|id| {
+                    self.reply(
+                        format!("OK console pattern add id={id} marker={marker}"),
+                        Reply::ConsolePatternAdded { id, marker },
+                    )
+                })
+            }
+            PatternCommand::Remove { id } => {
+                if self.patterns.unregister(id) {
+                    Ok(self.reply(
+                        format!("OK console pattern remove id={id}"),
+                        Reply::ConsolePatternRemoved(id),
+                    ))
+                } else {
+                    Err(PatternError::NotRegistered(id))
+                }
+            }
+            PatternCommand::List => {
+                let mut lines: Vec<String> = self
+                    .patterns
+                    .patterns()
+                    .map(|(id, marker, text)| {
+                        self.reply(
+                            format!("pattern {id} {marker} \"{}\"", Escaped(text)),
+                            Reply::ConsolePattern { id, marker, text },
+                        )
+                    })
+                    .collect();
+                let count = lines.len();
+                lines.push(self.reply(
+                    format!("OK patterns={count}"),
+                    Reply::ConsolePatternsListed { count },
+                ));
+                return lines;
+            }
+        };
+        match result {
+            Ok(line) => vec![line],
+            Err(err) => vec![self.error(format!("ERR console {err}"), ErrorReply::Pattern(&err))],
+        }
+    }
+
+    fn handle_script_line(&mut self, line: &str) -> Vec<String> {
+        match self.scripts.load_line(line) {
+            Ok(LoadProgress::Pending) => Vec::new(),
+            Ok(LoadProgress::Loaded { name, steps }) => vec![self.reply(
+                format!("OK script {name} loaded steps={steps}"),
+                Reply::ScriptLoaded { name: &name, steps },
+            )],
+            Err(err) => vec![self.error(format!("ERR script {err}"), ErrorReply::Script(&err))],
+        }
+    }
+
+    /// Runs a script against a virtual clock. The emulator has no Jetson
+    /// console, so sends and strap sequences are only reported and every
+    /// expect runs to its timeout. JSON mode reports the run like the
+    /// firmware does and leaves out the dry-run narration.
+    fn dry_run_script(&mut self, name: &ScriptName) -> Vec<String> {
+        let origin = HostInstant::now();
+        let mut offset = Duration::ZERO;
+        if let Err(err) = self
+            .scripts
+            .start(name, HostStatusInstant::from(origin + offset))
+        {
+            return vec![self.error(format!("ERR script {err}"), ErrorReply::Script(&err))];
+        }
+
+        let json = self.mode.is_json();
+        let mut lines = Vec::new();
+        if json {
+            lines.push(json_line(|line| Reply::ScriptStarted(name).write(line)));
+        }
+        while offset <= SCRIPT_DRY_RUN_LIMIT {
+            let now = HostStatusInstant::from(origin + offset);
+            match self.scripts.poll(now) {
+                Some(ScriptEvent::Send(_) | ScriptEvent::Sequence(_)) if json => {}
+                Some(ScriptEvent::Send(text)) => {
+                    lines.push(format!("script send bytes={} (dry run)", text.len()));
+                }
+                Some(ScriptEvent::Sequence(kind)) => {
+                    lines.push(format!("script queue {kind} (dry run)"));
+                }
+                Some(ScriptEvent::Expect(report) | ScriptEvent::Finished(report)) if json => {
+                    lines.push(json_line(|line| Event::Script(&report).write(line)));
+                    if report.result.is_final() {
+                        return lines;
+                    }
+                }
+                Some(ScriptEvent::Expect(report)) => lines.push(report.to_string()),
+                Some(ScriptEvent::Finished(report)) => {
+                    lines.push(format!("OK {report}"));
+                    return lines;
+                }
+                None => offset += SCRIPT_DRY_RUN_TICK,
+            }
+        }
+
+        if let Some(report) = self.scripts.stop(HostStatusInstant::from(origin + offset)) {
+            if json {
+                lines.push(json_line(|line| Event::Script(&report).write(line)));
+            } else {
+                lines.push(format!("ERR {report}"));
+            }
+        }
+        lines
     }
 
     fn record_output(&mut self, elapsed: Duration, lines: &[String]) -> io::Result<()> {
@@ -406,14 +905,14 @@ impl Session {
         sequence: StrapSequenceKind,
         requested_at: HostInstant,
         start_after: Duration,
-        elapsed: Duration,
         formatter: F,
-    ) -> io::Result<Vec<String>>
+    ) -> Vec<String>
     where
         F: FnOnce(&SequenceSummary) -> SequenceNarration,
     {
         self.command_count += 1;
         let sequence_id = self.command_count;
+        self.console.begin_run();
 
         let (queue_depth, template): (usize, SequenceTemplate) = {
             let scheduler = self.executor.scheduler();
@@ -462,7 +961,10 @@ impl Session {
 
         {
             let mut status = self.status.borrow_mut();
+            let mut rail = self.rail.borrow_mut();
+            rail.begin_capture(sequence);
             for step in template.steps() {
+                rail.record(Some(EMULATED_VDD_MV));
                 let strap = step.strap();
                 let asserted = matches!(step.action, StrapAction::AssertLow);
                 status.set_strap(strap.id, asserted);
@@ -470,16 +972,38 @@ impl Session {
                 status.bridge_tracker().set_waiting_for_activity(waiting);
             }
             status.bridge_tracker().set_waiting_for_activity(false);
+            let _ = rail.finish_capture();
         }
 
+        self.record_sequence_telemetry(
+            sequence,
+            &template,
+            queue_depth,
+            requested_at,
+            requested_at + start_after,
+        );
+
         {
             let scheduler = self.executor.scheduler_mut();
             let _ = scheduler.notify_completed(sequence, completion);
-            let _ = scheduler.producer_mut().pop_front();
+            if let Some(command) = scheduler.producer_mut().pop_front() {
+                let done = SequenceCompletion {
+                    tag: command.flags.tag,
+                    kind: command.kind,
+                    result: SequenceResult::Finished(SequenceOutcome::Completed),
+                    elapsed: completion.saturating_duration_since(command.requested_at),
+                };
+                if done.tag.is_some() {
+                    self.completions.push(done);
+                }
+                if self.finished.len() == WAIT_HISTORY_DEPTH {
+                    self.finished.pop_front();
+                }
+                self.finished.push_back(done);
+            }
         }
 
-        self.record_output(elapsed, &lines)?;
-        Ok(lines)
+        lines
     }
 }
 
@@ -513,6 +1037,44 @@ impl SequenceNarration {
     }
 }
 
+fn handle_help(page: &HelpPage) -> Vec<String> {
+    (0..page.line_count())
+        .map(|index| {
+            let mut line = String::new();
+            page.write_line(index, &mut line).expect("format help line");
+            line
+        })
+        .collect()
+}
+
+fn handle_power(report: &PowerReport) -> Vec<String> {
+    match report {
+        PowerReport::Stats { snapshot, reset } => {
+            let mut lines = format_rail_stats_lines(snapshot);
+            if *reset {
+                lines.push("OK power stats reset".to_string());
+            }
+            lines
+        }
+        PowerReport::History(report) => format_brown_out_lines(report),
+    }
+}
+
+fn handle_bridge(report: &BridgeStatsReport) -> Vec<String> {
+    let mut lines = format_bridge_stats_lines(&report.snapshot);
+    if report.reset {
+        lines.push("OK bridge stats reset".to_string());
+    }
+    lines
+}
+
+/// Renders one JSON object; writing into a `String` cannot run out of room.
+fn json_line(write: impl FnOnce(&mut String) -> std::fmt::Result) -> String {
+    let mut line = String::new();
+    write(&mut line).expect("format json line");
+    line
+}
+
 fn default_ack(summary: &SequenceSummary) -> String {
     format!(
         "OK {label} queued seq={} at=+{}ms start-after={} cooldown={} ready=+{}ms queue-depth={}",
@@ -690,17 +1252,6 @@ impl TranscriptRole {
     }
 }
 
-fn help_topic_list() -> String {
-    let mut buffer = String::new();
-    for (index, (name, _)) in HELP_TOPICS.iter().enumerate() {
-        if index > 0 {
-            buffer.push_str(", ");
-        }
-        buffer.push_str(name);
-    }
-    buffer
-}
-
 fn describe_schedule_error(
     error: &ScheduleError<(), HostInstant>,
     session_start: HostInstant,
@@ -761,6 +1312,14 @@ fn action_label(action: StrapAction) -> &'static str {
     }
 }
 
+fn format_timeout(duration: Duration) -> String {
+    if duration.subsec_millis() == 0 {
+        format!("{}s", duration.as_secs())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
 fn format_duration_short(duration: Duration) -> String {
     if duration.as_secs() == 0 {
         format!("{}ms", duration.as_millis())
@@ -774,6 +1333,7 @@ fn format_status_lines(snapshot: &StatusSnapshot) -> Vec<String> {
         format_strap_line(snapshot),
         format_power_line(snapshot),
         format_bridge_line(snapshot),
+        format_bridge_stats_line(snapshot),
     ]
 }
 
@@ -801,13 +1361,82 @@ fn format_bridge_line(snapshot: &StatusSnapshot) -> String {
     line
 }
 
+fn format_bridge_stats_line(snapshot: &StatusSnapshot) -> String {
+    let mut line = String::new();
+    StatusFormatter::new(snapshot)
+        .write_bridge_stats_line(&mut line)
+        .expect("format bridge stats line");
+    line
+}
+
+fn format_bridge_stats_lines(snapshot: &BridgeStatsSnapshot) -> Vec<String> {
+    let formatter = BridgeStatsFormatter::new(snapshot);
+    (0..formatter.line_count())
+        .map(|index| {
+            let mut line = String::new();
+            formatter
+                .write_line(&mut line, index)
+                .expect("format bridge stats line");
+            line
+        })
+        .collect()
+}
+
+fn format_rail_stats_lines(snapshot: &RailStatsSnapshot) -> Vec<String> {
+    let formatter = RailStatsFormatter::new(snapshot);
+    (0..formatter.line_count())
+        .map(|index| {
+            let mut line = String::new();
+            formatter
+                .write_line(&mut line, index)
+                .expect("format power stats line");
+            line
+        })
+        .collect()
+}
+
+fn format_brown_out_lines(report: &BrownOutReport) -> Vec<String> {
+    let formatter = BrownOutFormatter::new(report);
+    (0..formatter.line_count())
+        .map(|index| {
+            let mut line = String::new();
+            formatter
+                .write_line(&mut line, index)
+                .expect("format power history line");
+            line
+        })
+        .collect()
+}
+
+fn format_watchdog_lines(status: &WatchdogStatus) -> Vec<String> {
+    let formatter = WatchdogFormatter::new(status);
+    (0..formatter.line_count())
+        .map(|index| {
+            let mut line = String::new();
+            formatter
+                .write_line(&mut line, index)
+                .expect("format watchdog line");
+            line
+        })
+        .collect()
+}
+
 struct EmulatorStatusProvider {
     state: Rc<RefCell<StatusAccumulator<HostStatusInstant>>>,
+    rail: Rc<RefCell<RailStatistics>>,
+    watchdog: BootWatchdog<HostStatusInstant>,
 }
 
 impl EmulatorStatusProvider {
-    fn new(state: Rc<RefCell<StatusAccumulator<HostStatusInstant>>>) -> Self {
-        Self { state }
+    fn new(
+        state: Rc<RefCell<StatusAccumulator<HostStatusInstant>>>,
+        rail: Rc<RefCell<RailStatistics>>,
+    ) -> Self {
+        Self {
+            state,
+            rail,
+            watchdog: BootWatchdog::default(),
+        }
     }
 }
 
@@ -815,4 +1444,46 @@ impl StatusProvider<HostInstant> for EmulatorStatusProvider {
     fn snapshot(&mut self, now: HostInstant) -> Option<StatusSnapshot> {
         Some(self.state.borrow().snapshot(HostStatusInstant::from(now)))
     }
+
+    fn rail_stats(&mut self) -> Option<RailStatsSnapshot> {
+        Some(self.rail.borrow().snapshot())
+    }
+
+    fn reset_rail_stats(&mut self) {
+        self.rail.borrow_mut().reset();
+    }
+
+    fn bridge_stats(&mut self) -> Option<BridgeStatsSnapshot> {
+        Some(
+            self.state
+                .borrow_mut()
+                .bridge_tracker()
+                .stats_mut()
+                .snapshot(),
+        )
+    }
+
+    fn reset_bridge_stats(&mut self) {
+        self.state.borrow_mut().bridge_tracker().stats_mut().reset();
+    }
+
+    fn brown_out_history(&mut self, _now: HostInstant) -> Option<BrownOutReport> {
+        // The emulated rail never sags, so the log is always empty.
+        Some(BrownOutReport::default())
+    }
+
+    fn watchdog_status(&mut self, now: HostInstant) -> Option<WatchdogStatus> {
+        Some(self.watchdog.status(HostStatusInstant::from(now)))
+    }
+
+    fn update_watchdog(
+        &mut self,
+        update: WatchdogUpdate,
+        now: HostInstant,
+    ) -> Option<WatchdogStatus> {
+        // There is no emulated console, so only the configuration is exercised.
+        let now = HostStatusInstant::from(now);
+        self.watchdog.apply(update, now);
+        Some(self.watchdog.status(now))
+    }
 }
@@ -0,0 +1,113 @@
+#![cfg_attr(not(target_os = "none"), allow(dead_code))]
+
+//! Shared autoboot interrupter for `reboot to-bootloader`.
+//!
+//! The REPL task arms the interrupter after queueing the reboot, polls it,
+//! and writes the interrupt keys it asks for. The bridge task feeds it the
+//! Jetson console output. Final reports are queued for the strap task, which
+//! owns the telemetry ring.
+
+use controller_core::bridge::autoboot::{AutobootEvent, AutobootReport};
+
+use crate::straps::FirmwareInstant;
+
+#[cfg(target_os = "none")]
+use controller_core::bridge::autoboot::{AutobootConfig, AutobootInterrupter};
+#[cfg(target_os = "none")]
+use core::cell::RefCell;
+#[cfg(target_os = "none")]
+use embassy_sync::blocking_mutex::{Mutex, raw::ThreadModeRawMutex};
+#[cfg(target_os = "none")]
+use embassy_sync::channel::Channel;
+
+/// Interval between interrupter polls while a run is active.
+pub const AUTOBOOT_POLL_INTERVAL: embassy_time::Duration = embassy_time::Duration::from_millis(50);
+/// Depth of the queue carrying reports to the strap task.
+pub const AUTOBOOT_REPORT_QUEUE_DEPTH: usize = 2;
+
+/// Finished run waiting to be recorded as telemetry.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AutobootReportHit {
+    pub report: AutobootReport,
+    pub timestamp: FirmwareInstant,
+}
+
+#[cfg(target_os = "none")]
+static INTERRUPTER: Mutex<ThreadModeRawMutex, RefCell<AutobootInterrupter<FirmwareInstant>>> =
+    Mutex::new(RefCell::new(
+        AutobootInterrupter::new(AutobootConfig::new()),
+    ));
+
+#[cfg(target_os = "none")]
+static AUTOBOOT_REPORTS: Channel<
+    ThreadModeRawMutex,
+    AutobootReportHit,
+    AUTOBOOT_REPORT_QUEUE_DEPTH,
+> = Channel::new();
+
+/// Starts watching for the autoboot countdown; `false` if already watching.
+#[cfg(target_os = "none")]
+pub fn arm(now: FirmwareInstant) -> bool {
+    INTERRUPTER.lock(|interrupter| interrupter.borrow_mut().arm(now))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn arm(_: FirmwareInstant) -> bool {
+    false
+}
+
+/// Returns `true` while a run is in progress.
+#[cfg(target_os = "none")]
+pub fn is_active() -> bool {
+    INTERRUPTER.lock(|interrupter| interrupter.borrow().is_active())
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn is_active() -> bool {
+    false
+}
+
+/// Passes Jetson console output to the active run.
+#[cfg(target_os = "none")]
+pub fn feed_console(bytes: &[u8]) {
+    INTERRUPTER.lock(|interrupter| interrupter.borrow_mut().feed(bytes));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn feed_console(_: &[u8]) {}
+
+/// Returns the next key to send or the final report, if either is due.
+#[cfg(target_os = "none")]
+pub fn poll(now: FirmwareInstant) -> Option<AutobootEvent> {
+    INTERRUPTER.lock(|interrupter| interrupter.borrow_mut().poll(now))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn poll(_: FirmwareInstant) -> Option<AutobootEvent> {
+    None
+}
+
+/// Queues a final report for the strap task to record as telemetry.
+#[cfg(target_os = "none")]
+pub fn queue_report(report: AutobootReport, timestamp: FirmwareInstant) {
+    if AUTOBOOT_REPORTS
+        .try_send(AutobootReportHit { report, timestamp })
+        .is_err()
+    {
+        defmt::warn!("autoboot: dropping report (queue full)");
+    }
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn queue_report(_: AutobootReport, _: FirmwareInstant) {}
+
+/// Pops the oldest queued report, if any.
+#[cfg(target_os = "none")]
+pub fn take_report() -> Option<AutobootReportHit> {
+    AUTOBOOT_REPORTS.try_receive().ok()
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn take_report() -> Option<AutobootReportHit> {
+    None
+}
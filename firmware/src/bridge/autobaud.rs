@@ -0,0 +1,106 @@
+#![cfg_attr(not(target_os = "none"), allow(dead_code))]
+
+//! Shared baud rate detector for `console baud auto`.
+//!
+//! The bridge task starts a run when the REPL asks for one, switches the UART
+//! to each rate the detector names, and feeds it the bytes and receive errors
+//! seen along the way. Final reports are queued for the REPL task to print.
+
+use controller_core::bridge::autobaud::{AutobaudReport, AutobaudStep};
+
+use crate::straps::FirmwareInstant;
+
+#[cfg(target_os = "none")]
+use controller_core::bridge::autobaud::{AutobaudConfig, AutobaudDetector};
+#[cfg(target_os = "none")]
+use core::cell::RefCell;
+#[cfg(target_os = "none")]
+use embassy_sync::blocking_mutex::{Mutex, raw::ThreadModeRawMutex};
+#[cfg(target_os = "none")]
+use embassy_sync::channel::Channel;
+
+/// Interval between detector polls while a run is active.
+pub const AUTOBAUD_POLL_INTERVAL: embassy_time::Duration = embassy_time::Duration::from_millis(20);
+/// Depth of the queue carrying reports to the REPL task.
+pub const AUTOBAUD_REPORT_QUEUE_DEPTH: usize = 2;
+
+#[cfg(target_os = "none")]
+static DETECTOR: Mutex<ThreadModeRawMutex, RefCell<AutobaudDetector<FirmwareInstant>>> =
+    Mutex::new(RefCell::new(AutobaudDetector::new(AutobaudConfig::new())));
+
+#[cfg(target_os = "none")]
+static AUTOBAUD_REPORTS: Channel<ThreadModeRawMutex, AutobaudReport, AUTOBAUD_REPORT_QUEUE_DEPTH> =
+    Channel::new();
+
+/// Starts a run and returns the first rate to apply; `None` if already running.
+#[cfg(target_os = "none")]
+pub fn start(now: FirmwareInstant) -> Option<u32> {
+    DETECTOR.lock(|detector| detector.borrow_mut().start(now))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn start(_: FirmwareInstant) -> Option<u32> {
+    None
+}
+
+/// Returns `true` while a run is in progress.
+#[cfg(target_os = "none")]
+pub fn is_active() -> bool {
+    DETECTOR.lock(|detector| detector.borrow().is_active())
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn is_active() -> bool {
+    false
+}
+
+/// Credits Jetson console bytes to the rate under test.
+#[cfg(target_os = "none")]
+pub fn feed_console(now: FirmwareInstant, bytes: &[u8]) {
+    DETECTOR.lock(|detector| detector.borrow_mut().feed(now, bytes));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn feed_console(_: FirmwareInstant, _: &[u8]) {}
+
+/// Counts a UART receive error against the rate under test.
+#[cfg(target_os = "none")]
+pub fn record_error(now: FirmwareInstant) {
+    DETECTOR.lock(|detector| detector.borrow_mut().record_error(now));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn record_error(_: FirmwareInstant) {}
+
+/// Returns the next rate to apply or the final report, if either is due.
+#[cfg(target_os = "none")]
+pub fn poll(now: FirmwareInstant) -> Option<AutobaudStep> {
+    DETECTOR.lock(|detector| detector.borrow_mut().poll(now))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn poll(_: FirmwareInstant) -> Option<AutobaudStep> {
+    None
+}
+
+/// Queues a final report for the REPL task to print.
+#[cfg(target_os = "none")]
+pub fn queue_report(report: AutobaudReport) {
+    if AUTOBAUD_REPORTS.try_send(report).is_err() {
+        defmt::warn!("autobaud: dropping report (queue full)");
+    }
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn queue_report(_: AutobaudReport) {}
+
+/// Pops the oldest queued report, if any.
+#[cfg(target_os = "none")]
+pub fn take_report() -> Option<AutobaudReport> {
+    AUTOBAUD_REPORTS.try_receive().ok()
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn take_report() -> Option<AutobaudReport> {
+    None
+}
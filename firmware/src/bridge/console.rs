@@ -0,0 +1,317 @@
+#![cfg_attr(not(target_os = "none"), allow(dead_code))]
+
+//! Shared Jetson console state for the firmware target.
+//!
+//! This module owns the `ConsoleMatcher` and its pattern table, which
+//! `console pattern` edits at runtime, the `PanicCapture` that collects kernel
+//! panic lines, and the scrollback behind `console tail`/`console since` and
+//! the CDC1 replay. `feed_jetson_bytes` is the single entry point for the
+//! bridge task: every chunk it forwards to CDC1 is matched, captured, and
+//! appended here, then handed to the boot watchdog, the console script
+//! engine, and the autoboot interrupter. Marker hits and panic captures are
+//! queued for the strap task, which owns the telemetry ring, so the UART path
+//! never waits on it.
+
+use core::ops::Range;
+
+use controller_core::bridge::console::{
+    CONSOLE_PATTERN_CAPACITY, ConsoleMarker, MAX_CONSOLE_PATTERNS, PanicExcerpt, PatternError,
+    PatternId,
+};
+use controller_core::bridge::scrollback::{RunId, ScrollbackSelection};
+use heapless::{String, Vec};
+
+use crate::straps::FirmwareInstant;
+#[cfg(target_os = "none")]
+use crate::{autoboot, script, supervisor};
+
+#[cfg(target_os = "none")]
+use controller_core::bridge::console::{
+    ConsoleEvent, ConsoleMatcher, DEFAULT_CONSOLE_PATTERNS, PanicCapture,
+};
+#[cfg(target_os = "none")]
+use controller_core::bridge::scrollback::ConsoleScrollback;
+#[cfg(target_os = "none")]
+use core::cell::RefCell;
+#[cfg(target_os = "none")]
+use embassy_sync::blocking_mutex::{Mutex, raw::ThreadModeRawMutex};
+#[cfg(target_os = "none")]
+use embassy_sync::channel::Channel;
+
+/// Depth of the queue carrying marker hits to the strap task.
+pub const CONSOLE_MARKER_QUEUE_DEPTH: usize = 8;
+/// Depth of the queue carrying kernel panic lines to the strap task.
+pub const CONSOLE_PANIC_QUEUE_DEPTH: usize = 2;
+
+/// Marker match observed on the console stream.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ConsoleMarkerHit {
+    pub marker: ConsoleMarker,
+    pub pattern: PatternId,
+    pub line: u32,
+    pub timestamp: FirmwareInstant,
+}
+
+/// Registered pattern copied out for `console pattern list`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsolePatternEntry {
+    pub id: PatternId,
+    pub marker: ConsoleMarker,
+    pub text: Vec<u8, CONSOLE_PATTERN_CAPACITY>,
+}
+
+/// Scrollback counters reported after a `console` listing.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ScrollbackSummary {
+    pub run: RunId,
+    pub evicted: u32,
+}
+
+/// Kernel panic line captured on the console stream.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ConsolePanicHit {
+    pub excerpt: PanicExcerpt,
+    pub timestamp: FirmwareInstant,
+}
+
+#[cfg(target_os = "none")]
+static CONSOLE_MATCHER: Mutex<ThreadModeRawMutex, RefCell<(ConsoleMatcher, PanicCapture)>> =
+    Mutex::new(RefCell::new((ConsoleMatcher::new(), PanicCapture::new())));
+
+#[cfg(target_os = "none")]
+static SCROLLBACK: Mutex<ThreadModeRawMutex, RefCell<ConsoleScrollback<FirmwareInstant>>> =
+    Mutex::new(RefCell::new(ConsoleScrollback::new()));
+
+#[cfg(target_os = "none")]
+static CONSOLE_PANICS: Channel<ThreadModeRawMutex, ConsolePanicHit, CONSOLE_PANIC_QUEUE_DEPTH> =
+    Channel::new();
+
+#[cfg(target_os = "none")]
+static CONSOLE_MARKERS: Channel<ThreadModeRawMutex, ConsoleMarkerHit, CONSOLE_MARKER_QUEUE_DEPTH> =
+    Channel::new();
+
+/// Installs the built-in boot, login, panic, and recovery patterns.
+#[cfg(target_os = "none")]
+pub fn install_default_markers() {
+    CONSOLE_MATCHER.lock(|state| {
+        let (matcher, _) = &mut *state.borrow_mut();
+        for (marker, text) in DEFAULT_CONSOLE_PATTERNS {
+            if matcher.register(marker, text).is_err() {
+                defmt::warn!("bridge: console pattern table full");
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn install_default_markers() {}
+
+/// Scans a chunk of Jetson console output and queues any marker hits.
+///
+/// The boot watchdog sees the same traffic and markers so it can confirm a
+/// boot, notice console silence, or start its panic grace period.
+#[cfg(target_os = "none")]
+pub fn feed_jetson_bytes(bytes: &[u8], timestamp: FirmwareInstant) {
+    if bytes.is_empty() {
+        return;
+    }
+
+    supervisor::observe_console(timestamp);
+    script::feed_console(bytes);
+    autoboot::feed_console(bytes);
+    SCROLLBACK.lock(|scrollback| scrollback.borrow_mut().push(bytes, timestamp));
+    CONSOLE_MATCHER.lock(|state| {
+        let (matcher, capture) = &mut *state.borrow_mut();
+        matcher.feed(bytes, |event| {
+            if let Some(excerpt) = capture.observe(&event) {
+                let hit = ConsolePanicHit { excerpt, timestamp };
+                if CONSOLE_PANICS.try_send(hit).is_err() {
+                    defmt::warn!("bridge: dropping kernel panic line (queue full)");
+                }
+            }
+            if let ConsoleEvent::Marker {
+                pattern,
+                marker,
+                line,
+            } = event
+            {
+                supervisor::observe_marker(marker, timestamp);
+                let hit = ConsoleMarkerHit {
+                    marker,
+                    pattern,
+                    line,
+                    timestamp,
+                };
+                if CONSOLE_MARKERS.try_send(hit).is_err() {
+                    defmt::warn!("bridge: dropping console marker (queue full)");
+                }
+            }
+        });
+    });
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn feed_jetson_bytes(_: &[u8], _: FirmwareInstant) {}
+
+/// Registers an additional console pattern.
+#[cfg(target_os = "none")]
+pub fn register_pattern(marker: ConsoleMarker, text: &[u8]) -> Result<PatternId, PatternError> {
+    CONSOLE_MATCHER.lock(|state| state.borrow_mut().0.register(marker, text))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn register_pattern(_: ConsoleMarker, _: &[u8]) -> Result<PatternId, PatternError> {
+    Err(PatternError::Full)
+}
+
+/// Removes a previously registered console pattern.
+#[cfg(target_os = "none")]
+pub fn unregister_pattern(id: PatternId) -> Result<(), PatternError> {
+    if CONSOLE_MATCHER.lock(|state| state.borrow_mut().0.unregister(id)) {
+        Ok(())
+    } else {
+        Err(PatternError::NotRegistered(id))
+    }
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn unregister_pattern(id: PatternId) -> Result<(), PatternError> {
+    Err(PatternError::NotRegistered(id))
+}
+
+/// Copies out the registered console patterns in registration order.
+#[cfg(target_os = "none")]
+pub fn pattern_entries() -> Vec<ConsolePatternEntry, MAX_CONSOLE_PATTERNS> {
+    CONSOLE_MATCHER.lock(|state| {
+        state
+            .borrow()
+            .0
+            .patterns()
+            .filter_map(|(id, marker, text)| {
+                Some(ConsolePatternEntry {
+                    id,
+                    marker,
+                    text: Vec::from_slice(text).ok()?,
+                })
+            })
+            .collect()
+    })
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn pattern_entries() -> Vec<ConsolePatternEntry, MAX_CONSOLE_PATTERNS> {
+    Vec::new()
+}
+
+/// Pops the oldest queued marker hit, if any.
+#[cfg(target_os = "none")]
+pub fn take_marker_hit() -> Option<ConsoleMarkerHit> {
+    CONSOLE_MARKERS.try_receive().ok()
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn take_marker_hit() -> Option<ConsoleMarkerHit> {
+    None
+}
+
+/// Pops the oldest queued kernel panic line, if any.
+#[cfg(target_os = "none")]
+pub fn take_panic_capture() -> Option<ConsolePanicHit> {
+    CONSOLE_PANICS.try_receive().ok()
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn take_panic_capture() -> Option<ConsolePanicHit> {
+    None
+}
+
+/// Starts a new scrollback run; called when a strap sequence begins.
+#[cfg(target_os = "none")]
+pub fn begin_scrollback_run() -> RunId {
+    let run = SCROLLBACK.lock(|scrollback| scrollback.borrow_mut().begin_run());
+    defmt::info!("bridge: console scrollback run {=u32}", run);
+    run
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn begin_scrollback_run() -> RunId {
+    0
+}
+
+/// Sequence numbers of the retained lines covered by `selection`.
+#[cfg(target_os = "none")]
+pub fn scrollback_select(selection: ScrollbackSelection) -> Range<u32> {
+    SCROLLBACK.lock(|scrollback| scrollback.borrow().select(selection))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn scrollback_select(_: ScrollbackSelection) -> Range<u32> {
+    0..0
+}
+
+/// Formats retained line `seq` into `out`, with time measured from boot.
+///
+/// Returns `false` once the line has been evicted. Text that does not fit in
+/// `out` is cut short.
+#[cfg(target_os = "none")]
+pub fn format_scrollback_line<const N: usize>(seq: u32, out: &mut String<N>) -> bool {
+    use embassy_time::Instant;
+
+    let origin = FirmwareInstant::from(Instant::from_ticks(0));
+    SCROLLBACK.lock(|scrollback| {
+        let scrollback = scrollback.borrow();
+        let Some(line) = scrollback.line(seq) else {
+            return false;
+        };
+        out.clear();
+        let _ = line.write_to(out, origin);
+        true
+    })
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn format_scrollback_line<const N: usize>(_: u32, _: &mut String<N>) -> bool {
+    false
+}
+
+/// Current run and eviction count.
+#[cfg(target_os = "none")]
+pub fn scrollback_summary() -> ScrollbackSummary {
+    SCROLLBACK.lock(|scrollback| {
+        let scrollback = scrollback.borrow();
+        ScrollbackSummary {
+            run: scrollback.current_run(),
+            evicted: scrollback.evicted_lines(),
+        }
+    })
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn scrollback_summary() -> ScrollbackSummary {
+    ScrollbackSummary::default()
+}
+
+/// Raw byte offsets to replay when a host opens the bridge.
+#[cfg(target_os = "none")]
+pub fn replay_window() -> Range<u32> {
+    SCROLLBACK.lock(|scrollback| scrollback.borrow().raw_window())
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn replay_window() -> Range<u32> {
+    0..0
+}
+
+/// Copies replay bytes from `offset` up to `end` into `out`.
+///
+/// Returns `None` once `offset` has been overwritten by newer output.
+#[cfg(target_os = "none")]
+pub fn read_replay(offset: u32, end: u32, out: &mut [u8]) -> Option<usize> {
+    SCROLLBACK.lock(|scrollback| scrollback.borrow().read_raw(offset, end, out))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn read_replay(_: u32, _: u32, _: &mut [u8]) -> Option<usize> {
+    None
+}
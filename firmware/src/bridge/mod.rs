@@ -6,18 +6,27 @@
 
 #![allow(dead_code)]
 
+use core::time::Duration;
+
+pub mod autobaud;
+pub mod console;
+pub mod stats;
+
 use embassy_sync::channel::{Channel, Receiver, Sender};
+use embassy_sync::signal::Signal;
 use embassy_time::Instant;
 use heapless::Vec;
 
 use crate::straps::FirmwareInstant;
 use crate::telemetry::TelemetryRecorder;
 pub use controller_core::bridge::BridgeActivityKind;
+pub use controller_core::bridge::autobaud::BaudSetting;
+pub use controller_core::bridge::line_coding::{LineBreak, LineCoding};
 use controller_core::bridge::{
     BridgeActivityEvent as CoreBridgeActivityEvent,
     BridgeActivityMonitor as CoreBridgeActivityMonitor,
     BridgeActivityUpdate as CoreBridgeActivityUpdate,
-    BridgeDisconnectNotice as CoreBridgeDisconnectNotice,
+    BridgeDisconnectNotice as CoreBridgeDisconnectNotice, ReleaseCriterion,
 };
 
 #[cfg(not(target_os = "none"))]
@@ -39,6 +48,15 @@ pub const BRIDGE_QUEUE_DEPTH: usize = 4;
 /// Depth for activity events observed by the bridge monitor.
 pub const ACTIVITY_QUEUE_DEPTH: usize = 4;
 
+/// Console evidence required before `recovery now` releases REC.
+///
+/// Activity events carry frame sizes only, so the device counts bytes: a
+/// burst this size within the window is a booting Jetson, not power-up noise.
+pub const DEVICE_RELEASE_CRITERION: ReleaseCriterion = ReleaseCriterion::ByteCount {
+    bytes: 16,
+    window: Duration::from_millis(100),
+};
+
 /// Fixed-size frame exchanged between bridge tasks.
 pub type BridgeFrame = Vec<u8, BRIDGE_FRAME_SIZE>;
 
@@ -51,6 +69,23 @@ pub type BridgeSender<'a> = Sender<'a, BridgeMutex, BridgeFrame, BRIDGE_QUEUE_DE
 /// Receiver handle tied to a bridge channel.
 pub type BridgeReceiver<'a> = Receiver<'a, BridgeMutex, BridgeFrame, BRIDGE_QUEUE_DEPTH>;
 
+/// Jetson output bound for CDC1.
+pub struct ConsoleFrame {
+    pub bytes: BridgeFrame,
+    /// Scrollback offset just past these bytes, so a frame already covered
+    /// by a replay can be recognised and skipped.
+    pub replay_end: u32,
+}
+
+/// Channel carrying Jetson output to CDC1.
+pub type ConsoleChannel = Channel<BridgeMutex, ConsoleFrame, BRIDGE_QUEUE_DEPTH>;
+
+/// Sender handle for Jetson output.
+pub type ConsoleSender<'a> = Sender<'a, BridgeMutex, ConsoleFrame, BRIDGE_QUEUE_DEPTH>;
+
+/// Receiver handle for Jetson output.
+pub type ConsoleReceiver<'a> = Receiver<'a, BridgeMutex, ConsoleFrame, BRIDGE_QUEUE_DEPTH>;
+
 /// Bridge activity event shared with the orchestrator.
 pub type BridgeActivityEvent = CoreBridgeActivityEvent<FirmwareInstant>;
 
@@ -71,10 +106,25 @@ pub type BridgeActivitySender<'a> =
 pub type BridgeActivityReceiver<'a> =
     Receiver<'a, BridgeMutex, BridgeActivityEvent, ACTIVITY_QUEUE_DEPTH>;
 
+/// Break to drive on the Jetson UART, optionally followed by one byte.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BreakRequest {
+    pub line_break: LineBreak,
+    /// Written once the break ends (the sysrq key).
+    pub follow_up: Option<u8>,
+}
+
 /// Bundles the bounded USB↔UART channels so tasks can share a single instance.
+///
+/// The line coding, break, and baud signals carry the latest UART framing,
+/// break, and `console baud` requests; only the newest request of each
+/// matters, so they are not queued.
 pub struct BridgeQueue {
     pub usb_to_ttl: BridgeChannel,
-    pub ttl_to_usb: BridgeChannel,
+    pub ttl_to_usb: ConsoleChannel,
+    line_coding: Signal<BridgeMutex, LineCoding>,
+    line_break: Signal<BridgeMutex, BreakRequest>,
+    baud: Signal<BridgeMutex, BaudSetting>,
 }
 
 impl BridgeQueue {
@@ -83,6 +133,9 @@ impl BridgeQueue {
         Self {
             usb_to_ttl: Channel::new(),
             ttl_to_usb: Channel::new(),
+            line_coding: Signal::new(),
+            line_break: Signal::new(),
+            baud: Signal::new(),
         }
     }
 
@@ -97,14 +150,44 @@ impl BridgeQueue {
     }
 
     /// Returns a sender handle for the UART→USB path.
-    pub fn ttl_to_usb_sender(&self) -> BridgeSender<'_> {
+    pub fn ttl_to_usb_sender(&self) -> ConsoleSender<'_> {
         self.ttl_to_usb.sender()
     }
 
     /// Returns a receiver handle for the UART→USB path.
-    pub fn ttl_to_usb_receiver(&self) -> BridgeReceiver<'_> {
+    pub fn ttl_to_usb_receiver(&self) -> ConsoleReceiver<'_> {
         self.ttl_to_usb.receiver()
     }
+
+    /// Asks the bridge task to reframe the UART, replacing any pending request.
+    pub fn request_line_coding(&self, coding: LineCoding) {
+        self.line_coding.signal(coding);
+    }
+
+    /// Waits for the next UART framing request.
+    pub async fn line_coding_request(&self) -> LineCoding {
+        self.line_coding.wait().await
+    }
+
+    /// Asks the bridge task to start or end a UART break.
+    pub fn request_break(&self, request: BreakRequest) {
+        self.line_break.signal(request);
+    }
+
+    /// Waits for the next UART break request.
+    pub async fn break_request(&self) -> BreakRequest {
+        self.line_break.wait().await
+    }
+
+    /// Asks the bridge task to switch baud rate or search for one.
+    pub fn request_baud(&self, setting: BaudSetting) {
+        self.baud.signal(setting);
+    }
+
+    /// Waits for the next `console baud` request.
+    pub async fn baud_request(&self) -> BaudSetting {
+        self.baud.wait().await
+    }
 }
 
 /// Helper that owns the activity event channel and hands out producer/consumer halves.
@@ -142,10 +225,15 @@ impl<'a> BridgeActivityMonitor<'a> {
     pub fn new(subscriber: BridgeActivityReceiver<'a>) -> Self {
         Self {
             subscriber,
-            monitor: CoreBridgeActivityMonitor::new(),
+            monitor: CoreBridgeActivityMonitor::with_release_criterion(DEVICE_RELEASE_CRITERION),
         }
     }
 
+    /// Returns the evidence required before console activity releases REC.
+    pub fn release_criterion(&self) -> ReleaseCriterion {
+        self.monitor.release_criterion()
+    }
+
     /// Returns `true` when a recovery sequence is waiting on bridge activity.
     pub fn is_pending(&self) -> bool {
         self.monitor.is_pending()
@@ -169,7 +257,9 @@ impl<'a> BridgeActivityMonitor<'a> {
     /// Processes a single pending activity event, if available.
     pub fn poll(&mut self, telemetry: &mut TelemetryRecorder) -> Option<BridgeActivityUpdate> {
         let event = self.subscriber.try_receive().ok()?;
-        let update = self.monitor.process_event(event, telemetry.inner_mut())?;
+        let update = self.monitor.process_event(event, telemetry.inner_mut());
+        telemetry.publish();
+        let update = update?;
 
         log_bridge_activity(&update.event, update.release_recovery);
         Some(update)
@@ -392,4 +482,32 @@ mod tests {
         assert!(monitor.is_pending());
         assert_eq!(telemetry.len(), 0);
     }
+
+    #[test]
+    fn stray_bytes_do_not_release_pending_recovery() {
+        let bus = BridgeActivityBus::new();
+        let sender = bus.sender();
+        let subscriber = bus.receiver();
+        let mut monitor = BridgeActivityMonitor::new(subscriber);
+        let mut telemetry = TelemetryRecorder::new();
+
+        monitor.set_pending(true);
+
+        for (micros, bytes) in [(20_000, 2), (400_000, 3)] {
+            sender
+                .try_send(BridgeActivityEvent {
+                    kind: BridgeActivityKind::JetsonToUsb,
+                    timestamp: FirmwareInstant::from(Instant::from_micros(micros)),
+                    bytes,
+                })
+                .expect("send should succeed");
+            let update = monitor
+                .poll(&mut telemetry)
+                .expect("activity update missing");
+            assert!(!update.release_recovery);
+        }
+
+        assert!(monitor.is_pending());
+        assert_eq!(monitor.release_criterion(), DEVICE_RELEASE_CRITERION);
+    }
 }
@@ -0,0 +1,59 @@
+#![cfg_attr(not(target_os = "none"), allow(dead_code))]
+
+//! Shared bridge health counters for the firmware target.
+//!
+//! The bridge and USB tasks count frames and faults here as they forward
+//! traffic, and the REPL task reads or clears the totals for `status` and
+//! `bridge stats`.
+
+use controller_core::bridge::BridgeActivityKind;
+use controller_core::bridge::stats::{BridgeFault, BridgeStatsSnapshot};
+
+#[cfg(target_os = "none")]
+use controller_core::bridge::stats::BridgeStatistics;
+#[cfg(target_os = "none")]
+use core::cell::RefCell;
+#[cfg(target_os = "none")]
+use embassy_sync::blocking_mutex::{Mutex, raw::ThreadModeRawMutex};
+
+#[cfg(target_os = "none")]
+static BRIDGE_STATS: Mutex<ThreadModeRawMutex, RefCell<BridgeStatistics>> =
+    Mutex::new(RefCell::new(BridgeStatistics::new()));
+
+/// Counts a frame of `bytes` forwarded in the given direction.
+#[cfg(target_os = "none")]
+pub fn record_frame(kind: BridgeActivityKind, bytes: usize) {
+    BRIDGE_STATS.lock(|stats| stats.borrow_mut().record_frame(kind, bytes));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn record_frame(_: BridgeActivityKind, _: usize) {}
+
+/// Counts a transport error, drop, or queue-full wait.
+#[cfg(target_os = "none")]
+pub fn record_fault(fault: BridgeFault) {
+    BRIDGE_STATS.lock(|stats| stats.borrow_mut().record_fault(fault));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn record_fault(_: BridgeFault) {}
+
+/// Returns a copy of the counters.
+#[cfg(target_os = "none")]
+pub fn snapshot() -> BridgeStatsSnapshot {
+    BRIDGE_STATS.lock(|stats| stats.borrow().snapshot())
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn snapshot() -> BridgeStatsSnapshot {
+    BridgeStatsSnapshot::default()
+}
+
+/// Zeroes the counters.
+#[cfg(target_os = "none")]
+pub fn reset() {
+    BRIDGE_STATS.lock(|stats| stats.borrow_mut().reset());
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn reset() {}
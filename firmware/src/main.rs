@@ -1,11 +1,15 @@
 #![cfg_attr(target_os = "none", no_std)]
 #![cfg_attr(target_os = "none", no_main)]
 
+mod autoboot;
 mod bridge;
 mod hw;
+mod power;
 mod repl;
+mod script;
 mod status;
 mod straps;
+mod supervisor;
 mod telemetry;
 mod usb;
 
@@ -0,0 +1,146 @@
+#![cfg_attr(not(target_os = "none"), allow(dead_code))]
+
+//! Shared power-rail statistics for the firmware target.
+//!
+//! The strap task feeds every VREFINT reading into a `RailStatistics`
+//! accumulator guarded by a thread-mode mutex, and the REPL task reads or
+//! clears it for `power stats`. Brown-out episodes land in a bounded
+//! `BrownOutHistory` that backs `power history`.
+
+use controller_core::power::{BrownOutEpisode, BrownOutReport, RailStatsSnapshot};
+#[cfg(target_os = "none")]
+use controller_core::power::{BrownOutHistory, RailStatistics};
+use controller_core::sequences::StrapSequenceKind;
+
+use crate::straps::FirmwareInstant;
+
+#[cfg(target_os = "none")]
+use core::cell::RefCell;
+#[cfg(target_os = "none")]
+use embassy_sync::blocking_mutex::{Mutex, raw::ThreadModeRawMutex};
+
+#[cfg(target_os = "none")]
+static RAIL_STATS: Mutex<ThreadModeRawMutex, RefCell<RailStatistics>> =
+    Mutex::new(RefCell::new(RailStatistics::new()));
+
+#[cfg(target_os = "none")]
+static BROWN_OUT_HISTORY: Mutex<ThreadModeRawMutex, RefCell<BrownOutHistory<FirmwareInstant>>> =
+    Mutex::new(RefCell::new(BrownOutHistory::new()));
+
+/// Resizes the peak-to-peak window to span `window` at the monitor's sample interval.
+#[cfg(target_os = "none")]
+pub fn configure_rail_window(window: core::time::Duration, sample_interval: core::time::Duration) {
+    RAIL_STATS.lock(|stats| {
+        let mut stats = stats.borrow_mut();
+        let config = stats.config().with_window(window, sample_interval);
+        stats.set_config(config);
+    });
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn configure_rail_window(_: core::time::Duration, _: core::time::Duration) {}
+
+/// Folds a monitor reading into the rail statistics (`None` marks an invalid sample).
+#[cfg(target_os = "none")]
+pub fn record_rail_sample(millivolts: Option<u16>) {
+    RAIL_STATS.lock(|stats| stats.borrow_mut().record(millivolts));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn record_rail_sample(_: Option<u16>) {}
+
+/// Opens a rail capture for the strap sequence that just started.
+#[cfg(target_os = "none")]
+pub fn begin_sequence_capture(kind: StrapSequenceKind) {
+    RAIL_STATS.lock(|stats| stats.borrow_mut().begin_capture(kind));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn begin_sequence_capture(_: StrapSequenceKind) {}
+
+/// Closes the rail capture for the sequence that just finished.
+#[cfg(target_os = "none")]
+pub fn finish_sequence_capture() {
+    RAIL_STATS.lock(|stats| {
+        let _ = stats.borrow_mut().finish_capture();
+    });
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn finish_sequence_capture() {}
+
+/// Returns a copy of the accumulated rail statistics.
+#[cfg(target_os = "none")]
+pub fn rail_stats() -> Option<RailStatsSnapshot> {
+    Some(RAIL_STATS.lock(|stats| stats.borrow().snapshot()))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn rail_stats() -> Option<RailStatsSnapshot> {
+    None
+}
+
+/// Clears the accumulated rail statistics.
+#[cfg(target_os = "none")]
+pub fn reset_rail_stats() {
+    RAIL_STATS.lock(|stats| stats.borrow_mut().reset());
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn reset_rail_stats() {}
+
+/// Logs a brown-out reading, opening an episode tagged with the interrupted step if needed.
+#[cfg(target_os = "none")]
+pub fn record_brown_out(
+    timestamp: FirmwareInstant,
+    millivolts: Option<u16>,
+    sequence: Option<StrapSequenceKind>,
+    step: Option<u8>,
+) -> bool {
+    BROWN_OUT_HISTORY.lock(|history| {
+        history
+            .borrow_mut()
+            .record_brown_out(timestamp, millivolts, sequence, step)
+    })
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn record_brown_out(
+    _: FirmwareInstant,
+    _: Option<u16>,
+    _: Option<StrapSequenceKind>,
+    _: Option<u8>,
+) -> bool {
+    false
+}
+
+/// Notes whether the orchestrator retried the sequence the open episode interrupted.
+#[cfg(target_os = "none")]
+pub fn mark_brown_out_retry(retried: bool) {
+    BROWN_OUT_HISTORY.lock(|history| history.borrow_mut().mark_retry(retried));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn mark_brown_out_retry(_: bool) {}
+
+/// Closes the open brown-out episode once the rail reads stable again.
+#[cfg(target_os = "none")]
+pub fn close_brown_out(recovered_at: FirmwareInstant) -> Option<BrownOutEpisode<FirmwareInstant>> {
+    BROWN_OUT_HISTORY.lock(|history| history.borrow_mut().close(recovered_at))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn close_brown_out(_: FirmwareInstant) -> Option<BrownOutEpisode<FirmwareInstant>> {
+    None
+}
+
+/// Returns the brown-out log with ages measured from `now`.
+#[cfg(target_os = "none")]
+pub fn brown_out_report(now: FirmwareInstant) -> Option<BrownOutReport> {
+    Some(BROWN_OUT_HISTORY.lock(|history| history.borrow().report(now)))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn brown_out_report(_: FirmwareInstant) -> Option<BrownOutReport> {
+    None
+}
@@ -14,37 +14,116 @@ use embassy_sync::blocking_mutex::raw::ThreadModeRawMutex;
 use embassy_sync::channel::Channel;
 use heapless::Vec;
 
+#[cfg(target_os = "none")]
+use controller_core::bridge::attach::{ATTACH_ESCAPE, AttachEscape, AttachStep};
+#[cfg(target_os = "none")]
+use controller_core::bridge::autoboot::{AutobootEvent, AutobootReport};
+#[cfg(target_os = "none")]
+use controller_core::bridge::inject::{CONSOLE_NEWLINE, ConsoleText, Escaped};
+#[cfg(target_os = "none")]
+use controller_core::bridge::line_coding::{LineBreak, SYSRQ_BREAK, SysRqKey};
+#[cfg(target_os = "none")]
+use controller_core::bridge::scrollback::{SCROLLBACK_LINE_LIMIT, ScrollbackSelection};
+#[cfg(target_os = "none")]
+use controller_core::bridge::stats::{BridgeStatsFormatter, BridgeStatsSnapshot};
 #[cfg(target_os = "none")]
 use controller_core::orchestrator::{
-    CommandSource, QueueErrorKind, ScheduleError, ScheduleErrorInfo, SequenceScheduler,
-    schedule_error_info,
+    CommandSource, DEFAULT_BRIDGE_ACTIVITY_TIMEOUT, QueueErrorKind, RequestTag, ScheduleError,
+    ScheduleErrorInfo, SequenceScheduler, schedule_error_info,
 };
 #[cfg(target_os = "none")]
+use controller_core::power::{
+    BrownOutFormatter, BrownOutReport, RailStatsFormatter, RailStatsSnapshot,
+};
+#[cfg(target_os = "none")]
+use controller_core::repl::chain::CommandChain;
+#[cfg(target_os = "none")]
 use controller_core::repl::commands::{
-    CommandError as ExecutorError, CommandExecutor, CommandOutcome, FaultAck, RebootAck,
-    RecoveryAck,
+    BridgeStatsReport, CommandError as ExecutorError, CommandExecutor, CommandOutcome,
+    ConsoleAction, FaultAck, PowerReport, RebootAck, RecoveryAck,
 };
 #[cfg(target_os = "none")]
 use controller_core::repl::completion::{CompletionEngine, CompletionResult};
+use controller_core::repl::editor::{EDITOR_HISTORY_DEPTH, EDITOR_LINE_CAPACITY};
+#[cfg(target_os = "none")]
+use controller_core::repl::editor::{EditEvent, LineEditor};
+#[cfg(target_os = "none")]
+use controller_core::repl::grammar::{
+    self, EventsCommand, MacroCommand, PatternCommand, RecoveryCommand, ScriptCommand, WaitCommand,
+};
+#[cfg(target_os = "none")]
+use controller_core::repl::help::{HELP_LINE_CAPACITY, HelpPage};
+#[cfg(target_os = "none")]
+use controller_core::repl::json::{
+    ErrorReply, Event, JSON_LINE_CAPACITY, JsonFormatter, Reply, ResponseMode,
+};
+#[cfg(target_os = "none")]
+use controller_core::repl::macros::{MacroError, MacroLibrary};
 #[cfg(target_os = "none")]
 use controller_core::repl::status::{StatusFormatter, StatusProvider, StatusSnapshot};
 #[cfg(target_os = "none")]
-use core::fmt::Write as _;
+use controller_core::repl::tag::{TAG_MARKER_CAPACITY, TaggedLine};
+#[cfg(target_os = "none")]
+use controller_core::repl::wait::{PendingWait, WaitCondition, WaitReport, WaitTimeout};
+#[cfg(target_os = "none")]
+use controller_core::script::{LoadProgress, ScriptEvent, ScriptReport};
+#[cfg(target_os = "none")]
+use controller_core::supervisor::{
+    WatchdogFormatter, WatchdogStatus, WatchdogTrip, WatchdogUpdate, schedule_fault_recovery,
+};
+#[cfg(target_os = "none")]
+use controller_core::telemetry::{EventFilter, TelemetryInstant};
+#[cfg(target_os = "none")]
+use core::fmt::{self, Write as _};
+#[cfg(target_os = "none")]
+use embassy_futures::select::{Either, select};
 #[cfg(target_os = "none")]
 use embassy_sync::channel::{Receiver, Sender};
 #[cfg(target_os = "none")]
-use embassy_time::Instant;
+use embassy_time::{Instant, Timer};
 #[cfg(target_os = "none")]
 use heapless::String;
 
+#[cfg(target_os = "none")]
+use crate::autoboot;
+#[cfg(target_os = "none")]
+use crate::bridge::{
+    BaudSetting, BreakRequest, BridgeFrame, BridgeQueue, autobaud, console, stats as bridge_stats,
+};
+#[cfg(target_os = "none")]
+use crate::power;
+#[cfg(target_os = "none")]
+use crate::script;
 #[cfg(target_os = "none")]
 use crate::status;
 #[cfg(target_os = "none")]
-use crate::straps::{CommandProducer, FirmwareInstant};
+use crate::straps::{self, CommandProducer, FirmwareInstant};
+#[cfg(target_os = "none")]
+use crate::supervisor;
+#[cfg(target_os = "none")]
+use crate::telemetry;
 
 /// Capacity for USB CDC frames exchanged with the REPL task.
 pub const FRAME_CAPACITY: usize = 64;
 
+/// Line buffer for counter lines, which outgrow a frame once the counts get large.
+#[cfg(target_os = "none")]
+const STATS_LINE_CAPACITY: usize = 2 * FRAME_CAPACITY;
+
+/// Line buffer for a `macro list` entry, which repeats the whole body.
+#[cfg(target_os = "none")]
+const MACRO_LINE_CAPACITY: usize = 3 * FRAME_CAPACITY;
+
+/// Line buffer for a `console pattern list` entry, whose escaped text can take
+/// four characters per byte.
+#[cfg(target_os = "none")]
+const PATTERN_LINE_CAPACITY: usize = 3 * FRAME_CAPACITY;
+
+/// Line buffer for execution errors; syntax errors can echo an unknown word and
+/// the keywords it may have meant.
+#[cfg(target_os = "none")]
+const ERROR_LINE_CAPACITY: usize = 2 * FRAME_CAPACITY;
+
 /// Queue depth for REPL RX/TX channels.
 pub const FRAME_QUEUE_DEPTH: usize = 4;
 
@@ -65,89 +144,27 @@ pub static REPL_TX_QUEUE: Channel<ReplMutex, ReplFrame, FRAME_QUEUE_DEPTH> = Cha
 
 /// Maximum number of bytes accepted on a single REPL line (excluding the
 /// terminator).
-pub const MAX_LINE_LEN: usize = 96;
+pub const MAX_LINE_LEN: usize = EDITOR_LINE_CAPACITY;
 
-/// Errors that may occur while ingesting input into the line buffer.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub enum LineError {
-    /// The REPL line reached the configured capacity.
-    Overflow,
-}
-
-/// Bounded buffer that accumulates bytes until a newline completes the line.
-#[derive(Default)]
-pub struct LineBuffer {
-    buf: Vec<u8, MAX_LINE_LEN>,
-}
-
-impl LineBuffer {
-    /// Creates an empty line buffer.
-    pub const fn new() -> Self {
-        Self { buf: Vec::new() }
-    }
-
-    /// Clears any accumulated bytes.
-    pub fn clear(&mut self) {
-        self.buf.clear();
-    }
+/// Submitted lines kept for up/down recall.
+pub const HISTORY_DEPTH: usize = EDITOR_HISTORY_DEPTH;
 
-    /// Returns `true` when no input has been buffered.
-    pub fn is_empty(&self) -> bool {
-        self.buf.is_empty()
-    }
-
-    /// Number of bytes currently stored in the buffer.
-    pub fn len(&self) -> usize {
-        self.buf.len()
-    }
-
-    /// Appends a byte to the buffer.
-    pub fn push(&mut self, byte: u8) -> Result<(), LineError> {
-        self.buf.push(byte).map_err(|_| LineError::Overflow)
-    }
-
-    /// Removes the most recently buffered byte, if any.
-    pub fn pop(&mut self) {
-        self.buf.pop();
-    }
-
-    /// Yields a copy of the buffered line and clears the buffer.
-    ///
-    /// Returns `None` when the buffer is empty.
-    pub fn take(&mut self) -> Option<Vec<u8, MAX_LINE_LEN>> {
-        if self.buf.is_empty() {
-            return None;
-        }
-
-        let mut line = Vec::<u8, MAX_LINE_LEN>::new();
-        if line.extend_from_slice(self.buf.as_slice()).is_err() {
-            // Capacities match, so this branch should never trigger. Return
-            // `None` instead of panicking to keep the REPL resilient.
-            return None;
-        }
-
-        self.buf.clear();
-        Some(line)
-    }
+/// Room for a redrawn input row: the line plus the VT100 cursor controls.
+#[cfg(target_os = "none")]
+const REDRAW_CAPACITY: usize = MAX_LINE_LEN + 16;
 
-    /// Provides a read-only view of the underlying byte slice.
-    pub fn as_slice(&self) -> &[u8] {
-        self.buf.as_slice()
-    }
+/// Opens a blank row above a half-typed line for an event: index down
+/// (scrolling when the line is on the last row), save the cursor there, then
+/// step back up and insert a line, pushing the input row onto the saved one.
+#[cfg(target_os = "none")]
+const OPEN_EVENT_ROW: &[u8] = b"\x1bD\x1b7\x1b[A\x1b[L\r";
 
-    /// Replaces the tail of the buffer starting at `start` with the provided
-    /// `replacement`.
-    pub fn replace_from(&mut self, start: usize, replacement: &str) -> Result<(), LineError> {
-        if start > self.buf.len() {
-            return Err(LineError::Overflow);
-        }
+/// Returns to the cursor saved by [`OPEN_EVENT_ROW`], back in the input row.
+#[cfg(target_os = "none")]
+const RESTORE_INPUT_ROW: &[u8] = b"\x1b8";
 
-        self.buf.truncate(start);
-        self.buf
-            .extend_from_slice(replacement.as_bytes())
-            .map_err(|_| LineError::Overflow)
-    }
-}
+#[cfg(target_os = "none")]
+type ReplEditor = LineEditor<MAX_LINE_LEN, HISTORY_DEPTH>;
 
 #[cfg(target_os = "none")]
 type ReplReceiver<'a> = Receiver<'a, ReplMutex, ReplFrame, FRAME_QUEUE_DEPTH>;
@@ -164,6 +181,38 @@ impl StatusProvider<FirmwareInstant> for FirmwareStatusProvider {
     fn snapshot(&mut self, now: FirmwareInstant) -> Option<StatusSnapshot> {
         Some(status::snapshot(now))
     }
+
+    fn rail_stats(&mut self) -> Option<RailStatsSnapshot> {
+        power::rail_stats()
+    }
+
+    fn reset_rail_stats(&mut self) {
+        power::reset_rail_stats();
+    }
+
+    fn bridge_stats(&mut self) -> Option<BridgeStatsSnapshot> {
+        Some(bridge_stats::snapshot())
+    }
+
+    fn reset_bridge_stats(&mut self) {
+        bridge_stats::reset();
+    }
+
+    fn brown_out_history(&mut self, now: FirmwareInstant) -> Option<BrownOutReport> {
+        power::brown_out_report(now)
+    }
+
+    fn watchdog_status(&mut self, now: FirmwareInstant) -> Option<WatchdogStatus> {
+        supervisor::status(now)
+    }
+
+    fn update_watchdog(
+        &mut self,
+        update: WatchdogUpdate,
+        now: FirmwareInstant,
+    ) -> Option<WatchdogStatus> {
+        supervisor::update(update, now)
+    }
 }
 
 #[cfg(target_os = "none")]
@@ -177,70 +226,361 @@ pub struct ReplSession<'a> {
     rx: ReplReceiver<'a>,
     tx: ReplSender<'a>,
     executor: FirmwareExecutor<'a>,
-    buffer: LineBuffer,
+    bridge: &'a BridgeQueue,
+    editor: ReplEditor,
     completion: CompletionEngine,
-    drop_input: bool,
+    /// Escape tracker for `console attach`; only consulted while attached.
+    attach: AttachEscape,
+    /// Format selected with `mode`.
+    mode: ResponseMode,
+    /// Tag of the request being answered, spliced into every line sent.
+    tag: Option<RequestTag>,
+    /// Tags of requests whose result arrives later as an event.
+    follow_ups: FollowUpTags,
+    /// Telemetry categories selected with `events`.
+    events: EventFilter,
+    /// `wait` holding the session; input is not read until it ends.
+    wait: Option<PendingWait<FirmwareInstant>>,
+    /// Bytes typed after a `wait` in the same frame, replayed once it ends.
+    held: ReplFrame,
+    /// Commands of the current `;` chain still to run.
+    chain: CommandChain,
+    /// Macros stored with `macro define`.
+    macros: MacroLibrary,
+    /// Set when the command being run reports an error.
+    failed: bool,
+}
+
+/// Request tags held until the follow-up report they belong to is sent.
+#[cfg(target_os = "none")]
+#[derive(Default)]
+struct FollowUpTags {
+    bootloader: Option<RequestTag>,
+    autobaud: Option<RequestTag>,
+    script: Option<RequestTag>,
+    wait: Option<RequestTag>,
 }
 
 #[cfg(target_os = "none")]
 impl<'a> ReplSession<'a> {
     /// Creates a new REPL session bound to the shared command queue.
-    pub fn new(executor: FirmwareExecutor<'a>) -> Self {
+    ///
+    /// `bridge` carries console requests, such as sysrq breaks, to the bridge task.
+    pub fn new(executor: FirmwareExecutor<'a>, bridge: &'a BridgeQueue) -> Self {
         Self {
             rx: REPL_RX_QUEUE.receiver(),
             tx: REPL_TX_QUEUE.sender(),
             executor,
-            buffer: LineBuffer::new(),
+            bridge,
+            editor: ReplEditor::new(),
             completion: CompletionEngine::new(),
-            drop_input: false,
+            attach: AttachEscape::new(),
+            mode: ResponseMode::Text,
+            tag: None,
+            follow_ups: FollowUpTags::default(),
+            events: EventFilter::NONE,
+            wait: None,
+            held: ReplFrame::new(),
+            chain: CommandChain::new(),
+            macros: MacroLibrary::new(),
+            failed: false,
         }
     }
 
-    /// Drives the session indefinitely.
+    /// Drives the session indefinitely, polling the boot watchdog, the
+    /// autoboot interrupter, any running script, baud rate searches,
+    /// finished strap sequences, and subscribed telemetry between frames.
+    ///
+    /// While a `wait` is pending no frames are read, so typed input stays
+    /// queued until the wait ends and the rest of its chain has run.
     pub async fn run(&mut self) -> ! {
+        let mut polled_at = Instant::now();
         loop {
-            let frame = self.rx.receive().await;
-            self.consume_frame(&frame).await;
+            if self.wait.is_some() {
+                Timer::after(telemetry::EVENT_POLL_INTERVAL).await;
+                self.poll().await;
+                polled_at = Instant::now();
+                if self.wait.is_none() {
+                    self.run_chain().await;
+                }
+                if self.wait.is_none() {
+                    let held = core::mem::take(&mut self.held);
+                    self.consume_frame(&held).await;
+                }
+                continue;
+            }
+
+            let interval = if autoboot::is_active() {
+                autoboot::AUTOBOOT_POLL_INTERVAL
+            } else if autobaud::is_active() {
+                autobaud::AUTOBAUD_POLL_INTERVAL
+            } else if script::is_running() {
+                script::SCRIPT_POLL_INTERVAL
+            } else if !self.events.is_empty() {
+                telemetry::EVENT_POLL_INTERVAL
+            } else {
+                supervisor::WATCHDOG_POLL_INTERVAL
+            };
+            // Due relative to the last poll rather than restarted per frame,
+            // so steady CDC0 traffic cannot hold off the polled deadlines.
+            let due = polled_at + interval;
+            if let Either::First(frame) = select(self.rx.receive(), Timer::at(due)).await {
+                self.consume_frame(&frame).await;
+            }
+            if Instant::now() >= due {
+                self.poll().await;
+                polled_at = Instant::now();
+            }
+        }
+    }
+
+    async fn poll(&mut self) {
+        self.poll_watchdog();
+        self.poll_autoboot().await;
+        self.poll_autobaud().await;
+        self.poll_script().await;
+        self.poll_completions().await;
+        self.poll_events().await;
+        self.poll_wait().await;
+    }
+
+    /// Reports the end of strap sequences queued by tagged requests and
+    /// hands every completion to a pending `wait complete`.
+    async fn poll_completions(&mut self) {
+        while let Some(done) = straps::take_completion() {
+            if let Some(tag) = done.tag {
+                let mut message: String<STATS_LINE_CAPACITY> = String::new();
+                let _ = write!(message, "DONE {done}");
+                self.send_follow_up(Some(tag), message.as_str(), Event::Done(&done))
+                    .await;
+            }
+            let now = FirmwareInstant::from(Instant::now());
+            if let Some(report) = self.wait.and_then(|wait| wait.on_completion(&done, now)) {
+                self.finish_wait(Ok(report)).await;
+            }
+        }
+    }
+
+    /// Prints telemetry selected with `events` and hands every event to a
+    /// pending `wait event`. Over a half-typed line the event goes in a row
+    /// opened above it, so the input and its cursor are left untouched.
+    async fn poll_events(&mut self) {
+        while let Some(notice) = telemetry::take_event() {
+            if self.events.matches(notice.event) {
+                let mut message: String<STATS_LINE_CAPACITY> = String::new();
+                let _ = write!(message, "{notice}");
+                if !self.mode.is_json() && !self.editor.is_empty() {
+                    self.send_bytes(OPEN_EVENT_ROW).await;
+                    self.send_text(message.as_str()).await;
+                    self.send_bytes(RESTORE_INPUT_ROW).await;
+                } else {
+                    self.send_event(message.as_str(), Event::Telemetry(&notice))
+                        .await;
+                }
+            }
+            let now = FirmwareInstant::from(Instant::now());
+            if let Some(report) = self.wait.and_then(|wait| wait.on_event(&notice, now)) {
+                self.finish_wait(Ok(report)).await;
+            }
+        }
+    }
+
+    /// Ends a `wait idle` once the orchestrator has nothing left to run, or
+    /// any wait whose timeout has passed.
+    async fn poll_wait(&mut self) {
+        let Some(wait) = self.wait else {
+            return;
+        };
+        let now = FirmwareInstant::from(Instant::now());
+        if let Some(report) = wait.on_idle(self.sequencer_idle(), now) {
+            self.finish_wait(Ok(report)).await;
+        } else if let Some(timeout) = wait.expired(now) {
+            self.finish_wait(Err(timeout)).await;
+        }
+    }
+
+    /// Sends due interrupt keys and reports the end of a `reboot to-bootloader` run.
+    async fn poll_autoboot(&mut self) {
+        let now = FirmwareInstant::from(Instant::now());
+        while let Some(event) = autoboot::poll(now) {
+            match event {
+                AutobootEvent::SendKey(key) => {
+                    self.queue_console_bytes(&[key], false).await;
+                }
+                AutobootEvent::Finished(report) => self.send_autoboot_report(report, now).await,
+            }
+        }
+    }
+
+    /// Prints the result of a finished `console baud auto` search.
+    async fn poll_autobaud(&mut self) {
+        while let Some(report) = autobaud::take_report() {
+            let mut message: String<STATS_LINE_CAPACITY> = String::new();
+            let status = if report.result.is_success() {
+                "OK"
+            } else {
+                "ERR"
+            };
+            let _ = write!(message, "{status} {report}");
+            let tag = self.follow_ups.autobaud.take();
+            self.send_follow_up(tag, message.as_str(), Event::Autobaud(&report))
+                .await;
+        }
+    }
+
+    async fn send_autoboot_report(&mut self, report: AutobootReport, now: FirmwareInstant) {
+        if report.result.is_success() {
+            // Holding the Jetson at the prompt is deliberate; keep the boot
+            // watchdog from treating it as a hung boot.
+            supervisor::park();
+        }
+        let mut message: String<FRAME_CAPACITY> = String::new();
+        let status = if report.result.is_success() {
+            "OK"
+        } else {
+            "ERR"
+        };
+        let _ = write!(message, "{status} {report}");
+        let tag = self.follow_ups.bootloader.take();
+        self.send_follow_up(tag, message.as_str(), Event::Bootloader(&report))
+            .await;
+        autoboot::queue_report(report, now);
+    }
+
+    fn poll_watchdog(&mut self) {
+        let now = FirmwareInstant::from(Instant::now());
+        match supervisor::poll(now) {
+            Some(WatchdogTrip::Recover { reason, attempt }) => {
+                if schedule_fault_recovery(self.executor.scheduler_mut(), reason, now).is_ok() {
+                    defmt::warn!(
+                        "watchdog: {} - fault recovery queued (attempt {})",
+                        defmt::Display2Format(&reason),
+                        attempt
+                    );
+                } else {
+                    // Most likely the fault recovery cooldown; the attempt
+                    // does not count, and the check runs again after
+                    // another boot deadline.
+                    defmt::warn!("watchdog: fault recovery rejected; re-arming");
+                    supervisor::cancel_recovery(now);
+                }
+            }
+            Some(WatchdogTrip::Exhausted { reason }) => {
+                defmt::error!(
+                    "watchdog: {} - recovery limit reached, giving up",
+                    defmt::Display2Format(&reason)
+                );
+            }
+            None => {}
         }
     }
 
     async fn consume_frame(&mut self, frame: &ReplFrame) {
-        for &byte in frame {
-            match byte {
-                b'\r' | b'\n' => {
-                    if let Some(line) = self.buffer.take() {
-                        let discard = self.drop_input;
-                        self.drop_input = false;
-                        if discard {
-                            continue;
-                        }
-                        self.handle_line(line).await;
-                    }
+        let mut rest = frame.as_slice();
+        while !rest.is_empty() {
+            if self.wait.is_some() {
+                let _ = self.held.extend_from_slice(rest);
+                return;
+            }
+            rest = if status::console_attached() {
+                self.forward_attached(rest).await
+            } else {
+                self.edit_line(rest).await
+            };
+        }
+    }
+
+    /// Feeds typed bytes to the line editor. Returns the bytes left over
+    /// when a line switches the port into `console attach` or starts a `wait`.
+    ///
+    /// The input row is redrawn once per frame rather than once per byte so
+    /// pasted text does not repaint the line for every character.
+    async fn edit_line<'f>(&mut self, bytes: &'f [u8]) -> &'f [u8] {
+        let mut redraw = false;
+        for (index, &byte) in bytes.iter().enumerate() {
+            let event = self.editor.feed(byte);
+            if redraw && !matches!(event, EditEvent::Pending | EditEvent::Redraw) {
+                self.redraw_line().await;
+                redraw = false;
+            }
+            match event {
+                EditEvent::Pending => {}
+                EditEvent::Redraw => redraw = true,
+                EditEvent::Bell => self.emit_bell().await,
+                EditEvent::Complete => self.handle_completion().await,
+                EditEvent::Cancelled => self.send_bytes(b"^C\r\n").await,
+                EditEvent::Overflow => {
+                    self.send_bytes(b"\r\n").await;
+                    self.notify_error("ERR line-too-long", ErrorReply::LineTooLong)
+                        .await;
                 }
-                b'\x08' | b'\x7f' => {
-                    if !self.buffer.is_empty() {
-                        self.buffer.pop();
+                EditEvent::Submit => {
+                    self.send_bytes(b"\r\n").await;
+                    let line = self.editor.take_line();
+                    self.handle_line(line).await;
+                    if status::console_attached() || self.wait.is_some() {
+                        // The LF of a CRLF ending belongs to the command.
+                        let rest = &bytes[index + 1..];
+                        return rest.strip_prefix(b"\n").unwrap_or(rest);
                     }
                 }
-                b'\t' => {
-                    if !self.drop_input {
-                        self.handle_completion().await;
-                    }
+            }
+        }
+        if redraw {
+            self.redraw_line().await;
+        }
+        &[]
+    }
+
+    /// Repaints the input row after the line or cursor changed.
+    async fn redraw_line(&mut self) {
+        let mut row: String<REDRAW_CAPACITY> = String::new();
+        if self.editor.write_redraw(&mut row).is_ok() {
+            self.send_bytes(row.as_bytes()).await;
+        }
+    }
+
+    /// Passes typed bytes straight to the Jetson UART until the detach
+    /// escape. Returns the bytes typed after the escape.
+    async fn forward_attached<'f>(&mut self, bytes: &'f [u8]) -> &'f [u8] {
+        let sender = self.bridge.usb_to_ttl_sender();
+        let mut frame = BridgeFrame::new();
+        let mut rest: &[u8] = &[];
+        let mut detached = false;
+        for (index, &byte) in bytes.iter().enumerate() {
+            let (escape, byte) = match self.attach.push(byte) {
+                AttachStep::Hold => continue,
+                AttachStep::Send { escape, byte } => (escape, byte),
+                AttachStep::Detach => {
+                    rest = &bytes[index + 1..];
+                    detached = true;
+                    break;
                 }
-                byte if byte.is_ascii() && !self.drop_input => {
-                    if let Err(LineError::Overflow) = self.buffer.push(byte) {
-                        self.drop_input = true;
-                        self.notify_error("ERR line-too-long").await;
-                    }
+            };
+            for byte in escape.then_some(ATTACH_ESCAPE).into_iter().chain([byte]) {
+                if frame.is_full() {
+                    sender.send(core::mem::take(&mut frame)).await;
                 }
-                _ => {}
+                let _ = frame.push(byte);
             }
         }
+        if !frame.is_empty() {
+            sender.send(frame).await;
+        }
+
+        if detached {
+            status::set_console_attached(false);
+            self.send_bytes(b"\r\n").await;
+            self.send_event("OK console detached", Event::ConsoleDetached)
+                .await;
+        }
+        rest
     }
 
     async fn handle_line(&mut self, line: Vec<u8, MAX_LINE_LEN>) {
         let Ok(text) = core::str::from_utf8(line.as_slice()) else {
-            self.notify_error("ERR invalid-utf8").await;
+            self.notify_error("ERR invalid-utf8", ErrorReply::InvalidUtf8)
+                .await;
             return;
         };
 
@@ -248,96 +588,212 @@ impl<'a> ReplSession<'a> {
             return;
         }
 
-        let now = Instant::now();
-        let result = {
-            let trimmed = text.trim();
-            self.execute_command(trimmed, now)
-        };
-        match result {
-            Ok(outcome) => self.notify_success(outcome).await,
-            Err(err) => self.notify_execution_error(err, now).await,
+        if script::is_loading() {
+            self.load_script_line(text.trim()).await;
+            return;
         }
-    }
 
-    async fn handle_completion(&mut self) {
-        let buffer = self.buffer.as_slice();
-        let Ok(text) = core::str::from_utf8(buffer) else {
-            self.emit_bell().await;
+        let trimmed = text.trim();
+        let tag = grammar::request_tag(trimmed);
+        if let Err(error) = self.chain.start(trimmed, tag) {
+            self.tag = tag;
+            let mut message: String<FRAME_CAPACITY> = String::new();
+            let _ = write!(message, "ERR chain {error}");
+            self.notify_error(message.as_str(), ErrorReply::Chain(error))
+                .await;
+            self.tag = None;
             return;
-        };
+        }
+        self.run_chain().await;
+    }
+
+    /// Runs chained commands until the chain ends, a command fails, or a
+    /// `wait` suspends it; [`Self::run`] resumes the chain once the wait ends.
+    async fn run_chain(&mut self) {
+        while self.wait.is_none() {
+            let Some(command) = self.chain.next_command() else {
+                break;
+            };
+            let tag = command.tag;
+            let mut line: String<MAX_LINE_LEN> = String::new();
+            let _ = line.push_str(command.text);
+            self.tag = tag;
+            self.failed = false;
+
+            if script::is_loading() {
+                self.load_script_line(line.as_str()).await;
+            } else {
+                let now = Instant::now();
+                match self.execute_command(line.as_str(), now) {
+                    Ok(outcome) => self.notify_success(outcome).await,
+                    Err(err) => self.notify_execution_error(err, now).await,
+                }
+            }
+            // A pending wait reports its own failure from `finish_wait`.
+            if self.failed && self.wait.is_none() {
+                self.chain.fail();
+            }
+        }
+        self.tag = None;
+    }
 
-        let cursor = text.len();
-        let completion = self.completion.complete(text, cursor);
+    async fn handle_completion(&mut self) {
+        let cursor = self.editor.cursor();
         let CompletionResult {
             replacement,
             options,
-        } = completion;
+        } = self.completion.complete(self.editor.as_str(), cursor);
 
-        let options = options;
         if options.is_empty() {
             self.emit_bell().await;
             return;
         }
 
-        let option_count = options.len();
-        if option_count == 1 {
+        if options.len() == 1 {
             let Some(replacement) = replacement else {
                 self.emit_bell().await;
                 return;
             };
 
-            if replacement.end != cursor || replacement.start > replacement.end {
+            if self.editor.complete(&replacement).is_err() {
                 self.emit_bell().await;
                 return;
             }
+            self.redraw_line().await;
+        } else {
+            self.send_bytes(b"\r\n").await;
+            self.emit_suggestions(options.as_slice()).await;
+            self.redraw_line().await;
+        }
+    }
 
-            let removal = replacement.end - replacement.start;
-            if let Err(LineError::Overflow) = self
-                .buffer
-                .replace_from(replacement.start, replacement.value)
-            {
-                self.drop_input = true;
-                self.notify_error("ERR line-too-long").await;
-                return;
+    async fn notify_success(&mut self, outcome: CommandOutcome<FirmwareInstant>) {
+        if let CommandOutcome::Reboot(ack) = &outcome
+            && ack.to_bootloader
+        {
+            if autoboot::arm(ack.requested_at) {
+                self.follow_ups.bootloader = self.tag;
+            } else {
+                defmt::warn!("autoboot: interrupter already active; keeping the earlier run");
             }
+        }
 
-            self.echo_backspaces(removal).await;
-            self.echo_bytes(replacement.value.as_bytes()).await;
-        } else {
-            let mut snapshot: Vec<u8, MAX_LINE_LEN> = Vec::new();
-            if snapshot.extend_from_slice(self.buffer.as_slice()).is_err() {
-                return;
+        match outcome {
+            CommandOutcome::Console(action) => match action {
+                ConsoleAction::Show(selection) => self.notify_console(selection).await,
+                ConsoleAction::SysRq(key) => self.send_sysrq(key).await,
+                ConsoleAction::Send { text, newline } => {
+                    self.send_console_text(&text, newline).await;
+                }
+                ConsoleAction::Attach => self.attach_console().await,
+                ConsoleAction::Baud(setting) => self.set_console_baud(setting).await,
+                ConsoleAction::Pattern(command) => self.handle_console_pattern(command).await,
+            },
+            CommandOutcome::Script(action) => self.handle_script(action).await,
+            CommandOutcome::Mode(mode) => self.set_mode(mode).await,
+            CommandOutcome::Events(action) => self.set_events(action).await,
+            CommandOutcome::Wait(command) => self.start_wait(command).await,
+            CommandOutcome::Macro(action) => self.handle_macro(action).await,
+            outcome if self.mode.is_json() => self.notify_json(&outcome).await,
+            CommandOutcome::Status(snapshot) => self.notify_status(snapshot).await,
+            CommandOutcome::Power(report) => self.notify_power(report).await,
+            CommandOutcome::Bridge(report) => self.notify_bridge_stats(report).await,
+            CommandOutcome::Watchdog(status) => self.notify_watchdog(status).await,
+            CommandOutcome::Help(page) => self.notify_help(&page).await,
+            CommandOutcome::Reboot(ack) => {
+                let mut message: String<FRAME_CAPACITY> = String::new();
+                format_reboot_ack(&mut message, ack);
+                self.send_line(message.as_str()).await;
+            }
+            CommandOutcome::Recovery(ack) => {
+                let mut message: String<FRAME_CAPACITY> = String::new();
+                format_recovery_ack(&mut message, ack);
+                self.send_line(message.as_str()).await;
+            }
+            CommandOutcome::Fault(ack) => {
+                let mut message: String<FRAME_CAPACITY> = String::new();
+                format_fault_ack(&mut message, ack);
+                self.send_line(message.as_str()).await;
             }
-            self.emit_suggestions(options.as_slice()).await;
-            self.echo_bytes(snapshot.as_slice()).await;
         }
     }
 
-    async fn notify_success(&mut self, outcome: CommandOutcome<FirmwareInstant>) {
-        if let CommandOutcome::Status(snapshot) = outcome {
-            self.notify_status(snapshot).await;
-            return;
+    /// Switches the response format; the acknowledgement already uses the
+    /// format now in effect.
+    async fn set_mode(&mut self, mode: Option<ResponseMode>) {
+        if let Some(mode) = mode {
+            self.mode = mode;
         }
+        let mut message: String<FRAME_CAPACITY> = String::new();
+        let _ = write!(message, "OK mode {}", self.mode);
+        self.respond(message.as_str(), Reply::Mode(self.mode)).await;
+    }
 
+    /// Updates the telemetry categories printed as they are recorded.
+    async fn set_events(&mut self, action: EventsCommand) {
+        self.events = action.apply(self.events);
+        telemetry::set_event_filter(self.events);
         let mut message: String<FRAME_CAPACITY> = String::new();
+        let _ = write!(message, "OK events {}", self.events);
+        self.respond(message.as_str(), Reply::Events(self.events))
+            .await;
+    }
 
-        match outcome {
-            CommandOutcome::Reboot(ack) => format_reboot_ack(&mut message, ack),
-            CommandOutcome::Recovery(ack) => format_recovery_ack(&mut message, ack),
-            CommandOutcome::Fault(ack) => format_fault_ack(&mut message, ack),
-            CommandOutcome::Status(_) => unreachable!(),
+    /// Holds the session until the wait's condition holds. Completions and
+    /// events already queued are reported first so only later ones end it.
+    async fn start_wait(&mut self, command: WaitCommand) {
+        self.poll_completions().await;
+        self.poll_events().await;
+
+        let now = FirmwareInstant::from(Instant::now());
+        let wait = PendingWait::new(command.condition, command.timeout, now);
+        self.follow_ups.wait = self.tag;
+        if let Some(report) = wait.on_idle(self.sequencer_idle(), now) {
+            self.finish_wait(Ok(report)).await;
+            return;
         }
+        telemetry::set_event_wait(matches!(command.condition, WaitCondition::Event(_)));
+        self.wait = Some(wait);
+    }
 
-        if message.is_empty() {
-            let _ = message.push_str("OK");
+    /// Answers a `wait` under the tag of the request that started it.
+    async fn finish_wait(&mut self, result: Result<WaitReport, WaitTimeout>) {
+        self.wait = None;
+        telemetry::set_event_wait(false);
+        let tag = self.follow_ups.wait.take();
+        let answering = core::mem::replace(&mut self.tag, tag);
+        let mut message: String<STATS_LINE_CAPACITY> = String::new();
+        match result {
+            Ok(report) => {
+                let _ = write!(message, "OK wait {report}");
+                self.respond(message.as_str(), Reply::Wait(&report)).await;
+            }
+            Err(timeout) => {
+                let _ = write!(message, "ERR timeout {timeout}");
+                self.notify_error(message.as_str(), ErrorReply::Timeout(&timeout))
+                    .await;
+                self.chain.fail();
+            }
         }
+        self.tag = answering;
+    }
 
-        self.send_line(message.as_str()).await;
+    /// Returns `true` when no strap sequence is running or queued.
+    fn sequencer_idle(&self) -> bool {
+        !status::sequencer_busy() && self.executor.scheduler().producer().is_empty() != Some(false)
+    }
+
+    async fn notify_json(&mut self, outcome: &CommandOutcome<FirmwareInstant>) {
+        let formatter = JsonFormatter::new(outcome);
+        for index in 0..formatter.line_count() {
+            self.send_json(|line| formatter.write_line(line, index))
+                .await;
+        }
     }
 
     async fn notify_status(&mut self, snapshot: StatusSnapshot) {
         let formatter = StatusFormatter::new(&snapshot);
-        let mut line: String<FRAME_CAPACITY> = String::new();
+        let mut line: String<STATS_LINE_CAPACITY> = String::new();
 
         if formatter.write_straps_line(&mut line).is_ok() {
             self.send_line(line.as_str()).await;
@@ -358,6 +814,434 @@ impl<'a> ReplSession<'a> {
         } else {
             self.send_line("ERR status-line-overflow").await;
         }
+
+        line.clear();
+        if formatter.write_bridge_stats_line(&mut line).is_ok() {
+            self.send_line(line.as_str()).await;
+        } else {
+            self.send_line("ERR status-line-overflow").await;
+        }
+    }
+
+    async fn notify_help(&mut self, page: &HelpPage) {
+        let mut line: String<HELP_LINE_CAPACITY> = String::new();
+        for index in 0..page.line_count() {
+            line.clear();
+            if page.write_line(index, &mut line).is_ok() {
+                self.send_line(line.as_str()).await;
+            } else {
+                self.send_line("ERR help-line-overflow").await;
+            }
+        }
+    }
+
+    async fn notify_bridge_stats(&mut self, report: BridgeStatsReport) {
+        let formatter = BridgeStatsFormatter::new(&report.snapshot);
+        let mut line: String<STATS_LINE_CAPACITY> = String::new();
+        for index in 0..formatter.line_count() {
+            line.clear();
+            if formatter.write_line(&mut line, index).is_ok() {
+                self.send_line(line.as_str()).await;
+            } else {
+                self.send_line("ERR status-line-overflow").await;
+            }
+        }
+
+        if report.reset {
+            self.send_line("OK bridge stats reset").await;
+        }
+    }
+
+    async fn notify_power(&mut self, report: PowerReport) {
+        match report {
+            PowerReport::Stats { snapshot, reset } => {
+                let formatter = RailStatsFormatter::new(&snapshot);
+                let mut line: String<FRAME_CAPACITY> = String::new();
+                for index in 0..formatter.line_count() {
+                    line.clear();
+                    if formatter.write_line(&mut line, index).is_ok() {
+                        self.send_line(line.as_str()).await;
+                    } else {
+                        self.send_line("ERR status-line-overflow").await;
+                    }
+                }
+
+                if reset {
+                    self.send_line("OK power stats reset").await;
+                }
+            }
+            PowerReport::History(report) => {
+                let formatter = BrownOutFormatter::new(&report);
+                let mut line: String<FRAME_CAPACITY> = String::new();
+                for index in 0..formatter.line_count() {
+                    line.clear();
+                    if formatter.write_line(&mut line, index).is_ok() {
+                        self.send_line(line.as_str()).await;
+                    } else {
+                        self.send_line("ERR status-line-overflow").await;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn notify_watchdog(&mut self, status: WatchdogStatus) {
+        let formatter = WatchdogFormatter::new(&status);
+        let mut line: String<FRAME_CAPACITY> = String::new();
+        for index in 0..formatter.line_count() {
+            line.clear();
+            if formatter.write_line(&mut line, index).is_ok() {
+                self.send_line(line.as_str()).await;
+            } else {
+                self.send_line("ERR status-line-overflow").await;
+            }
+        }
+    }
+
+    async fn notify_console(&mut self, selection: ScrollbackSelection) {
+        // Each line is copied out under the lock so the bridge task can keep
+        // appending while this one waits on the CDC0 queue.
+        let mut line: String<{ SCROLLBACK_LINE_LIMIT + 32 }> = String::new();
+        let mut shown = 0u32;
+        for seq in console::scrollback_select(selection) {
+            if console::format_scrollback_line(seq, &mut line) {
+                self.respond(line.as_str(), Reply::ConsoleLine(line.as_str()))
+                    .await;
+                shown += 1;
+            }
+        }
+
+        let summary = console::scrollback_summary();
+        let mut message: String<FRAME_CAPACITY> = String::new();
+        let _ = write!(
+            message,
+            "OK console lines={shown} run={} dropped={}",
+            summary.run, summary.evicted
+        );
+        let reply = Reply::ConsoleShown {
+            lines: shown,
+            run: summary.run,
+            dropped: summary.evicted,
+        };
+        self.respond(message.as_str(), reply).await;
+    }
+
+    async fn send_sysrq(&mut self, key: SysRqKey) {
+        self.bridge.request_break(BreakRequest {
+            line_break: LineBreak::Timed(SYSRQ_BREAK),
+            follow_up: Some(key.byte()),
+        });
+
+        let mut message: String<FRAME_CAPACITY> = String::new();
+        let _ = write!(
+            message,
+            "OK console sysrq {key} break={}ms",
+            SYSRQ_BREAK.as_millis()
+        );
+        self.respond(message.as_str(), Reply::ConsoleSysRq(key))
+            .await;
+    }
+
+    /// Hands CDC0 over to the Jetson console. The bridge task copies console
+    /// output here while attached; CDC1 keeps its own copy.
+    async fn attach_console(&mut self) {
+        self.respond(
+            "OK console attach (type ~. at line start to detach)",
+            Reply::ConsoleAttached,
+        )
+        .await;
+        self.attach = AttachEscape::new();
+        status::set_console_attached(true);
+        // Typed bytes now belong to the Jetson, so the rest of the chain is dropped.
+        self.chain.clear();
+    }
+
+    /// Hands the request to the bridge task. A search reports back through
+    /// [`Self::poll_autobaud`] once every candidate rate has been tried.
+    async fn set_console_baud(&mut self, setting: BaudSetting) {
+        self.bridge.request_baud(setting);
+
+        let mut message: String<FRAME_CAPACITY> = String::new();
+        let _ = write!(message, "OK console baud {setting}");
+        if setting == BaudSetting::Auto {
+            let _ = message.push_str(" (searching)");
+            self.follow_ups.autobaud = self.tag;
+        }
+        self.respond(message.as_str(), Reply::ConsoleBaud(setting))
+            .await;
+    }
+
+    async fn handle_console_pattern(&mut self, command: PatternCommand) {
+        let mut message: String<PATTERN_LINE_CAPACITY> = String::new();
+        let result = match command {
+            PatternCommand::Add { marker, text } => {
+                console::register_pattern(marker, &text).map(|id| {
+                    let _ = write!(message, "OK console pattern add id={id} marker={marker}");
+                    Reply::ConsolePatternAdded { id, marker }
+                })
+            }
+            PatternCommand::Remove { id } => console::unregister_pattern(id).map(|()| {
+                let _ = write!(message, "OK console pattern remove id={id}");
+                Reply::ConsolePatternRemoved(id)
+            }),
+            PatternCommand::List => {
+                let entries = console::pattern_entries();
+                for entry in &entries {
+                    message.clear();
+                    let _ = write!(
+                        message,
+                        "pattern {} {} \"{}\"",
+                        entry.id,
+                        entry.marker,
+                        Escaped(&entry.text)
+                    );
+                    let reply = Reply::ConsolePattern {
+                        id: entry.id,
+                        marker: entry.marker,
+                        text: &entry.text,
+                    };
+                    self.respond(message.as_str(), reply).await;
+                }
+                message.clear();
+                let _ = write!(message, "OK patterns={}", entries.len());
+                Ok(Reply::ConsolePatternsListed {
+                    count: entries.len(),
+                })
+            }
+        };
+        match result {
+            Ok(reply) => self.respond(message.as_str(), reply).await,
+            Err(err) => {
+                message.clear();
+                let _ = write!(message, "ERR console {err}");
+                self.notify_error(message.as_str(), ErrorReply::Pattern(&err))
+                    .await;
+            }
+        }
+    }
+
+    async fn send_console_text(&mut self, text: &ConsoleText, newline: bool) {
+        let sent = self.queue_console_bytes(text, newline).await;
+
+        let mut message: String<FRAME_CAPACITY> = String::new();
+        let _ = write!(message, "OK console send bytes={sent}");
+        self.respond(message.as_str(), Reply::ConsoleSent { bytes: sent })
+            .await;
+    }
+
+    /// Queues bytes on the USB→UART path, so the bridge task writes them and
+    /// records them as host traffic. Returns the number of bytes queued.
+    async fn queue_console_bytes(&mut self, text: &[u8], newline: bool) -> usize {
+        let sender = self.bridge.usb_to_ttl_sender();
+        let newline = if newline { &[CONSOLE_NEWLINE][..] } else { &[] };
+        let mut frame = BridgeFrame::new();
+        let mut sent = 0usize;
+        for &byte in text.iter().chain(newline) {
+            if frame.is_full() {
+                sent += frame.len();
+                sender.send(core::mem::take(&mut frame)).await;
+            }
+            let _ = frame.push(byte);
+        }
+        if !frame.is_empty() {
+            sent += frame.len();
+            sender.send(frame).await;
+        }
+        sent
+    }
+
+    async fn handle_script(&mut self, action: ScriptCommand) {
+        let now = FirmwareInstant::from(Instant::now());
+        let mut message: String<FRAME_CAPACITY> = String::new();
+        match action {
+            ScriptCommand::Load { name } => match script::begin_load(name.clone()) {
+                Ok(()) => {
+                    let _ = write!(message, "OK script load {name} (finish with `end`)");
+                    self.respond(message.as_str(), Reply::ScriptLoading(&name))
+                        .await;
+                }
+                Err(err) => {
+                    let _ = write!(message, "ERR script {err}");
+                    self.notify_error(message.as_str(), ErrorReply::Script(&err))
+                        .await;
+                }
+            },
+            ScriptCommand::List => {
+                let summaries = script::summaries();
+                for summary in &summaries {
+                    message.clear();
+                    let _ = write!(message, "script {} steps={}", summary.name, summary.steps);
+                    if let Some(line) = summary.running_line {
+                        let _ = write!(message, " running line={line}");
+                    }
+                    self.respond(message.as_str(), Reply::ScriptEntry(summary))
+                        .await;
+                }
+                message.clear();
+                let _ = write!(message, "OK scripts={}", summaries.len());
+                let reply = Reply::ScriptListed {
+                    count: summaries.len(),
+                };
+                self.respond(message.as_str(), reply).await;
+            }
+            ScriptCommand::Run { name } => match script::start(&name, now) {
+                Ok(()) => {
+                    self.follow_ups.script = self.tag;
+                    let _ = write!(message, "OK script run {name}");
+                    self.respond(message.as_str(), Reply::ScriptStarted(&name))
+                        .await;
+                    self.poll_script().await;
+                }
+                Err(err) => {
+                    let _ = write!(message, "ERR script {err}");
+                    self.notify_error(message.as_str(), ErrorReply::Script(&err))
+                        .await;
+                }
+            },
+            ScriptCommand::Stop => {
+                if let Some(report) = script::stop(now) {
+                    self.send_script_report(report, now).await;
+                } else {
+                    self.notify_error("ERR script not running", ErrorReply::Script(&"not running"))
+                        .await;
+                }
+            }
+        }
+    }
+
+    async fn handle_macro(&mut self, action: MacroCommand) {
+        let mut message: String<MACRO_LINE_CAPACITY> = String::new();
+        match action {
+            MacroCommand::Define {
+                name,
+                body,
+                continue_on_error,
+            } => match self.macros.define(name, &body, continue_on_error).cloned() {
+                Ok(entry) => {
+                    let _ = write!(
+                        message,
+                        "OK macro define {} commands={}",
+                        entry.name, entry.commands
+                    );
+                    self.respond(message.as_str(), Reply::MacroDefined(&entry))
+                        .await;
+                }
+                Err(err) => self.notify_macro_error(err).await,
+            },
+            MacroCommand::Run { name } => {
+                let Some(entry) = self.macros.get(&name).cloned() else {
+                    self.notify_macro_error(MacroError::NotFound).await;
+                    return;
+                };
+                match self
+                    .chain
+                    .push(&entry.body, entry.continue_on_error, self.tag)
+                {
+                    Ok(_) => {
+                        let _ = write!(
+                            message,
+                            "OK macro run {} commands={}",
+                            entry.name, entry.commands
+                        );
+                        self.respond(message.as_str(), Reply::MacroStarted(&entry))
+                            .await;
+                    }
+                    Err(err) => self.notify_macro_error(err.into()).await,
+                }
+            }
+            MacroCommand::List => {
+                let count = self.macros.len();
+                for index in 0..count {
+                    let Some(entry) = self.macros.entries().nth(index).cloned() else {
+                        break;
+                    };
+                    message.clear();
+                    let _ = write!(message, "{entry}");
+                    self.respond(message.as_str(), Reply::MacroEntry(&entry))
+                        .await;
+                }
+                message.clear();
+                let _ = write!(message, "OK macros={count}");
+                self.respond(message.as_str(), Reply::MacroListed { count })
+                    .await;
+            }
+        }
+    }
+
+    async fn notify_macro_error(&mut self, error: MacroError) {
+        let mut message: String<FRAME_CAPACITY> = String::new();
+        let _ = write!(message, "ERR macro {error}");
+        self.notify_error(message.as_str(), ErrorReply::Macro(&error))
+            .await;
+    }
+
+    async fn load_script_line(&mut self, line: &str) {
+        let mut message: String<FRAME_CAPACITY> = String::new();
+        match script::load_line(line) {
+            Ok(LoadProgress::Pending) => {}
+            Ok(LoadProgress::Loaded { name, steps }) => {
+                let _ = write!(message, "OK script {name} loaded steps={steps}");
+                let reply = Reply::ScriptLoaded { name: &name, steps };
+                self.respond(message.as_str(), reply).await;
+            }
+            Err(err) => {
+                let _ = write!(message, "ERR script {err}");
+                self.notify_error(message.as_str(), ErrorReply::Script(&err))
+                    .await;
+            }
+        }
+    }
+
+    /// Carries out everything the running script can do right now.
+    async fn poll_script(&mut self) {
+        loop {
+            let now = FirmwareInstant::from(Instant::now());
+            let Some(event) = script::poll(now) else {
+                return;
+            };
+            match event {
+                ScriptEvent::Send(text) => {
+                    self.queue_console_bytes(&text, false).await;
+                }
+                ScriptEvent::Sequence(kind) => {
+                    let queued =
+                        self.executor
+                            .scheduler_mut()
+                            .enqueue(kind, now, CommandSource::Script);
+                    if queued.is_err() {
+                        defmt::warn!(
+                            "script: {} rejected; stopping",
+                            defmt::Display2Format(&kind)
+                        );
+                        self.notify_error(
+                            "ERR script sequence rejected",
+                            ErrorReply::Script(&"sequence rejected"),
+                        )
+                        .await;
+                        if let Some(report) = script::stop(now) {
+                            self.send_script_report(report, now).await;
+                        }
+                        return;
+                    }
+                }
+                ScriptEvent::Expect(report) | ScriptEvent::Finished(report) => {
+                    self.send_script_report(report, now).await;
+                }
+            }
+        }
+    }
+
+    async fn send_script_report(&mut self, report: ScriptReport, now: FirmwareInstant) {
+        let mut message: String<FRAME_CAPACITY> = String::new();
+        let _ = write!(message, "{report}");
+        let tag = if report.result.is_final() {
+            self.follow_ups.script.take()
+        } else {
+            self.follow_ups.script
+        };
+        self.send_follow_up(tag, message.as_str(), Event::Script(&report))
+            .await;
+        script::queue_report(report, now);
     }
 
     async fn notify_execution_error(
@@ -365,7 +1249,17 @@ impl<'a> ReplSession<'a> {
         error: ExecutorError<(), FirmwareInstant>,
         now: Instant,
     ) {
-        let mut message: String<FRAME_CAPACITY> = String::new();
+        self.failed = true;
+        if self.mode.is_json() {
+            let now = FirmwareInstant::from(now);
+            let error = ErrorReply::from_command_error(&error, |ready_at| {
+                ready_at.saturating_duration_since(now)
+            });
+            self.send_json(|line| error.write(line)).await;
+            return;
+        }
+
+        let mut message: String<ERROR_LINE_CAPACITY> = String::new();
 
         match error {
             ExecutorError::Parse(parse) => {
@@ -387,8 +1281,54 @@ impl<'a> ReplSession<'a> {
         self.send_line(message.as_str()).await;
     }
 
-    async fn notify_error(&mut self, message: &str) {
-        self.send_line(message).await;
+    async fn notify_error(&mut self, message: &str, error: ErrorReply<'_>) {
+        self.failed = true;
+        if self.mode.is_json() {
+            self.send_json(|line| error.write(line)).await;
+        } else {
+            self.send_line(message).await;
+        }
+    }
+
+    /// Sends `message`, or the JSON form of `reply` in JSON mode.
+    async fn respond(&mut self, message: &str, reply: Reply<'_>) {
+        if self.mode.is_json() {
+            self.send_json(|line| reply.write(line)).await;
+        } else {
+            self.send_line(message).await;
+        }
+    }
+
+    /// Sends an asynchronous report in the current response format.
+    async fn send_event(&mut self, message: &str, event: Event<'_>) {
+        if self.mode.is_json() {
+            self.send_json(|line| event.write(line)).await;
+        } else {
+            self.send_line(message).await;
+        }
+    }
+
+    /// Sends an asynchronous report under the tag of the request that started
+    /// it; an untagged report keeps the tag of the request being answered.
+    async fn send_follow_up(&mut self, tag: Option<RequestTag>, message: &str, event: Event<'_>) {
+        let tag = tag.or(self.tag);
+        let answering = core::mem::replace(&mut self.tag, tag);
+        self.send_event(message, event).await;
+        self.tag = answering;
+    }
+
+    /// Renders one JSON object and sends it as a line. An object that does
+    /// not fit is replaced by a `line-overflow` error.
+    async fn send_json(
+        &mut self,
+        write: impl FnOnce(&mut String<JSON_LINE_CAPACITY>) -> fmt::Result,
+    ) {
+        let mut line: String<JSON_LINE_CAPACITY> = String::new();
+        if write(&mut line).is_err() {
+            line.clear();
+            let _ = ErrorReply::LineOverflow.write(&mut line);
+        }
+        self.send_line(line.as_str()).await;
     }
 
     async fn send_line(&mut self, message: &str) {
@@ -396,10 +1336,20 @@ impl<'a> ReplSession<'a> {
             return;
         }
 
-        self.send_bytes(message.as_bytes()).await;
+        self.send_text(message).await;
         self.send_bytes(b"\n").await;
     }
 
+    /// Sends `message` with its tag but without ending the line.
+    async fn send_text(&mut self, message: &str) {
+        let line = TaggedLine::new(message, self.tag);
+        let mut marker: String<TAG_MARKER_CAPACITY> = String::new();
+        let _ = write!(marker, "{}", line.marker());
+        self.send_bytes(line.head().as_bytes()).await;
+        self.send_bytes(marker.as_bytes()).await;
+        self.send_bytes(line.tail().as_bytes()).await;
+    }
+
     async fn send_bytes(&mut self, bytes: &[u8]) {
         if bytes.is_empty() {
             return;
@@ -421,27 +1371,6 @@ impl<'a> ReplSession<'a> {
         self.send_bytes(&[0x07]).await;
     }
 
-    async fn echo_bytes(&mut self, bytes: &[u8]) {
-        self.send_bytes(bytes).await;
-    }
-
-    async fn echo_backspaces(&mut self, count: usize) {
-        if count == 0 {
-            return;
-        }
-
-        let mut remaining = count;
-        let mut scratch = [0u8; FRAME_CAPACITY];
-        while remaining > 0 {
-            let chunk = remaining.min(FRAME_CAPACITY);
-            for byte in &mut scratch[..chunk] {
-                *byte = b'\x08';
-            }
-            self.send_bytes(&scratch[..chunk]).await;
-            remaining -= chunk;
-        }
-    }
-
     async fn emit_suggestions(&mut self, options: &[&'static str]) {
         for option in options.iter().copied() {
             self.send_line(option).await;
@@ -461,6 +1390,9 @@ impl<'a> ReplSession<'a> {
 #[cfg(target_os = "none")]
 fn format_reboot_ack(buffer: &mut String<FRAME_CAPACITY>, ack: RebootAck<FirmwareInstant>) {
     let _ = buffer.push_str("OK reboot");
+    if ack.to_bootloader {
+        let _ = buffer.push_str(" to-bootloader");
+    }
     if let Some(delay) = ack.start_after {
         let millis = delay.as_millis();
         let _ = write!(buffer, " start-after={}ms", millis);
@@ -471,6 +1403,10 @@ fn format_reboot_ack(buffer: &mut String<FRAME_CAPACITY>, ack: RebootAck<Firmwar
 fn format_recovery_ack(buffer: &mut String<FRAME_CAPACITY>, ack: RecoveryAck<FirmwareInstant>) {
     let _ = buffer.push_str("OK recovery");
     let _ = write!(buffer, " sequence={}", ack.sequence);
+    if let RecoveryCommand::Now { timeout } = ack.command {
+        let timeout = timeout.unwrap_or(DEFAULT_BRIDGE_ACTIVITY_TIMEOUT);
+        let _ = write!(buffer, " timeout={}ms", timeout.as_millis());
+    }
 }
 
 #[cfg(target_os = "none")]
@@ -481,7 +1417,7 @@ fn format_fault_ack(buffer: &mut String<FRAME_CAPACITY>, ack: FaultAck<FirmwareI
 
 #[cfg(target_os = "none")]
 fn describe_schedule_error(
-    buffer: &mut String<FRAME_CAPACITY>,
+    buffer: &mut String<ERROR_LINE_CAPACITY>,
     error: ScheduleError<(), FirmwareInstant>,
     now: Instant,
 ) {
@@ -512,33 +1448,3 @@ fn describe_queue_error(buffer: &mut String<FRAME_CAPACITY>, error: QueueErrorKi
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn pushes_and_takes_lines() {
-        let mut buffer = LineBuffer::new();
-        buffer.push(b'r').unwrap();
-        buffer.push(b'e').unwrap();
-        buffer.push(b'b').unwrap();
-        buffer.push(b'o').unwrap();
-        buffer.push(b'o').unwrap();
-        buffer.push(b't').unwrap();
-
-        let line = buffer.take().expect("line missing");
-        assert_eq!(line.as_slice(), b"reboot");
-        assert!(buffer.is_empty());
-    }
-
-    #[test]
-    fn pop_resets_tail() {
-        let mut buffer = LineBuffer::new();
-        buffer.push(b'a').unwrap();
-        buffer.push(b'b').unwrap();
-        buffer.pop();
-        let line = buffer.take().expect("line missing");
-        assert_eq!(line.as_slice(), b"a");
-    }
-}
@@ -1,20 +1,33 @@
 use embassy_futures::join::join;
+use embassy_futures::select::{Either, Either4, select, select4};
 use embassy_stm32 as hal;
 use embassy_stm32::Peri;
-use embassy_stm32::usart::{BufferedUart, Config as UartConfig, DataBits, Parity, StopBits};
+use embassy_stm32::usart::{
+    BufferedUart, BufferedUartTx, Config as UartConfig, DataBits, Error as UartError, Parity,
+    StopBits,
+};
+use embassy_sync::channel::TrySendError;
 use embassy_time::{Duration, Instant, Timer};
 use embedded_io_async::{Read, Write};
 
 use crate::bridge::{
-    BRIDGE_FRAME_SIZE, BRIDGE_QUEUE_DEPTH, BridgeActivityBus, BridgeActivityEvent,
-    BridgeActivityKind, BridgeFrame, BridgeQueue,
+    BRIDGE_FRAME_SIZE, BRIDGE_QUEUE_DEPTH, BaudSetting, BreakRequest, BridgeActivityBus,
+    BridgeActivityEvent, BridgeActivityKind, BridgeActivitySender, BridgeFrame, BridgeQueue,
+    BridgeReceiver, ConsoleFrame, LineBreak, LineCoding, autobaud, console, stats,
 };
+use crate::repl::REPL_TX_QUEUE;
 use crate::status;
 use crate::straps::FirmwareInstant;
+use controller_core::bridge::autobaud::{AutobaudStep, BaudSource};
+use controller_core::bridge::line_coding::{Parity as LineParity, StopBits as LineStopBits};
+use controller_core::bridge::stats::BridgeFault;
 use static_cell::StaticCell;
 
 const BRIDGE_UART_BUFFER_SIZE: usize = BRIDGE_FRAME_SIZE * BRIDGE_QUEUE_DEPTH;
-const JETSON_UART_BAUD: u32 = 115_200;
+/// Longest break held for a host that never sends the matching release.
+const BREAK_HOLD_LIMIT: Duration = Duration::from_secs(5);
+/// Idle time between the end of a break and its follow-up byte.
+const BREAK_FOLLOW_UP_GAP: Duration = Duration::from_millis(2);
 
 static UART_TX_BUFFER: StaticCell<[u8; BRIDGE_UART_BUFFER_SIZE]> = StaticCell::new();
 static UART_RX_BUFFER: StaticCell<[u8; BRIDGE_UART_BUFFER_SIZE]> = StaticCell::new();
@@ -31,11 +44,8 @@ pub async fn run(
     tx_pin: Peri<'static, hal::peripherals::PB0>,
     rx_pin: Peri<'static, hal::peripherals::PB1>,
 ) -> ! {
-    let mut config = UartConfig::default();
-    config.baudrate = JETSON_UART_BAUD;
-    config.data_bits = DataBits::DataBits8;
-    config.stop_bits = StopBits::STOP1;
-    config.parity = Parity::ParityNone;
+    let config = uart_config(LineCoding::DEFAULT);
+    status::record_bridge_line_coding(LineCoding::DEFAULT);
 
     let tx_buffer = UART_TX_BUFFER.init([0; BRIDGE_UART_BUFFER_SIZE]);
     let rx_buffer = UART_RX_BUFFER.init([0; BRIDGE_UART_BUFFER_SIZE]);
@@ -46,6 +56,7 @@ pub async fn run(
     .expect("failed to initialize bridge UART");
 
     let (mut uart_tx, mut uart_rx) = uart.split();
+    console::install_default_markers();
 
     let usb_to_ttl = queue.usb_to_ttl_receiver();
     let ttl_to_usb = queue.ttl_to_usb_sender();
@@ -55,43 +66,42 @@ pub async fn run(
 
     let usb_to_uart = async move {
         loop {
-            let frame = usb_to_ttl.receive().await;
-            if frame.is_empty() {
-                continue;
-            }
-
-            let data = frame.as_slice();
-            let mut written = 0usize;
-
-            while written < data.len() {
-                match uart_tx.write(&data[written..]).await {
-                    Ok(count) if count > 0 => {
-                        written += count;
-                    }
-                    Ok(_) => {}
-                    Err(_) => {
-                        defmt::warn!("bridge: UART write error");
-                        Timer::after(Duration::from_millis(5)).await;
-                        break;
-                    }
+            let frame = match select4(
+                usb_to_ttl.receive(),
+                queue.line_coding_request(),
+                queue.break_request(),
+                queue.baud_request(),
+            )
+            .await
+            {
+                Either4::First(frame) => frame,
+                Either4::Second(coding) => {
+                    // Bytes the host wrote before changing the line coding
+                    // still go out with the old framing.
+                    drain_host_frames(&mut uart_tx, &usb_to_ttl, &usb_activity).await;
+                    apply_line_coding(&mut uart_tx, coding, BaudSource::Host);
+                    continue;
                 }
-            }
-
-            if written == data.len() {
-                if let Err(_) = uart_tx.flush().await {
-                    defmt::warn!("bridge: UART flush error");
-                    Timer::after(Duration::from_millis(5)).await;
+                Either4::Third(request) => {
+                    drain_host_frames(&mut uart_tx, &usb_to_ttl, &usb_activity).await;
+                    drive_break(&mut uart_tx, queue, request).await;
                     continue;
                 }
+                Either4::Fourth(setting) => {
+                    drain_host_frames(&mut uart_tx, &usb_to_ttl, &usb_activity).await;
+                    match setting {
+                        BaudSetting::Fixed(baud) => {
+                            let coding = status::bridge_line_coding().with_baud(baud);
+                            apply_line_coding(&mut uart_tx, coding, BaudSource::Manual);
+                        }
+                        BaudSetting::Auto => run_autobaud(&mut uart_tx).await,
+                    }
+                    continue;
+                }
+            };
 
-                let timestamp = FirmwareInstant::from(Instant::now());
-                let event = BridgeActivityEvent {
-                    kind: BridgeActivityKind::UsbToJetson,
-                    timestamp,
-                    bytes: data.len(),
-                };
-                status::record_bridge_tx(event.timestamp);
-                usb_activity.send(event).await;
+            if write_frame(&mut uart_tx, frame.as_slice()).await {
+                note_usb_to_jetson(&usb_activity, frame.len()).await;
             }
         }
     };
@@ -104,12 +114,40 @@ pub async fn run(
                     let mut frame = BridgeFrame::new();
                     if frame.extend_from_slice(&ingress[..count]).is_err() {
                         defmt::warn!("bridge: dropping Jetson frame len={} (overflow)", count);
+                        stats::record_fault(BridgeFault::Dropped(BridgeActivityKind::JetsonToUsb));
                         continue;
                     }
 
-                    ttl_to_usb.send(frame).await;
-
                     let timestamp = FirmwareInstant::from(Instant::now());
+                    autobaud::feed_console(timestamp, &ingress[..count]);
+                    console::feed_jetson_bytes(&ingress[..count], timestamp);
+                    let replay_end = console::replay_window().end;
+                    stats::record_frame(BridgeActivityKind::JetsonToUsb, count);
+                    if status::console_attached() && REPL_TX_QUEUE.try_send(frame.clone()).is_err()
+                    {
+                        // Never stall the UART on CDC0; CDC1 and the
+                        // scrollback still get the bytes.
+                        stats::record_fault(BridgeFault::Dropped(BridgeActivityKind::JetsonToUsb));
+                    }
+                    let frame = ConsoleFrame {
+                        bytes: frame,
+                        replay_end,
+                    };
+                    if status::bridge_host_attached() {
+                        // A full queue means the host is not reading fast
+                        // enough; waiting here backs up into UART overruns.
+                        if let Err(TrySendError::Full(frame)) = ttl_to_usb.try_send(frame) {
+                            stats::record_fault(BridgeFault::QueueFull(
+                                BridgeActivityKind::JetsonToUsb,
+                            ));
+                            ttl_to_usb.send(frame).await;
+                        }
+                    } else {
+                        // Keep draining the UART with no reader; the
+                        // scrollback holds the bytes for replay on attach.
+                        let _ = ttl_to_usb.try_send(frame);
+                    }
+
                     let event = BridgeActivityEvent {
                         kind: BridgeActivityKind::JetsonToUsb,
                         timestamp,
@@ -119,8 +157,16 @@ pub async fn run(
                     jetson_activity.send(event).await;
                 }
                 Ok(_) => {}
-                Err(_) => {
+                Err(err) => {
                     defmt::warn!("bridge: UART read error");
+                    if let Some(fault) = uart_fault(err) {
+                        stats::record_fault(fault);
+                        if fault != BridgeFault::UartOverrun {
+                            // Overruns mean we read too slowly, not that the
+                            // rate is wrong.
+                            autobaud::record_error(FirmwareInstant::from(Instant::now()));
+                        }
+                    }
                     Timer::after(Duration::from_millis(5)).await;
                 }
             }
@@ -132,3 +178,216 @@ pub async fn run(
         core::future::pending::<()>().await;
     }
 }
+
+/// Writes one host frame to the Jetson UART and waits for it to drain.
+///
+/// Returns `true` when every byte was written and flushed.
+async fn write_frame(uart_tx: &mut BufferedUartTx<'static>, data: &[u8]) -> bool {
+    if data.is_empty() {
+        return false;
+    }
+
+    let mut written = 0usize;
+    while written < data.len() {
+        match uart_tx.write(&data[written..]).await {
+            Ok(count) if count > 0 => {
+                written += count;
+            }
+            Ok(_) => {}
+            Err(_) => {
+                defmt::warn!("bridge: UART write error");
+                stats::record_fault(BridgeFault::UartWrite);
+                Timer::after(Duration::from_millis(5)).await;
+                return false;
+            }
+        }
+    }
+
+    if uart_tx.flush().await.is_err() {
+        defmt::warn!("bridge: UART flush error");
+        stats::record_fault(BridgeFault::UartWrite);
+        Timer::after(Duration::from_millis(5)).await;
+        return false;
+    }
+
+    true
+}
+
+/// Writes every frame already queued by the host and waits for the UART to drain.
+async fn drain_host_frames(
+    uart_tx: &mut BufferedUartTx<'static>,
+    usb_to_ttl: &BridgeReceiver<'static>,
+    activity: &BridgeActivitySender<'static>,
+) {
+    while let Ok(frame) = usb_to_ttl.try_receive() {
+        if write_frame(uart_tx, frame.as_slice()).await {
+            note_usb_to_jetson(activity, frame.len()).await;
+        }
+    }
+    if uart_tx.flush().await.is_err() {
+        defmt::warn!("bridge: UART flush error");
+    }
+}
+
+/// Holds the Jetson RX line low for the requested break.
+///
+/// Inverting the idle TX level gives one continuous break rather than a train
+/// of break characters, which matters for sysrq: the Linux serial core treats
+/// a second break as cancelling the first. A new request while the break is
+/// held replaces its remaining time; a release ends it.
+async fn drive_break(
+    uart_tx: &mut BufferedUartTx<'static>,
+    queue: &'static BridgeQueue,
+    request: BreakRequest,
+) {
+    let mut line_break = request.line_break;
+    let mut follow_up = request.follow_up;
+    if line_break == LineBreak::Release {
+        return;
+    }
+
+    let coding = status::bridge_line_coding();
+    let mut held = uart_config(coding);
+    held.invert_tx = true;
+    if uart_tx.set_config(&held).is_err() {
+        defmt::warn!("bridge: UART refused break");
+        return;
+    }
+
+    loop {
+        let remaining = match line_break {
+            LineBreak::Timed(duration) => Duration::try_from(duration).unwrap_or(BREAK_HOLD_LIMIT),
+            LineBreak::Hold => BREAK_HOLD_LIMIT,
+            LineBreak::Release => break,
+        };
+        match select(Timer::after(remaining), queue.break_request()).await {
+            Either::First(()) => break,
+            Either::Second(next) => {
+                line_break = next.line_break;
+                follow_up = next.follow_up.or(follow_up);
+            }
+        }
+    }
+
+    if uart_tx.set_config(&uart_config(coding)).is_err() {
+        defmt::error!("bridge: UART failed to leave break");
+        return;
+    }
+
+    if let Some(byte) = follow_up {
+        Timer::after(BREAK_FOLLOW_UP_GAP).await;
+        let _ = write_frame(uart_tx, &[byte]).await;
+    }
+}
+
+async fn note_usb_to_jetson(activity: &BridgeActivitySender<'static>, bytes: usize) {
+    let timestamp = FirmwareInstant::from(Instant::now());
+    let event = BridgeActivityEvent {
+        kind: BridgeActivityKind::UsbToJetson,
+        timestamp,
+        bytes,
+    };
+    status::record_bridge_tx(event.timestamp);
+    stats::record_frame(BridgeActivityKind::UsbToJetson, bytes);
+    activity.send(event).await;
+}
+
+/// Maps a UART receive error onto the counter it belongs to.
+fn uart_fault(err: UartError) -> Option<BridgeFault> {
+    match err {
+        UartError::Framing => Some(BridgeFault::UartFraming),
+        UartError::Noise => Some(BridgeFault::UartNoise),
+        UartError::Overrun => Some(BridgeFault::UartOverrun),
+        UartError::Parity => Some(BridgeFault::UartParity),
+        _ => None,
+    }
+}
+
+/// Steps USART5 through the detector's candidate rates, then settles on the
+/// locked rate or returns to the framing in use before the search.
+async fn run_autobaud(uart_tx: &mut BufferedUartTx<'static>) {
+    let previous = status::bridge_line_coding();
+    let Some(first) = autobaud::start(FirmwareInstant::from(Instant::now())) else {
+        return;
+    };
+    status::record_baud_source(BaudSource::Detecting);
+
+    let mut pending = Some(first);
+    let report = loop {
+        if let Some(baud) = pending.take()
+            && uart_tx
+                .set_config(&uart_config(previous.with_baud(baud)))
+                .is_err()
+        {
+            defmt::warn!("autobaud: UART rejected {=u32} baud", baud);
+        }
+        Timer::after(autobaud::AUTOBAUD_POLL_INTERVAL).await;
+        match autobaud::poll(FirmwareInstant::from(Instant::now())) {
+            Some(AutobaudStep::Apply(baud)) => pending = Some(baud),
+            Some(AutobaudStep::Finished(report)) => break report,
+            None => {}
+        }
+    };
+
+    let (coding, source) = match report.locked_baud() {
+        Some(baud) => (previous.with_baud(baud), BaudSource::Detected),
+        None => (previous, BaudSource::Undetected),
+    };
+    if uart_tx.set_config(&uart_config(coding)).is_ok() {
+        status::record_bridge_line_coding(coding);
+        status::record_baud_source(source);
+    } else {
+        defmt::error!("autobaud: UART rejected {=u32} baud", coding.baud);
+        let _ = uart_tx.set_config(&uart_config(previous));
+        status::record_baud_source(BaudSource::Undetected);
+    }
+    autobaud::queue_report(report);
+}
+
+/// Reframes USART5; the previous framing stays active if the peripheral refuses.
+fn apply_line_coding(
+    uart_tx: &mut BufferedUartTx<'static>,
+    coding: LineCoding,
+    source: BaudSource,
+) {
+    if status::bridge_line_coding() == coding {
+        status::record_baud_source(source);
+        return;
+    }
+
+    match uart_tx.set_config(&uart_config(coding)) {
+        Ok(()) => {
+            status::record_bridge_line_coding(coding);
+            status::record_baud_source(source);
+            defmt::info!(
+                "bridge: UART now {=u32} baud {=u8} data bits",
+                coding.baud,
+                coding.data_bits
+            );
+        }
+        Err(_) => {
+            defmt::warn!("bridge: UART rejected {=u32} baud line coding", coding.baud);
+        }
+    }
+}
+
+fn uart_config(coding: LineCoding) -> UartConfig {
+    let mut config = UartConfig::default();
+    config.baudrate = coding.baud;
+    config.data_bits = if coding.data_bits == 7 {
+        DataBits::DataBits7
+    } else {
+        DataBits::DataBits8
+    };
+    config.parity = match coding.parity {
+        LineParity::None => Parity::ParityNone,
+        LineParity::Odd => Parity::ParityOdd,
+        LineParity::Even => Parity::ParityEven,
+    };
+    config.stop_bits = match coding.stop_bits {
+        LineStopBits::One => StopBits::STOP1,
+        LineStopBits::OnePointFive => StopBits::STOP1P5,
+        LineStopBits::Two => StopBits::STOP2,
+    };
+    config
+}
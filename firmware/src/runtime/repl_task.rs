@@ -1,4 +1,4 @@
-use super::COMMAND_QUEUE;
+use super::{BRIDGE_QUEUE, COMMAND_QUEUE};
 use crate::repl::{FirmwareStatusProvider, ReplSession};
 use crate::straps::CommandProducer;
 use controller_core::orchestrator::{SequenceScheduler, register_default_templates};
@@ -17,6 +17,6 @@ pub async fn run() -> ! {
 
     let executor =
         CommandExecutor::new(scheduler).with_status_provider(FirmwareStatusProvider::default());
-    let mut session = ReplSession::new(executor);
+    let mut session = ReplSession::new(executor, &BRIDGE_QUEUE);
     session.run().await;
 }
@@ -1,11 +1,17 @@
 use super::{BRIDGE_QUEUE, USB_STORAGE};
-use crate::bridge::BridgeFrame;
+use crate::bridge::{
+    BreakRequest, BridgeActivityKind, BridgeFrame, ConsoleFrame, LineBreak, console, stats,
+};
 use crate::repl::{REPL_RX_QUEUE, REPL_TX_QUEUE, ReplFrame};
+use crate::status;
 use crate::usb::{self, UsbDeviceStrings};
+use controller_core::bridge::line_coding::LineCoding;
+use controller_core::bridge::stats::BridgeFault;
 use embassy_futures::join::{join, join3};
 use embassy_futures::select::{Either3, select3};
 use embassy_stm32 as hal;
 use embassy_stm32::Peri;
+use embassy_sync::channel::TrySendError;
 use embassy_usb::driver::EndpointError;
 
 embassy_stm32::bind_interrupts!(struct UsbIrqs {
@@ -21,7 +27,12 @@ pub async fn run(
     let storage = USB_STORAGE.init(usb::UsbDeviceStorage::new());
     let driver = embassy_stm32::usb::Driver::new(usb, UsbIrqs, dp, dm);
 
-    let mut composite = usb::UsbComposite::new(driver, storage, UsbDeviceStrings::default());
+    let mut composite = usb::UsbComposite::new(
+        driver,
+        storage,
+        UsbDeviceStrings::default(),
+        request_bridge_break,
+    );
 
     let usb::CdcAcmHandle {
         sender: repl_sender,
@@ -136,6 +147,10 @@ where
                 }
             }
         }
+
+        // A host that closes the port mid `console attach` should come back
+        // to the REPL, not a console it cannot see the start of.
+        status::set_console_attached(false);
     }
 }
 
@@ -152,19 +167,42 @@ where
     let mut ingress = [0u8; usb::MAX_PACKET_SIZE as usize];
     let mut tx_packet = [0u8; usb::MAX_PACKET_SIZE as usize];
     let mut pending_tx: Option<BridgeFrame> = None;
+    // Start from the class default so the UART keeps its boot framing until
+    // the host actually sends SET_LINE_CODING.
+    let mut host_coding = cdc_line_coding(&sender);
 
     loop {
         join(receiver.wait_connection(), sender.wait_connection()).await;
         wait_for_dtr(&control, &mut sender).await;
 
         defmt::info!("usb: bridge interface connected");
+        forward_line_coding(&sender, &mut host_coding);
+
+        // Frames queued while nobody was listening are already in the
+        // scrollback; drop them and replay the scrollback instead. A frame
+        // the bridge task was still waiting to queue lands after the drain,
+        // so frames ending inside the replayed window are skipped as well.
+        status::set_bridge_host_attached(true);
+        pending_tx = None;
+        while ttl_to_usb.try_receive().is_ok() {}
+        let window = console::replay_window();
+        let replayed_end = window.end;
+        if replay_scrollback(&mut sender, &mut tx_packet, window)
+            .await
+            .is_err()
+        {
+            defmt::warn!("usb: bridge replay aborted");
+        }
 
         loop {
             match select3(
                 receiver.read_packet(&mut ingress),
                 async {
-                    if pending_tx.is_none() {
-                        pending_tx = Some(ttl_to_usb.receive().await);
+                    while pending_tx.is_none() {
+                        let frame = ttl_to_usb.receive().await;
+                        if !replayed(&frame, replayed_end) {
+                            pending_tx = Some(frame.bytes);
+                        }
                     }
 
                     let frame = pending_tx
@@ -193,10 +231,16 @@ where
                     let mut frame = BridgeFrame::new();
                     if frame.extend_from_slice(&ingress[..count]).is_err() {
                         defmt::warn!("usb: dropping bridge frame len={} (overflow)", count);
+                        stats::record_fault(BridgeFault::Dropped(BridgeActivityKind::UsbToJetson));
                         continue;
                     }
 
-                    usb_to_ttl.send(frame).await;
+                    if let Err(TrySendError::Full(frame)) = usb_to_ttl.try_send(frame) {
+                        stats::record_fault(BridgeFault::QueueFull(
+                            BridgeActivityKind::UsbToJetson,
+                        ));
+                        usb_to_ttl.send(frame).await;
+                    }
                 }
                 Either3::First(Err(EndpointError::Disabled)) => {
                     defmt::warn!("usb: bridge interface disabled");
@@ -212,18 +256,104 @@ where
                 }
                 Either3::Second(Err(_)) => {
                     defmt::warn!("usb: bridge write error");
+                    stats::record_fault(BridgeFault::UsbWrite);
                 }
                 Either3::Third(()) => {
                     if !sender.dtr() {
                         defmt::warn!("usb: bridge host dropped DTR");
                         break;
                     }
+                    forward_line_coding(&sender, &mut host_coding);
                 }
             }
         }
+
+        status::set_bridge_host_attached(false);
     }
 }
 
+/// Forwards a host `SEND_BREAK` on CDC1 to the bridge task.
+fn request_bridge_break(value: u16) {
+    BRIDGE_QUEUE.request_break(BreakRequest {
+        line_break: LineBreak::from_cdc(value),
+        follow_up: None,
+    });
+}
+
+/// Raw CDC line coding fields: rate, `bCharFormat`, `bParityType`, `bDataBits`.
+type CdcLineCoding = (u32, u8, u8, u8);
+
+fn cdc_line_coding<D>(sender: &embassy_usb::class::cdc_acm::Sender<'static, D>) -> CdcLineCoding
+where
+    D: embassy_usb::driver::Driver<'static>,
+{
+    let coding = sender.line_coding();
+    (
+        coding.data_rate(),
+        coding.stop_bits() as u8,
+        coding.parity_type() as u8,
+        coding.data_bits(),
+    )
+}
+
+/// Passes a changed host line coding on to the bridge task.
+fn forward_line_coding<D>(
+    sender: &embassy_usb::class::cdc_acm::Sender<'static, D>,
+    last: &mut CdcLineCoding,
+) where
+    D: embassy_usb::driver::Driver<'static>,
+{
+    let current = cdc_line_coding(sender);
+    if current == *last {
+        return;
+    }
+    *last = current;
+
+    let (baud, stop_bits, parity, data_bits) = current;
+    match LineCoding::from_cdc(baud, stop_bits, parity, data_bits) {
+        Ok(coding) => BRIDGE_QUEUE.request_line_coding(coding),
+        Err(_) => defmt::warn!(
+            "usb: ignoring bridge line coding {=u32} baud {=u8} data bits",
+            baud,
+            data_bits
+        ),
+    }
+}
+
+/// Writes retained console output to a newly opened bridge interface.
+async fn replay_scrollback<D>(
+    sender: &mut embassy_usb::class::cdc_acm::Sender<'static, D>,
+    packet: &mut [u8],
+    window: core::ops::Range<u32>,
+) -> Result<(), EndpointError>
+where
+    D: embassy_usb::driver::Driver<'static>,
+{
+    let mut offset = window.start;
+    while offset != window.end {
+        let Some(count) = console::read_replay(offset, window.end, packet) else {
+            defmt::warn!("usb: bridge replay overtaken by new console output");
+            return Ok(());
+        };
+        if count == 0 {
+            break;
+        }
+        sender.write_packet(&packet[..count]).await?;
+        let Ok(count) = u32::try_from(count) else {
+            break;
+        };
+        offset = offset.wrapping_add(count);
+    }
+    Ok(())
+}
+
+/// Returns `true` when `frame` ends at or before `replayed_end`, meaning the
+/// scrollback replay already sent its bytes.
+fn replayed(frame: &ConsoleFrame, replayed_end: u32) -> bool {
+    // Offsets wrap, so compare by distance rather than magnitude.
+    replayed_end.wrapping_sub(frame.replay_end) <= u32::MAX / 2
+}
+
 async fn wait_for_dtr<D>(
     control: &embassy_usb::class::cdc_acm::ControlChanged<'static>,
     sender: &mut embassy_usb::class::cdc_acm::Sender<'static, D>,
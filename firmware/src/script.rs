@@ -0,0 +1,172 @@
+#![cfg_attr(not(target_os = "none"), allow(dead_code))]
+
+//! Shared console script engine for the firmware target.
+//!
+//! The REPL task loads, starts, and polls scripts and carries out the sends
+//! and strap sequences they request. The bridge task feeds the engine the same
+//! Jetson output it forwards to CDC1, so expect steps see every byte. Reports
+//! are queued for the strap task, which owns the telemetry ring.
+
+use controller_core::script::{
+    LoadError, LoadProgress, MAX_SCRIPTS, ScriptError, ScriptEvent, ScriptName, ScriptReport,
+    ScriptSummary,
+};
+use heapless::Vec;
+
+use crate::straps::FirmwareInstant;
+
+#[cfg(target_os = "none")]
+use controller_core::script::ScriptEngine;
+#[cfg(target_os = "none")]
+use core::cell::RefCell;
+#[cfg(target_os = "none")]
+use embassy_sync::blocking_mutex::{Mutex, raw::ThreadModeRawMutex};
+#[cfg(target_os = "none")]
+use embassy_sync::channel::Channel;
+
+/// Interval between script polls while a script is running.
+pub const SCRIPT_POLL_INTERVAL: embassy_time::Duration = embassy_time::Duration::from_millis(100);
+/// Depth of the queue carrying script reports to the strap task.
+pub const SCRIPT_REPORT_QUEUE_DEPTH: usize = 4;
+
+/// Script report waiting to be recorded as telemetry.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScriptReportHit {
+    pub report: ScriptReport,
+    pub timestamp: FirmwareInstant,
+}
+
+#[cfg(target_os = "none")]
+static SCRIPTS: Mutex<ThreadModeRawMutex, RefCell<ScriptEngine<FirmwareInstant>>> =
+    Mutex::new(RefCell::new(ScriptEngine::new()));
+
+#[cfg(target_os = "none")]
+static SCRIPT_REPORTS: Channel<ThreadModeRawMutex, ScriptReportHit, SCRIPT_REPORT_QUEUE_DEPTH> =
+    Channel::new();
+
+/// Passes Jetson console output to the running script's pending expect.
+#[cfg(target_os = "none")]
+pub fn feed_console(bytes: &[u8]) {
+    SCRIPTS.lock(|engine| {
+        engine.borrow_mut().feed(bytes);
+    });
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn feed_console(_: &[u8]) {}
+
+/// Starts collecting REPL lines for the named script.
+#[cfg(target_os = "none")]
+pub fn begin_load(name: ScriptName) -> Result<(), ScriptError> {
+    SCRIPTS.lock(|engine| engine.borrow_mut().begin_load(name))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn begin_load(_: ScriptName) -> Result<(), ScriptError> {
+    Err(ScriptError::LibraryFull)
+}
+
+/// Returns `true` while REPL lines belong to a script being loaded.
+#[cfg(target_os = "none")]
+pub fn is_loading() -> bool {
+    SCRIPTS.lock(|engine| engine.borrow().is_loading())
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn is_loading() -> bool {
+    false
+}
+
+/// Adds one line to the script being loaded.
+#[cfg(target_os = "none")]
+pub fn load_line(line: &str) -> Result<LoadProgress, LoadError> {
+    SCRIPTS.lock(|engine| engine.borrow_mut().load_line(line))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn load_line(_: &str) -> Result<LoadProgress, LoadError> {
+    Err(LoadError {
+        line: 0,
+        error: ScriptError::NotLoading,
+    })
+}
+
+/// Copies the library listing out of the engine.
+#[cfg(target_os = "none")]
+pub fn summaries() -> Vec<ScriptSummary, MAX_SCRIPTS> {
+    SCRIPTS.lock(|engine| engine.borrow().summaries().collect())
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn summaries() -> Vec<ScriptSummary, MAX_SCRIPTS> {
+    Vec::new()
+}
+
+/// Returns `true` while a script is running.
+#[cfg(target_os = "none")]
+pub fn is_running() -> bool {
+    SCRIPTS.lock(|engine| engine.borrow().is_running())
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn is_running() -> bool {
+    false
+}
+
+/// Starts the named script.
+#[cfg(target_os = "none")]
+pub fn start(name: &str, now: FirmwareInstant) -> Result<(), ScriptError> {
+    SCRIPTS.lock(|engine| engine.borrow_mut().start(name, now))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn start(_: &str, _: FirmwareInstant) -> Result<(), ScriptError> {
+    Err(ScriptError::NotFound)
+}
+
+/// Stops the running script and returns its final report.
+#[cfg(target_os = "none")]
+pub fn stop(now: FirmwareInstant) -> Option<ScriptReport> {
+    SCRIPTS.lock(|engine| engine.borrow_mut().stop(now))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn stop(_: FirmwareInstant) -> Option<ScriptReport> {
+    None
+}
+
+/// Advances the running script by one event.
+#[cfg(target_os = "none")]
+pub fn poll(now: FirmwareInstant) -> Option<ScriptEvent> {
+    SCRIPTS.lock(|engine| engine.borrow_mut().poll(now))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn poll(_: FirmwareInstant) -> Option<ScriptEvent> {
+    None
+}
+
+/// Queues a report for the strap task to record as telemetry.
+#[cfg(target_os = "none")]
+pub fn queue_report(report: ScriptReport, timestamp: FirmwareInstant) {
+    if SCRIPT_REPORTS
+        .try_send(ScriptReportHit { report, timestamp })
+        .is_err()
+    {
+        defmt::warn!("script: dropping report (queue full)");
+    }
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn queue_report(_: ScriptReport, _: FirmwareInstant) {}
+
+/// Pops the oldest queued script report, if any.
+#[cfg(target_os = "none")]
+pub fn take_report() -> Option<ScriptReportHit> {
+    SCRIPT_REPORTS.try_receive().ok()
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn take_report() -> Option<ScriptReportHit> {
+    None
+}
@@ -8,6 +8,8 @@
 
 use core::{convert::TryFrom, time::Duration};
 
+use controller_core::bridge::autobaud::BaudSource;
+use controller_core::bridge::line_coding::{LineCoding, Parity, StopBits};
 use controller_core::repl::status::{
     DebugLinkState, StatusAccumulator, StatusInstant, StatusSnapshot,
 };
@@ -17,6 +19,7 @@ use portable_atomic::{AtomicBool, AtomicU8, AtomicU32, Ordering};
 #[cfg(target_os = "none")]
 use cortex_m::peripheral::DCB;
 
+use crate::bridge::{DEVICE_RELEASE_CRITERION, stats as bridge_stats};
 use crate::straps::FirmwareInstant;
 
 const UNKNOWN_VDD: u32 = 0;
@@ -43,6 +46,18 @@ static BRIDGE_RX_MICROS: AtomicU32 = AtomicU32::new(0);
 static BRIDGE_TX_MICROS: AtomicU32 = AtomicU32::new(0);
 /// Tracks whether the USB control link is attached.
 static CONTROL_LINK_ATTACHED: AtomicBool = AtomicBool::new(true);
+/// Baud rate currently applied to the Jetson UART.
+static BRIDGE_BAUD: AtomicU32 = AtomicU32::new(LineCoding::DEFAULT.baud);
+/// Data bits, parity, and stop bits applied to the Jetson UART.
+static BRIDGE_FRAMING: AtomicU8 = AtomicU8::new(encode_framing(LineCoding::DEFAULT));
+/// How the Jetson UART baud rate was chosen (see [`encode_baud_source`]).
+static BRIDGE_BAUD_SOURCE: AtomicU8 = AtomicU8::new(encode_baud_source(BaudSource::Host));
+/// Tracks whether a host has CDC1 open with DTR asserted.
+static BRIDGE_HOST_ATTACHED: AtomicBool = AtomicBool::new(false);
+/// Tracks whether CDC0 is handed over to the Jetson console (`console attach`).
+static CONSOLE_ATTACHED: AtomicBool = AtomicBool::new(false);
+/// Tracks whether the orchestrator has a strap sequence running or queued.
+static SEQUENCER_BUSY: AtomicBool = AtomicBool::new(false);
 
 fn bit_for(id: StrapId) -> u8 {
     1 << id.as_index()
@@ -70,6 +85,45 @@ fn micros_from_instant(instant: FirmwareInstant) -> MonotonicMicros {
     }
 }
 
+const fn encode_framing(coding: LineCoding) -> u8 {
+    let parity = match coding.parity {
+        Parity::None => 0,
+        Parity::Odd => 1,
+        Parity::Even => 2,
+    };
+    let stop_bits = match coding.stop_bits {
+        StopBits::One => 0,
+        StopBits::OnePointFive => 1,
+        StopBits::Two => 2,
+    };
+    (coding.data_bits << 4) | (parity << 2) | stop_bits
+}
+
+const fn encode_baud_source(source: BaudSource) -> u8 {
+    match source {
+        BaudSource::Host => 0,
+        BaudSource::Manual => 1,
+        BaudSource::Detecting => 2,
+        BaudSource::Detected => 3,
+        BaudSource::Undetected => 4,
+    }
+}
+
+fn decode_baud_source(raw: u8) -> BaudSource {
+    match raw {
+        1 => BaudSource::Manual,
+        2 => BaudSource::Detecting,
+        3 => BaudSource::Detected,
+        4 => BaudSource::Undetected,
+        _ => BaudSource::Host,
+    }
+}
+
+fn decode_framing(baud: u32, raw: u8) -> LineCoding {
+    LineCoding::from_cdc(baud, raw & 0b11, (raw >> 2) & 0b11, raw >> 4)
+        .unwrap_or(LineCoding::DEFAULT)
+}
+
 fn control_link_attached() -> bool {
     CONTROL_LINK_ATTACHED.load(Ordering::Relaxed)
 }
@@ -125,6 +179,60 @@ pub fn set_control_link_attached(attached: bool) {
     CONTROL_LINK_ATTACHED.store(attached, Ordering::Relaxed);
 }
 
+/// Records the UART framing applied to the Jetson console.
+pub fn record_bridge_line_coding(coding: LineCoding) {
+    BRIDGE_BAUD.store(coding.baud, Ordering::Relaxed);
+    BRIDGE_FRAMING.store(encode_framing(coding), Ordering::Relaxed);
+}
+
+/// Returns the UART framing applied to the Jetson console.
+pub fn bridge_line_coding() -> LineCoding {
+    decode_framing(
+        BRIDGE_BAUD.load(Ordering::Relaxed),
+        BRIDGE_FRAMING.load(Ordering::Relaxed),
+    )
+}
+
+/// Records how the Jetson UART baud rate was chosen.
+pub fn record_baud_source(source: BaudSource) {
+    BRIDGE_BAUD_SOURCE.store(encode_baud_source(source), Ordering::Relaxed);
+}
+
+/// Returns how the Jetson UART baud rate was chosen.
+pub fn baud_source() -> BaudSource {
+    decode_baud_source(BRIDGE_BAUD_SOURCE.load(Ordering::Relaxed))
+}
+
+/// Marks whether a host is reading the bridge interface.
+pub fn set_bridge_host_attached(attached: bool) {
+    BRIDGE_HOST_ATTACHED.store(attached, Ordering::Relaxed);
+}
+
+/// Returns `true` while a host is reading the bridge interface.
+pub fn bridge_host_attached() -> bool {
+    BRIDGE_HOST_ATTACHED.load(Ordering::Relaxed)
+}
+
+/// Marks whether the REPL port is attached to the Jetson console.
+pub fn set_console_attached(attached: bool) {
+    CONSOLE_ATTACHED.store(attached, Ordering::Relaxed);
+}
+
+/// Returns `true` while Jetson output should also be copied to the REPL port.
+pub fn console_attached() -> bool {
+    CONSOLE_ATTACHED.load(Ordering::Relaxed)
+}
+
+/// Records whether the orchestrator has a strap sequence running or queued.
+pub fn record_sequencer_busy(busy: bool) {
+    SEQUENCER_BUSY.store(busy, Ordering::Relaxed);
+}
+
+/// Returns `true` while a strap sequence is running or waiting to start.
+pub fn sequencer_busy() -> bool {
+    SEQUENCER_BUSY.load(Ordering::Relaxed)
+}
+
 /// Builds a [`StatusSnapshot`] using the stored metrics.
 pub fn snapshot(now: FirmwareInstant) -> StatusSnapshot {
     let mut accumulator = StatusAccumulator::<MonotonicMicros>::new();
@@ -142,9 +250,14 @@ pub fn snapshot(now: FirmwareInstant) -> StatusSnapshot {
         if let Some(rx) = decode_micros(BRIDGE_RX_MICROS.load(Ordering::Relaxed)) {
             bridge.record_rx(rx);
         }
+        bridge.set_line_coding(bridge_line_coding());
+        bridge.set_baud_source(baud_source());
+        bridge.set_release_criterion(DEVICE_RELEASE_CRITERION);
     }
 
-    accumulator.snapshot(micros_from_instant(now))
+    let mut snapshot = accumulator.snapshot(micros_from_instant(now));
+    snapshot.bridge = snapshot.bridge.with_stats(bridge_stats::snapshot());
+    snapshot
 }
 
 #[cfg(target_os = "none")]
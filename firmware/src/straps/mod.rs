@@ -13,7 +13,7 @@ use embassy_sync::blocking_mutex::raw::ThreadModeRawMutex;
 use embassy_sync::channel::{Channel, Receiver, Sender, TrySendError};
 use embassy_time::{Duration as EmbassyDuration, Instant as EmbassyInstant};
 
-pub use core_orch::{EventId, SequenceError, SequenceOutcome, SequenceState};
+pub use core_orch::{EventId, SequenceError, SequenceOutcome, SequenceResult, SequenceState};
 pub use core_seq::{
     ALL_STRAPS, SequenceTemplate, StepCompletion, StrapAction, StrapId, StrapLine,
     StrapSequenceKind, StrapStep, strap_by_id,
@@ -23,6 +23,9 @@ use core::ops::Add;
 
 /// Depth of the command queue shared between producers and the orchestrator.
 pub const COMMAND_QUEUE_DEPTH: usize = 4;
+/// Depth of the queue carrying tagged completion events to the REPL.
+#[cfg_attr(not(target_os = "none"), allow(dead_code))]
+pub const COMPLETION_QUEUE_DEPTH: usize = COMMAND_QUEUE_DEPTH;
 
 #[cfg(target_os = "none")]
 type StrapMutex = ThreadModeRawMutex;
@@ -83,6 +86,29 @@ impl TelemetryInstant for FirmwareInstant {
     }
 }
 
+#[cfg(target_os = "none")]
+static COMPLETIONS: Channel<StrapMutex, core_orch::SequenceCompletion, COMPLETION_QUEUE_DEPTH> =
+    Channel::new();
+
+/// Queues the end of `command` for the REPL, which prints `DONE@<tag>` for
+/// tagged requests and hands every completion to a pending `wait complete`.
+#[cfg(target_os = "none")]
+pub fn report_completion(command: &SequenceCommand, result: SequenceResult, now: FirmwareInstant) {
+    let completion = core_orch::SequenceCompletion::for_command(command, result, now);
+    if COMPLETIONS.try_send(completion).is_err() {
+        defmt::warn!("straps: dropping completion event (queue full)");
+    }
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn report_completion(_: &SequenceCommand, _: SequenceResult, _: FirmwareInstant) {}
+
+/// Pops the oldest completion event for the REPL, if any.
+#[cfg(target_os = "none")]
+pub fn take_completion() -> Option<core_orch::SequenceCompletion> {
+    COMPLETIONS.try_receive().ok()
+}
+
 /// Runtime state for an executing strap sequence.
 pub type SequenceRun = core_orch::SequenceRun<FirmwareInstant>;
 
@@ -132,4 +158,8 @@ impl core_orch::CommandQueueProducer for CommandProducer<'_> {
     fn capacity(&self) -> Option<usize> {
         Some(COMMAND_QUEUE_DEPTH)
     }
+
+    fn len(&self) -> Option<usize> {
+        Some(self.sender.len())
+    }
 }
@@ -9,9 +9,15 @@
 
 use core::convert::TryFrom;
 
-use crate::bridge::BridgeDisconnectNotice;
+use crate::autoboot;
+use crate::bridge::{BridgeDisconnectNotice, console};
+use crate::power;
+use crate::script;
 use crate::status;
-use crate::telemetry::{TelemetryPayload, TelemetryRecorder};
+use crate::supervisor;
+use crate::telemetry::{
+    FaultRecoveryReason, FaultRecoveryTelemetry, TelemetryPayload, TelemetryRecorder,
+};
 use controller_core::orchestrator::{
     self as core_orchestrator, NoopStrapDriver, StrapDriver, retry_budget_for,
 };
@@ -19,6 +25,7 @@ pub use controller_core::orchestrator::{
     ActiveRunError, CommandRejection, CommandRejectionReason, OrchestratorState, TemplateRegistry,
 };
 use controller_core::telemetry::TelemetryInstant;
+use embassy_futures::select::{Either, select};
 use embassy_time::{Duration, Instant, Timer};
 use heapless::Deque;
 
@@ -27,8 +34,9 @@ use embassy_stm32::gpio::OutputOpenDrain;
 
 use super::{
     COMMAND_QUEUE_DEPTH, CommandReceiver, EventId, FirmwareInstant, SequenceCommand, SequenceError,
-    SequenceOutcome, SequenceRun, SequenceState, SequenceTemplate, StepCompletion, StrapAction,
-    StrapId, StrapLine, StrapStep, TelemetryEventKind, strap_by_id,
+    SequenceOutcome, SequenceResult, SequenceRun, SequenceState, SequenceTemplate, StepCompletion,
+    StrapAction, StrapId, StrapLine, StrapSequenceKind, StrapStep, TelemetryEventKind,
+    report_completion, strap_by_id,
 };
 
 pub type PowerSample = core_orchestrator::PowerSample<FirmwareInstant>;
@@ -381,6 +389,37 @@ fn remaining_delay(queued: &QueuedCommand) -> Option<Duration> {
 }
 
 const MIN_PROCESS_SLEEP_MS: u64 = 1;
+/// Span of each peak-to-peak window reported by `power stats`.
+const RAIL_STATS_WINDOW: core::time::Duration = core::time::Duration::from_secs(1);
+
+fn record_power_sample(millivolts: Option<u16>) {
+    status::record_vdd_sample(millivolts);
+    power::record_rail_sample(millivolts);
+}
+
+/// Moves console marker hits, panic captures, script reports, and autoboot
+/// interrupt results queued by other tasks into telemetry.
+fn drain_console_markers(telemetry: &mut TelemetryRecorder) {
+    while let Some(hit) = console::take_marker_hit() {
+        telemetry.record_console_marker(hit.marker, hit.pattern, hit.line, hit.timestamp);
+    }
+    while let Some(panic) = console::take_panic_capture() {
+        telemetry.record_kernel_panic(panic.excerpt, panic.timestamp);
+    }
+    while let Some(hit) = script::take_report() {
+        telemetry.record_script(&hit.report, hit.timestamp);
+    }
+    while let Some(hit) = autoboot::take_report() {
+        telemetry.record_bootloader_interrupt(hit.report, hit.timestamp);
+    }
+}
+
+/// Closes any open brown-out episode and records it as telemetry.
+fn note_power_stable(sample: &PowerSample, telemetry: &mut TelemetryRecorder) {
+    if let Some(episode) = power::close_brown_out(sample.timestamp) {
+        telemetry.record_brown_out(&episode, sample.timestamp);
+    }
+}
 
 #[cfg(target_os = "none")]
 fn log_brown_out_detected(sample: &PowerSample, retries_used: u8, retry_budget: u8) {
@@ -511,6 +550,7 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
         mut strap_driver: D,
     ) -> Self {
         strap_driver.release_all();
+        power::configure_rail_window(RAIL_STATS_WINDOW, power_monitor.sample_interval());
         status::set_control_link_attached(true);
         status::record_bridge_waiting(false);
         Self {
@@ -646,7 +686,7 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
         }
 
         while let Some(queued) = self.pending_commands.pop_front() {
-            self.last_rejection = Some(CommandRejection::control_link_lost(queued.command));
+            self.drop_command(CommandRejection::control_link_lost(queued.command));
         }
 
         if had_active_run {
@@ -674,11 +714,21 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
         self.active_run = Some(SequenceRun::new(command));
         self.recovering_power = false;
         self.last_power_sample = None;
+        power::begin_sequence_capture(command.kind);
         Ok(())
     }
 
     /// Finishes the active run and returns to idle.
     pub fn finish_run(&mut self) {
+        if let Some(run) = self.active_run.as_ref() {
+            power::finish_sequence_capture();
+            let result = match run.state {
+                SequenceState::Complete(outcome) => SequenceResult::Finished(outcome),
+                SequenceState::Error(error) => SequenceResult::Failed(error),
+                _ => SequenceResult::Failed(SequenceError::UnexpectedState),
+            };
+            report_completion(&run.command, result, Instant::now().into());
+        }
         self.active_run = None;
         self.recovering_power = false;
         self.last_power_sample = None;
@@ -691,19 +741,41 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
         outcome: SequenceOutcome,
         timestamp: FirmwareInstant,
     ) -> Result<(), ActiveRunError> {
-        let (kind, started_at, requested_at, events_recorded) = match self.active_run.as_ref() {
-            Some(run) => (
-                run.command.kind,
-                run.sequence_started_at,
-                run.command.requested_at,
-                run.emitted_events.len(),
-            ),
-            None => return Err(ActiveRunError::NoActiveRun),
-        };
+        let (kind, started_at, requested_at, events_recorded, fault_reason, retries) =
+            match self.active_run.as_ref() {
+                Some(run) => (
+                    run.command.kind,
+                    run.sequence_started_at,
+                    run.command.requested_at,
+                    run.emitted_events.len(),
+                    run.command.flags.fault_reason,
+                    run.retry_count,
+                ),
+                None => return Err(ActiveRunError::NoActiveRun),
+            };
 
         let start = started_at.or(Some(requested_at));
-        let event_id =
-            telemetry.record_sequence_completion(kind, outcome, start, timestamp, events_recorded);
+        let event_id = if kind == StrapSequenceKind::FaultRecovery {
+            let reason = fault_reason.unwrap_or(FaultRecoveryReason::ManualRequest);
+            telemetry.record_fault_recovery_completion(
+                outcome,
+                start,
+                timestamp,
+                events_recorded,
+                FaultRecoveryTelemetry::new(reason, retries),
+            )
+        } else {
+            telemetry.record_sequence_completion(kind, outcome, start, timestamp, events_recorded)
+        };
+
+        match kind {
+            StrapSequenceKind::NormalReboot | StrapSequenceKind::FaultRecovery => {
+                supervisor::arm(timestamp);
+            }
+            StrapSequenceKind::RecoveryEntry | StrapSequenceKind::RecoveryImmediate => {
+                supervisor::park();
+            }
+        }
 
         if let Some(run) = self.active_run.as_mut() {
             let _ = run.track_event(event_id);
@@ -736,7 +808,16 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
 
     /// Handles the intake of commands and basic lifecycle management.
     pub async fn run(mut self, telemetry: &mut TelemetryRecorder) -> ! {
+        // Treat controller start-up as a power-on so a Jetson that never comes
+        // up is still caught.
+        supervisor::arm(Instant::now().into());
+
         loop {
+            drain_console_markers(telemetry);
+            status::record_sequencer_busy(
+                self.active_run.is_some() || !self.pending_commands.is_empty(),
+            );
+
             if self.active_run.is_some() {
                 self.collect_pending_commands(telemetry);
                 self.process_active_run(telemetry).await;
@@ -753,14 +834,23 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
                     continue;
                 }
 
-                match self.start_queued_command(&queued, telemetry) {
-                    Ok(()) => self.last_rejection = None,
-                    Err(rejection) => self.last_rejection = Some(rejection),
-                }
+                let started = self.start_queued_command(&queued, telemetry);
+                self.note_start(started);
                 continue;
             }
 
-            let command = self.command_rx.receive().await;
+            // Keep sampling the rail while idle so `power stats` covers more than strap runs.
+            let command =
+                match select(self.command_rx.receive(), Timer::after(self.idle_delay())).await {
+                    Either::First(command) => {
+                        status::record_sequencer_busy(true);
+                        command
+                    }
+                    Either::Second(()) => {
+                        self.sample_idle_power(telemetry);
+                        continue;
+                    }
+                };
             let not_before = compute_not_before(&command);
             let queued = QueuedCommand {
                 command,
@@ -772,13 +862,33 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
                 Timer::after(delay).await;
             }
 
-            match self.start_queued_command(&queued, telemetry) {
-                Ok(()) => self.last_rejection = None,
-                Err(rejection) => self.last_rejection = Some(rejection),
+            let started = self.start_queued_command(&queued, telemetry);
+            self.note_start(started);
+        }
+    }
+
+    /// Records the result of starting a queued command. A busy command has
+    /// already been put back in the queue; anything else is dropped.
+    fn note_start(&mut self, started: Result<(), CommandRejection<FirmwareInstant>>) {
+        match started {
+            Ok(()) => self.last_rejection = None,
+            Err(rejection) if rejection.reason() == CommandRejectionReason::Busy => {
+                self.last_rejection = Some(rejection);
             }
+            Err(rejection) => self.drop_command(rejection),
         }
     }
 
+    /// Records a rejection that drops the command and tells a tagged requester.
+    fn drop_command(&mut self, rejection: CommandRejection<FirmwareInstant>) {
+        report_completion(
+            rejection.command(),
+            SequenceResult::Rejected(rejection.reason()),
+            Instant::now().into(),
+        );
+        self.last_rejection = Some(rejection);
+    }
+
     async fn process_active_run(&mut self, telemetry: &mut TelemetryRecorder) {
         if self.state().is_terminal() {
             self.finish_run();
@@ -787,18 +897,20 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
 
         match self.power_monitor.poll() {
             PowerStatus::BrownOut(sample) => {
-                status::record_vdd_sample(sample.millivolts);
+                record_power_sample(sample.millivolts);
+                self.note_brown_out(&sample);
                 self.last_power_sample = Some(sample);
                 if self.handle_brown_out(sample, telemetry).await {
                     return;
                 }
             }
             PowerStatus::Stable(sample) => {
-                status::record_vdd_sample(sample.millivolts);
+                record_power_sample(sample.millivolts);
+                note_power_stable(&sample, telemetry);
                 self.last_power_sample = Some(sample);
             }
             PowerStatus::Unknown => {
-                status::record_vdd_sample(None);
+                record_power_sample(None);
             }
         }
 
@@ -809,6 +921,31 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
         Timer::after(self.idle_delay()).await;
     }
 
+    fn sample_idle_power(&mut self, telemetry: &mut TelemetryRecorder) {
+        match self.power_monitor.poll() {
+            PowerStatus::Stable(sample) => {
+                record_power_sample(sample.millivolts);
+                note_power_stable(&sample, telemetry);
+            }
+            PowerStatus::BrownOut(sample) => {
+                record_power_sample(sample.millivolts);
+                self.note_brown_out(&sample);
+            }
+            PowerStatus::Unknown => record_power_sample(None),
+        }
+    }
+
+    /// Opens (or deepens) a brown-out episode tagged with the run it interrupts.
+    fn note_brown_out(&self, sample: &PowerSample) {
+        let (sequence, step) = self.active_run.as_ref().map_or((None, None), |run| {
+            let step = run
+                .current_step_index
+                .and_then(|index| u8::try_from(index).ok());
+            (Some(run.command.kind), step)
+        });
+        power::record_brown_out(sample.timestamp, sample.millivolts, sequence, step);
+    }
+
     fn drive_active_run(&mut self, telemetry: &mut TelemetryRecorder, now: Instant) {
         loop {
             let kind = match self.active_run.as_ref() {
@@ -898,7 +1035,17 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
                             false
                         }
                     }
-                    StepCompletion::OnBridgeActivity | StepCompletion::OnEvent(_) => false,
+                    // Console activity has not shown up in time; fall back to
+                    // finishing the step so REC is not held forever.
+                    StepCompletion::OnBridgeActivity => {
+                        let expired = self
+                            .active_run
+                            .as_ref()
+                            .and_then(|run| run.step_deadline)
+                            .is_some_and(|deadline| now >= Instant::from(deadline));
+                        expired && self.finish_step(template, telemetry, now)
+                    }
+                    StepCompletion::OnEvent(_) => false,
                 }
             }
             SequenceState::Cooldown => {
@@ -923,8 +1070,12 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
                 run.sequence_started_at = Some(now.into());
             }
             let hold = core_duration_to_embassy(step.hold_duration());
+            let bridge = core_orchestrator::BridgeHoldConfig::for_flags(&run.command.flags);
             run.step_deadline = match step.completion {
                 StepCompletion::AfterDuration => Some((now + hold).into()),
+                StepCompletion::OnBridgeActivity if bridge.has_timeout() => {
+                    Some((now + core_duration_to_embassy(bridge.timeout())).into())
+                }
                 _ => None,
             };
         } else {
@@ -1057,6 +1208,7 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
         };
 
         log_brown_out_detected(&sample, retries_used, retry_budget);
+        power::mark_brown_out_retry(did_retry);
 
         let release_timestamp = FirmwareInstant::from(Instant::now());
         self.release_all_straps(telemetry, release_timestamp);
@@ -1084,7 +1236,8 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
 
             match self.power_monitor.poll() {
                 PowerStatus::Stable(sample) => {
-                    status::record_vdd_sample(sample.millivolts);
+                    record_power_sample(sample.millivolts);
+                    note_power_stable(&sample, telemetry);
                     self.last_power_sample = Some(sample);
                     if first_stable.is_none() {
                         first_stable = Some(sample);
@@ -1106,7 +1259,8 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
                     }
                 }
                 PowerStatus::BrownOut(sample) => {
-                    status::record_vdd_sample(sample.millivolts);
+                    record_power_sample(sample.millivolts);
+                    self.note_brown_out(&sample);
                     self.last_power_sample = Some(sample);
                     first_stable = None;
                 }
@@ -1114,7 +1268,7 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
                     let now = Instant::now();
                     let now_instant = FirmwareInstant::from(now);
                     let sample = PowerSample::new(now_instant, None);
-                    status::record_vdd_sample(sample.millivolts);
+                    record_power_sample(sample.millivolts);
                     self.last_power_sample = Some(sample);
                     if first_stable.is_none() {
                         first_stable = Some(sample);
@@ -1179,12 +1333,12 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
     fn collect_pending_commands(&mut self, telemetry: &mut TelemetryRecorder) {
         while let Ok(command) = self.command_rx.try_receive() {
             if !self.control_link_attached {
-                self.last_rejection = Some(CommandRejection::control_link_lost(command));
+                self.drop_command(CommandRejection::control_link_lost(command));
                 continue;
             }
 
             if self.pending_commands.is_full() {
-                self.last_rejection = Some(CommandRejection::busy(command));
+                self.drop_command(CommandRejection::busy(command));
                 continue;
             }
 
@@ -1221,6 +1375,7 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
 
         match self.begin_run(command) {
             Ok(()) => {
+                console::begin_scrollback_run();
                 if let Some(run) = self.active_run.as_mut() {
                     if let Some(event_id) = pending_event {
                         let _ = run.track_event(event_id);
@@ -1247,7 +1402,7 @@ impl<'a, M: PowerMonitor, D: StrapDriver> StrapOrchestrator<'a, M, D> {
 
                     // If reinsertion fails the queue is full; drop the request after flagging busy.
                     if self.pending_commands.push_front(queued).is_err() {
-                        self.last_rejection = Some(CommandRejection::busy(*rejection.command()));
+                        self.drop_command(CommandRejection::busy(*rejection.command()));
                     }
                 }
 
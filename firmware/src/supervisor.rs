@@ -0,0 +1,109 @@
+#![cfg_attr(not(target_os = "none"), allow(dead_code))]
+
+//! Shared boot watchdog for the firmware target.
+//!
+//! The strap task arms the `BootWatchdog` whenever a reboot or fault recovery
+//! completes (and once at power-on), the bridge task feeds it console traffic
+//! and marker hits, and the REPL task polls it and owns the scheduler that
+//! turns a trip into a `FaultRecovery` request.
+
+use controller_core::bridge::console::ConsoleMarker;
+use controller_core::supervisor::{WatchdogStatus, WatchdogTrip, WatchdogUpdate};
+
+use crate::straps::FirmwareInstant;
+
+#[cfg(target_os = "none")]
+use controller_core::supervisor::{BootWatchdog, WatchdogConfig};
+#[cfg(target_os = "none")]
+use core::cell::RefCell;
+#[cfg(target_os = "none")]
+use embassy_sync::blocking_mutex::{Mutex, raw::ThreadModeRawMutex};
+
+/// Interval between watchdog deadline checks.
+pub const WATCHDOG_POLL_INTERVAL: embassy_time::Duration = embassy_time::Duration::from_secs(1);
+
+#[cfg(target_os = "none")]
+static WATCHDOG: Mutex<ThreadModeRawMutex, RefCell<BootWatchdog<FirmwareInstant>>> =
+    Mutex::new(RefCell::new(BootWatchdog::new(WatchdogConfig::new())));
+
+/// Starts the boot deadline after a reboot, fault recovery, or power-on.
+#[cfg(target_os = "none")]
+pub fn arm(now: FirmwareInstant) {
+    WATCHDOG.lock(|watchdog| watchdog.borrow_mut().arm(now));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn arm(_: FirmwareInstant) {}
+
+/// Stops supervision while the Jetson is intentionally held in recovery mode.
+#[cfg(target_os = "none")]
+pub fn park() {
+    WATCHDOG.lock(|watchdog| watchdog.borrow_mut().park());
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn park() {}
+
+/// Takes back the last trip's recovery after the scheduler rejected it.
+#[cfg(target_os = "none")]
+pub fn cancel_recovery(now: FirmwareInstant) {
+    WATCHDOG.lock(|watchdog| watchdog.borrow_mut().cancel_recovery(now));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn cancel_recovery(_: FirmwareInstant) {}
+
+/// Notes Jetson console traffic.
+#[cfg(target_os = "none")]
+pub fn observe_console(now: FirmwareInstant) {
+    WATCHDOG.lock(|watchdog| watchdog.borrow_mut().observe_console(now));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn observe_console(_: FirmwareInstant) {}
+
+/// Notes a console marker hit.
+#[cfg(target_os = "none")]
+pub fn observe_marker(marker: ConsoleMarker, now: FirmwareInstant) {
+    WATCHDOG.lock(|watchdog| watchdog.borrow_mut().observe_marker(marker, now));
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn observe_marker(_: ConsoleMarker, _: FirmwareInstant) {}
+
+/// Checks the active deadline.
+#[cfg(target_os = "none")]
+pub fn poll(now: FirmwareInstant) -> Option<WatchdogTrip> {
+    WATCHDOG.lock(|watchdog| watchdog.borrow_mut().poll(now))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn poll(_: FirmwareInstant) -> Option<WatchdogTrip> {
+    None
+}
+
+/// Returns the watchdog state with remaining time measured from `now`.
+#[cfg(target_os = "none")]
+pub fn status(now: FirmwareInstant) -> Option<WatchdogStatus> {
+    Some(WATCHDOG.lock(|watchdog| watchdog.borrow().status(now)))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn status(_: FirmwareInstant) -> Option<WatchdogStatus> {
+    None
+}
+
+/// Applies a REPL configuration change.
+#[cfg(target_os = "none")]
+pub fn update(update: WatchdogUpdate, now: FirmwareInstant) -> Option<WatchdogStatus> {
+    Some(WATCHDOG.lock(|watchdog| {
+        let mut watchdog = watchdog.borrow_mut();
+        watchdog.apply(update, now);
+        watchdog.status(now)
+    }))
+}
+
+#[cfg(not(target_os = "none"))]
+pub fn update(_: WatchdogUpdate, _: FirmwareInstant) -> Option<WatchdogStatus> {
+    None
+}
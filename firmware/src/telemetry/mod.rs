@@ -2,7 +2,8 @@
 //!
 //! The underlying ring buffer implementation lives in `controller-core`. This
 //! module wraps the shared recorder so firmware can continue emitting defmt /
-//! host console diagnostics whenever telemetry is recorded.
+//! host console diagnostics whenever telemetry is recorded, and forwards
+//! records in the categories selected with `events` to the REPL task.
 
 #![allow(dead_code)]
 
@@ -12,12 +13,64 @@ use crate::straps::{
     EventId, FirmwareInstant, SequenceOutcome, StrapAction, StrapId, StrapSequenceKind,
     TelemetryEventKind,
 };
-pub use controller_core::telemetry::{CommandTelemetry, SequenceTelemetry, TelemetryPayload};
+use controller_core::bridge::autoboot::AutobootReport;
+use controller_core::bridge::console::{ConsoleMarker, PanicExcerpt, PatternId};
+use controller_core::power::BrownOutEpisode;
+use controller_core::script::ScriptReport;
+pub use controller_core::telemetry::{
+    BrownOutTelemetry, CommandTelemetry, FaultRecoveryReason, FaultRecoveryTelemetry,
+    SequenceTelemetry, TelemetryPayload,
+};
 use controller_core::telemetry::{
-    TelemetryRecord as CoreTelemetryRecord, TelemetryRecorder as CoreTelemetryRecorder,
-    TelemetryRing as CoreTelemetryRing,
+    EventFilter, TelemetryNotice, TelemetryRecord as CoreTelemetryRecord,
+    TelemetryRecorder as CoreTelemetryRecorder, TelemetryRing as CoreTelemetryRing,
 };
 use heapless::OldestOrdered;
+use portable_atomic::{AtomicBool, AtomicU8, Ordering};
+
+#[cfg(target_os = "none")]
+use embassy_sync::{blocking_mutex::raw::ThreadModeRawMutex, channel::Channel};
+
+/// Depth of the queue carrying subscribed events to the REPL task.
+pub const EVENT_QUEUE_DEPTH: usize = 16;
+
+/// How often the REPL drains the event queue while `events` is on.
+pub const EVENT_POLL_INTERVAL: embassy_time::Duration = embassy_time::Duration::from_millis(50);
+
+/// Categories selected with `events`, as [`EventFilter::bits`].
+static EVENT_FILTER: AtomicU8 = AtomicU8::new(EventFilter::NONE.bits());
+/// Set while a `wait event` needs every event, whatever the filter.
+static EVENT_WAIT: AtomicBool = AtomicBool::new(false);
+
+#[cfg(target_os = "none")]
+static EVENTS: Channel<ThreadModeRawMutex, TelemetryNotice, EVENT_QUEUE_DEPTH> = Channel::new();
+
+/// Selects the categories forwarded to the REPL. Recording itself is unaffected.
+pub fn set_event_filter(filter: EventFilter) {
+    EVENT_FILTER.store(filter.bits(), Ordering::Relaxed);
+}
+
+/// Forwards every event while a `wait event` is pending, so the REPL can
+/// match it even when its category is not selected.
+pub fn set_event_wait(waiting: bool) {
+    EVENT_WAIT.store(waiting, Ordering::Relaxed);
+}
+
+/// Pops the oldest forwarded event, if any.
+#[cfg(target_os = "none")]
+pub fn take_event() -> Option<TelemetryNotice> {
+    EVENTS.try_receive().ok()
+}
+
+#[cfg(target_os = "none")]
+fn forward(notice: TelemetryNotice) {
+    if EVENTS.try_send(notice).is_err() {
+        defmt::warn!("telemetry: dropping REPL event (queue full)");
+    }
+}
+
+#[cfg(not(target_os = "none"))]
+fn forward(_: TelemetryNotice) {}
 
 /// Telemetry ring buffer type alias specialized for firmware timestamps.
 pub type TelemetryRing = CoreTelemetryRing<FirmwareInstant>;
@@ -29,6 +82,8 @@ pub type TelemetryRecord = CoreTelemetryRecord<FirmwareInstant>;
 /// with logging hooks.
 pub struct TelemetryRecorder {
     inner: CoreTelemetryRecorder<FirmwareInstant>,
+    /// Newest record already offered to the REPL.
+    published: Option<EventId>,
 }
 
 impl TelemetryRecorder {
@@ -36,6 +91,7 @@ impl TelemetryRecorder {
     pub fn new() -> Self {
         Self {
             inner: CoreTelemetryRecorder::new(),
+            published: None,
         }
     }
 
@@ -74,6 +130,7 @@ impl TelemetryRecorder {
             );
         }
 
+        self.publish();
         id
     }
 
@@ -95,6 +152,7 @@ impl TelemetryRecorder {
             log_command_event(CommandStage::Pending, kind, record.timestamp, details);
         }
 
+        self.publish();
         id
     }
 
@@ -116,6 +174,7 @@ impl TelemetryRecorder {
             log_command_event(CommandStage::Started, kind, record.timestamp, details);
         }
 
+        self.publish();
         id
     }
 
@@ -142,6 +201,108 @@ impl TelemetryRecorder {
             log_sequence_completion(kind, outcome, record.timestamp, details);
         }
 
+        self.publish();
+        id
+    }
+
+    /// Records the completion of a fault recovery run along with its trigger.
+    pub fn record_fault_recovery_completion(
+        &mut self,
+        outcome: SequenceOutcome,
+        started_at: Option<FirmwareInstant>,
+        timestamp: FirmwareInstant,
+        events_recorded: usize,
+        fault: FaultRecoveryTelemetry,
+    ) -> EventId {
+        let id = self.inner.record_fault_recovery_completion(
+            outcome,
+            started_at,
+            timestamp,
+            events_recorded,
+            fault,
+        );
+
+        if let Some(record) = self.inner.latest().copied()
+            && let TelemetryPayload::Sequence(details) = record.details
+        {
+            log_sequence_completion(
+                StrapSequenceKind::FaultRecovery,
+                outcome,
+                record.timestamp,
+                details,
+            );
+            if let Some(fault) = details.fault {
+                log_fault_recovery(fault);
+            }
+        }
+
+        self.publish();
+        id
+    }
+
+    /// Records a brown-out episode once the rail has recovered.
+    pub fn record_brown_out(
+        &mut self,
+        episode: &BrownOutEpisode<FirmwareInstant>,
+        timestamp: FirmwareInstant,
+    ) -> EventId {
+        let id = self.inner.record_brown_out(episode, timestamp);
+
+        if let Some(record) = self.inner.latest().copied()
+            && let TelemetryPayload::BrownOut(details) = record.details
+        {
+            log_brown_out(record.timestamp, details);
+        }
+
+        self.publish();
+        id
+    }
+
+    /// Records a console marker matched on the Jetson bridge stream.
+    pub fn record_console_marker(
+        &mut self,
+        marker: ConsoleMarker,
+        pattern: PatternId,
+        line: u32,
+        timestamp: FirmwareInstant,
+    ) -> EventId {
+        let id = self
+            .inner
+            .record_console_marker(marker, pattern, line, timestamp);
+        log_console_marker(marker, pattern, line, timestamp);
+        self.publish();
+        id
+    }
+
+    /// Records a kernel panic with the console line that announced it.
+    pub fn record_kernel_panic(
+        &mut self,
+        excerpt: PanicExcerpt,
+        timestamp: FirmwareInstant,
+    ) -> EventId {
+        let id = self.inner.record_kernel_panic(excerpt, timestamp);
+        emit_kernel_panic_log(&excerpt, timestamp.into_embassy().as_micros());
+        self.publish();
+        id
+    }
+
+    /// Records a script expect result or finished run.
+    pub fn record_script(&mut self, report: &ScriptReport, timestamp: FirmwareInstant) -> EventId {
+        let id = self.inner.record_script(report, timestamp);
+        emit_script_log(report, timestamp.into_embassy().as_micros());
+        self.publish();
+        id
+    }
+
+    /// Records how a `reboot to-bootloader` run ended.
+    pub fn record_bootloader_interrupt(
+        &mut self,
+        report: AutobootReport,
+        timestamp: FirmwareInstant,
+    ) -> EventId {
+        let id = self.inner.record_bootloader_interrupt(report, timestamp);
+        emit_bootloader_interrupt_log(&report, timestamp.into_embassy().as_micros());
+        self.publish();
         id
     }
 
@@ -152,13 +313,34 @@ impl TelemetryRecorder {
         payload: TelemetryPayload,
         timestamp: FirmwareInstant,
     ) -> EventId {
-        self.inner.record(event, payload, timestamp)
+        let id = self.inner.record(event, payload, timestamp);
+        self.publish();
+        id
     }
 
-    /// Provides mutable access to the shared telemetry recorder.
+    /// Provides mutable access to the shared telemetry recorder. Call
+    /// [`Self::publish`] afterwards so the REPL sees what was recorded.
     pub(crate) fn inner_mut(&mut self) -> &mut CoreTelemetryRecorder<FirmwareInstant> {
         &mut self.inner
     }
+
+    /// Forwards the newest record to the REPL when its category is selected
+    /// with `events` or a `wait event` is pending; each record is offered at
+    /// most once.
+    pub(crate) fn publish(&mut self) {
+        let Some(record) = self.inner.latest().copied() else {
+            return;
+        };
+        if self.published == Some(record.id) {
+            return;
+        }
+        self.published = Some(record.id);
+        let filter = EventFilter::from_bits(EVENT_FILTER.load(Ordering::Relaxed));
+        if filter.matches(record.event) || EVENT_WAIT.load(Ordering::Relaxed) {
+            let uptime = Duration::from_micros(record.timestamp.into_embassy().as_micros());
+            forward(TelemetryNotice::new(&record, uptime));
+        }
+    }
 }
 
 impl Default for TelemetryRecorder {
@@ -382,6 +564,181 @@ fn emit_sequence_log(
     }
 }
 
+fn log_fault_recovery(details: FaultRecoveryTelemetry) {
+    emit_fault_log(fault_reason_label(details.reason), details.retries);
+}
+
+fn fault_reason_label(reason: FaultRecoveryReason) -> &'static str {
+    match reason {
+        FaultRecoveryReason::ManualRequest => "ManualRequest",
+        FaultRecoveryReason::BrownOutDetected => "BrownOutDetected",
+        FaultRecoveryReason::ControlLinkLost => "ControlLinkLost",
+        FaultRecoveryReason::ConsoleWatchdogTimeout => "ConsoleWatchdogTimeout",
+        FaultRecoveryReason::BootTimeout => "BootTimeout",
+        FaultRecoveryReason::KernelPanic => "KernelPanic",
+        FaultRecoveryReason::Custom(_) => "Custom",
+    }
+}
+
+#[cfg(target_os = "none")]
+fn emit_fault_log(reason: &'static str, retries: u8) {
+    defmt::info!("telemetry:fault reason={} retries={}", reason, retries);
+}
+
+#[cfg(not(target_os = "none"))]
+fn emit_fault_log(reason: &'static str, retries: u8) {
+    println!("telemetry:fault reason={reason} retries={retries}");
+}
+
+fn log_brown_out(timestamp: FirmwareInstant, details: BrownOutTelemetry) {
+    let sequence = details.sequence.map_or("idle", sequence_kind_label);
+    let timestamp_us = timestamp.into_embassy().as_micros();
+    let duration_us = details.duration.map(duration_to_micros);
+
+    emit_brown_out_log(
+        sequence,
+        details.step,
+        details.min_mv,
+        timestamp_us,
+        duration_us,
+        details.retried,
+    );
+}
+
+#[cfg(target_os = "none")]
+fn emit_brown_out_log(
+    sequence: &'static str,
+    step: Option<u8>,
+    min_mv: Option<u16>,
+    timestamp_us: u64,
+    duration_us: Option<u64>,
+    retried: bool,
+) {
+    defmt::info!(
+        "telemetry:brown-out seq={} step={} min={}mV t={}us duration={}us retried={}",
+        sequence,
+        step,
+        min_mv,
+        timestamp_us,
+        duration_us,
+        retried
+    );
+}
+
+#[cfg(not(target_os = "none"))]
+fn emit_brown_out_log(
+    sequence: &'static str,
+    step: Option<u8>,
+    min_mv: Option<u16>,
+    timestamp_us: u64,
+    duration_us: Option<u64>,
+    retried: bool,
+) {
+    println!(
+        "telemetry:brown-out seq={sequence} step={step:?} min={min_mv:?}mV t={timestamp_us}us duration={duration_us:?}us retried={retried}"
+    );
+}
+
+fn log_console_marker(
+    marker: ConsoleMarker,
+    pattern: PatternId,
+    line: u32,
+    timestamp: FirmwareInstant,
+) {
+    let label = console_marker_label(marker);
+    let timestamp_us = timestamp.into_embassy().as_micros();
+
+    emit_console_marker_log(label, pattern, line, timestamp_us);
+}
+
+fn console_marker_label(marker: ConsoleMarker) -> &'static str {
+    match marker {
+        ConsoleMarker::BootBanner => "BootBanner",
+        ConsoleMarker::LoginPrompt => "LoginPrompt",
+        ConsoleMarker::KernelPanic => "KernelPanic",
+        ConsoleMarker::TegraRecovery => "TegraRecovery",
+        ConsoleMarker::Custom => "Custom",
+    }
+}
+
+#[cfg(target_os = "none")]
+fn emit_console_marker_log(marker: &'static str, pattern: PatternId, line: u32, timestamp_us: u64) {
+    defmt::info!(
+        "telemetry:console marker={} pattern={} line={} t={}us",
+        marker,
+        pattern,
+        line,
+        timestamp_us
+    );
+}
+
+#[cfg(not(target_os = "none"))]
+fn emit_console_marker_log(marker: &'static str, pattern: PatternId, line: u32, timestamp_us: u64) {
+    println!("telemetry:console marker={marker} pattern={pattern} line={line} t={timestamp_us}us");
+}
+
+#[cfg(target_os = "none")]
+fn emit_kernel_panic_log(excerpt: &PanicExcerpt, timestamp_us: u64) {
+    defmt::warn!(
+        "telemetry:kernel-panic line={} t={}us text={}",
+        excerpt.line,
+        timestamp_us,
+        defmt::Display2Format(excerpt)
+    );
+}
+
+#[cfg(not(target_os = "none"))]
+fn emit_kernel_panic_log(excerpt: &PanicExcerpt, timestamp_us: u64) {
+    println!(
+        "telemetry:kernel-panic line={} t={timestamp_us}us text={excerpt}",
+        excerpt.line
+    );
+}
+
+#[cfg(target_os = "none")]
+fn emit_script_log(report: &ScriptReport, timestamp_us: u64) {
+    defmt::info!(
+        "telemetry:script name={=str} result={} line={} elapsed={}us t={}us",
+        report.name.as_str(),
+        defmt::Display2Format(&report.result),
+        report.line,
+        duration_to_micros(report.elapsed),
+        timestamp_us
+    );
+}
+
+#[cfg(not(target_os = "none"))]
+fn emit_script_log(report: &ScriptReport, timestamp_us: u64) {
+    println!(
+        "telemetry:script name={} result={} line={} elapsed={}us t={timestamp_us}us",
+        report.name,
+        report.result,
+        report.line,
+        duration_to_micros(report.elapsed)
+    );
+}
+
+#[cfg(target_os = "none")]
+fn emit_bootloader_interrupt_log(report: &AutobootReport, timestamp_us: u64) {
+    defmt::info!(
+        "telemetry:bootloader-interrupt result={} elapsed={}us keys={} t={}us",
+        defmt::Display2Format(&report.result),
+        duration_to_micros(report.elapsed),
+        report.keys_sent,
+        timestamp_us
+    );
+}
+
+#[cfg(not(target_os = "none"))]
+fn emit_bootloader_interrupt_log(report: &AutobootReport, timestamp_us: u64) {
+    println!(
+        "telemetry:bootloader-interrupt result={} elapsed={}us keys={} t={timestamp_us}us",
+        report.result,
+        duration_to_micros(report.elapsed),
+        report.keys_sent
+    );
+}
+
 fn duration_to_micros(duration: Duration) -> u64 {
     let clamped = duration.as_micros().min(u128::from(u64::MAX));
     u64::try_from(clamped).unwrap_or(u64::MAX)
@@ -47,6 +47,13 @@ const USB_DEVICE_PROTOCOL_IAD: u8 = 0x01;
 #[cfg(target_os = "none")]
 /// Maximum bus power (in milliamps) advertised to the USB host.
 const USB_MAX_BUS_POWER_MA: u16 = 250;
+#[cfg(target_os = "none")]
+/// CDC PSTN `SEND_BREAK` class request code.
+const CDC_REQ_SEND_BREAK: u8 = 0x23;
+#[cfg(target_os = "none")]
+/// Communication interface of the bridge port; the REPL port is built first
+/// and takes interfaces 0 (communication) and 1 (data).
+const BRIDGE_COMM_INTERFACE: u16 = 2;
 
 /// User-visible strings advertised in the USB descriptors.
 #[derive(Clone, Copy, Debug)]
@@ -72,6 +79,41 @@ impl Default for UsbDeviceStrings {
     }
 }
 
+/// Callback receiving the `wValue` (break length in ms) of a bridge `SEND_BREAK`.
+pub type BreakCallback = fn(u16);
+
+/// Handles CDC `SEND_BREAK` on the bridge interface.
+///
+/// Embassy's CDC ACM class rejects requests it does not implement, so this
+/// handler has to be registered before the bridge class to see them.
+#[cfg(target_os = "none")]
+struct BreakRequestHandler {
+    on_break: Option<BreakCallback>,
+}
+
+#[cfg(target_os = "none")]
+impl embassy_usb::Handler for BreakRequestHandler {
+    fn control_out(
+        &mut self,
+        req: embassy_usb::control::Request,
+        _data: &[u8],
+    ) -> Option<embassy_usb::control::OutResponse> {
+        use embassy_usb::control::{OutResponse, Recipient, RequestType};
+
+        if req.request_type != RequestType::Class
+            || req.recipient != Recipient::Interface
+            || req.index != BRIDGE_COMM_INTERFACE
+            || req.request != CDC_REQ_SEND_BREAK
+        {
+            return None;
+        }
+
+        let on_break = self.on_break?;
+        on_break(req.value);
+        Some(OutResponse::Accepted)
+    }
+}
+
 /// Backing storage for the Embassy USB builder and CDC ACM classes.
 #[cfg(target_os = "none")]
 pub struct UsbDeviceStorage {
@@ -81,6 +123,7 @@ pub struct UsbDeviceStorage {
     msos_descriptor: [u8; MSOS_DESCRIPTOR_LEN],
     repl_state: embassy_usb::class::cdc_acm::State<'static>,
     bridge_state: embassy_usb::class::cdc_acm::State<'static>,
+    bridge_break: BreakRequestHandler,
 }
 
 #[cfg(target_os = "none")]
@@ -94,6 +137,7 @@ impl UsbDeviceStorage {
             msos_descriptor: [0; MSOS_DESCRIPTOR_LEN],
             repl_state: embassy_usb::class::cdc_acm::State::new(),
             bridge_state: embassy_usb::class::cdc_acm::State::new(),
+            bridge_break: BreakRequestHandler { on_break: None },
         }
     }
 }
@@ -149,10 +193,14 @@ where
     D: embassy_usb::driver::Driver<'static>,
 {
     /// Creates the USB composite device exposing REPL and bridge CDC interfaces.
+    ///
+    /// `on_bridge_break` is called from the USB task for every `SEND_BREAK`
+    /// the host sends to the bridge interface.
     pub fn new(
         driver: D,
         storage: &'static mut UsbDeviceStorage,
         strings: UsbDeviceStrings,
+        on_bridge_break: BreakCallback,
     ) -> Self {
         let mut config =
             embassy_usb::Config::new(USB_VENDOR_ID_PID_CODES, USB_PRODUCT_ID_ORIN_CONTROLLER);
@@ -181,6 +229,8 @@ where
             &mut storage.repl_state,
             MAX_PACKET_SIZE,
         );
+        storage.bridge_break.on_break = Some(on_bridge_break);
+        builder.handler(&mut storage.bridge_break);
         let bridge = embassy_usb::class::cdc_acm::CdcAcmClass::new(
             &mut builder,
             &mut storage.bridge_state,
@@ -249,7 +299,12 @@ pub struct UsbComposite<D> {
 
 #[cfg(not(target_os = "none"))]
 impl<D> UsbComposite<D> {
-    pub fn new(_: D, _: &'static mut UsbDeviceStorage, _: UsbDeviceStrings) -> Self {
+    pub fn new(
+        _: D,
+        _: &'static mut UsbDeviceStorage,
+        _: UsbDeviceStrings,
+        _: BreakCallback,
+    ) -> Self {
         Self {
             device: (),
             _marker: core::marker::PhantomData,